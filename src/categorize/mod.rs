@@ -7,11 +7,24 @@ pub(crate) struct Categorizer {
     rules: Vec<CompiledRule>,
 }
 
+/// The rule responsible for a categorization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RuleMatch {
+    pub(crate) rule_id: Option<i64>,
+    /// The pattern as the user entered it.
+    pub(crate) pattern: String,
+}
+
 struct CompiledRule {
+    rule_id: Option<i64>,
+    /// Pattern as entered, reported back in `RuleMatch`.
+    source: String,
     pattern: String,
     regex: Option<Regex>,
     category_id: i64,
-    is_regex: bool,
+    /// Matched through `regex` (regex and whole-word rules) rather than a
+    /// plain substring search.
+    use_regex: bool,
 }
 
 impl Categorizer {
@@ -22,10 +35,9 @@ impl Categorizer {
         let compiled = rules
             .iter()
             .map(|r| {
-                let regex = if r.is_regex {
-                    // Lowercase the pattern so it matches against the lowercased
-                    // input, consistent with the contains rule behavior
-                    match Regex::new(&r.pattern.to_lowercase()) {
+                let use_regex = r.is_regex || r.whole_word;
+                let regex = if use_regex {
+                    match Regex::new(&rule_regex(r)) {
                         Ok(re) => Some(re),
                         Err(_) => {
                             bad_patterns.push(r.pattern.clone());
@@ -36,10 +48,12 @@ impl Categorizer {
                     None
                 };
                 CompiledRule {
+                    rule_id: r.id,
+                    source: r.pattern.clone(),
                     pattern: r.pattern.to_lowercase(),
                     regex,
                     category_id: r.category_id,
-                    is_regex: r.is_regex,
+                    use_regex,
                 }
             })
             .collect();
@@ -47,39 +61,193 @@ impl Categorizer {
         (Self { rules: compiled }, bad_patterns)
     }
 
-    pub(crate) fn categorize(&self, description: &str) -> Option<i64> {
+    /// Category for `description` from the first matching rule, along with
+    /// the rule that matched.
+    pub(crate) fn categorize(&self, description: &str) -> Option<(i64, RuleMatch)> {
         let desc_lower = description.to_lowercase();
 
         for rule in &self.rules {
-            let matched = if rule.is_regex {
+            let matched = if rule.use_regex {
                 rule.regex
                     .as_ref()
-                    .is_some_and(|re| re.is_match(&desc_lower))
+                    .is_some_and(|re| re.is_match(description))
             } else {
                 desc_lower.contains(&rule.pattern)
             };
 
             if matched {
-                return Some(rule.category_id);
+                return Some((
+                    rule.category_id,
+                    RuleMatch {
+                        rule_id: rule.rule_id,
+                        pattern: rule.source.clone(),
+                    },
+                ));
             }
         }
 
         None
     }
 
-    pub(crate) fn categorize_batch(&self, transactions: &mut [crate::models::Transaction]) {
-        for txn in transactions.iter_mut() {
-            if txn.category_id.is_none() {
-                txn.category_id = self.categorize(&txn.original_description);
-            }
+    /// Fill in categories for uncategorized transactions. Returns the matching
+    /// rule for each transaction, `None` where nothing was assigned.
+    pub(crate) fn categorize_batch(
+        &self,
+        transactions: &mut [crate::models::Transaction],
+    ) -> Vec<Option<RuleMatch>> {
+        transactions
+            .iter_mut()
+            .map(|txn| {
+                if txn.category_id.is_some() {
+                    return None;
+                }
+                let (category_id, rule) = self.categorize(&txn.original_description)?;
+                txn.category_id = Some(category_id);
+                Some(rule)
+            })
+            .collect()
+    }
+}
+
+/// Regex source for a rule. Matching is case-insensitive, consistent with
+/// contains rules; a pattern that already starts with `(?i)` isn't wrapped
+/// twice. Whole-word rules are anchored with `\b` on both sides.
+fn rule_regex(rule: &ImportRule) -> String {
+    let body = if rule.is_regex {
+        rule.pattern
+            .strip_prefix("(?i)")
+            .unwrap_or(&rule.pattern)
+            .to_string()
+    } else {
+        regex::escape(&rule.pattern)
+    };
+
+    if rule.whole_word {
+        format!(r"(?i)\b(?:{body})\b")
+    } else {
+        format!("(?i){body}")
+    }
+}
+
+/// Payment processor tags written before a `*` ahead of the merchant, as
+/// in `SQ *`, `TST* `, and `PAYPAL *`.
+const PROCESSOR_TAGS: [&str; 12] = [
+    "SQ", "TST", "PAYPAL", "PP", "SP", "PY", "IN", "DD", "DNH", "BT", "GOOGLE", "APL",
+];
+
+/// Card-terminal noise some banks put before the merchant. A `#` stands
+/// for a word of digits or a date like `01/15`.
+const TERMINAL_PREFIXES: [&[&str]; 5] = [
+    &["POS", "PURCHASE"],
+    &["POS"],
+    &["CHECKCARD", "#"],
+    &["DEBIT", "CARD", "PURCHASE"],
+    &["PURCHASE", "AUTHORIZED", "ON", "#"],
+];
+
+/// `s` without a leading run of whitespace-separated `words`, compared
+/// case-insensitively.
+fn strip_words<'a>(s: &'a str, words: &[&str]) -> Option<&'a str> {
+    let mut rest = s;
+    for word in words {
+        let trimmed = rest.trim_start();
+        let end = trimmed.find(char::is_whitespace)?;
+        let token = &trimmed[..end];
+        let matches = if *word == "#" {
+            token.chars().all(|c| c.is_ascii_digit() || c == '/')
+        } else {
+            token.eq_ignore_ascii_case(word)
+        };
+        if !matches {
+            return None;
+        }
+        rest = &trimmed[end..];
+    }
+    Some(rest.trim_start())
+}
+
+fn strip_processor_tag(s: &str) -> &str {
+    if let Some(star) = s.find('*') {
+        let tag = s[..star].trim();
+        if PROCESSOR_TAGS.iter().any(|p| p.eq_ignore_ascii_case(tag)) {
+            return s[star + 1..].trim_start();
         }
     }
+    s
+}
+
+/// US state and territory codes, for spotting a trailing `CITY ST`.
+const STATE_CODES: [&str; 54] = [
+    "AL", "AK", "AZ", "AR", "CA", "CO", "CT", "DE", "FL", "GA", "HI", "ID", "IL", "IN", "IA", "KS",
+    "KY", "LA", "ME", "MD", "MA", "MI", "MN", "MS", "MO", "MT", "NE", "NV", "NH", "NJ", "NM", "NY",
+    "NC", "ND", "OH", "OK", "OR", "PA", "RI", "SC", "SD", "TN", "TX", "UT", "VT", "VA", "WA", "WV",
+    "WI", "WY", "DC", "PR", "GU", "VI",
+];
+
+/// A store or terminal number: `#1234`, `0012345`, `T-1234`.
+fn is_store_number(token: &str) -> bool {
+    let digits = token.chars().filter(char::is_ascii_digit).count();
+    let letters = token.chars().filter(|c| c.is_alphabetic()).count();
+    token.starts_with('#') && digits > 0 || digits >= 3 && letters <= 1
 }
 
-/// Suggest a new rule based on a description and category assignment.
+/// The merchant name from a raw bank description, e.g.
+/// `SQ *BLUE BOTTLE COFFEE 0012345 OAKLAND CA` gives `BLUE BOTTLE COFFEE`.
+/// Strips payment processor prefixes, then everything from the first store
+/// number on, or a trailing `CITY ST` (state code in capitals) when there's
+/// no store number. Case is kept. Falls back to the trimmed input if
+/// nothing would be left.
+pub(crate) fn normalize_merchant(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let without_terminal = TERMINAL_PREFIXES
+        .iter()
+        .find_map(|words| strip_words(trimmed, words))
+        .unwrap_or(trimmed);
+    let body = strip_processor_tag(without_terminal);
+
+    // Banks often pad the merchant from the location with a run of spaces
+    let merchant_part = body.split("  ").next().unwrap_or(body);
+    let padded_tail = merchant_part.len() < body.trim_end().len();
+    let mut tokens: Vec<&str> = merchant_part.split_whitespace().collect();
+
+    if let Some(pos) = tokens.iter().skip(1).position(|t| is_store_number(t)) {
+        tokens.truncate(pos + 1);
+    } else if !padded_tail
+        && tokens.len() >= 3
+        && tokens.last().is_some_and(|t| STATE_CODES.contains(t))
+    {
+        // `MERCHANT CITY ST`: the city is taken to be one word. Only an
+        // uppercase code counts, so words like "in" or "me" aren't states
+        tokens.truncate(tokens.len() - 2);
+    }
+
+    let name = tokens
+        .join(" ")
+        .trim_end_matches(['*', '#', '-', ','])
+        .trim()
+        .to_string();
+    if name.is_empty() {
+        trimmed.to_string()
+    } else {
+        name
+    }
+}
+
+/// Replace each transaction's description with the merchant name from its
+/// original description. The original is left as imported so duplicate
+/// detection and rules still see it.
+pub(crate) fn clean_descriptions(transactions: &mut [crate::models::Transaction]) {
+    for txn in transactions {
+        txn.description = normalize_merchant(&txn.original_description);
+    }
+}
+
+/// Suggest a new rule based on a description and category assignment. The
+/// pattern comes from the merchant name and always appears in
+/// `description`, since rules match the original description.
 pub(crate) fn suggest_rule(description: &str) -> Result<String> {
     // Extract the most likely merchant/vendor name
-    let cleaned = description
+    let cleaned = normalize_merchant(description)
         .to_uppercase()
         .replace(|c: char| c.is_ascii_digit(), "")
         .replace('#', "")
@@ -97,7 +265,12 @@ pub(crate) fn suggest_rule(description: &str) -> Result<String> {
         description.to_string()
     };
 
-    Ok(pattern.to_lowercase())
+    // Two words split by `*` or a store number wouldn't match as a contains rule
+    let pattern = pattern.to_lowercase();
+    if words.len() >= 2 && !description.to_lowercase().contains(&pattern) {
+        return Ok(words[0].to_lowercase());
+    }
+    Ok(pattern)
 }
 
 #[cfg(test)]
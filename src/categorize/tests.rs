@@ -12,6 +12,10 @@ fn make_regex_rule(pattern: &str, cat_id: i64) -> ImportRule {
     ImportRule::new_regex(pattern.to_string(), cat_id)
 }
 
+fn cat_id(categorizer: &Categorizer, description: &str) -> Option<i64> {
+    categorizer.categorize(description).map(|(id, _)| id)
+}
+
 fn make_txn(desc: &str) -> Transaction {
     Transaction {
         id: None,
@@ -25,6 +29,10 @@ fn make_txn(desc: &str) -> Transaction {
         is_transfer: false,
         import_hash: String::new(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     }
 }
 
@@ -34,24 +42,24 @@ fn make_txn(desc: &str) -> Transaction {
 fn test_categorize_contains_match() {
     let rules = vec![make_rule("coffee", 1), make_rule("amazon", 2)];
     let (cat, _) = Categorizer::new(&rules);
-    assert_eq!(cat.categorize("STARBUCKS COFFEE #123"), Some(1));
-    assert_eq!(cat.categorize("AMAZON.COM PURCHASE"), Some(2));
+    assert_eq!(cat_id(&cat, "STARBUCKS COFFEE #123"), Some(1));
+    assert_eq!(cat_id(&cat, "AMAZON.COM PURCHASE"), Some(2));
 }
 
 #[test]
 fn test_categorize_case_insensitive() {
     let rules = vec![make_rule("coffee", 1)];
     let (cat, _) = Categorizer::new(&rules);
-    assert_eq!(cat.categorize("Coffee Shop"), Some(1));
-    assert_eq!(cat.categorize("COFFEE SHOP"), Some(1));
-    assert_eq!(cat.categorize("coffee shop"), Some(1));
+    assert_eq!(cat_id(&cat, "Coffee Shop"), Some(1));
+    assert_eq!(cat_id(&cat, "COFFEE SHOP"), Some(1));
+    assert_eq!(cat_id(&cat, "coffee shop"), Some(1));
 }
 
 #[test]
 fn test_categorize_no_match() {
     let rules = vec![make_rule("coffee", 1)];
     let (cat, _) = Categorizer::new(&rules);
-    assert_eq!(cat.categorize("GROCERY STORE"), None);
+    assert_eq!(cat_id(&cat, "GROCERY STORE"), None);
 }
 
 #[test]
@@ -59,15 +67,15 @@ fn test_categorize_first_match_wins() {
     let rules = vec![make_rule("shop", 1), make_rule("coffee shop", 2)];
     let (cat, _) = Categorizer::new(&rules);
     // "shop" matches first
-    assert_eq!(cat.categorize("Coffee Shop"), Some(1));
+    assert_eq!(cat_id(&cat, "Coffee Shop"), Some(1));
 }
 
 #[test]
 fn test_categorize_regex() {
     let rules = vec![make_regex_rule(r"^AMZN.*MKTP", 1)];
     let (cat, _) = Categorizer::new(&rules);
-    assert_eq!(cat.categorize("AMZN MKTP US*2A1B3C"), Some(1));
-    assert_eq!(cat.categorize("AMAZON.COM"), None);
+    assert_eq!(cat_id(&cat, "AMZN MKTP US*2A1B3C"), Some(1));
+    assert_eq!(cat_id(&cat, "AMAZON.COM"), None);
 }
 
 #[test]
@@ -75,9 +83,69 @@ fn test_categorize_regex_case_insensitive() {
     // Regex matching is case-insensitive (consistent with contains rules)
     let rules = vec![make_regex_rule(r"STARBUCKS", 1)];
     let (cat, _) = Categorizer::new(&rules);
-    assert_eq!(cat.categorize("STARBUCKS COFFEE"), Some(1));
-    assert_eq!(cat.categorize("starbucks coffee"), Some(1));
-    assert_eq!(cat.categorize("Starbucks Coffee"), Some(1));
+    assert_eq!(cat_id(&cat, "STARBUCKS COFFEE"), Some(1));
+    assert_eq!(cat_id(&cat, "starbucks coffee"), Some(1));
+    assert_eq!(cat_id(&cat, "Starbucks Coffee"), Some(1));
+}
+
+#[test]
+fn test_categorize_regex_mixed_case_merchants() {
+    let rules = vec![make_regex_rule(r"^AMZN\s*Mktp", 1)];
+    let (cat, _) = Categorizer::new(&rules);
+    assert_eq!(cat_id(&cat, "AMZN MKTP US*2A1B3C"), Some(1));
+    assert_eq!(cat_id(&cat, "amzn mktp us"), Some(1));
+    assert_eq!(cat_id(&cat, "Amzn Mktp"), Some(1));
+    assert_eq!(cat_id(&cat, "PAYPAL *AMZN MKTP"), None);
+}
+
+#[test]
+fn test_categorize_regex_uppercase_escapes_preserved() {
+    // \S must stay "non-whitespace" rather than being lowercased to \s
+    let rules = vec![make_regex_rule(r"^SQ \*\S+", 1)];
+    let (cat, _) = Categorizer::new(&rules);
+    assert_eq!(cat_id(&cat, "SQ *BlueBottle"), Some(1));
+    assert_eq!(cat_id(&cat, "SQ * BlueBottle"), None);
+}
+
+#[test]
+fn test_categorize_regex_explicit_case_flag_not_doubled() {
+    let rules = vec![make_regex_rule(r"(?i)^Uber\b", 1)];
+    let (cat, bad) = Categorizer::new(&rules);
+    assert!(bad.is_empty());
+    assert_eq!(cat_id(&cat, "UBER TRIP"), Some(1));
+    assert_eq!(cat_id(&cat, "uber eats"), Some(1));
+    assert_eq!(rule_regex(&rules[0]), r"(?i)^Uber\b");
+}
+
+#[test]
+fn test_categorize_whole_word_contains() {
+    let mut rule = make_rule("shell", 1);
+    rule.whole_word = true;
+    let (cat, _) = Categorizer::new(&[rule]);
+    assert_eq!(cat_id(&cat, "SHELL OIL 12345"), Some(1));
+    assert_eq!(cat_id(&cat, "Shell Gas Station"), Some(1));
+    assert_eq!(cat_id(&cat, "EGGSHELL DESIGNS"), None);
+    assert_eq!(cat_id(&cat, "SHELLFISH SHACK"), None);
+}
+
+#[test]
+fn test_categorize_whole_word_escapes_metacharacters() {
+    let mut rule = make_rule("at&t", 1);
+    rule.whole_word = true;
+    let (cat, bad) = Categorizer::new(&[rule]);
+    assert!(bad.is_empty());
+    assert_eq!(cat_id(&cat, "AT&T WIRELESS"), Some(1));
+    assert_eq!(cat_id(&cat, "WHAT&THE DELI"), None);
+}
+
+#[test]
+fn test_categorize_whole_word_regex() {
+    let mut rule = make_regex_rule(r"(?i)lyft|uber", 1);
+    rule.whole_word = true;
+    let (cat, _) = Categorizer::new(&[rule]);
+    assert_eq!(cat_id(&cat, "Uber Technologies"), Some(1));
+    assert_eq!(cat_id(&cat, "LYFT RIDE"), Some(1));
+    assert_eq!(cat_id(&cat, "UBERTI FIREARMS"), None);
 }
 
 #[test]
@@ -85,8 +153,8 @@ fn test_categorize_regex_pattern_match() {
     // Test regex patterns with quantifiers and anchors
     let rules = vec![make_regex_rule(r"^SQ \*", 1)];
     let (cat, _) = Categorizer::new(&rules);
-    assert_eq!(cat.categorize("SQ *COFFEE SHOP"), Some(1));
-    assert_eq!(cat.categorize("NOT SQ *COFFEE"), None);
+    assert_eq!(cat_id(&cat, "SQ *COFFEE SHOP"), Some(1));
+    assert_eq!(cat_id(&cat, "NOT SQ *COFFEE"), None);
 }
 
 #[test]
@@ -94,15 +162,23 @@ fn test_categorize_invalid_regex_skipped() {
     let rules = vec![make_regex_rule(r"[invalid", 1)];
     let (cat, bad) = Categorizer::new(&rules);
     // Invalid regex compiles to None, match returns false
-    assert_eq!(cat.categorize("anything"), None);
+    assert_eq!(cat_id(&cat, "anything"), None);
     assert_eq!(bad, vec!["[invalid"]);
 }
 
+#[test]
+fn test_categorize_invalid_regex_does_not_block_later_rules() {
+    let rules = vec![make_regex_rule(r"(coffee", 1), make_rule("coffee", 2)];
+    let (cat, bad) = Categorizer::new(&rules);
+    assert_eq!(bad, vec!["(coffee"]);
+    assert_eq!(cat_id(&cat, "COFFEE BAR"), Some(2));
+}
+
 #[test]
 fn test_categorize_empty_rules() {
     let rules: Vec<ImportRule> = vec![];
     let (cat, _) = Categorizer::new(&rules);
-    assert_eq!(cat.categorize("anything"), None);
+    assert_eq!(cat_id(&cat, "anything"), None);
 }
 
 #[test]
@@ -110,7 +186,7 @@ fn test_categorize_empty_description() {
     let rules = vec![make_rule("", 1)];
     let (cat, _) = Categorizer::new(&rules);
     // Empty pattern matches everything (contains "")
-    assert_eq!(cat.categorize("anything"), Some(1));
+    assert_eq!(cat_id(&cat, "anything"), Some(1));
 }
 
 #[test]
@@ -122,10 +198,29 @@ fn test_categorize_mixed_rules() {
         make_rule("target", 3),
     ];
     let (cat, _) = Categorizer::new(&rules);
-    assert_eq!(cat.categorize("WALMART SUPERCENTER"), Some(1));
-    assert_eq!(cat.categorize("AMZN MKTP US"), Some(2));
-    assert_eq!(cat.categorize("TARGET STORE #123"), Some(3));
-    assert_eq!(cat.categorize("COSTCO WHOLESALE"), None);
+    assert_eq!(cat_id(&cat, "WALMART SUPERCENTER"), Some(1));
+    assert_eq!(cat_id(&cat, "AMZN MKTP US"), Some(2));
+    assert_eq!(cat_id(&cat, "TARGET STORE #123"), Some(3));
+    assert_eq!(cat_id(&cat, "COSTCO WHOLESALE"), None);
+}
+
+#[test]
+fn test_categorize_reports_matching_rule() {
+    let mut coffee = make_rule("Coffee", 1);
+    coffee.id = Some(10);
+    let mut amzn = make_regex_rule(r"^AMZN", 2);
+    amzn.id = Some(20);
+    let (cat, _) = Categorizer::new(&[coffee, amzn]);
+
+    let (id, rule) = cat.categorize("AMZN MKTP US").unwrap();
+    assert_eq!(id, 2);
+    assert_eq!(rule.rule_id, Some(20));
+    assert_eq!(rule.pattern, "^AMZN");
+
+    // Pattern is reported as entered, not lowercased
+    let (_, rule) = cat.categorize("BLUE BOTTLE COFFEE").unwrap();
+    assert_eq!(rule.pattern, "Coffee");
+    assert_eq!(rule.rule_id, Some(10));
 }
 
 // ── Batch categorization ──────────────────────────────────────
@@ -139,10 +234,15 @@ fn test_categorize_batch() {
         make_txn("GROCERY STORE"),
         make_txn("UNKNOWN MERCHANT"),
     ];
-    cat.categorize_batch(&mut txns);
+    let matches = cat.categorize_batch(&mut txns);
     assert_eq!(txns[0].category_id, Some(1));
     assert_eq!(txns[1].category_id, Some(2));
     assert_eq!(txns[2].category_id, None);
+    let patterns: Vec<Option<&str>> = matches
+        .iter()
+        .map(|m| m.as_ref().map(|r| r.pattern.as_str()))
+        .collect();
+    assert_eq!(patterns, vec![Some("coffee"), Some("grocery"), None]);
 }
 
 #[test]
@@ -151,8 +251,9 @@ fn test_categorize_batch_preserves_existing() {
     let (cat, _) = Categorizer::new(&rules);
     let mut txns = vec![make_txn("COFFEE SHOP")];
     txns[0].category_id = Some(99); // Already categorized
-    cat.categorize_batch(&mut txns);
+    let matches = cat.categorize_batch(&mut txns);
     assert_eq!(txns[0].category_id, Some(99)); // Not overwritten
+    assert_eq!(matches, vec![None]);
 }
 
 #[test]
@@ -180,12 +281,104 @@ fn test_categorize_batch_uses_original_description() {
         is_transfer: false,
         import_hash: String::new(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     }];
     cat.categorize_batch(&mut txns);
     // Should match on original_description
     assert_eq!(txns[0].category_id, Some(1));
 }
 
+// ── normalize_merchant ────────────────────────────────────────
+
+#[test]
+fn test_normalize_merchant_processor_prefixes() {
+    assert_eq!(normalize_merchant("SQ *COFFEE 0012345"), "COFFEE");
+    assert_eq!(
+        normalize_merchant("SQ *BLUE BOTTLE COFFEE"),
+        "BLUE BOTTLE COFFEE"
+    );
+    assert_eq!(normalize_merchant("TST* JOE'S PIZZA 4412"), "JOE'S PIZZA");
+    assert_eq!(normalize_merchant("PAYPAL *SPOTIFYUSA"), "SPOTIFYUSA");
+    assert_eq!(normalize_merchant("PayPal *Etsy Inc"), "Etsy Inc");
+    assert_eq!(
+        normalize_merchant("GOOGLE *YouTubePremium"),
+        "YouTubePremium"
+    );
+}
+
+#[test]
+fn test_normalize_merchant_store_numbers_and_locations() {
+    assert_eq!(
+        normalize_merchant("WHOLEFDS MKT #10234 AUSTIN TX"),
+        "WHOLEFDS MKT"
+    );
+    assert_eq!(
+        normalize_merchant("TARGET 00012345 MINNEAPOLIS MN"),
+        "TARGET"
+    );
+    assert_eq!(
+        normalize_merchant("SHELL OIL 57444291007 HOUSTON TX"),
+        "SHELL OIL"
+    );
+    assert_eq!(
+        normalize_merchant("CHIPOTLE ONLINE DENVER CO"),
+        "CHIPOTLE ONLINE"
+    );
+    assert_eq!(
+        normalize_merchant("TRADER JOE S #552     SAN FRANCISCO CA"),
+        "TRADER JOE S"
+    );
+    assert_eq!(
+        normalize_merchant("SAFEWAY            SAN FRANCISCO CA"),
+        "SAFEWAY"
+    );
+}
+
+#[test]
+fn test_normalize_merchant_terminal_noise() {
+    assert_eq!(normalize_merchant("POS PURCHASE KROGER #123"), "KROGER");
+    assert_eq!(
+        normalize_merchant("CHECKCARD 0115 SQ *TACO TRUCK"),
+        "TACO TRUCK"
+    );
+    assert_eq!(
+        normalize_merchant("PURCHASE AUTHORIZED ON 01/15 STARBUCKS STORE 0401 SEATTLE WA"),
+        "STARBUCKS STORE"
+    );
+}
+
+#[test]
+fn test_normalize_merchant_leaves_clean_names_alone() {
+    assert_eq!(normalize_merchant("NETFLIX.COM"), "NETFLIX.COM");
+    assert_eq!(normalize_merchant("7-ELEVEN"), "7-ELEVEN");
+    assert_eq!(normalize_merchant("  Payroll Deposit  "), "Payroll Deposit");
+    // Lowercase words that spell a state code aren't a location
+    assert_eq!(
+        normalize_merchant("Zelle from John in"),
+        "Zelle from John in"
+    );
+    assert_eq!(normalize_merchant("Venmo to Sam me"), "Venmo to Sam me");
+    assert_eq!(normalize_merchant("Gift for Mom or"), "Gift for Mom or");
+    assert_eq!(normalize_merchant("Acme Widget co"), "Acme Widget co");
+    assert_eq!(normalize_merchant("Say hello hi"), "Say hello hi");
+    assert_eq!(normalize_merchant("Looks fine ok"), "Looks fine ok");
+    assert_eq!(normalize_merchant("Dues for Grand pa"), "Dues for Grand pa");
+    // Nothing would be left, so keep the input
+    assert_eq!(normalize_merchant("#12345"), "#12345");
+    assert_eq!(normalize_merchant(""), "");
+}
+
+#[test]
+fn test_clean_descriptions_keeps_original() {
+    let mut txns = vec![make_txn("SQ *COFFEE 0012345")];
+    clean_descriptions(&mut txns);
+    assert_eq!(txns[0].description, "COFFEE");
+    assert_eq!(txns[0].original_description, "SQ *COFFEE 0012345");
+}
+
 // ── suggest_rule ──────────────────────────────────────────────
 
 #[test]
@@ -197,8 +390,17 @@ fn test_suggest_rule_basic() {
 #[test]
 fn test_suggest_rule_strips_numbers() {
     let s = suggest_rule("AMZ*AMAZON 12345").unwrap();
-    // Numbers stripped, * becomes space
-    assert!(s.contains("amz"));
+    // "amz amazon" wouldn't match the raw text, so only the first word
+    assert_eq!(s, "amz");
+}
+
+#[test]
+fn test_suggest_rule_uses_merchant_name() {
+    assert_eq!(suggest_rule("SQ *COFFEE 0012345").unwrap(), "coffee");
+    assert_eq!(
+        suggest_rule("TST* JOE'S PIZZA 4412").unwrap(),
+        "joe's pizza"
+    );
 }
 
 #[test]
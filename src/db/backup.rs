@@ -0,0 +1,284 @@
+//! Whole-database JSON backup. Each table is dumped as a list of row objects
+//! keyed by column name, ids included, so relationships survive the trip.
+//! Restoring inserts fresh rows and remaps every foreign key to the new ids.
+
+use anyhow::{Context, Result};
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use super::{amount_from_units, amount_to_units, Database};
+
+/// Bumped when the backup layout changes incompatibly.
+const BACKUP_VERSION: i64 = 1;
+
+/// Backed-up tables in restore order (referenced tables first), with their
+/// columns other than `id`. Columns added later may be missing from older
+/// backups; those rows get the column's default on restore.
+const TABLES: &[(&str, &[&str])] = &[
+    (
+        "accounts",
+        &[
+            "name",
+            "account_type",
+            "institution",
+            "currency",
+            "notes",
+            "created_at",
+            "opening_balance",
+            "archived",
+        ],
+    ),
+    ("categories", &["name", "parent_id", "icon", "color"]),
+    (
+        "transactions",
+        &[
+            "account_id",
+            "date",
+            "description",
+            "original_description",
+            "amount",
+            "category_id",
+            "notes",
+            "is_transfer",
+            "import_hash",
+            "created_at",
+            "tags",
+            "quantity",
+            "symbol",
+            "import_batch",
+            "transfer_pair_id",
+            "flagged",
+        ],
+    ),
+    (
+        "budgets",
+        &[
+            "category_id",
+            "month",
+            "limit_amount",
+            "include_subcategories",
+            "period",
+            "rollover",
+        ],
+    ),
+    (
+        "import_rules",
+        &[
+            "pattern",
+            "category_id",
+            "is_regex",
+            "priority",
+            "whole_word",
+            "source",
+            "created_at",
+            "last_matched_at",
+        ],
+    ),
+    (
+        "exchange_rates",
+        &["from_currency", "to_currency", "rate", "as_of"],
+    ),
+];
+
+/// Which table's ids a foreign key column refers to.
+fn referenced_table(column: &str) -> Option<&'static str> {
+    match column {
+        "account_id" => Some("accounts"),
+        "category_id" | "parent_id" => Some("categories"),
+        _ => None,
+    }
+}
+
+/// Transaction amounts are stored as scaled integers but backed up as
+/// decimal strings like `"-5.25"`, the layout backups have always used.
+fn is_amount(table: &str, column: &str) -> bool {
+    table == "transactions" && column == "amount"
+}
+
+fn amount_to_sql(value: &Value) -> Result<SqlValue> {
+    let text = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        other => anyhow::bail!("Malformed backup: transaction amount {other} is not a number"),
+    };
+    let amount = rust_decimal::Decimal::from_str_exact(&text)
+        .or_else(|_| rust_decimal::Decimal::from_scientific(&text))
+        .with_context(|| {
+            format!("Malformed backup: transaction amount '{text}' is not a number")
+        })?;
+    Ok(SqlValue::Integer(amount_to_units(amount)?))
+}
+
+fn to_json(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Value::from(f),
+        ValueRef::Text(t) => Value::from(String::from_utf8_lossy(t).into_owned()),
+        ValueRef::Blob(b) => Value::from(String::from_utf8_lossy(b).into_owned()),
+    }
+}
+
+fn to_sql(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(i64::from(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+impl Database {
+    /// Whether there is anything a restore would destroy: transactions,
+    /// budgets, rules, exchange rates, or accounts beyond the untouched
+    /// "Default" one created on first run. Seeded categories don't count.
+    pub(crate) fn has_user_data(&self) -> Result<bool> {
+        let count = |sql: &str| -> Result<i64> { Ok(self.conn.query_row(sql, [], |r| r.get(0))?) };
+        Ok(count("SELECT COUNT(*) FROM transactions")? > 0
+            || count("SELECT COUNT(*) FROM budgets")? > 0
+            || count("SELECT COUNT(*) FROM import_rules")? > 0
+            || count("SELECT COUNT(*) FROM exchange_rates")? > 0
+            || count(
+                "SELECT COUNT(*) FROM accounts
+                 WHERE name != 'Default' OR CAST(opening_balance AS REAL) != 0",
+            )? > 0)
+    }
+
+    /// Every account, category, transaction, budget, rule, and exchange rate
+    /// as a JSON document for `load_all`.
+    pub(crate) fn dump_all(&self) -> Result<Value> {
+        let mut doc = Map::new();
+        doc.insert("budgetui_backup".into(), Value::from(BACKUP_VERSION));
+        for (table, columns) in TABLES {
+            let sql = format!("SELECT id, {} FROM {table} ORDER BY id", columns.join(", "));
+            let mut stmt = self.conn.prepare(&sql)?;
+            let mut rows = stmt.query([])?;
+            let mut dumped = Vec::new();
+            while let Some(row) = rows.next()? {
+                let mut obj = Map::new();
+                obj.insert("id".into(), to_json(row.get_ref(0)?));
+                for (i, column) in columns.iter().enumerate() {
+                    let value = match row.get_ref(i + 1)? {
+                        ValueRef::Integer(units) if is_amount(table, column) => {
+                            Value::from(amount_from_units(units).to_string())
+                        }
+                        value => to_json(value),
+                    };
+                    obj.insert((*column).into(), value);
+                }
+                dumped.push(Value::Object(obj));
+            }
+            doc.insert((*table).into(), Value::Array(dumped));
+        }
+        Ok(Value::Object(doc))
+    }
+
+    /// Replace all budgeting data with a `dump_all` document. Rows get new
+    /// ids and foreign keys are remapped to them. Runs in one transaction, so
+    /// a bad backup leaves the database untouched. Returns the number of rows
+    /// restored per table.
+    pub(crate) fn load_all(&mut self, doc: &Value) -> Result<Vec<(&'static str, usize)>> {
+        match doc.get("budgetui_backup").and_then(Value::as_i64) {
+            Some(BACKUP_VERSION) => {}
+            Some(v) => anyhow::bail!("Unsupported backup version {v}"),
+            None => anyhow::bail!("Not a BudgeTUI backup file"),
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute_batch(
+            "DELETE FROM transactions;
+             DELETE FROM budgets;
+             DELETE FROM import_rules;
+             DELETE FROM accounts;
+             DELETE FROM categories;
+             DELETE FROM exchange_rates;",
+        )?;
+
+        let mut id_maps: HashMap<&str, HashMap<i64, i64>> = HashMap::new();
+        // Category parents may come later in the list, so they're linked
+        // once every category exists: (new id, old parent id).
+        let mut parents: Vec<(i64, i64)> = Vec::new();
+        let mut counts = Vec::new();
+
+        for (table, columns) in TABLES {
+            let rows = match doc.get(*table) {
+                Some(Value::Array(rows)) => rows.as_slice(),
+                Some(_) => anyhow::bail!("Malformed backup: '{table}' is not a list"),
+                None => &[],
+            };
+            let mut new_ids = HashMap::new();
+
+            for row in rows {
+                let old_id = row.get("id").and_then(Value::as_i64);
+                let present: Vec<&str> = columns
+                    .iter()
+                    .copied()
+                    .filter(|c| row.get(*c).is_some())
+                    .collect();
+                let placeholders: Vec<String> =
+                    (1..=present.len()).map(|i| format!("?{i}")).collect();
+                let sql = if present.is_empty() {
+                    format!("INSERT INTO {table} DEFAULT VALUES")
+                } else {
+                    format!(
+                        "INSERT INTO {table} ({}) VALUES ({})",
+                        present.join(", "),
+                        placeholders.join(", ")
+                    )
+                };
+                let mut stmt = tx.prepare_cached(&sql)?;
+                let mut values = Vec::with_capacity(present.len());
+                for column in &present {
+                    let value = row.get(*column).unwrap_or(&Value::Null);
+                    let old_ref = value.as_i64();
+                    let value = match (*column, referenced_table(column), old_ref) {
+                        // Linked in a second pass
+                        ("parent_id", _, _) => SqlValue::Null,
+                        (column, _, _) if is_amount(table, column) => amount_to_sql(value)?,
+                        (_, Some(target), Some(old)) => {
+                            let new = id_maps
+                                .get(target)
+                                .and_then(|m| m.get(&old))
+                                .with_context(|| {
+                                    format!("Malformed backup: {table} row refers to missing {target} id {old}")
+                                })?;
+                            SqlValue::Integer(*new)
+                        }
+                        _ => to_sql(value),
+                    };
+                    values.push(value);
+                }
+                stmt.execute(rusqlite::params_from_iter(values))
+                    .with_context(|| format!("Failed to restore a row into {table}"))?;
+                let new_id = tx.last_insert_rowid();
+                if let Some(old) = old_id {
+                    new_ids.insert(old, new_id);
+                }
+                if let Some(parent) = row.get("parent_id").and_then(Value::as_i64) {
+                    parents.push((new_id, parent));
+                }
+            }
+            id_maps.insert(table, new_ids);
+            counts.push((*table, rows.len()));
+        }
+
+        let categories = id_maps.get("categories").cloned().unwrap_or_default();
+        for (id, old_parent) in parents {
+            let parent = categories.get(&old_parent).with_context(|| {
+                format!("Malformed backup: category parent id {old_parent} is missing")
+            })?;
+            tx.execute(
+                "UPDATE categories SET parent_id = ?1 WHERE id = ?2",
+                rusqlite::params![parent, id],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(counts)
+    }
+}
@@ -1,11 +1,14 @@
+mod backup;
 mod schema;
 
 use anyhow::{Context, Result};
 use rusqlite::{params, Connection, Row};
 use rust_decimal::Decimal;
+use std::cell::Cell;
 use std::path::Path;
 use std::str::FromStr;
 
+use crate::import::{CsvProfile, SignConvention};
 use crate::models::*;
 
 /// Parse a Decimal from a string, defaulting to zero on failure.
@@ -13,29 +16,105 @@ fn parse_decimal(s: &str) -> Decimal {
     Decimal::from_str(s).unwrap_or_default()
 }
 
+/// Decimal places kept for transaction amounts. `transactions.amount` is an
+/// INTEGER count of ten-thousandths, so SQL sums and comparisons are exact
+/// and `1234.5678` survives the round trip.
+const AMOUNT_SCALE: u32 = 4;
+
+/// A transaction amount as stored: rounded to [`AMOUNT_SCALE`] places and
+/// scaled to an integer.
+fn amount_to_units(amount: Decimal) -> Result<i64> {
+    use rust_decimal::prelude::ToPrimitive;
+    amount
+        .round_dp(AMOUNT_SCALE)
+        .checked_mul(Decimal::from(10_i64.pow(AMOUNT_SCALE)))
+        .and_then(|units| units.to_i64())
+        .with_context(|| format!("Amount {amount} is too large to store"))
+}
+
+/// A stored amount (or sum of amounts) back as a `Decimal`, with trailing
+/// zeros trimmed to no fewer than two places: `-52500` → `-5.25`.
+fn amount_from_units(units: i64) -> Decimal {
+    let mut amount = Decimal::new(units, AMOUNT_SCALE).normalize();
+    if amount.scale() < 2 {
+        amount.rescale(2);
+    }
+    amount
+}
+
+/// Amount and normalized descriptions of a stored transaction.
+type ExistingTxn = (Decimal, String, String);
+
+/// Lowercased description with punctuation dropped and whitespace collapsed,
+/// so `AMAZON.COM*123  ` and `Amazon com 123` compare equal.
+fn normalize_description(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Map a rusqlite Row to a Transaction. Expects columns in the standard order:
-/// id, account_id, date, description, original_description, amount(TEXT),
-/// category_id, notes, is_transfer, import_hash, created_at
+/// id, account_id, date, description, original_description, amount(units),
+/// category_id, notes, is_transfer, import_hash, created_at, tags, quantity,
+/// symbol, flagged
 fn row_to_transaction(row: &Row<'_>) -> rusqlite::Result<Transaction> {
-    let amount_str: String = row.get(5)?;
     Ok(Transaction {
         id: Some(row.get(0)?),
         account_id: row.get(1)?,
         date: row.get(2)?,
         description: row.get(3)?,
         original_description: row.get(4)?,
-        amount: parse_decimal(&amount_str),
+        amount: amount_from_units(row.get(5)?),
         category_id: row.get(6)?,
         notes: row.get(7)?,
         is_transfer: row.get(8)?,
         import_hash: row.get(9)?,
         created_at: row.get(10)?,
+        tags: Transaction::parse_tags(&row.get::<_, String>(11)?),
+        quantity: row.get::<_, Option<String>>(12)?.map(|q| parse_decimal(&q)),
+        symbol: row.get(13)?,
+        flagged: row.get(14)?,
     })
 }
 
+/// Map a rusqlite Row to an Account. Expects the columns in [`ACCOUNT_COLUMNS`].
+fn row_to_account(row: &Row<'_>) -> rusqlite::Result<Account> {
+    Ok(Account {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        account_type: AccountType::parse(&row.get::<_, String>(2)?),
+        institution: row.get(3)?,
+        currency: row.get(4)?,
+        notes: row.get(5)?,
+        created_at: row.get(6)?,
+        opening_balance: parse_decimal(&row.get::<_, String>(7)?),
+        archived: row.get(8)?,
+    })
+}
+
+/// Standard SELECT columns for account queries.
+const ACCOUNT_COLUMNS: &str =
+    "id, name, account_type, institution, currency, notes, created_at, opening_balance, archived";
+
 /// Standard SELECT columns for transaction queries.
 const TXN_COLUMNS: &str = "t.id, t.account_id, t.date, t.description, t.original_description, \
-     t.amount, t.category_id, t.notes, t.is_transfer, t.import_hash, t.created_at";
+     t.amount, t.category_id, t.notes, t.is_transfer, t.import_hash, t.created_at, t.tags, \
+     t.quantity, t.symbol, t.flagged";
+
+/// How many import directories the file browser remembers.
+pub(crate) const RECENT_DIRS_KEPT: usize = 5;
+
+/// How many `:` commands the command bar remembers.
+pub(crate) const COMMAND_HISTORY_KEPT: usize = 100;
 
 /// Build a dynamic SQL param vector and push a new boxed value, returning the placeholder string.
 fn push_param(
@@ -46,6 +125,56 @@ fn push_param(
     format!("?{}", params.len())
 }
 
+/// The `YYYY-MM` month before `month`, or `None` if it doesn't parse.
+fn previous_month(month: &str) -> Option<String> {
+    chrono::NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.checked_sub_months(chrono::Months::new(1)))
+        .map(|d| d.format("%Y-%m").to_string())
+}
+
+/// Half-open date range `[start, end)` covering a `YYYY-MM` month or a
+/// `YYYY` year, e.g. `2024-01` → `("2024-01-01", "2024-02-01")`. Comparing
+/// against a range instead of `LIKE '2024-01%'` lets SQLite use the date
+/// index and never matches a malformed date that merely shares the prefix.
+/// With a `start_day` past the 1st, months run from that day to the day
+/// before it in the next month: `2024-01` on the 25th is Jan 25 – Feb 24.
+fn period_range(period: &str, start_day: u32) -> Option<(String, String)> {
+    let (month, months) = if period.len() == 4 {
+        (format!("{period}-01"), 12)
+    } else {
+        (period.to_string(), 1)
+    };
+    let start = budget_month_start(&month, start_day)?;
+    let end = start.checked_add_months(chrono::Months::new(months))?;
+    Some((
+        start.format("%Y-%m-%d").to_string(),
+        end.format("%Y-%m-%d").to_string(),
+    ))
+}
+
+/// Append ` AND {column} >= start AND {column} < end` for `period` to a
+/// dynamically built query.
+fn push_period_filter(
+    p: &mut Vec<Box<dyn rusqlite::types::ToSql>>,
+    sql: &mut String,
+    column: &str,
+    period: &str,
+    start_day: u32,
+) -> Result<()> {
+    match period_range(period, start_day) {
+        Some((start, end)) => {
+            let start_ph = push_param(p, Box::new(start));
+            let end_ph = push_param(p, Box::new(end));
+            sql.push_str(&format!(
+                " AND {column} >= {start_ph} AND {column} < {end_ph}"
+            ));
+            Ok(())
+        }
+        None => anyhow::bail!("Invalid month '{period}' (expected YYYY-MM)"),
+    }
+}
+
 /// Escape LIKE special characters (`%`, `_`, `\`) so they match literally.
 fn escape_like(s: &str) -> String {
     s.replace('\\', "\\\\")
@@ -53,8 +182,282 @@ fn escape_like(s: &str) -> String {
         .replace('_', "\\_")
 }
 
+/// Rules with no match since `?1`. Timestamps are RFC 3339, so they
+/// compare against a `YYYY-MM-DD` cutoff as text; rules older than the
+/// tracking columns have an empty `created_at`, which sorts first.
+const UNMATCHED_RULES: &str = "(last_matched_at IS NULL AND created_at < ?1) \
+     OR last_matched_at < ?1";
+
+/// Insert the default categories when the table is empty.
+fn seed_default_categories(conn: &Connection) -> Result<()> {
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM categories", [], |row| row.get(0))?;
+    if count > 0 {
+        return Ok(());
+    }
+
+    let defaults = [
+        "Bills & Subscriptions",
+        "Clothing",
+        "Coffee Shops",
+        "Doctor",
+        "Education",
+        "Electronics",
+        "Entertainment",
+        "Fees & Charges",
+        "Flights",
+        "Food & Dining",
+        "Freelance",
+        "Games",
+        "Gas & Fuel",
+        "Gifts & Donations",
+        "Groceries",
+        "Gym",
+        "Health & Fitness",
+        "Home & Garden",
+        "Hotels",
+        "Housing",
+        "Income",
+        "Insurance",
+        "Interest",
+        "Movies & Shows",
+        "Parking",
+        "Personal Care",
+        "Pharmacy",
+        "Public Transit",
+        "Rent/Mortgage",
+        "Restaurants",
+        "Ride Share",
+        "Shopping",
+        "Streaming",
+        "Transfer",
+        "Transportation",
+        "Travel",
+        UNCATEGORIZED,
+        "Utilities",
+    ];
+
+    for name in &defaults {
+        conn.execute(
+            "INSERT OR IGNORE INTO categories (name) VALUES (?1)",
+            params![name],
+        )?;
+    }
+    Ok(())
+}
+
+/// Create a "Default" checking account if no accounts exist yet.
+fn ensure_default_account(conn: &Connection) -> Result<()> {
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM accounts", [], |row| row.get(0))?;
+    if count == 0 {
+        let account = Account::new("Default".into(), AccountType::Checking, String::new());
+        insert_account(conn, &account)?;
+    }
+    Ok(())
+}
+
+fn insert_account(conn: &Connection, account: &Account) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO accounts (name, account_type, institution, currency, notes, created_at, opening_balance, archived)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            account.name,
+            account.account_type.as_str(),
+            account.institution,
+            account.currency,
+            account.notes,
+            account.created_at,
+            account.opening_balance.to_string(),
+            account.archived,
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Set `last_matched_at` to now on each rule in `rule_ids`.
+fn stamp_rules_matched(conn: &Connection, rule_ids: &[i64]) -> Result<()> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut stmt = conn.prepare("UPDATE import_rules SET last_matched_at = ?1 WHERE id = ?2")?;
+    for id in rule_ids {
+        stmt.execute(params![now, id])?;
+    }
+    Ok(())
+}
+
+/// The column mapping last used to import into an account, and the header
+/// row of the file it was used on.
+#[derive(Debug, Clone)]
+pub(crate) struct AccountProfile {
+    pub(crate) account_id: i64,
+    pub(crate) headers: Vec<String>,
+    pub(crate) profile: CsvProfile,
+    pub(crate) flip_signs: bool,
+}
+
+const ACCOUNT_PROFILE_COLUMNS: &str = "account_id, headers, name, date_column, \
+     description_column, amount_column, debit_column, credit_column, date_format, has_header, \
+     skip_rows, sign_convention, is_credit_account, delimiter, flip_signs";
+
+/// Map a rusqlite Row to an AccountProfile. Expects the columns in
+/// [`ACCOUNT_PROFILE_COLUMNS`].
+fn row_to_account_profile(row: &Row<'_>) -> rusqlite::Result<AccountProfile> {
+    Ok(AccountProfile {
+        account_id: row.get(0)?,
+        headers: serde_json::from_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+        profile: CsvProfile {
+            name: row.get(2)?,
+            date_column: row.get(3)?,
+            description_column: row.get(4)?,
+            amount_column: row.get(5)?,
+            debit_column: row.get(6)?,
+            credit_column: row.get(7)?,
+            date_format: row.get(8)?,
+            has_header: row.get(9)?,
+            skip_rows: row.get(10)?,
+            sign_convention: SignConvention::parse(&row.get::<_, String>(11)?)
+                .unwrap_or(SignConvention::AmountSigned),
+            is_credit_account: row.get(12)?,
+            delimiter: row.get(13)?,
+        },
+        flip_signs: row.get(14)?,
+    })
+}
+
+/// Narrow `get_transactions` by category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CategoryFilter {
+    /// Non-transfer rows with no category or the seed "Uncategorized"
+    /// one — the ones left to categorize.
+    Uncategorized,
+}
+
+/// Filters for `get_transactions`. Fields left at their defaults don't
+/// narrow anything, so callers set only what they need.
+///
+/// `min_amount`/`max_amount` are inclusive unless `min_exclusive`/
+/// `max_exclusive` is set. `date_from`/`date_to` accept
+/// `YYYY`, `YYYY-MM`, or `YYYY-MM-DD` and include the whole period.
+/// Every tag in `tags` must be present on a returned transaction.
+/// `flagged_only` keeps just the rows flagged for review.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct TransactionQuery<'a> {
+    pub(crate) limit: Option<u32>,
+    pub(crate) offset: Option<u32>,
+    pub(crate) account_id: Option<i64>,
+    pub(crate) category: Option<CategoryFilter>,
+    pub(crate) search: Option<&'a str>,
+    pub(crate) month: Option<&'a str>,
+    pub(crate) min_amount: Option<Decimal>,
+    pub(crate) max_amount: Option<Decimal>,
+    pub(crate) min_exclusive: bool,
+    pub(crate) max_exclusive: bool,
+    pub(crate) date_from: Option<&'a str>,
+    pub(crate) date_to: Option<&'a str>,
+    pub(crate) tags: &'a [String],
+    pub(crate) flagged_only: bool,
+}
+
+/// A column `export_to_csv` can write, named on the command line by `key`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExportColumn {
+    Date,
+    Description,
+    OriginalDescription,
+    Amount,
+    Category,
+    Account,
+    Notes,
+    Tags,
+    IsTransfer,
+    Quantity,
+    Symbol,
+}
+
+impl ExportColumn {
+    pub(crate) const ALL: [ExportColumn; 11] = [
+        Self::Date,
+        Self::Description,
+        Self::OriginalDescription,
+        Self::Amount,
+        Self::Category,
+        Self::Account,
+        Self::Notes,
+        Self::Tags,
+        Self::IsTransfer,
+        Self::Quantity,
+        Self::Symbol,
+    ];
+
+    /// What an export writes when no columns are given.
+    pub(crate) const DEFAULT: [ExportColumn; 7] = [
+        Self::Date,
+        Self::Description,
+        Self::Amount,
+        Self::Category,
+        Self::Account,
+        Self::Notes,
+        Self::Tags,
+    ];
+
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::Description => "description",
+            Self::OriginalDescription => "original_description",
+            Self::Amount => "amount",
+            Self::Category => "category",
+            Self::Account => "account",
+            Self::Notes => "notes",
+            Self::Tags => "tags",
+            Self::IsTransfer => "is_transfer",
+            Self::Quantity => "quantity",
+            Self::Symbol => "symbol",
+        }
+    }
+
+    fn header(self) -> &'static str {
+        match self {
+            Self::Date => "Date",
+            Self::Description => "Description",
+            Self::OriginalDescription => "Original Description",
+            Self::Amount => "Amount",
+            Self::Category => "Category",
+            Self::Account => "Account",
+            Self::Notes => "Notes",
+            Self::Tags => "Tags",
+            Self::IsTransfer => "Transfer",
+            Self::Quantity => "Quantity",
+            Self::Symbol => "Symbol",
+        }
+    }
+
+    /// Parse a comma-separated list like `date,amount,category`, in the
+    /// order given. Names are case-insensitive.
+    pub(crate) fn parse_list(list: &str) -> Result<Vec<ExportColumn>> {
+        let mut columns = Vec::new();
+        for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let lower = name.to_lowercase();
+            match Self::ALL.iter().find(|c| c.key() == lower) {
+                Some(column) => columns.push(*column),
+                None => {
+                    let valid: Vec<&str> = Self::ALL.iter().map(|c| c.key()).collect();
+                    anyhow::bail!(
+                        "Unknown export column '{name}'. Valid columns: {}",
+                        valid.join(", ")
+                    );
+                }
+            }
+        }
+        if columns.is_empty() {
+            anyhow::bail!("No export columns given");
+        }
+        Ok(columns)
+    }
+}
+
 pub(crate) struct Database {
     conn: Connection,
+    /// Cached `budget_start_day` setting, applied to every month filter.
+    budget_start_day: Cell<u32>,
 }
 
 impl Database {
@@ -63,9 +466,14 @@ impl Database {
             .with_context(|| format!("Failed to open database: {}", path.display()))?;
         conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")
             .context("Failed to set database pragmas")?;
-        let mut db = Self { conn };
+        let mut db = Self {
+            conn,
+            budget_start_day: Cell::new(1),
+        };
         db.migrate().context("Database migration failed")?;
         db.seed_default_categories()?;
+        db.budget_start_day
+            .set(db.load_ui_state()?.budget_start_day.unwrap_or(1));
         Ok(db)
     }
 
@@ -73,7 +481,10 @@ impl Database {
     pub(crate) fn open_in_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
         conn.execute_batch("PRAGMA foreign_keys=ON;")?;
-        let mut db = Self { conn };
+        let mut db = Self {
+            conn,
+            budget_start_day: Cell::new(1),
+        };
         db.migrate()?;
         db.seed_default_categories()?;
         Ok(db)
@@ -108,6 +519,9 @@ impl Database {
         for &(target_version, sql) in schema::MIGRATIONS {
             if current < target_version {
                 self.conn.execute_batch(sql)?;
+                if target_version == schema::AMOUNT_UNITS_VERSION {
+                    self.convert_amounts_to_units()?;
+                }
             }
         }
 
@@ -121,117 +535,88 @@ impl Database {
         Ok(())
     }
 
-    fn seed_default_categories(&mut self) -> Result<()> {
-        let count: i64 = self
-            .conn
-            .query_row("SELECT COUNT(*) FROM categories", [], |row| row.get(0))?;
-        if count > 0 {
-            return Ok(());
+    /// Second half of the [`schema::AMOUNT_UNITS_VERSION`] migration: parse
+    /// each old TEXT amount as a `Decimal` (never through REAL) into the new
+    /// INTEGER column, then drop the TEXT copy. Text that never parsed was
+    /// already read as zero, and is stored as zero.
+    fn convert_amounts_to_units(&mut self) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut select = tx.prepare("SELECT id, amount_text FROM transactions")?;
+            let mut update = tx.prepare("UPDATE transactions SET amount = ?1 WHERE id = ?2")?;
+            let rows = select.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for row in rows {
+                let (id, text) = row?;
+                update.execute(params![amount_to_units(parse_decimal(text.trim()))?, id])?;
+            }
         }
+        tx.execute_batch("ALTER TABLE transactions DROP COLUMN amount_text;")?;
+        tx.commit()?;
+        Ok(())
+    }
 
-        let defaults = [
-            "Bills & Subscriptions",
-            "Clothing",
-            "Coffee Shops",
-            "Doctor",
-            "Education",
-            "Electronics",
-            "Entertainment",
-            "Fees & Charges",
-            "Flights",
-            "Food & Dining",
-            "Freelance",
-            "Games",
-            "Gas & Fuel",
-            "Gifts & Donations",
-            "Groceries",
-            "Gym",
-            "Health & Fitness",
-            "Home & Garden",
-            "Hotels",
-            "Housing",
-            "Income",
-            "Insurance",
-            "Interest",
-            "Movies & Shows",
-            "Parking",
-            "Personal Care",
-            "Pharmacy",
-            "Public Transit",
-            "Rent/Mortgage",
-            "Restaurants",
-            "Ride Share",
-            "Shopping",
-            "Streaming",
-            "Transfer",
-            "Transportation",
-            "Travel",
-            "Uncategorized",
-            "Utilities",
-        ];
+    fn seed_default_categories(&mut self) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        seed_default_categories(&tx)?;
+        tx.commit()?;
+        Ok(())
+    }
 
+    /// Delete every transaction, account, budget, rule, and category, then
+    /// reseed the default categories and a fresh "Default" account, all in
+    /// one transaction. Settings and recent import directories are kept;
+    /// the schema itself is left untouched.
+    pub(crate) fn wipe_all_data(&mut self) -> Result<()> {
         let tx = self.conn.transaction()?;
-        for name in &defaults {
-            tx.execute(
-                "INSERT OR IGNORE INTO categories (name) VALUES (?1)",
-                params![name],
-            )?;
-        }
+        tx.execute_batch(
+            "DELETE FROM transactions;
+             DELETE FROM budgets;
+             DELETE FROM import_rules;
+             DELETE FROM account_import_profiles;
+             DELETE FROM accounts;
+             DELETE FROM categories;
+             DELETE FROM exchange_rates;
+             DELETE FROM app_state WHERE key IN ('account_index', 'budgets_filled_through');",
+        )?;
+        seed_default_categories(&tx)?;
+        ensure_default_account(&tx)?;
         tx.commit()?;
         Ok(())
     }
 
     // ── Accounts ──────────────────────────────────────────────
 
+    /// Create a "Default" checking account if no accounts exist yet.
+    pub(crate) fn ensure_default_account(&self) -> Result<()> {
+        ensure_default_account(&self.conn)
+    }
+
     pub(crate) fn insert_account(&self, account: &Account) -> Result<i64> {
-        self.conn.execute(
-            "INSERT INTO accounts (name, account_type, institution, currency, notes, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![
-                account.name,
-                account.account_type.as_str(),
-                account.institution,
-                account.currency,
-                account.notes,
-                account.created_at,
-            ],
-        )?;
-        Ok(self.conn.last_insert_rowid())
+        insert_account(&self.conn, account)
     }
 
-    pub(crate) fn get_accounts(&self) -> Result<Vec<Account>> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT id, name, account_type, institution, currency, notes, created_at FROM accounts ORDER BY name")?;
-        let rows = stmt.query_map([], |row| {
-            Ok(Account {
-                id: Some(row.get(0)?),
-                name: row.get(1)?,
-                account_type: AccountType::parse(&row.get::<_, String>(2)?),
-                institution: row.get(3)?,
-                currency: row.get(4)?,
-                notes: row.get(5)?,
-                created_at: row.get(6)?,
-            })
-        })?;
+    /// Accounts by name. Archived accounts are left out unless
+    /// `include_archived` is set.
+    pub(crate) fn get_accounts(&self, include_archived: bool) -> Result<Vec<Account>> {
+        let filter = if include_archived {
+            ""
+        } else {
+            "WHERE archived = 0"
+        };
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts {filter} ORDER BY name"
+        ))?;
+        let rows = stmt.query_map([], row_to_account)?;
         Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
     }
 
     pub(crate) fn get_account_by_id(&self, id: i64) -> Result<Option<Account>> {
         let result = self.conn.query_row(
-            "SELECT id, name, account_type, institution, currency, notes, created_at FROM accounts WHERE id = ?1",
+            &format!("SELECT {ACCOUNT_COLUMNS} FROM accounts WHERE id = ?1"),
             params![id],
-            |row| {
-                Ok(Account {
-                    id: Some(row.get(0)?),
-                    name: row.get(1)?,
-                    account_type: AccountType::parse(&row.get::<_, String>(2)?),
-                    institution: row.get(3)?,
-                    currency: row.get(4)?,
-                    notes: row.get(5)?,
-                    created_at: row.get(6)?,
-                })
-            },
+            row_to_account,
         );
         match result {
             Ok(a) => Ok(Some(a)),
@@ -240,30 +625,105 @@ impl Database {
         }
     }
 
+    pub(crate) fn update_account(&self, account: &Account) -> Result<()> {
+        let id = account.id.context("Account has no ID")?;
+        self.conn.execute(
+            "UPDATE accounts SET name = ?1, account_type = ?2, institution = ?3, currency = ?4, notes = ?5,
+                 opening_balance = ?6, archived = ?7
+             WHERE id = ?8",
+            params![
+                account.name,
+                account.account_type.as_str(),
+                account.institution,
+                account.currency,
+                account.notes,
+                account.opening_balance.to_string(),
+                account.archived,
+                id,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Hide an account from pickers and the Accounts screen, or bring it
+    /// back. Its transactions are untouched and still count in net worth.
+    pub(crate) fn set_account_archived(&self, id: i64, archived: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE accounts SET archived = ?1 WHERE id = ?2",
+            params![archived, id],
+        )?;
+        Ok(())
+    }
+
+    /// Delete an account. Fails if it still has transactions unless `force`
+    /// is set, in which case its transactions are deleted too.
+    pub(crate) fn delete_account(&mut self, id: i64, force: bool) -> Result<()> {
+        let txn_count = self.get_account_transaction_count(id)?;
+        if txn_count > 0 && !force {
+            anyhow::bail!("Account has {txn_count} transactions");
+        }
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM transactions WHERE account_id = ?1",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM accounts WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub(crate) fn get_account_transaction_count(&self, account_id: i64) -> Result<i64> {
+        Ok(self.conn.query_row(
+            "SELECT COUNT(*) FROM transactions WHERE account_id = ?1",
+            params![account_id],
+            |row| row.get(0),
+        )?)
+    }
+
+    pub(crate) fn update_account_currency(&self, account_id: i64, currency: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE accounts SET currency = ?1 WHERE id = ?2",
+            params![currency, account_id],
+        )?;
+        Ok(())
+    }
+
     // ── Transactions ──────────────────────────────────────────
 
     pub(crate) fn insert_transaction(&self, txn: &Transaction) -> Result<i64> {
         self.conn.execute(
-            "INSERT INTO transactions (account_id, date, description, original_description, amount, category_id, notes, is_transfer, import_hash, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO transactions (account_id, date, description, original_description, amount, category_id, notes, is_transfer, import_hash, created_at, tags, quantity, symbol, flagged)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
             params![
                 txn.account_id,
                 txn.date,
                 txn.description,
                 txn.original_description,
-                txn.amount.to_string(),
+                amount_to_units(txn.amount)?,
                 txn.category_id,
                 txn.notes,
                 txn.is_transfer,
                 txn.import_hash,
                 txn.created_at,
+                txn.tags_string(),
+                txn.quantity.map(|q| q.to_string()),
+                txn.symbol,
+                txn.flagged,
             ],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
 
+    /// Insert an import, skipping rows whose `import_hash` is already stored.
+    /// Every row inserted by one call shares a new `import_batch` number, so
+    /// the batch can be found again with [`Self::last_import_batch`].
     pub(crate) fn insert_transactions_batch(&mut self, txns: &[Transaction]) -> Result<usize> {
         let tx = self.conn.transaction()?;
+        let batch: i64 = tx.query_row(
+            "SELECT COALESCE(MAX(import_batch), 0) + 1 FROM transactions",
+            [],
+            |row| row.get(0),
+        )?;
         let mut count = 0;
         for txn in txns {
             // Skip duplicates based on import_hash (only when hash is non-empty)
@@ -278,19 +738,23 @@ impl Database {
                 }
             }
             tx.execute(
-                "INSERT INTO transactions (account_id, date, description, original_description, amount, category_id, notes, is_transfer, import_hash, created_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                "INSERT INTO transactions (account_id, date, description, original_description, amount, category_id, notes, is_transfer, import_hash, created_at, tags, quantity, symbol, import_batch)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
                 params![
                     txn.account_id,
                     txn.date,
                     txn.description,
                     txn.original_description,
-                    txn.amount.to_string(),
+                    amount_to_units(txn.amount)?,
                     txn.category_id,
                     txn.notes,
                     txn.is_transfer,
                     txn.import_hash,
                     txn.created_at,
+                    txn.tags_string(),
+                    txn.quantity.map(|q| q.to_string()),
+                    txn.symbol,
+                    batch,
                 ],
             )?;
             count += 1;
@@ -299,27 +763,76 @@ impl Database {
         Ok(count)
     }
 
-    pub(crate) fn get_transactions(
+    /// Record money moving between accounts as its two legs, `out` and
+    /// `into`, which share a new `transfer_pair_id`. Returns that id.
+    pub(crate) fn insert_transfer_pair(
+        &mut self,
+        out: &Transaction,
+        into: &Transaction,
+    ) -> Result<i64> {
+        let tx = self.conn.transaction()?;
+        let pair: i64 = tx.query_row(
+            "SELECT COALESCE(MAX(transfer_pair_id), 0) + 1 FROM transactions",
+            [],
+            |row| row.get(0),
+        )?;
+        for txn in [out, into] {
+            tx.execute(
+                "INSERT INTO transactions (account_id, date, description, original_description, amount, category_id, notes, is_transfer, import_hash, created_at, tags, quantity, symbol, transfer_pair_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                params![
+                    txn.account_id,
+                    txn.date,
+                    txn.description,
+                    txn.original_description,
+                    amount_to_units(txn.amount)?,
+                    txn.category_id,
+                    txn.notes,
+                    txn.is_transfer,
+                    txn.import_hash,
+                    txn.created_at,
+                    txn.tags_string(),
+                    txn.quantity.map(|q| q.to_string()),
+                    txn.symbol,
+                    pair,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(pair)
+    }
+
+    pub(crate) fn get_transactions(&self, query: &TransactionQuery) -> Result<Vec<Transaction>> {
+        let (sql, p) = self.transactions_sql(query)?;
+        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(refs.as_slice(), row_to_transaction)?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// The SELECT `get_transactions` runs for `query`, with its parameters.
+    fn transactions_sql(
         &self,
-        limit: Option<u32>,
-        offset: Option<u32>,
-        account_id: Option<i64>,
-        category_id: Option<i64>,
-        search: Option<&str>,
-        month: Option<&str>,
-    ) -> Result<Vec<Transaction>> {
+        query: &TransactionQuery,
+    ) -> Result<(String, Vec<Box<dyn rusqlite::types::ToSql>>)> {
         let mut sql = format!("SELECT {TXN_COLUMNS} FROM transactions t WHERE 1=1");
         let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
-        if let Some(aid) = account_id {
+        if let Some(aid) = query.account_id {
             let ph = push_param(&mut p, Box::new(aid));
             sql.push_str(&format!(" AND t.account_id = {ph}"));
         }
-        if let Some(cid) = category_id {
-            let ph = push_param(&mut p, Box::new(cid));
-            sql.push_str(&format!(" AND t.category_id = {ph}"));
+        match query.category {
+            Some(CategoryFilter::Uncategorized) => {
+                let ph = push_param(&mut p, Box::new(UNCATEGORIZED));
+                sql.push_str(&format!(
+                    " AND t.is_transfer = 0 AND (t.category_id IS NULL \
+                     OR t.category_id IN (SELECT id FROM categories WHERE name = {ph}))"
+                ));
+            }
+            None => {}
         }
-        if let Some(s) = search {
+        if let Some(s) = query.search {
             let escaped = escape_like(s);
             let ph = push_param(&mut p, Box::new(format!("%{escaped}%")));
             sql.push_str(&format!(
@@ -328,26 +841,51 @@ impl Database {
                  OR t.notes LIKE {ph} ESCAPE '\\')"
             ));
         }
-        if let Some(m) = month {
-            let ph = push_param(&mut p, Box::new(format!("{m}%")));
-            sql.push_str(&format!(" AND t.date LIKE {ph}"));
+        if let Some(m) = query.month {
+            push_period_filter(&mut p, &mut sql, "t.date", m, self.budget_start_day())?;
+        }
+        if let Some(min) = query.min_amount {
+            let op = if query.min_exclusive { ">" } else { ">=" };
+            let ph = push_param(&mut p, Box::new(amount_to_units(min)?));
+            sql.push_str(&format!(" AND t.amount {op} {ph}"));
+        }
+        if let Some(max) = query.max_amount {
+            let op = if query.max_exclusive { "<" } else { "<=" };
+            let ph = push_param(&mut p, Box::new(amount_to_units(max)?));
+            sql.push_str(&format!(" AND t.amount {op} {ph}"));
+        }
+        if let Some(from) = query.date_from {
+            let ph = push_param(&mut p, Box::new(from.to_string()));
+            sql.push_str(&format!(" AND t.date >= {ph}"));
+        }
+        if let Some(to) = query.date_to {
+            // Compare on the prefix so `2024-03` includes every day in March
+            let ph = push_param(&mut p, Box::new(to.to_string()));
+            sql.push_str(&format!(" AND substr(t.date, 1, length({ph})) <= {ph}"));
+        }
+        for tag in query.tags {
+            // Wrap in commas so `tax` doesn't match `tax-deductible`
+            let escaped = escape_like(tag);
+            let ph = push_param(&mut p, Box::new(format!("%,{escaped},%")));
+            sql.push_str(&format!(
+                " AND (',' || t.tags || ',') LIKE {ph} ESCAPE '\\'"
+            ));
+        }
+        if query.flagged_only {
+            sql.push_str(" AND t.flagged = 1");
         }
 
         sql.push_str(" ORDER BY t.date DESC, t.id DESC");
 
-        if let Some(l) = limit {
+        if let Some(l) = query.limit {
             let ph = push_param(&mut p, Box::new(l));
             sql.push_str(&format!(" LIMIT {ph}"));
         }
-        if let Some(o) = offset {
+        if let Some(o) = query.offset {
             let ph = push_param(&mut p, Box::new(o));
             sql.push_str(&format!(" OFFSET {ph}"));
         }
-
-        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
-        let mut stmt = self.conn.prepare(&sql)?;
-        let rows = stmt.query_map(refs.as_slice(), row_to_transaction)?;
-        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+        Ok((sql, p))
     }
 
     pub(crate) fn get_transaction_count(&self) -> Result<i64> {
@@ -356,6 +894,26 @@ impl Database {
             .query_row("SELECT COUNT(*) FROM transactions", [], |row| row.get(0))?)
     }
 
+    pub(crate) fn set_transaction_transfer(
+        &self,
+        transaction_id: i64,
+        is_transfer: bool,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE transactions SET is_transfer = ?1 WHERE id = ?2",
+            params![is_transfer, transaction_id],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn set_transaction_flagged(&self, transaction_id: i64, flagged: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE transactions SET flagged = ?1 WHERE id = ?2",
+            params![flagged, transaction_id],
+        )?;
+        Ok(())
+    }
+
     pub(crate) fn update_transaction_category(
         &self,
         transaction_id: i64,
@@ -380,6 +938,34 @@ impl Database {
         Ok(())
     }
 
+    pub(crate) fn update_transaction_amount(
+        &self,
+        transaction_id: i64,
+        amount: Decimal,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE transactions SET amount = ?1 WHERE id = ?2",
+            params![amount_to_units(amount)?, transaction_id],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn update_transaction_date(&self, transaction_id: i64, date: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE transactions SET date = ?1 WHERE id = ?2",
+            params![date, transaction_id],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn set_transaction_tags(&self, transaction_id: i64, tags: &[String]) -> Result<()> {
+        self.conn.execute(
+            "UPDATE transactions SET tags = ?1 WHERE id = ?2",
+            params![tags.join(","), transaction_id],
+        )?;
+        Ok(())
+    }
+
     pub(crate) fn delete_transaction(&self, id: i64) -> Result<()> {
         self.conn
             .execute("DELETE FROM transactions WHERE id = ?1", params![id])?;
@@ -396,16 +982,93 @@ impl Database {
         Ok(count)
     }
 
+    /// Indices of `txns` that look like transactions already in the database:
+    /// same account, date, and amount, with a description that matches once
+    /// case, punctuation, and spacing are ignored. Each existing transaction
+    /// accounts for at most one incoming row, so two identical purchases on
+    /// the same day are only flagged if both are already present.
+    pub(crate) fn find_probable_duplicates(&self, txns: &[Transaction]) -> Result<Vec<usize>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT amount, description, original_description FROM transactions
+             WHERE account_id = ?1 AND date = ?2",
+        )?;
+        // Existing (amount, description, original description) per account and date
+        let mut existing: std::collections::HashMap<(i64, String), Vec<ExistingTxn>> =
+            std::collections::HashMap::new();
+        let mut duplicates = Vec::new();
+
+        for (i, txn) in txns.iter().enumerate() {
+            let key = (txn.account_id, txn.date.clone());
+            if !existing.contains_key(&key) {
+                let rows = stmt
+                    .query_map(params![txn.account_id, txn.date], |row| {
+                        Ok((
+                            amount_from_units(row.get(0)?),
+                            normalize_description(&row.get::<_, String>(1)?),
+                            normalize_description(&row.get::<_, String>(2)?),
+                        ))
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                existing.insert(key.clone(), rows);
+            }
+            let candidates = existing.entry(key).or_default();
+
+            let description = normalize_description(&txn.description);
+            let original = normalize_description(&txn.original_description);
+            let matched = candidates.iter().position(|(amount, desc, orig)| {
+                *amount == txn.amount.round_dp(AMOUNT_SCALE)
+                    && ((!original.is_empty() && (original == *orig || original == *desc))
+                        || (!description.is_empty() && description == *desc))
+            });
+            if let Some(pos) = matched {
+                candidates.swap_remove(pos);
+                duplicates.push(i);
+            }
+        }
+        Ok(duplicates)
+    }
+
+    /// Balance after each transaction in an account, keyed by transaction id:
+    /// the opening balance plus every amount up to and including that row,
+    /// oldest first. Same-day rows are ordered by id.
+    pub(crate) fn get_running_balances(
+        &self,
+        account_id: i64,
+    ) -> Result<std::collections::HashMap<i64, Decimal>> {
+        let opening = self
+            .get_account_by_id(account_id)?
+            .map(|a| a.opening_balance)
+            .unwrap_or_default();
+        let mut stmt = self.conn.prepare(
+            "SELECT id, amount FROM transactions WHERE account_id = ?1 ORDER BY date, id",
+        )?;
+        let rows = stmt.query_map(params![account_id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        let mut balance = opening;
+        let mut balances = std::collections::HashMap::new();
+        for row in rows {
+            let (id, amount) = row?;
+            balance += amount_from_units(amount);
+            balances.insert(id, balance);
+        }
+        Ok(balances)
+    }
+
     pub(crate) fn get_all_transactions_for_export(
         &self,
         month: Option<&str>,
+        account_id: Option<i64>,
     ) -> Result<Vec<Transaction>> {
-        let mut sql = format!("SELECT {TXN_COLUMNS} FROM transactions t");
+        let mut sql = format!("SELECT {TXN_COLUMNS} FROM transactions t WHERE 1=1");
         let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
         if let Some(m) = month {
-            let ph = push_param(&mut p, Box::new(format!("{m}%")));
-            sql.push_str(&format!(" WHERE t.date LIKE {ph}"));
+            push_period_filter(&mut p, &mut sql, "t.date", m, self.budget_start_day())?;
+        }
+        if let Some(aid) = account_id {
+            let ph = push_param(&mut p, Box::new(aid));
+            sql.push_str(&format!(" AND t.account_id = {ph}"));
         }
         sql.push_str(" ORDER BY t.date DESC, t.id DESC");
 
@@ -420,11 +1083,14 @@ impl Database {
     pub(crate) fn get_categories(&self) -> Result<Vec<Category>> {
         let mut stmt = self
             .conn
-            .prepare("SELECT id, name FROM categories ORDER BY name")?;
+            .prepare("SELECT id, name, parent_id, icon, color FROM categories ORDER BY name")?;
         let rows = stmt.query_map([], |row| {
             Ok(Category {
                 id: Some(row.get(0)?),
                 name: row.get(1)?,
+                parent_id: row.get(2)?,
+                icon: row.get(3)?,
+                color: row.get(4)?,
             })
         })?;
         Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
@@ -432,23 +1098,92 @@ impl Database {
 
     pub(crate) fn insert_category(&self, cat: &Category) -> Result<i64> {
         self.conn.execute(
-            "INSERT INTO categories (name) VALUES (?1)",
-            params![cat.name],
+            "INSERT INTO categories (name, parent_id, icon, color) VALUES (?1, ?2, ?3, ?4)",
+            params![cat.name, cat.parent_id, cat.icon, cat.color],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
 
-    // ── Budgets ───────────────────────────────────────────────
+    pub(crate) fn update_category_parent(
+        &self,
+        category_id: i64,
+        parent_id: Option<i64>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE categories SET parent_id = ?1 WHERE id = ?2",
+            params![parent_id, category_id],
+        )?;
+        Ok(())
+    }
 
-    pub(crate) fn get_budgets(&self, month: Option<&str>) -> Result<Vec<Budget>> {
-        let mut sql = String::from("SELECT id, category_id, month, limit_amount FROM budgets");
-        let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-        if let Some(m) = month {
-            let ph = push_param(&mut p, Box::new(m.to_string()));
-            sql.push_str(&format!(" WHERE month = {ph}"));
-        }
-        sql.push_str(" ORDER BY month DESC");
-        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
+    /// Set a category's icon and color. Empty strings clear them.
+    pub(crate) fn update_category_style(
+        &self,
+        category_id: i64,
+        icon: &str,
+        color: &str,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE categories SET icon = ?1, color = ?2 WHERE id = ?3",
+            params![icon, color, category_id],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn get_category_transaction_count(&self, category_id: i64) -> Result<i64> {
+        Ok(self.conn.query_row(
+            "SELECT COUNT(*) FROM transactions WHERE category_id = ?1",
+            params![category_id],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Delete a category. Its transactions become uncategorized, its budgets
+    /// and rules are removed, and its subcategories move up to its parent.
+    /// The seed "Uncategorized" category cannot be deleted.
+    pub(crate) fn delete_category(&mut self, id: i64) -> Result<()> {
+        let (name, parent_id): (String, Option<i64>) = self.conn.query_row(
+            "SELECT name, parent_id FROM categories WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        if name == UNCATEGORIZED {
+            anyhow::bail!("The '{UNCATEGORIZED}' category cannot be deleted");
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "UPDATE transactions SET category_id = NULL WHERE category_id = ?1",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM budgets WHERE category_id = ?1", params![id])?;
+        tx.execute(
+            "DELETE FROM import_rules WHERE category_id = ?1",
+            params![id],
+        )?;
+        tx.execute(
+            "UPDATE categories SET parent_id = ?1 WHERE parent_id = ?2",
+            params![parent_id, id],
+        )?;
+        tx.execute("DELETE FROM categories WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // ── Budgets ───────────────────────────────────────────────
+
+    pub(crate) fn get_budgets(&self, month: Option<&str>) -> Result<Vec<Budget>> {
+        let mut sql = String::from(
+            "SELECT id, category_id, month, limit_amount, include_subcategories, period, rollover
+             FROM budgets",
+        );
+        let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        if let Some(m) = month {
+            let ph = push_param(&mut p, Box::new(m.to_string()));
+            sql.push_str(&format!(" WHERE month = {ph}"));
+        }
+        sql.push_str(" ORDER BY month DESC");
+        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
         let mut stmt = self.conn.prepare(&sql)?;
         let rows = stmt.query_map(refs.as_slice(), |row| {
             let amt_str: String = row.get(3)?;
@@ -457,6 +1192,9 @@ impl Database {
                 category_id: row.get(1)?,
                 month: row.get(2)?,
                 limit_amount: parse_decimal(&amt_str),
+                include_subcategories: row.get(4)?,
+                period: BudgetPeriod::parse(&row.get::<_, String>(5)?),
+                rollover: row.get(6)?,
             })
         })?;
         Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
@@ -464,18 +1202,190 @@ impl Database {
 
     pub(crate) fn upsert_budget(&self, budget: &Budget) -> Result<i64> {
         self.conn.execute(
-            "INSERT INTO budgets (category_id, month, limit_amount)
-             VALUES (?1, ?2, ?3)
+            "INSERT INTO budgets (category_id, month, limit_amount, include_subcategories, period, rollover)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
              ON CONFLICT(category_id, month) DO UPDATE SET limit_amount = ?3",
             params![
                 budget.category_id,
                 budget.month,
                 budget.limit_amount.to_string(),
+                budget.include_subcategories,
+                budget.period.as_str(),
+                budget.rollover,
             ],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
 
+    pub(crate) fn set_budget_include_subcategories(&self, id: i64, include: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE budgets SET include_subcategories = ?1 WHERE id = ?2",
+            params![include, id],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn set_budget_rollover(&self, id: i64, rollover: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE budgets SET rollover = ?1 WHERE id = ?2",
+            params![rollover, id],
+        )?;
+        Ok(())
+    }
+
+    /// Amount carried into `month` (`YYYY-MM`) from the unbroken run of
+    /// rollover-enabled monthly budgets for the category immediately before
+    /// it. Each month contributes `limit - spent`, so overspending carries
+    /// forward as a negative amount.
+    pub(crate) fn get_budget_rollover(&self, category_id: i64, month: &str) -> Result<Decimal> {
+        let mut stmt = self.conn.prepare(
+            "SELECT month, limit_amount, include_subcategories FROM budgets
+             WHERE category_id = ?1 AND period = 'Monthly' AND rollover = 1 AND month < ?2
+             ORDER BY month DESC",
+        )?;
+        let rows = stmt
+            .query_map(params![category_id, month], |row| {
+                let amt_str: String = row.get(1)?;
+                Ok((
+                    row.get::<_, String>(0)?,
+                    parse_decimal(&amt_str),
+                    row.get::<_, bool>(2)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let categories = self.get_categories()?;
+        let mut expected = previous_month(month);
+        let mut total = Decimal::ZERO;
+        for (budget_month, limit, include_subcategories) in rows {
+            if expected.as_deref() != Some(budget_month.as_str()) {
+                break;
+            }
+            let mut ids = vec![category_id];
+            if include_subcategories {
+                ids.extend(Category::descendant_ids(&categories, category_id));
+            }
+            total += limit - self.get_category_spent(&ids, &budget_month)?;
+            expected = previous_month(&budget_month);
+        }
+        Ok(total)
+    }
+
+    /// Spend counted against `budget` in its own month or year: the
+    /// category's, plus its descendants' when the budget includes them.
+    pub(crate) fn get_budget_spent(&self, budget: &Budget) -> Result<Decimal> {
+        let mut ids = vec![budget.category_id];
+        if budget.include_subcategories {
+            ids.extend(Category::descendant_ids(
+                &self.get_categories()?,
+                budget.category_id,
+            ));
+        }
+        self.get_category_spent(&ids, &budget.month)
+    }
+
+    /// Absolute spend across `category_ids` during a `YYYY-MM` month or a
+    /// `YYYY` year.
+    fn get_category_spent(&self, category_ids: &[i64], month: &str) -> Result<Decimal> {
+        let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        let placeholders: Vec<String> = category_ids
+            .iter()
+            .map(|id| push_param(&mut p, Box::new(*id)))
+            .collect();
+        let mut sql = format!(
+            "SELECT COALESCE(SUM(amount), 0) FROM transactions
+             WHERE category_id IN ({}) AND amount < 0 AND is_transfer = 0",
+            placeholders.join(",")
+        );
+        push_period_filter(&mut p, &mut sql, "date", month, self.budget_start_day())?;
+        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
+        let total: i64 = self
+            .conn
+            .query_row(&sql, refs.as_slice(), |row| row.get(0))?;
+        Ok(amount_from_units(total).abs())
+    }
+
+    /// Average monthly spend in a category over the `months` budget months
+    /// before this one, as a positive amount. Zero with no history.
+    pub(crate) fn suggest_budget(&self, category_id: i64, months: usize) -> Result<Decimal> {
+        let today = chrono::Local::now().date_naive();
+        let this_month = budget_month_of(today, self.budget_start_day());
+        self.suggest_budget_before(category_id, months, &this_month)
+    }
+
+    /// [`Self::suggest_budget`] for the `months` months before `month`.
+    fn suggest_budget_before(
+        &self,
+        category_id: i64,
+        months: usize,
+        month: &str,
+    ) -> Result<Decimal> {
+        if months == 0 {
+            return Ok(Decimal::ZERO);
+        }
+        let mut total = Decimal::ZERO;
+        let mut month = month.to_string();
+        for _ in 0..months {
+            match previous_month(&month) {
+                Some(m) => month = m,
+                None => anyhow::bail!("Invalid month '{month}' (expected YYYY-MM)"),
+            }
+            total += self.get_category_spent(&[category_id], &month)?;
+        }
+        Ok((total / Decimal::from(months)).round_dp(2))
+    }
+
+    /// Copy the monthly budgets of `from` (`YYYY-MM`) into `to`, keeping their
+    /// subcategory and rollover settings. Categories already budgeted in `to`
+    /// keep their limit unless `force` is set. Returns how many budgets were
+    /// created or overwritten.
+    pub(crate) fn copy_budgets(&self, from: &str, to: &str, force: bool) -> Result<usize> {
+        let conflict = if force {
+            "DO UPDATE SET limit_amount = excluded.limit_amount,
+                include_subcategories = excluded.include_subcategories,
+                rollover = excluded.rollover"
+        } else {
+            "DO NOTHING"
+        };
+        let sql = format!(
+            "INSERT INTO budgets (category_id, month, limit_amount, include_subcategories, period, rollover)
+             SELECT category_id, ?2, limit_amount, include_subcategories, period, rollover
+             FROM budgets WHERE month = ?1 AND period = 'Monthly'
+             ON CONFLICT(category_id, month) {conflict}"
+        );
+        Ok(self.conn.execute(&sql, params![from, to])?)
+    }
+
+    /// Start an empty month from the previous month's budgets, so a recurring
+    /// set doesn't have to be re-entered. Each month is filled at most once:
+    /// a month whose budgets were all deleted stays empty. Returns the month
+    /// copied from and the number of budgets created.
+    pub(crate) fn fill_budgets_from_previous_month(
+        &self,
+        month: &str,
+    ) -> Result<Option<(String, usize)>> {
+        let filled_through = self.get_app_state("budgets_filled_through")?;
+        if filled_through.is_some_and(|m| m.as_str() >= month) {
+            return Ok(None);
+        }
+        let prev = match previous_month(month) {
+            Some(prev) => prev,
+            None => return Ok(None),
+        };
+        let has_budgets = |m: &str| -> Result<bool> {
+            Ok(self
+                .get_budgets(Some(m))?
+                .iter()
+                .any(|b| b.period == BudgetPeriod::Monthly))
+        };
+        if has_budgets(month)? || !has_budgets(&prev)? {
+            return Ok(None);
+        }
+        let copied = self.copy_budgets(&prev, month, false)?;
+        self.set_app_state("budgets_filled_through", month)?;
+        Ok(Some((prev, copied)))
+    }
+
     pub(crate) fn delete_budget(&self, id: i64) -> Result<()> {
         self.conn
             .execute("DELETE FROM budgets WHERE id = ?1", params![id])?;
@@ -486,7 +1396,10 @@ impl Database {
 
     pub(crate) fn get_import_rules(&self) -> Result<Vec<ImportRule>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, pattern, category_id, is_regex, priority FROM import_rules ORDER BY priority DESC, pattern",
+            "SELECT id, pattern, category_id, is_regex, priority, whole_word, source, created_at,
+                    last_matched_at
+             FROM import_rules
+             ORDER BY priority DESC, pattern",
         )?;
         let rows = stmt.query_map([], |row| {
             Ok(ImportRule {
@@ -495,42 +1408,505 @@ impl Database {
                 category_id: row.get(2)?,
                 is_regex: row.get(3)?,
                 priority: row.get(4)?,
+                whole_word: row.get(5)?,
+                source: RuleSource::parse(&row.get::<_, String>(6)?),
+                created_at: row.get(7)?,
+                last_matched_at: row.get(8)?,
             })
         })?;
         Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
     }
 
+    /// Non-transfer transactions with no category, or filed under the seed
+    /// "Uncategorized" category, oldest first.
+    pub(crate) fn get_uncategorized_transactions(&self) -> Result<Vec<Transaction>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {TXN_COLUMNS} FROM transactions t
+             LEFT JOIN categories c ON t.category_id = c.id
+             WHERE t.is_transfer = 0 AND (t.category_id IS NULL OR c.name = ?1)
+             ORDER BY t.date, t.id"
+        ))?;
+        let rows = stmt.query_map(params![UNCATEGORIZED], row_to_transaction)?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// The newest import batch that still has transactions, if any.
+    pub(crate) fn last_import_batch(&self) -> Result<Option<i64>> {
+        Ok(self
+            .conn
+            .query_row("SELECT MAX(import_batch) FROM transactions", [], |row| {
+                row.get(0)
+            })?)
+    }
+
+    /// Non-transfer transactions from `batch` that have no category or the
+    /// seed "Uncategorized" one, oldest first.
+    pub(crate) fn get_uncategorized_in_batch(&self, batch: i64) -> Result<Vec<Transaction>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {TXN_COLUMNS} FROM transactions t
+             LEFT JOIN categories c ON t.category_id = c.id
+             WHERE t.import_batch = ?1 AND t.is_transfer = 0
+               AND (t.category_id IS NULL OR c.name = ?2)
+             ORDER BY t.date, t.id"
+        ))?;
+        let rows = stmt.query_map(params![batch, UNCATEGORIZED], row_to_transaction)?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Run the import rules over every uncategorized transaction and save
+    /// the matches. Returns how many transactions were given a category.
+    pub(crate) fn recategorize_uncategorized(&mut self) -> Result<usize> {
+        let (categorizer, _) = crate::categorize::Categorizer::new(&self.get_import_rules()?);
+        self.categorize_existing(&categorizer)
+    }
+
+    /// Categorize existing uncategorized transactions that match `rule`.
+    /// Returns how many were updated.
+    pub(crate) fn apply_rule_to_existing(&mut self, rule: &ImportRule) -> Result<usize> {
+        let (categorizer, bad_patterns) =
+            crate::categorize::Categorizer::new(std::slice::from_ref(rule));
+        if !bad_patterns.is_empty() {
+            anyhow::bail!("Invalid regex: {}", rule.pattern);
+        }
+        self.categorize_existing(&categorizer)
+    }
+
+    fn categorize_existing(
+        &mut self,
+        categorizer: &crate::categorize::Categorizer,
+    ) -> Result<usize> {
+        let txns = self.get_uncategorized_transactions()?;
+
+        let tx = self.conn.transaction()?;
+        let mut count = 0;
+        let mut matched = Vec::new();
+        for txn in &txns {
+            if let Some((category_id, rule)) = categorizer.categorize(&txn.original_description) {
+                count += tx.execute(
+                    "UPDATE transactions SET category_id = ?1 WHERE id = ?2",
+                    params![category_id, txn.id],
+                )?;
+                matched.extend(rule.rule_id);
+            }
+        }
+        stamp_rules_matched(&tx, &matched)?;
+        tx.commit()?;
+        Ok(count)
+    }
+
+    /// Record that each rule in `rule_ids` just categorized transactions
+    /// that were stored. Repeated ids are stamped once.
+    pub(crate) fn mark_rules_matched(&mut self, rule_ids: &[i64]) -> Result<()> {
+        let mut ids = rule_ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        let tx = self.conn.transaction()?;
+        stamp_rules_matched(&tx, &ids)?;
+        tx.commit()?;
+        Ok(())
+    }
+
     pub(crate) fn insert_import_rule(&self, rule: &ImportRule) -> Result<i64> {
         self.conn.execute(
-            "INSERT INTO import_rules (pattern, category_id, is_regex, priority)
-             VALUES (?1, ?2, ?3, ?4)",
-            params![rule.pattern, rule.category_id, rule.is_regex, rule.priority],
+            "INSERT INTO import_rules (pattern, category_id, is_regex, priority, whole_word,
+                                       source, created_at, last_matched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                rule.pattern,
+                rule.category_id,
+                rule.is_regex,
+                rule.priority,
+                rule.whole_word,
+                rule.source.as_str(),
+                rule.created_at,
+                rule.last_matched_at
+            ],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
 
+    pub(crate) fn update_import_rule(&self, rule: &ImportRule) -> Result<()> {
+        let id = rule
+            .id
+            .ok_or_else(|| anyhow::anyhow!("Cannot update rule without ID"))?;
+        self.conn.execute(
+            "UPDATE import_rules
+             SET pattern = ?1, category_id = ?2, is_regex = ?3, priority = ?4, whole_word = ?5
+             WHERE id = ?6",
+            params![
+                rule.pattern,
+                rule.category_id,
+                rule.is_regex,
+                rule.priority,
+                rule.whole_word,
+                id
+            ],
+        )?;
+        Ok(())
+    }
+
     pub(crate) fn delete_import_rule(&self, id: i64) -> Result<()> {
         self.conn
             .execute("DELETE FROM import_rules WHERE id = ?1", params![id])?;
         Ok(())
     }
 
+    /// How many rules [`Self::delete_unmatched_rules`] would remove.
+    pub(crate) fn count_unmatched_rules(&self, before: &str) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM import_rules WHERE {UNMATCHED_RULES}"),
+            params![before],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Delete rules that haven't matched since `before` (`YYYY-MM-DD`),
+    /// including ones that never matched at all. A rule created on or after
+    /// `before` is kept even if unmatched, since it hasn't had the chance.
+    /// Returns how many were deleted.
+    pub(crate) fn delete_unmatched_rules(&self, before: &str) -> Result<usize> {
+        Ok(self.conn.execute(
+            &format!("DELETE FROM import_rules WHERE {UNMATCHED_RULES}"),
+            params![before],
+        )?)
+    }
+
+    // ── Exchange Rates ────────────────────────────────────────
+
+    pub(crate) fn get_exchange_rates(&self) -> Result<Vec<ExchangeRate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT from_currency, to_currency, rate, as_of FROM exchange_rates
+             ORDER BY from_currency, to_currency",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let rate_str: String = row.get(2)?;
+            Ok(ExchangeRate {
+                from_currency: row.get(0)?,
+                to_currency: row.get(1)?,
+                rate: parse_decimal(&rate_str),
+                as_of: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    pub(crate) fn upsert_exchange_rate(&self, rate: &ExchangeRate) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO exchange_rates (from_currency, to_currency, rate, as_of)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(from_currency, to_currency) DO UPDATE SET rate = ?3, as_of = ?4",
+            params![
+                rate.from_currency,
+                rate.to_currency,
+                rate.rate.to_string(),
+                rate.as_of,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ── UI State ──────────────────────────────────────────────
+
+    fn get_app_state(&self, key: &str) -> Result<Option<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT value FROM app_state WHERE key = ?1")?;
+        let mut rows = stmt.query_map(params![key], |row| row.get::<_, String>(0))?;
+        Ok(rows.next().transpose()?)
+    }
+
+    fn set_app_state(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO app_state (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = ?2",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Remember where the TUI was left. An all-time view is stored as an
+    /// empty month.
+    pub(crate) fn save_ui_state(&self, state: &UiState) -> Result<()> {
+        if let Some(screen) = &state.screen {
+            self.set_app_state("screen", screen)?;
+        }
+        if let Some(month) = &state.month {
+            self.set_app_state("month", month.as_deref().unwrap_or(""))?;
+        }
+        if let Some(index) = state.account_index {
+            self.set_app_state("account_index", &index.to_string())?;
+        }
+        if let Some(size) = state.page_size {
+            self.set_app_state("page_size", &size.to_string())?;
+        }
+        if let Some(month_only) = state.search_month_only {
+            let scope = if month_only { "month" } else { "all" };
+            self.set_app_state("search_scope", scope)?;
+        }
+        if let Some(clean) = state.clean_names {
+            self.set_app_state("clean_names", if clean { "on" } else { "off" })?;
+        }
+        if let Some(group) = state.group_by_day {
+            self.set_app_state("group_by_day", if group { "on" } else { "off" })?;
+        }
+        if let Some(scoped) = state.dashboard_scoped {
+            let scope = if scoped { "account" } else { "all" };
+            self.set_app_state("dashboard_scope", scope)?;
+        }
+        if let Some(day) = state.budget_start_day {
+            self.set_app_state("budget_start_day", &day.to_string())?;
+            self.budget_start_day.set(day);
+        }
+        if let Some(confirm) = state.confirm_quit {
+            self.set_app_state("confirm_quit", if confirm { "on" } else { "off" })?;
+        }
+        Ok(())
+    }
+
+    /// Day of the month budget months start on; 1 for calendar months.
+    pub(crate) fn budget_start_day(&self) -> u32 {
+        self.budget_start_day.get()
+    }
+
+    /// SQL expression for the `YYYY-MM` budget month of `column`, shifted so
+    /// that months start on `budget_start_day`.
+    fn month_label_sql(&self, column: &str) -> String {
+        format!(
+            "strftime('%Y-%m', {column}, '-{} days')",
+            self.budget_start_day() - 1
+        )
+    }
+
+    /// The saved UI state. Missing or unreadable values come back as `None`
+    /// so the caller keeps its defaults.
+    pub(crate) fn load_ui_state(&self) -> Result<UiState> {
+        let month = self.get_app_state("month")?.and_then(|m| {
+            if m.is_empty() {
+                Some(None)
+            } else if m.len() == 7
+                && chrono::NaiveDate::parse_from_str(&format!("{m}-01"), "%Y-%m-%d").is_ok()
+            {
+                Some(Some(m))
+            } else {
+                None
+            }
+        });
+        Ok(UiState {
+            screen: self.get_app_state("screen")?,
+            month,
+            account_index: self
+                .get_app_state("account_index")?
+                .and_then(|i| i.parse().ok()),
+            page_size: self
+                .get_app_state("page_size")?
+                .and_then(|s| s.parse().ok())
+                .filter(|s| PAGE_SIZE_RANGE.contains(s)),
+            search_month_only: match self.get_app_state("search_scope")?.as_deref() {
+                Some("month") => Some(true),
+                Some("all") => Some(false),
+                _ => None,
+            },
+            clean_names: match self.get_app_state("clean_names")?.as_deref() {
+                Some("on") => Some(true),
+                Some("off") => Some(false),
+                _ => None,
+            },
+            group_by_day: match self.get_app_state("group_by_day")?.as_deref() {
+                Some("on") => Some(true),
+                Some("off") => Some(false),
+                _ => None,
+            },
+            dashboard_scoped: match self.get_app_state("dashboard_scope")?.as_deref() {
+                Some("account") => Some(true),
+                Some("all") => Some(false),
+                _ => None,
+            },
+            budget_start_day: self
+                .get_app_state("budget_start_day")?
+                .and_then(|d| d.parse().ok())
+                .filter(|d| BUDGET_START_DAY_RANGE.contains(d)),
+            confirm_quit: match self.get_app_state("confirm_quit")?.as_deref() {
+                Some("on") => Some(true),
+                Some("off") => Some(false),
+                _ => None,
+            },
+        })
+    }
+
+    // ── Recent directories ────────────────────────────────────
+
+    /// Record `path` as the most recently used import directory, keeping
+    /// only the newest [`RECENT_DIRS_KEPT`].
+    pub(crate) fn push_recent_dir(&self, path: &str) -> Result<()> {
+        // A counter rather than a timestamp, so two pushes in the same
+        // second still order correctly.
+        self.conn.execute(
+            "INSERT INTO recent_dirs (path, used_at)
+             VALUES (?1, (SELECT COALESCE(MAX(used_at), 0) + 1 FROM recent_dirs))
+             ON CONFLICT(path) DO UPDATE SET used_at = excluded.used_at",
+            params![path],
+        )?;
+        self.conn.execute(
+            "DELETE FROM recent_dirs WHERE path NOT IN
+             (SELECT path FROM recent_dirs ORDER BY used_at DESC LIMIT ?1)",
+            params![RECENT_DIRS_KEPT as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Recently used import directories, newest first.
+    pub(crate) fn get_recent_dirs(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path FROM recent_dirs ORDER BY used_at DESC")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    // ── Import profiles ───────────────────────────────────────
+
+    /// Remember `profile` as the mapping for imports into `account_id` from
+    /// files with this header row, replacing any saved before.
+    pub(crate) fn save_account_profile(
+        &self,
+        account_id: i64,
+        headers: &[String],
+        profile: &CsvProfile,
+        flip_signs: bool,
+    ) -> Result<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO account_import_profiles ({ACCOUNT_PROFILE_COLUMNS}, used_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15,
+                         (SELECT COALESCE(MAX(used_at), 0) + 1 FROM account_import_profiles))"
+            ),
+            params![
+                account_id,
+                serde_json::to_string(headers)?,
+                profile.name,
+                profile.date_column,
+                profile.description_column,
+                profile.amount_column,
+                profile.debit_column,
+                profile.credit_column,
+                profile.date_format,
+                profile.has_header,
+                profile.skip_rows,
+                profile.sign_convention.as_str(),
+                profile.is_credit_account,
+                profile.delimiter,
+                flip_signs,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn get_account_profile(&self, account_id: i64) -> Result<Option<AccountProfile>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ACCOUNT_PROFILE_COLUMNS} FROM account_import_profiles WHERE account_id = ?1"
+        ))?;
+        let mut rows = stmt.query_map(params![account_id], row_to_account_profile)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// The most recently used profile saved from a file with exactly this
+    /// header row, skipping archived accounts.
+    pub(crate) fn find_account_profile(
+        &self,
+        headers: &[String],
+    ) -> Result<Option<AccountProfile>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {ACCOUNT_PROFILE_COLUMNS} FROM account_import_profiles
+             WHERE headers = ?1
+               AND account_id IN (SELECT id FROM accounts WHERE archived = 0)
+             ORDER BY used_at DESC LIMIT 1"
+        ))?;
+        let mut rows = stmt.query_map(
+            params![serde_json::to_string(headers)?],
+            row_to_account_profile,
+        )?;
+        Ok(rows.next().transpose()?)
+    }
+
+    // ── Date formats ──────────────────────────────────────────
+
+    /// Date formats added with `:add-date-format`, oldest first.
+    pub(crate) fn get_custom_date_formats(&self) -> Result<Vec<String>> {
+        Ok(self
+            .get_app_state("date_formats")?
+            .map(|s| s.lines().map(str::to_string).collect())
+            .unwrap_or_default())
+    }
+
+    /// Remember `fmt` for the column mapper. Returns false if it was
+    /// already saved.
+    pub(crate) fn add_custom_date_format(&self, fmt: &str) -> Result<bool> {
+        let mut formats = self.get_custom_date_formats()?;
+        if formats.iter().any(|f| f == fmt) {
+            return Ok(false);
+        }
+        formats.push(fmt.to_string());
+        self.set_app_state("date_formats", &formats.join("\n"))?;
+        Ok(true)
+    }
+
+    // ── Command history ───────────────────────────────────────
+
+    /// Commands run from the command bar, oldest first.
+    pub(crate) fn get_command_history(&self) -> Result<Vec<String>> {
+        Ok(self
+            .get_app_state("command_history")?
+            .map(|s| s.lines().map(str::to_string).collect())
+            .unwrap_or_default())
+    }
+
+    /// Save the command bar history, keeping only the newest
+    /// [`COMMAND_HISTORY_KEPT`] entries.
+    pub(crate) fn set_command_history(&self, history: &[String]) -> Result<()> {
+        let start = history.len().saturating_sub(COMMAND_HISTORY_KEPT);
+        self.set_app_state("command_history", &history[start..].join("\n"))
+    }
+
     // ── Analytics ─────────────────────────────────────────────
 
     pub(crate) fn get_spending_by_category(
         &self,
         month: Option<&str>,
+    ) -> Result<Vec<(String, Decimal)>> {
+        self.spending_by_category(month, None)
+    }
+
+    /// [`Self::get_spending_by_category`] for one account's transactions.
+    pub(crate) fn get_spending_by_account_category(
+        &self,
+        account_id: i64,
+        month: Option<&str>,
+    ) -> Result<Vec<(String, Decimal)>> {
+        self.spending_by_category(month, Some(account_id))
+    }
+
+    /// Spending per category in `month` (or all time), largest first,
+    /// optionally limited to one account. Transfers and `:buy` positions
+    /// are left out.
+    fn spending_by_category(
+        &self,
+        month: Option<&str>,
+        account_id: Option<i64>,
     ) -> Result<Vec<(String, Decimal)>> {
         let mut sql = String::from(
-            "SELECT COALESCE(c.name, 'Uncategorized'), CAST(SUM(t.amount) AS TEXT)
+            "SELECT COALESCE(c.name, 'Uncategorized'), SUM(t.amount)
              FROM transactions t
              LEFT JOIN categories c ON t.category_id = c.id
-             WHERE CAST(t.amount AS REAL) < 0",
+             WHERE t.amount < 0 AND t.is_transfer = 0 AND t.quantity IS NULL",
         );
         let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        if let Some(aid) = account_id {
+            let ph = push_param(&mut p, Box::new(aid));
+            sql.push_str(&format!(" AND t.account_id = {ph}"));
+        }
         if let Some(m) = month {
-            let ph = push_param(&mut p, Box::new(format!("{m}%")));
-            sql.push_str(&format!(" AND t.date LIKE {ph}"));
+            push_period_filter(&mut p, &mut sql, "t.date", m, self.budget_start_day())?;
         }
         sql.push_str(
             " GROUP BY COALESCE(c.name, 'Uncategorized')
@@ -540,38 +1916,241 @@ impl Database {
         let mut stmt = self.conn.prepare(&sql)?;
         let rows = stmt.query_map(refs.as_slice(), |row| {
             let name: String = row.get(0)?;
-            let amt_str: String = row.get(1)?;
-            Ok((name, parse_decimal(&amt_str)))
+            let total: i64 = row.get(1)?;
+            Ok((name, amount_from_units(total)))
         })?;
         Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
     }
 
+    /// Spending per category in `month` with the change from the month
+    /// before, both as positive amounts: `(name, spent, change)`. A category
+    /// with nothing spent last month shows its full amount as the change.
+    pub(crate) fn get_category_spending_delta(
+        &self,
+        month: &str,
+        account_id: Option<i64>,
+    ) -> Result<Vec<(String, Decimal, Decimal)>> {
+        let previous = match previous_month(month) {
+            Some(m) => self.spending_by_category(Some(&m), account_id)?,
+            None => Vec::new(),
+        };
+        Ok(self
+            .spending_by_category(Some(month), account_id)?
+            .into_iter()
+            .map(|(name, amount)| {
+                let before = previous
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, a)| a.abs())
+                    .unwrap_or(Decimal::ZERO);
+                let spent = amount.abs();
+                (name, spent, spent - before)
+            })
+            .collect())
+    }
+
     pub(crate) fn get_monthly_totals(&self, month: Option<&str>) -> Result<(Decimal, Decimal)> {
         let query_sum = |sign: &str| -> Result<Decimal> {
             let mut sql = format!(
-                "SELECT CAST(COALESCE(SUM(amount), 0) AS TEXT) FROM transactions WHERE CAST(amount AS REAL) {sign} 0"
+                "SELECT COALESCE(SUM(amount), 0) FROM transactions
+                 WHERE is_transfer = 0 AND quantity IS NULL AND amount {sign} 0"
             );
             let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
             if let Some(m) = month {
-                let ph = push_param(&mut p, Box::new(format!("{m}%")));
-                sql.push_str(&format!(" AND date LIKE {ph}"));
+                push_period_filter(&mut p, &mut sql, "date", m, self.budget_start_day())?;
             }
             let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
-            let val: String = self
+            let val: i64 = self
                 .conn
                 .query_row(&sql, refs.as_slice(), |row| row.get(0))?;
-            Ok(parse_decimal(&val))
+            Ok(amount_from_units(val))
         };
         Ok((query_sum(">")?, query_sum("<")?))
     }
 
+    /// Spending in `month` (or all time) that still needs a category, as a
+    /// positive total and the number of transactions behind it. Counts the
+    /// same rows as `CategoryFilter::Uncategorized`, less any income.
+    pub(crate) fn get_uncategorized_summary(
+        &self,
+        month: Option<&str>,
+        account_id: Option<i64>,
+    ) -> Result<(Decimal, i64)> {
+        let mut sql = String::from(
+            "SELECT COALESCE(-SUM(t.amount), 0), COUNT(*)
+             FROM transactions t LEFT JOIN categories c ON t.category_id = c.id
+             WHERE (t.category_id IS NULL OR c.name = ?1)
+               AND t.is_transfer = 0 AND t.quantity IS NULL AND t.amount < 0",
+        );
+        let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(UNCATEGORIZED)];
+        if let Some(aid) = account_id {
+            let ph = push_param(&mut p, Box::new(aid));
+            sql.push_str(&format!(" AND t.account_id = {ph}"));
+        }
+        if let Some(m) = month {
+            push_period_filter(&mut p, &mut sql, "t.date", m, self.budget_start_day())?;
+        }
+        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
+        let (total, count): (i64, i64) = self
+            .conn
+            .query_row(&sql, refs.as_slice(), |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok((amount_from_units(total), count))
+    }
+
+    /// Transfers in `month` (or all time), which the income, expense, and
+    /// spending figures leave out, optionally in one account only.
+    pub(crate) fn get_transfer_count(
+        &self,
+        month: Option<&str>,
+        account_id: Option<i64>,
+    ) -> Result<usize> {
+        let mut sql = String::from("SELECT COUNT(*) FROM transactions WHERE is_transfer = 1");
+        let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        if let Some(aid) = account_id {
+            let ph = push_param(&mut p, Box::new(aid));
+            sql.push_str(&format!(" AND account_id = {ph}"));
+        }
+        if let Some(m) = month {
+            push_period_filter(&mut p, &mut sql, "date", m, self.budget_start_day())?;
+        }
+        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
+        let count: i64 = self
+            .conn
+            .query_row(&sql, refs.as_slice(), |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// Net worth in `BASE_CURRENCY`. Balances in currencies without an
+    /// exchange rate are excluded — see `get_unconverted_balances`.
     pub(crate) fn get_net_worth(&self) -> Result<Decimal> {
-        let total: String = self.conn.query_row(
-            "SELECT CAST(COALESCE(SUM(amount), 0) AS TEXT) FROM transactions",
-            [],
-            |row| row.get(0),
-        )?;
-        Ok(parse_decimal(&total))
+        let balances = self.get_balances_by_currency(None)?;
+        let rates = self.get_exchange_rates()?;
+        Ok(ExchangeRate::convert_balances(&balances, &rates, BASE_CURRENCY).0)
+    }
+
+    /// Net worth at the end of each of the last `months` months, ending with
+    /// the current month, oldest first.
+    pub(crate) fn get_net_worth_history(&self, months: usize) -> Result<Vec<(String, Decimal)>> {
+        let today = chrono::Local::now().date_naive();
+        let this_month = budget_month_of(today, self.budget_start_day());
+        self.get_net_worth_history_until(months, &this_month)
+    }
+
+    /// Running net worth for the `months` months ending with `last_month`.
+    /// Months with no transactions carry the prior balance forward.
+    fn get_net_worth_history_until(
+        &self,
+        months: usize,
+        last_month: &str,
+    ) -> Result<Vec<(String, Decimal)>> {
+        let mut labels: Vec<String> = Vec::with_capacity(months);
+        let mut month = Some(last_month.to_string());
+        while labels.len() < months {
+            match month {
+                Some(m) => {
+                    month = previous_month(&m);
+                    labels.push(m);
+                }
+                None => break,
+            }
+        }
+        labels.reverse();
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} AS month, a.currency,
+                    SUM(t.amount)
+             FROM transactions t JOIN accounts a ON t.account_id = a.id
+             GROUP BY month, a.currency
+             ORDER BY month",
+            self.month_label_sql("t.date")
+        ))?;
+        let monthly = stmt
+            .query_map([], |row| {
+                let month: String = row.get(0)?;
+                let currency: String = row.get(1)?;
+                let total: i64 = row.get(2)?;
+                Ok((month, currency, amount_from_units(total)))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        // Per-currency running totals, starting from opening balances
+        let mut running: Vec<(String, Decimal)> = Vec::new();
+        let add =
+            |running: &mut Vec<(String, Decimal)>, currency: &str, amount: Decimal| match running
+                .iter_mut()
+                .find(|(c, _)| c == currency)
+            {
+                Some((_, total)) => *total += amount,
+                None => running.push((currency.to_string(), amount)),
+            };
+        for account in self.get_accounts(true)? {
+            add(&mut running, &account.currency, account.opening_balance);
+        }
+
+        let rates = self.get_exchange_rates()?;
+        let mut monthly = monthly.into_iter().peekable();
+        let mut history = Vec::with_capacity(labels.len());
+        for label in labels {
+            while let Some((_, currency, amount)) = monthly.next_if(|(m, _, _)| *m <= label) {
+                add(&mut running, &currency, amount);
+            }
+            let (net_worth, _) = ExchangeRate::convert_balances(&running, &rates, BASE_CURRENCY);
+            history.push((label, net_worth));
+        }
+        Ok(history)
+    }
+
+    /// Per-currency balances that could not be converted to `BASE_CURRENCY`.
+    pub(crate) fn get_unconverted_balances(&self) -> Result<Vec<(String, Decimal)>> {
+        let balances = self.get_balances_by_currency(None)?;
+        let rates = self.get_exchange_rates()?;
+        Ok(ExchangeRate::convert_balances(&balances, &rates, BASE_CURRENCY).1)
+    }
+
+    /// Opening balances plus transactions, grouped by account currency and
+    /// optionally limited to the given account types.
+    fn get_balances_by_currency(
+        &self,
+        account_types: Option<&[&str]>,
+    ) -> Result<Vec<(String, Decimal)>> {
+        let mut sql = String::from(
+            "SELECT a.currency, COALESCE(SUM(t.amount), 0)
+             FROM transactions t JOIN accounts a ON t.account_id = a.id",
+        );
+        let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        if let Some(types) = account_types {
+            let placeholders: Vec<String> = types
+                .iter()
+                .map(|at| push_param(&mut p, Box::new(at.to_string())))
+                .collect();
+            sql.push_str(&format!(
+                " WHERE a.account_type IN ({})",
+                placeholders.join(",")
+            ));
+        }
+        sql.push_str(" GROUP BY a.currency ORDER BY a.currency");
+        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(refs.as_slice(), |row| {
+            let currency: String = row.get(0)?;
+            let total: i64 = row.get(1)?;
+            Ok((currency, amount_from_units(total)))
+        })?;
+        let mut balances = rows.collect::<std::result::Result<Vec<_>, _>>()?;
+
+        for account in self.get_accounts(true)? {
+            if account.opening_balance.is_zero()
+                || account_types
+                    .is_some_and(|types| !types.contains(&account.account_type.as_str()))
+            {
+                continue;
+            }
+            match balances.iter_mut().find(|(c, _)| *c == account.currency) {
+                Some((_, total)) => *total += account.opening_balance,
+                None => balances.push((account.currency, account.opening_balance)),
+            }
+        }
+        Ok(balances)
     }
 
     /// Monthly income/expenses filtered by account type(s).
@@ -580,17 +2159,16 @@ impl Database {
         month: Option<&str>,
         account_types: &[&str],
     ) -> Result<(Decimal, Decimal)> {
-        let build_params = |sign: &str| -> (String, Vec<Box<dyn rusqlite::types::ToSql>>) {
+        let build_params = |sign: &str| -> Result<(String, Vec<Box<dyn rusqlite::types::ToSql>>)> {
             let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
             let mut sql = String::from(
-                "SELECT CAST(COALESCE(SUM(t.amount), 0) AS TEXT)
+                "SELECT COALESCE(SUM(t.amount), 0)
                  FROM transactions t JOIN accounts a ON t.account_id = a.id
-                 WHERE CAST(t.amount AS REAL)",
+                 WHERE t.is_transfer = 0 AND t.quantity IS NULL AND t.amount",
             );
             sql.push_str(&format!(" {sign} 0"));
             if let Some(m) = month {
-                let ph = push_param(&mut p, Box::new(format!("{m}%")));
-                sql.push_str(&format!(" AND t.date LIKE {ph}"));
+                push_period_filter(&mut p, &mut sql, "t.date", m, self.budget_start_day())?;
             }
             let placeholders: String = account_types
                 .iter()
@@ -598,44 +2176,32 @@ impl Database {
                 .collect::<Vec<_>>()
                 .join(",");
             sql.push_str(&format!(" AND a.account_type IN ({placeholders})"));
-            (sql, p)
+            Ok((sql, p))
         };
 
         let query_sum = |sign: &str| -> Result<Decimal> {
-            let (sql, p) = build_params(sign);
+            let (sql, p) = build_params(sign)?;
             let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
-            let val: String = self
+            let val: i64 = self
                 .conn
                 .query_row(&sql, refs.as_slice(), |row| row.get(0))?;
-            Ok(parse_decimal(&val))
+            Ok(amount_from_units(val))
         };
 
         Ok((query_sum(">")?, query_sum("<")?))
     }
 
     /// All-time balance for accounts of the given type(s).
+    /// Balance of the given account types in `BASE_CURRENCY`.
     pub(crate) fn get_balance_by_account_type(&self, account_types: &[&str]) -> Result<Decimal> {
-        let placeholders: String = (0..account_types.len())
-            .map(|i| format!("?{}", i + 1))
-            .collect::<Vec<_>>()
-            .join(",");
-        let sql = format!(
-            "SELECT CAST(COALESCE(SUM(t.amount), 0) AS TEXT)
-             FROM transactions t JOIN accounts a ON t.account_id = a.id
-             WHERE a.account_type IN ({placeholders})"
-        );
-        let p: Vec<Box<dyn rusqlite::types::ToSql>> = account_types
-            .iter()
-            .map(|at| Box::new(at.to_string()) as _)
-            .collect();
-        let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
-        let total: String = self
-            .conn
-            .query_row(&sql, refs.as_slice(), |row| row.get(0))?;
-        Ok(parse_decimal(&total))
+        let balances = self.get_balances_by_currency(Some(account_types))?;
+        let rates = self.get_exchange_rates()?;
+        Ok(ExchangeRate::convert_balances(&balances, &rates, BASE_CURRENCY).0)
     }
 
     /// Income/expenses for a single account, optionally filtered by month.
+    /// Transfers and positions bought with `:buy` aren't income or spending
+    /// and are left out, as in the all-accounts totals.
     pub(crate) fn get_account_monthly_totals(
         &self,
         account_id: i64,
@@ -643,76 +2209,126 @@ impl Database {
     ) -> Result<(Decimal, Decimal)> {
         let query_sum = |sign: &str| -> Result<Decimal> {
             let mut sql = format!(
-                "SELECT CAST(COALESCE(SUM(amount), 0) AS TEXT) FROM transactions WHERE account_id = ?1 AND CAST(amount AS REAL) {sign} 0"
+                "SELECT COALESCE(SUM(amount), 0) FROM transactions
+                 WHERE account_id = ?1 AND is_transfer = 0 AND quantity IS NULL AND amount {sign} 0"
             );
             let mut p: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
             p.push(Box::new(account_id));
             if let Some(m) = month {
-                let ph = push_param(&mut p, Box::new(format!("{m}%")));
-                sql.push_str(&format!(" AND date LIKE {ph}"));
+                push_period_filter(&mut p, &mut sql, "date", m, self.budget_start_day())?;
             }
             let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
-            let val: String = self
+            let val: i64 = self
                 .conn
                 .query_row(&sql, refs.as_slice(), |row| row.get(0))?;
-            Ok(parse_decimal(&val))
+            Ok(amount_from_units(val))
         };
         Ok((query_sum(">")?, query_sum("<")?))
     }
 
     /// All-time balance for a single account.
+    /// Opening balance plus the sum of the account's transactions.
     pub(crate) fn get_account_balance(&self, account_id: i64) -> Result<Decimal> {
-        let total: String = self.conn.query_row(
-            "SELECT CAST(COALESCE(SUM(amount), 0) AS TEXT) FROM transactions WHERE account_id = ?1",
+        let total: i64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0) FROM transactions WHERE account_id = ?1",
+            params![account_id],
+            |row| row.get(0),
+        )?;
+        let opening = self
+            .get_account_by_id(account_id)?
+            .map(|a| a.opening_balance)
+            .unwrap_or(Decimal::ZERO);
+        Ok(opening + amount_from_units(total))
+    }
+
+    /// What has gone into positions logged with `:buy` in an account, at
+    /// the price paid.
+    pub(crate) fn get_invested_total(&self, account_id: i64) -> Result<Decimal> {
+        let total: i64 = self.conn.query_row(
+            "SELECT COALESCE(-SUM(amount), 0) FROM transactions
+             WHERE account_id = ?1 AND quantity IS NOT NULL",
             params![account_id],
             |row| row.get(0),
         )?;
-        Ok(parse_decimal(&total))
+        Ok(amount_from_units(total))
     }
 
     pub(crate) fn get_monthly_trend(
         &self,
         months: usize,
     ) -> Result<Vec<(String, Decimal, Decimal)>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT strftime('%Y-%m', date) as month,
-                    CAST(SUM(CASE WHEN CAST(amount AS REAL) > 0 THEN amount ELSE 0 END) AS TEXT) as income,
-                    CAST(SUM(CASE WHEN CAST(amount AS REAL) < 0 THEN amount ELSE 0 END) AS TEXT) as expenses
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} as month,
+                    SUM(CASE WHEN amount > 0 THEN amount ELSE 0 END) as income,
+                    SUM(CASE WHEN amount < 0 THEN amount ELSE 0 END) as expenses
              FROM transactions
+             WHERE is_transfer = 0 AND quantity IS NULL
              GROUP BY month
              ORDER BY month DESC
              LIMIT ?1",
-        )?;
+            self.month_label_sql("date")
+        ))?;
         let rows = stmt.query_map(params![months as i64], |row| {
             let month: String = row.get(0)?;
-            let inc_str: String = row.get(1)?;
-            let exp_str: String = row.get(2)?;
-            Ok((month, parse_decimal(&inc_str), parse_decimal(&exp_str)))
+            let income: i64 = row.get(1)?;
+            let expenses: i64 = row.get(2)?;
+            Ok((
+                month,
+                amount_from_units(income),
+                amount_from_units(expenses),
+            ))
         })?;
         let mut result: Vec<_> = rows.collect::<std::result::Result<Vec<_>, _>>()?;
         result.reverse();
         Ok(result)
     }
 
-    /// Export transactions to a CSV file. Returns the number of transactions written.
-    pub(crate) fn export_to_csv(&self, path: &str, month: Option<&str>) -> Result<usize> {
-        let txns = self.get_all_transactions_for_export(month)?;
+    /// Income and expenses for each Monday-based week of `month` (YYYY-MM),
+    /// keyed by the week's Monday (YYYY-MM-DD), oldest first. Weeks without
+    /// transactions are omitted. A week straddling two months only counts the
+    /// days inside `month`, matching the month filter elsewhere.
+    pub(crate) fn get_weekly_totals(&self, month: &str) -> Result<Vec<(String, Decimal, Decimal)>> {
+        let (start, end) = match period_range(month, self.budget_start_day()) {
+            Some(range) => range,
+            None => anyhow::bail!("Invalid month '{month}' (expected YYYY-MM)"),
+        };
+        let mut stmt = self.conn.prepare(
+            "SELECT date(date, '-6 days', 'weekday 1') as week_start,
+                    SUM(CASE WHEN amount > 0 THEN amount ELSE 0 END) as income,
+                    SUM(CASE WHEN amount < 0 THEN amount ELSE 0 END) as expenses
+             FROM transactions
+             WHERE is_transfer = 0 AND quantity IS NULL AND date >= ?1 AND date < ?2
+             GROUP BY week_start
+             ORDER BY week_start",
+        )?;
+        let rows = stmt.query_map(params![start, end], |row| {
+            let week: String = row.get(0)?;
+            let income: i64 = row.get(1)?;
+            let expenses: i64 = row.get(2)?;
+            Ok((week, amount_from_units(income), amount_from_units(expenses)))
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Export transactions to a CSV file, optionally limited to one account.
+    /// Returns the number of transactions written.
+    pub(crate) fn export_to_csv(
+        &self,
+        path: &str,
+        month: Option<&str>,
+        account_id: Option<i64>,
+        columns: &[ExportColumn],
+    ) -> Result<usize> {
+        let txns = self.get_all_transactions_for_export(month, account_id)?;
         if txns.is_empty() {
             return Ok(0);
         }
 
         let categories = self.get_categories()?;
-        let accounts = self.get_accounts()?;
+        let accounts = self.get_accounts(true)?;
 
         let mut wtr = csv::Writer::from_path(path).context("Failed to create export file")?;
-        wtr.write_record([
-            "Date",
-            "Description",
-            "Amount",
-            "Category",
-            "Account",
-            "Notes",
-        ])?;
+        wtr.write_record(columns.iter().map(|c| c.header()))?;
 
         for txn in &txns {
             let cat_name = txn
@@ -725,19 +2341,127 @@ impl Database {
                 .find(|a| a.id == Some(txn.account_id))
                 .map(|a| a.name.as_str())
                 .unwrap_or("");
-            wtr.write_record([
-                &txn.date,
-                &txn.description,
-                &txn.amount.to_string(),
-                cat_name,
-                acct_name,
-                &txn.notes,
-            ])?;
+            wtr.write_record(columns.iter().map(|column| match column {
+                ExportColumn::Date => txn.date.clone(),
+                ExportColumn::Description => txn.description.clone(),
+                ExportColumn::OriginalDescription => txn.original_description.clone(),
+                ExportColumn::Amount => txn.amount.to_string(),
+                ExportColumn::Category => cat_name.to_string(),
+                ExportColumn::Account => acct_name.to_string(),
+                ExportColumn::Notes => txn.notes.clone(),
+                ExportColumn::Tags => txn.tags_string(),
+                ExportColumn::IsTransfer => txn.is_transfer.to_string(),
+                ExportColumn::Quantity => txn.quantity.map(|q| q.to_string()).unwrap_or_default(),
+                ExportColumn::Symbol => txn.symbol.clone().unwrap_or_default(),
+            }))?;
         }
 
         wtr.flush()?;
         Ok(txns.len())
     }
+
+    /// Export transactions to a QIF file for Quicken, GnuCash and other
+    /// tools that read it. Each account gets an `!Account` block naming it,
+    /// followed by its own `!Type:` section, so a multi-account export
+    /// imports back into separate registers. Dates are written
+    /// `MM/DD/YYYY`, amounts with two decimals, and subcategories as
+    /// `Parent:Child`. A transfer leg gets `L[Other Account]` naming the
+    /// account its pair landed in; a transfer without a recorded pair has
+    /// no `L` line, since QIF readers would create an account for any
+    /// bracketed name. Returns the number of transactions written.
+    pub(crate) fn export_to_qif(
+        &self,
+        path: &str,
+        month: Option<&str>,
+        account_id: Option<i64>,
+    ) -> Result<usize> {
+        let txns = self.get_all_transactions_for_export(month, account_id)?;
+        if txns.is_empty() {
+            return Ok(0);
+        }
+
+        let categories = self.get_categories()?;
+        let accounts = self.get_accounts(true)?;
+        let counterparts = self.transfer_counterparts()?;
+        // QIF fields are one line each
+        let one_line = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let mut out = String::new();
+        for account in &accounts {
+            let mut rows = txns
+                .iter()
+                .filter(|t| Some(t.account_id) == account.id)
+                .peekable();
+            if rows.peek().is_none() {
+                continue;
+            }
+            let kind = qif_account_type(&account.account_type);
+            out.push_str(&format!(
+                "!Account\nN{}\nT{kind}\n^\n!Type:{kind}\n",
+                one_line(&account.name)
+            ));
+            for txn in rows {
+                let date = chrono::NaiveDate::parse_from_str(&txn.date, "%Y-%m-%d")
+                    .map(|d| d.format("%m/%d/%Y").to_string())
+                    .unwrap_or_else(|_| txn.date.clone());
+                out.push_str(&format!("D{date}\n"));
+                out.push_str(&format!("T{:.2}\n", txn.amount.round_dp(2)));
+                out.push_str(&format!("P{}\n", one_line(&txn.description)));
+                let category = txn
+                    .category_id
+                    .and_then(|cid| Category::find_by_id(&categories, cid))
+                    .map(|c| {
+                        match c
+                            .parent_id
+                            .and_then(|pid| Category::find_by_id(&categories, pid))
+                        {
+                            Some(parent) => format!("{}:{}", parent.name, c.name),
+                            None => c.name.clone(),
+                        }
+                    });
+                if txn.is_transfer {
+                    if let Some(name) = txn.id.and_then(|id| counterparts.get(&id)) {
+                        out.push_str(&format!("L[{}]\n", one_line(name)));
+                    }
+                } else if let Some(category) = category {
+                    out.push_str(&format!("L{}\n", one_line(&category)));
+                }
+                if !txn.notes.is_empty() {
+                    out.push_str(&format!("M{}\n", one_line(&txn.notes)));
+                }
+                out.push_str("^\n");
+            }
+        }
+
+        std::fs::write(path, out).context("Failed to create export file")?;
+        Ok(txns.len())
+    }
+
+    /// The name of the account on the other side of each paired transfer
+    /// leg, keyed by the leg's transaction id.
+    fn transfer_counterparts(&self) -> Result<std::collections::HashMap<i64, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.id, a.name FROM transactions t
+             JOIN transactions o ON o.transfer_pair_id = t.transfer_pair_id AND o.id != t.id
+             JOIN accounts a ON a.id = o.account_id
+             WHERE t.transfer_pair_id IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<std::result::Result<_, _>>()?)
+    }
+}
+
+/// The QIF `!Type:` for an account's register. Investment accounts are
+/// written as plain asset registers, since the `Invst` layout has its own
+/// fields.
+fn qif_account_type(account_type: &AccountType) -> &'static str {
+    match account_type {
+        AccountType::Checking | AccountType::Savings => "Bank",
+        AccountType::CreditCard => "CCard",
+        AccountType::Cash => "Cash",
+        AccountType::Loan => "Oth L",
+        AccountType::Investment | AccountType::Other => "Oth A",
+    }
 }
 
 #[cfg(test)]
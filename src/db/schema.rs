@@ -10,13 +10,17 @@ CREATE TABLE IF NOT EXISTS accounts (
     institution TEXT NOT NULL DEFAULT '',
     currency    TEXT NOT NULL DEFAULT 'USD',
     notes       TEXT NOT NULL DEFAULT '',
-    created_at  TEXT NOT NULL
+    created_at  TEXT NOT NULL,
+    opening_balance TEXT NOT NULL DEFAULT '0',
+    archived    BOOLEAN NOT NULL DEFAULT 0
 );
 
 CREATE TABLE IF NOT EXISTS categories (
     id        INTEGER PRIMARY KEY AUTOINCREMENT,
     name      TEXT NOT NULL UNIQUE,
-    parent_id INTEGER REFERENCES categories(id)
+    parent_id INTEGER REFERENCES categories(id),
+    icon      TEXT NOT NULL DEFAULT '',
+    color     TEXT NOT NULL DEFAULT ''
 );
 
 CREATE TABLE IF NOT EXISTS transactions (
@@ -25,17 +29,24 @@ CREATE TABLE IF NOT EXISTS transactions (
     date                  TEXT NOT NULL,
     description           TEXT NOT NULL,
     original_description  TEXT NOT NULL DEFAULT '',
-    amount                TEXT NOT NULL,
+    amount                INTEGER NOT NULL, -- ten-thousandths, see AMOUNT_SCALE
     category_id           INTEGER REFERENCES categories(id),
     notes                 TEXT NOT NULL DEFAULT '',
     is_transfer           BOOLEAN NOT NULL DEFAULT 0,
     import_hash           TEXT NOT NULL DEFAULT '',
-    created_at            TEXT NOT NULL
+    created_at            TEXT NOT NULL,
+    tags                  TEXT NOT NULL DEFAULT '',
+    quantity              TEXT,
+    symbol                TEXT,
+    import_batch          INTEGER,
+    transfer_pair_id      INTEGER,
+    flagged               BOOLEAN NOT NULL DEFAULT 0
 );
 
 CREATE INDEX IF NOT EXISTS idx_transactions_date ON transactions(date);
 CREATE INDEX IF NOT EXISTS idx_transactions_account ON transactions(account_id);
 CREATE INDEX IF NOT EXISTS idx_transactions_category ON transactions(category_id);
+CREATE INDEX IF NOT EXISTS idx_transactions_account_date ON transactions(account_id, date);
 CREATE INDEX IF NOT EXISTS idx_transactions_hash ON transactions(import_hash);
 CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_hash_unique ON transactions(import_hash) WHERE import_hash != '';
 
@@ -44,6 +55,9 @@ CREATE TABLE IF NOT EXISTS budgets (
     category_id   INTEGER NOT NULL REFERENCES categories(id),
     month         TEXT NOT NULL,
     limit_amount  TEXT NOT NULL,
+    include_subcategories BOOLEAN NOT NULL DEFAULT 0,
+    period        TEXT NOT NULL DEFAULT 'Monthly',
+    rollover      BOOLEAN NOT NULL DEFAULT 0,
     UNIQUE(category_id, month)
 );
 
@@ -52,17 +66,173 @@ CREATE TABLE IF NOT EXISTS import_rules (
     pattern     TEXT NOT NULL,
     category_id INTEGER NOT NULL REFERENCES categories(id),
     is_regex    BOOLEAN NOT NULL DEFAULT 0,
-    priority    INTEGER NOT NULL DEFAULT 0
+    priority    INTEGER NOT NULL DEFAULT 0,
+    whole_word  BOOLEAN NOT NULL DEFAULT 0,
+    source      TEXT NOT NULL DEFAULT 'manual',
+    created_at  TEXT NOT NULL DEFAULT '',
+    last_matched_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS exchange_rates (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    from_currency TEXT NOT NULL,
+    to_currency   TEXT NOT NULL,
+    rate          TEXT NOT NULL,
+    as_of         TEXT NOT NULL,
+    UNIQUE(from_currency, to_currency)
+);
+
+CREATE TABLE IF NOT EXISTS app_state (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS recent_dirs (
+    path    TEXT PRIMARY KEY,
+    used_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS account_import_profiles (
+    account_id          INTEGER PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    headers             TEXT NOT NULL, -- JSON array of the file's header row
+    name                TEXT NOT NULL,
+    date_column         INTEGER NOT NULL,
+    description_column  INTEGER NOT NULL,
+    amount_column       INTEGER,
+    debit_column        INTEGER,
+    credit_column       INTEGER,
+    date_format         TEXT NOT NULL,
+    has_header          BOOLEAN NOT NULL,
+    skip_rows           INTEGER NOT NULL,
+    sign_convention     TEXT NOT NULL,
+    is_credit_account   BOOLEAN NOT NULL,
+    delimiter           INTEGER NOT NULL,
+    flip_signs          BOOLEAN NOT NULL,
+    used_at             INTEGER NOT NULL
 );
 
 "#;
 
-pub(crate) const CURRENT_VERSION: i32 = 1;
+pub(crate) const CURRENT_VERSION: i32 = 20;
+
+/// The migration that moves `transactions.amount` from decimal TEXT to
+/// scaled INTEGER. Its SQL only makes room for the new column; the values
+/// are converted in Rust by `Database::convert_amounts_to_units`.
+pub(crate) const AMOUNT_UNITS_VERSION: i32 = 15;
 
 /// Incremental migrations. Each entry is (target_version, sql) where
 /// `target_version` is the schema version that results from applying the SQL.
 /// A migration runs when the current DB version < target_version.
 pub(crate) const MIGRATIONS: &[(i32, &str)] = &[
-    // Future migrations go here:
-    // (2, "ALTER TABLE transactions ADD COLUMN recurring BOOLEAN NOT NULL DEFAULT 0;"),
+    (
+        2,
+        "ALTER TABLE budgets ADD COLUMN include_subcategories BOOLEAN NOT NULL DEFAULT 0;",
+    ),
+    (
+        3,
+        "CREATE TABLE IF NOT EXISTS exchange_rates (
+            id            INTEGER PRIMARY KEY AUTOINCREMENT,
+            from_currency TEXT NOT NULL,
+            to_currency   TEXT NOT NULL,
+            rate          TEXT NOT NULL,
+            as_of         TEXT NOT NULL,
+            UNIQUE(from_currency, to_currency)
+        );",
+    ),
+    (
+        4,
+        "ALTER TABLE budgets ADD COLUMN period TEXT NOT NULL DEFAULT 'Monthly';",
+    ),
+    (
+        5,
+        "ALTER TABLE budgets ADD COLUMN rollover BOOLEAN NOT NULL DEFAULT 0;",
+    ),
+    (
+        6,
+        "ALTER TABLE accounts ADD COLUMN opening_balance TEXT NOT NULL DEFAULT '0';",
+    ),
+    (
+        7,
+        "ALTER TABLE transactions ADD COLUMN tags TEXT NOT NULL DEFAULT '';",
+    ),
+    (
+        8,
+        "ALTER TABLE import_rules ADD COLUMN whole_word BOOLEAN NOT NULL DEFAULT 0;",
+    ),
+    (
+        9,
+        "CREATE TABLE IF NOT EXISTS app_state (
+            key   TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );",
+    ),
+    (
+        10,
+        "CREATE TABLE IF NOT EXISTS recent_dirs (
+            path    TEXT PRIMARY KEY,
+            used_at INTEGER NOT NULL
+        );",
+    ),
+    (
+        11,
+        "ALTER TABLE accounts ADD COLUMN archived BOOLEAN NOT NULL DEFAULT 0;",
+    ),
+    (
+        12,
+        "ALTER TABLE categories ADD COLUMN icon TEXT NOT NULL DEFAULT '';
+         ALTER TABLE categories ADD COLUMN color TEXT NOT NULL DEFAULT '';",
+    ),
+    (
+        13,
+        "ALTER TABLE transactions ADD COLUMN quantity TEXT;
+         ALTER TABLE transactions ADD COLUMN symbol TEXT;",
+    ),
+    (
+        14,
+        "CREATE INDEX IF NOT EXISTS idx_transactions_account_date ON transactions(account_id, date);",
+    ),
+    (
+        AMOUNT_UNITS_VERSION,
+        "ALTER TABLE transactions RENAME COLUMN amount TO amount_text;
+         ALTER TABLE transactions ADD COLUMN amount INTEGER NOT NULL DEFAULT 0;",
+    ),
+    (
+        16,
+        "ALTER TABLE transactions ADD COLUMN import_batch INTEGER;",
+    ),
+    (
+        17,
+        "ALTER TABLE transactions ADD COLUMN transfer_pair_id INTEGER;",
+    ),
+    (
+        18,
+        "CREATE TABLE IF NOT EXISTS account_import_profiles (
+            account_id          INTEGER PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+            headers             TEXT NOT NULL,
+            name                TEXT NOT NULL,
+            date_column         INTEGER NOT NULL,
+            description_column  INTEGER NOT NULL,
+            amount_column       INTEGER,
+            debit_column        INTEGER,
+            credit_column       INTEGER,
+            date_format         TEXT NOT NULL,
+            has_header          BOOLEAN NOT NULL,
+            skip_rows           INTEGER NOT NULL,
+            sign_convention     TEXT NOT NULL,
+            is_credit_account   BOOLEAN NOT NULL,
+            delimiter           INTEGER NOT NULL,
+            flip_signs          BOOLEAN NOT NULL,
+            used_at             INTEGER NOT NULL
+        );",
+    ),
+    (
+        19,
+        "ALTER TABLE transactions ADD COLUMN flagged BOOLEAN NOT NULL DEFAULT 0;",
+    ),
+    (
+        20,
+        "ALTER TABLE import_rules ADD COLUMN source TEXT NOT NULL DEFAULT 'manual';
+         ALTER TABLE import_rules ADD COLUMN created_at TEXT NOT NULL DEFAULT '';
+         ALTER TABLE import_rules ADD COLUMN last_matched_at TEXT;",
+    ),
 ];
@@ -36,10 +36,65 @@ fn test_account_crud() {
     assert!(fetched.is_some());
     assert_eq!(fetched.unwrap().name, "Test Bank");
 
-    let all = db.get_accounts().unwrap();
+    let all = db.get_accounts(true).unwrap();
     assert!(!all.is_empty());
 }
 
+#[test]
+fn test_update_account() {
+    let db = Database::open_in_memory().unwrap();
+    let account = Account::new("Chase".into(), AccountType::Checking, String::new());
+    let id = db.insert_account(&account).unwrap();
+
+    let mut fetched = db.get_account_by_id(id).unwrap().unwrap();
+    fetched.name = "Chase Sapphire".into();
+    fetched.account_type = AccountType::CreditCard;
+    db.update_account(&fetched).unwrap();
+
+    let updated = db.get_account_by_id(id).unwrap().unwrap();
+    assert_eq!(updated.name, "Chase Sapphire");
+    assert_eq!(updated.account_type, AccountType::CreditCard);
+}
+
+#[test]
+fn test_delete_account() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    let empty = Account::new("Empty".into(), AccountType::Savings, String::new());
+    let empty_id = db.insert_account(&empty).unwrap();
+
+    db.delete_account(empty_id, false).unwrap();
+    assert!(db.get_account_by_id(empty_id).unwrap().is_none());
+
+    // Refuses while transactions exist unless forced
+    assert!(db.delete_account(account_id, false).is_err());
+    assert_eq!(db.get_account_transaction_count(account_id).unwrap(), 4);
+
+    db.delete_account(account_id, true).unwrap();
+    assert!(db.get_account_by_id(account_id).unwrap().is_none());
+    assert_eq!(db.get_transaction_count().unwrap(), 0);
+}
+
+#[test]
+fn test_archived_accounts_hidden_but_counted() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    let net_worth = db.get_net_worth().unwrap();
+
+    db.set_account_archived(account_id, true).unwrap();
+    assert!(db.get_account_by_id(account_id).unwrap().unwrap().archived);
+    let active = db.get_accounts(false).unwrap();
+    assert!(active.iter().all(|a| a.id != Some(account_id)));
+    let all = db.get_accounts(true).unwrap();
+    assert!(all.iter().any(|a| a.id == Some(account_id)));
+    // History still counts
+    assert_eq!(db.get_net_worth().unwrap(), net_worth);
+
+    db.set_account_archived(account_id, false).unwrap();
+    let active = db.get_accounts(false).unwrap();
+    assert!(active.iter().any(|a| a.id == Some(account_id)));
+}
+
 #[test]
 fn test_account_by_id_not_found() {
     let db = Database::open_in_memory().unwrap();
@@ -61,7 +116,7 @@ fn test_multiple_accounts() {
     db.insert_account(&a2).unwrap();
     db.insert_account(&a3).unwrap();
 
-    let all = db.get_accounts().unwrap();
+    let all = db.get_accounts(true).unwrap();
     assert!(all.len() >= 3);
     // Accounts are sorted by name
     let names: Vec<&str> = all.iter().map(|a| a.name.as_str()).collect();
@@ -89,6 +144,10 @@ fn setup_test_data(db: &mut Database) -> i64 {
             is_transfer: false,
             import_hash: "hash-1".into(),
             created_at: "2024-01-10T00:00:00Z".into(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
         },
         Transaction {
             id: None,
@@ -102,6 +161,10 @@ fn setup_test_data(db: &mut Database) -> i64 {
             is_transfer: false,
             import_hash: "hash-2".into(),
             created_at: "2024-01-15T00:00:00Z".into(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
         },
         Transaction {
             id: None,
@@ -115,6 +178,10 @@ fn setup_test_data(db: &mut Database) -> i64 {
             is_transfer: false,
             import_hash: "hash-3".into(),
             created_at: "2024-01-20T00:00:00Z".into(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
         },
         Transaction {
             id: None,
@@ -128,6 +195,10 @@ fn setup_test_data(db: &mut Database) -> i64 {
             is_transfer: false,
             import_hash: "hash-4".into(),
             created_at: "2024-02-05T00:00:00Z".into(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
         },
     ];
 
@@ -156,6 +227,10 @@ fn test_transaction_insert_and_query() {
         is_transfer: false,
         import_hash: "test-hash-1".into(),
         created_at: "2024-01-15T00:00:00Z".into(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     };
 
     assert!(txn.is_expense());
@@ -172,7 +247,11 @@ fn test_transaction_insert_and_query() {
     assert_eq!(batch_count, 0); // duplicate skipped
 
     let txns = db
-        .get_transactions(Some(10), None, None, None, None, Some("2024-01"))
+        .get_transactions(&TransactionQuery {
+            limit: Some(10),
+            month: Some("2024-01"),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(txns.len(), 1);
 
@@ -180,14 +259,37 @@ fn test_transaction_insert_and_query() {
     db.update_transaction_description(txn_id, "My Coffee")
         .unwrap();
     let updated = db
-        .get_transactions(Some(1), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(1),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(updated[0].description, "My Coffee");
 
+    // Update amount
+    db.update_transaction_amount(txn_id, dec!(-5.25)).unwrap();
+    let updated = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(updated[0].amount, dec!(-5.25));
+
     // Update category
     let cats = db.get_categories().unwrap();
     let food_cat = cats.iter().find(|c| c.name == "Food & Dining").unwrap();
     db.update_transaction_category(txn_id, food_cat.id).unwrap();
+
+    // Update date
+    db.update_transaction_date(txn_id, "2024-02-29").unwrap();
+    let updated = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(updated[0].date, "2024-02-29");
 }
 
 #[test]
@@ -196,20 +298,32 @@ fn test_transaction_search() {
     setup_test_data(&mut db);
 
     let results = db
-        .get_transactions(Some(100), None, None, None, Some("coffee"), None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            search: Some("coffee"),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].description, "Starbucks Coffee");
 
     // Search by notes
     let results = db
-        .get_transactions(Some(100), None, None, None, Some("morning"), None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            search: Some("morning"),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(results.len(), 1);
 
     // Search by original description
     let results = db
-        .get_transactions(Some(100), None, None, None, Some("AMZN"), None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            search: Some("AMZN"),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(results.len(), 1);
 }
@@ -220,28 +334,72 @@ fn test_transaction_search_no_results() {
     setup_test_data(&mut db);
 
     let results = db
-        .get_transactions(Some(100), None, None, None, Some("nonexistent"), None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            search: Some("nonexistent"),
+            ..Default::default()
+        })
         .unwrap();
     assert!(results.is_empty());
 }
 
+#[test]
+fn test_flagged_transactions_filter() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let all = db.get_transactions(&TransactionQuery::default()).unwrap();
+    assert!(all.iter().all(|t| !t.flagged));
+    let id = all[0].id.unwrap();
+
+    db.set_transaction_flagged(id, true).unwrap();
+    let flagged = db
+        .get_transactions(&TransactionQuery {
+            flagged_only: true,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(flagged.len(), 1);
+    assert_eq!(flagged[0].id, Some(id));
+    assert!(flagged[0].flagged);
+
+    db.set_transaction_flagged(id, false).unwrap();
+    let flagged = db
+        .get_transactions(&TransactionQuery {
+            flagged_only: true,
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(flagged.is_empty());
+}
+
 #[test]
 fn test_transaction_month_filter() {
     let mut db = Database::open_in_memory().unwrap();
     setup_test_data(&mut db);
 
     let jan = db
-        .get_transactions(Some(100), None, None, None, None, Some("2024-01"))
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            month: Some("2024-01"),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(jan.len(), 3);
 
     let feb = db
-        .get_transactions(Some(100), None, None, None, None, Some("2024-02"))
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            month: Some("2024-02"),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(feb.len(), 1);
 
     let all = db
-        .get_transactions(Some(100), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(all.len(), 4);
 }
@@ -252,23 +410,187 @@ fn test_transaction_month_filter_no_results() {
     setup_test_data(&mut db);
 
     let results = db
-        .get_transactions(Some(100), None, None, None, None, Some("2025-06"))
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            month: Some("2025-06"),
+            ..Default::default()
+        })
         .unwrap();
     assert!(results.is_empty());
 }
 
+#[test]
+fn test_month_filter_boundaries() {
+    let db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+    for (i, date) in ["2023-12-31", "2024-01-01", "2024-01-31", "2024-02-01"]
+        .iter()
+        .enumerate()
+    {
+        db.insert_transaction(&Transaction {
+            id: None,
+            account_id,
+            date: date.to_string(),
+            description: format!("Purchase {i}"),
+            original_description: format!("PURCHASE {i}"),
+            amount: dec!(-10),
+            category_id: None,
+            notes: String::new(),
+            is_transfer: false,
+            import_hash: format!("boundary-{i}"),
+            created_at: format!("{date}T00:00:00Z"),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
+        })
+        .unwrap();
+    }
+
+    let dates = |month: &str| -> Vec<String> {
+        db.get_transactions(&TransactionQuery {
+            month: Some(month),
+            ..Default::default()
+        })
+        .unwrap()
+        .into_iter()
+        .map(|t| t.date)
+        .collect()
+    };
+    assert_eq!(dates("2024-01"), vec!["2024-01-31", "2024-01-01"]);
+    assert_eq!(dates("2024-02"), vec!["2024-02-01"]);
+    assert_eq!(dates("2023-12"), vec!["2023-12-31"]);
+
+    let (_, expenses) = db.get_monthly_totals(Some("2024-01")).unwrap();
+    assert_eq!(expenses, dec!(-20));
+    let spending = db.get_spending_by_category(Some("2024-01")).unwrap();
+    assert_eq!(spending, vec![("Uncategorized".to_string(), dec!(-20))]);
+    let exported = db
+        .get_all_transactions_for_export(Some("2024-01"), None)
+        .unwrap();
+    assert_eq!(exported.len(), 2);
+
+    // A whole year runs from Jan 1 up to the next Jan 1
+    let yearly = db.get_spending_by_category(Some("2024")).unwrap();
+    assert_eq!(yearly, vec![("Uncategorized".to_string(), dec!(-30))]);
+
+    assert!(db.get_monthly_totals(Some("2024-13")).is_err());
+}
+
+#[test]
+fn test_period_range() {
+    assert_eq!(
+        period_range("2024-01", 1),
+        Some(("2024-01-01".into(), "2024-02-01".into()))
+    );
+    assert_eq!(
+        period_range("2024-12", 1),
+        Some(("2024-12-01".into(), "2025-01-01".into()))
+    );
+    assert_eq!(
+        period_range("2024", 1),
+        Some(("2024-01-01".into(), "2025-01-01".into()))
+    );
+    assert_eq!(period_range("2024-1x", 1), None);
+
+    // Months starting on the 25th are named for the month they start in
+    assert_eq!(
+        period_range("2024-01", 25),
+        Some(("2024-01-25".into(), "2024-02-25".into()))
+    );
+    assert_eq!(
+        period_range("2024", 25),
+        Some(("2024-01-25".into(), "2025-01-25".into()))
+    );
+}
+
+#[test]
+fn test_budget_start_day_shifts_month_filters() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+    for (date, amount) in [
+        ("2024-01-24", dec!(-1)),
+        ("2024-01-25", dec!(-10)),
+        ("2024-02-24", dec!(-100)),
+        ("2024-02-25", dec!(-1000)),
+    ] {
+        db.insert_transaction(&Transaction {
+            id: None,
+            account_id,
+            date: date.into(),
+            description: "SHOP".into(),
+            original_description: "SHOP".into(),
+            amount,
+            category_id: None,
+            notes: String::new(),
+            is_transfer: false,
+            import_hash: format!("hash-{date}"),
+            created_at: format!("{date}T00:00:00Z"),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
+        })
+        .unwrap();
+    }
+    assert_eq!(
+        db.get_monthly_totals(Some("2024-01")).unwrap(),
+        (dec!(0), dec!(-11))
+    );
+
+    db.save_ui_state(&UiState {
+        budget_start_day: Some(25),
+        ..UiState::default()
+    })
+    .unwrap();
+    assert_eq!(db.budget_start_day(), 25);
+    assert_eq!(
+        db.get_monthly_totals(Some("2024-01")).unwrap(),
+        (dec!(0), dec!(-110))
+    );
+    let trend: Vec<(String, Decimal)> = db
+        .get_monthly_trend(12)
+        .unwrap()
+        .into_iter()
+        .map(|(month, _, expenses)| (month, expenses))
+        .collect();
+    assert_eq!(
+        trend,
+        vec![
+            ("2023-12".to_string(), dec!(-1)),
+            ("2024-01".to_string(), dec!(-110)),
+            ("2024-02".to_string(), dec!(-1000)),
+        ]
+    );
+
+    // Wiping data keeps settings
+    db.wipe_all_data().unwrap();
+    assert_eq!(db.budget_start_day(), 25);
+    assert_eq!(db.load_ui_state().unwrap().budget_start_day, Some(25));
+}
+
 #[test]
 fn test_transaction_account_filter() {
     let mut db = Database::open_in_memory().unwrap();
     let account_id = setup_test_data(&mut db);
 
     let results = db
-        .get_transactions(Some(100), None, Some(account_id), None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            account_id: Some(account_id),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(results.len(), 4);
 
     let results = db
-        .get_transactions(Some(100), None, Some(9999), None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            account_id: Some(9999),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(results.len(), 0);
 }
@@ -288,15 +610,134 @@ fn test_transaction_category_filter() {
 
     // Assign one transaction to a category
     let txns = db
-        .get_transactions(Some(100), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
         .unwrap();
     db.update_transaction_category(txns[0].id.unwrap(), Some(food_id))
         .unwrap();
 
     let filtered = db
-        .get_transactions(Some(100), None, None, Some(food_id), None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            category: Some(CategoryFilter::Uncategorized),
+            ..Default::default()
+        })
+        .unwrap();
+    let open = txns.iter().filter(|t| !t.is_transfer).count();
+    assert_eq!(filtered.len(), open - 1);
+    assert!(filtered.iter().all(|t| t.id != txns[0].id));
+}
+
+#[test]
+fn test_transaction_amount_and_date_range() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+
+    // Expenses of $40 or more
+    let results = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            max_amount: Some(dec!(-40)),
+            ..Default::default()
+        })
+        .unwrap();
+    let descs: Vec<&str> = results.iter().map(|t| t.description.as_str()).collect();
+    assert_eq!(descs, vec!["Grocery Store", "Amazon Purchase"]);
+
+    let results = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            min_amount: Some(dec!(-5.25)),
+            max_amount: Some(dec!(0)),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].description, "Starbucks Coffee");
+
+    // Exclusive bounds leave out a row sitting exactly on them
+    let results = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            min_amount: Some(dec!(-5.25)),
+            max_amount: Some(dec!(0)),
+            min_exclusive: true,
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(results.is_empty());
+
+    // Month-granular upper bound includes the whole month
+    let results = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            date_from: Some("2024-01-12"),
+            date_to: Some("2024-02"),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(results.len(), 3);
+
+    let results = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            date_to: Some("2024-01-10"),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(results.len(), 1);
+}
+
+#[test]
+fn test_transaction_tags() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+
+    let all = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
+        .unwrap();
+    let coffee = all
+        .iter()
+        .find(|t| t.description == "Starbucks Coffee")
+        .unwrap();
+    let amazon = all
+        .iter()
+        .find(|t| t.description == "Amazon Purchase")
+        .unwrap();
+    db.set_transaction_tags(coffee.id.unwrap(), &["work".into(), "reimbursable".into()])
+        .unwrap();
+    db.set_transaction_tags(amazon.id.unwrap(), &["workshop".into()])
         .unwrap();
-    assert_eq!(filtered.len(), 1);
+
+    let search = |db: &Database, tags: &[String]| {
+        db.get_transactions(&TransactionQuery {
+            limit: Some(100),
+            tags,
+            ..Default::default()
+        })
+        .unwrap()
+    };
+
+    // Whole-tag match only: #work must not match "workshop"
+    let results = search(&db, &["work".into()]);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].description, "Starbucks Coffee");
+    assert_eq!(results[0].tags, vec!["work", "reimbursable"]);
+
+    // Multiple tags are ANDed
+    assert_eq!(
+        search(&db, &["work".into(), "reimbursable".into()]).len(),
+        1
+    );
+    assert!(search(&db, &["work".into(), "workshop".into()]).is_empty());
+
+    db.set_transaction_tags(coffee.id.unwrap(), &[]).unwrap();
+    assert!(search(&db, &["work".into()]).is_empty());
 }
 
 #[test]
@@ -306,14 +747,13 @@ fn test_transaction_combined_filters() {
 
     // Search + month filter
     let results = db
-        .get_transactions(
-            Some(100),
-            None,
-            Some(account_id),
-            None,
-            Some("coffee"),
-            Some("2024-01"),
-        )
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            account_id: Some(account_id),
+            search: Some("coffee"),
+            month: Some("2024-01"),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].description, "Starbucks Coffee");
@@ -325,12 +765,19 @@ fn test_transaction_limit_offset() {
     setup_test_data(&mut db);
 
     let limited = db
-        .get_transactions(Some(2), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(2),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(limited.len(), 2);
 
     let offset = db
-        .get_transactions(Some(2), Some(2), None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(2),
+            offset: Some(2),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(offset.len(), 2);
 
@@ -344,7 +791,10 @@ fn test_transaction_delete() {
     setup_test_data(&mut db);
 
     let txns = db
-        .get_transactions(Some(100), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
         .unwrap();
     let count_before = txns.len();
     let id = txns[0].id.unwrap();
@@ -352,7 +802,10 @@ fn test_transaction_delete() {
     db.delete_transaction(id).unwrap();
 
     let txns = db
-        .get_transactions(Some(100), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(txns.len(), count_before - 1);
     assert!(!txns.iter().any(|t| t.id == Some(id)));
@@ -364,7 +817,10 @@ fn test_transaction_delete_batch() {
     setup_test_data(&mut db);
 
     let txns = db
-        .get_transactions(Some(100), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
         .unwrap();
     let count_before = txns.len();
     let ids: Vec<i64> = txns.iter().take(2).filter_map(|t| t.id).collect();
@@ -373,7 +829,10 @@ fn test_transaction_delete_batch() {
     assert_eq!(deleted, 2);
 
     let txns = db
-        .get_transactions(Some(100), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(txns.len(), count_before - 2);
     for id in &ids {
@@ -387,7 +846,10 @@ fn test_transaction_ordering() {
     setup_test_data(&mut db);
 
     let txns = db
-        .get_transactions(Some(100), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
         .unwrap();
     // Should be ordered by date DESC, id DESC
     for window in txns.windows(2) {
@@ -428,8 +890,108 @@ fn test_categories_sorted_by_name() {
     assert_eq!(names, sorted);
 }
 
+#[test]
+fn test_category_parent() {
+    let db = Database::open_in_memory().unwrap();
+    let cats = db.get_categories().unwrap();
+    let housing_id = Category::find_by_name(&cats, "Housing")
+        .unwrap()
+        .id
+        .unwrap();
+    let utilities_id = Category::find_by_name(&cats, "Utilities")
+        .unwrap()
+        .id
+        .unwrap();
+
+    db.update_category_parent(utilities_id, Some(housing_id))
+        .unwrap();
+    let cats = db.get_categories().unwrap();
+    let utilities = Category::find_by_id(&cats, utilities_id).unwrap();
+    assert_eq!(utilities.parent_id, Some(housing_id));
+
+    db.update_category_parent(utilities_id, None).unwrap();
+    let cats = db.get_categories().unwrap();
+    assert!(Category::find_by_id(&cats, utilities_id)
+        .unwrap()
+        .parent_id
+        .is_none());
+}
+
+#[test]
+fn test_update_category_style() {
+    let db = Database::open_in_memory().unwrap();
+    let food = category_id(&db, "Food & Dining");
+    db.update_category_style(food, "🍔", "#ff8800").unwrap();
+    let cats = db.get_categories().unwrap();
+    let cat = Category::find_by_id(&cats, food).unwrap();
+    assert_eq!(cat.icon, "🍔");
+    assert_eq!(cat.color, "#ff8800");
+
+    db.update_category_style(food, "", "").unwrap();
+    let cats = db.get_categories().unwrap();
+    assert!(Category::find_by_id(&cats, food).unwrap().icon.is_empty());
+}
+
 // ── Budget CRUD ───────────────────────────────────────────────
 
+#[test]
+fn test_delete_category() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let food_id = cats
+        .iter()
+        .find(|c| c.name == "Food & Dining")
+        .unwrap()
+        .id
+        .unwrap();
+    let coffee_id = cats
+        .iter()
+        .find(|c| c.name == "Coffee Shops")
+        .unwrap()
+        .id
+        .unwrap();
+    db.update_category_parent(coffee_id, Some(food_id)).unwrap();
+
+    let txns = db.get_transactions(&TransactionQuery::default()).unwrap();
+    let txn_id = txns[0].id.unwrap();
+    db.update_transaction_category(txn_id, Some(food_id))
+        .unwrap();
+    db.upsert_budget(&Budget::new(food_id, "2024-01".into(), dec!(500)))
+        .unwrap();
+    db.insert_import_rule(&ImportRule::new_contains("coffee".into(), food_id))
+        .unwrap();
+
+    db.delete_category(food_id).unwrap();
+
+    let cats = db.get_categories().unwrap();
+    assert!(cats.iter().all(|c| c.id != Some(food_id)));
+    let coffee = cats.iter().find(|c| c.id == Some(coffee_id)).unwrap();
+    assert_eq!(coffee.parent_id, None);
+    assert_eq!(db.get_category_transaction_count(food_id).unwrap(), 0);
+    assert!(db.get_budgets(Some("2024-01")).unwrap().is_empty());
+    assert!(db.get_import_rules().unwrap().is_empty());
+    assert_eq!(db.get_transaction_count().unwrap(), 4);
+}
+
+#[test]
+fn test_delete_uncategorized_rejected() {
+    let mut db = Database::open_in_memory().unwrap();
+    let cats = db.get_categories().unwrap();
+    let id = cats
+        .iter()
+        .find(|c| c.name == UNCATEGORIZED)
+        .unwrap()
+        .id
+        .unwrap();
+    assert!(db.delete_category(id).is_err());
+    assert!(db
+        .get_categories()
+        .unwrap()
+        .iter()
+        .any(|c| c.name == UNCATEGORIZED));
+}
+
 #[test]
 fn test_budget_crud() {
     let db = Database::open_in_memory().unwrap();
@@ -482,33 +1044,429 @@ fn test_budget_different_months() {
     assert_eq!(db.get_budgets(Some("2024-03")).unwrap().len(), 0);
 }
 
-// ── Import Rule CRUD ──────────────────────────────────────────
-
 #[test]
-fn test_import_rule_crud() {
-    let db = Database::open_in_memory().unwrap();
+fn test_budget_monthly_and_yearly_coexist() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
     let cats = db.get_categories().unwrap();
-    let shopping_id = cats
+    let food_id = cats
         .iter()
-        .find(|c| c.name == "Shopping")
+        .find(|c| c.name == "Food & Dining")
         .unwrap()
         .id
         .unwrap();
 
-    let rule = ImportRule::new_contains("amazon".into(), shopping_id);
-    let id = db.insert_import_rule(&rule).unwrap();
-    assert!(id > 0);
+    db.upsert_budget(&Budget::new(food_id, "2024-01".into(), dec!(500)))
+        .unwrap();
+    db.upsert_budget(&Budget::new_yearly(food_id, "2024".into(), dec!(6000)))
+        .unwrap();
 
-    let regex_rule = ImportRule::new_regex("^AMZN.*".into(), shopping_id);
-    let regex_id = db.insert_import_rule(&regex_rule).unwrap();
-    assert!(regex_id > 0);
+    let monthly = db.get_budgets(Some("2024-01")).unwrap();
+    assert_eq!(monthly.len(), 1);
+    assert_eq!(monthly[0].period, BudgetPeriod::Monthly);
 
-    let rules = db.get_import_rules().unwrap();
-    assert!(rules.len() >= 2);
+    let yearly = db.get_budgets(Some("2024")).unwrap();
+    assert_eq!(yearly.len(), 1);
+    assert_eq!(yearly[0].period, BudgetPeriod::Yearly);
+    assert_eq!(yearly[0].limit_amount, dec!(6000));
 
-    db.delete_import_rule(id).unwrap();
-    let rules = db.get_import_rules().unwrap();
-    assert!(rules.iter().all(|r| r.pattern != "amazon"));
+    for txn in db.get_all_transactions_for_export(None, None).unwrap() {
+        db.update_transaction_category(txn.id.unwrap(), Some(food_id))
+            .unwrap();
+    }
+    // Yearly spend covers every month in the year
+    assert_eq!(db.get_budget_spent(&yearly[0]).unwrap(), dec!(135.54));
+    let (_, january) = db.get_monthly_totals(Some("2024-01")).unwrap();
+    assert_eq!(db.get_budget_spent(&monthly[0]).unwrap(), -january);
+}
+
+#[test]
+fn test_budget_rollover() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let food_id = cats
+        .iter()
+        .find(|c| c.name == "Food & Dining")
+        .unwrap()
+        .id
+        .unwrap();
+    let txns = db.get_transactions(&TransactionQuery::default()).unwrap();
+    for txn in &txns {
+        if txn.description == "Starbucks Coffee" || txn.description == "Grocery Store" {
+            db.update_transaction_category(txn.id.unwrap(), Some(food_id))
+                .unwrap();
+        }
+    }
+
+    for month in ["2024-01", "2024-02"] {
+        let mut budget = Budget::new(food_id, month.into(), dec!(100));
+        budget.rollover = true;
+        db.upsert_budget(&budget).unwrap();
+    }
+
+    // Jan: 100 - 5.25, Feb: 100 - 87.30
+    assert_eq!(
+        db.get_budget_rollover(food_id, "2024-02").unwrap(),
+        dec!(94.75)
+    );
+    assert_eq!(
+        db.get_budget_rollover(food_id, "2024-03").unwrap(),
+        dec!(107.45)
+    );
+
+    // Turning rollover off in January breaks the chain there
+    let jan = db.get_budgets(Some("2024-01")).unwrap();
+    db.set_budget_rollover(jan[0].id.unwrap(), false).unwrap();
+    assert_eq!(
+        db.get_budget_rollover(food_id, "2024-03").unwrap(),
+        dec!(12.70)
+    );
+
+    // A month with no rollover budget in between carries nothing
+    assert_eq!(
+        db.get_budget_rollover(food_id, "2024-04").unwrap(),
+        Decimal::ZERO
+    );
+}
+
+#[test]
+fn test_suggest_budget_averages_trailing_months() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let id_of = |name: &str| cats.iter().find(|c| c.name == name).unwrap().id.unwrap();
+    let food_id = id_of("Food & Dining");
+    let txns = db.get_transactions(&TransactionQuery::default()).unwrap();
+    for txn in &txns {
+        if txn.description == "Starbucks Coffee" || txn.description == "Grocery Store" {
+            db.update_transaction_category(txn.id.unwrap(), Some(food_id))
+                .unwrap();
+        }
+    }
+
+    // Dec: nothing, Jan: 5.25, Feb: 87.30; the month itself isn't counted
+    assert_eq!(
+        db.suggest_budget_before(food_id, 3, "2024-03").unwrap(),
+        dec!(30.85)
+    );
+    assert_eq!(
+        db.suggest_budget_before(food_id, 1, "2024-02").unwrap(),
+        dec!(5.25)
+    );
+    assert_eq!(
+        db.suggest_budget_before(food_id, 0, "2024-03").unwrap(),
+        dec!(0)
+    );
+    assert_eq!(
+        db.suggest_budget_before(id_of("Travel"), 3, "2024-03")
+            .unwrap(),
+        dec!(0)
+    );
+}
+
+fn category_id(db: &Database, name: &str) -> i64 {
+    let cats = db.get_categories().unwrap();
+    Category::find_by_name(&cats, name).unwrap().id.unwrap()
+}
+
+#[test]
+fn test_copy_budgets_merges_unless_forced() {
+    let db = Database::open_in_memory().unwrap();
+    let food = category_id(&db, "Food & Dining");
+    let housing = category_id(&db, "Housing");
+
+    let mut food_budget = Budget::new(food, "2024-01".into(), dec!(400));
+    food_budget.rollover = true;
+    db.upsert_budget(&food_budget).unwrap();
+    db.upsert_budget(&Budget::new(housing, "2024-01".into(), dec!(2000)))
+        .unwrap();
+    db.upsert_budget(&Budget::new_yearly(food, "2024".into(), dec!(5000)))
+        .unwrap();
+    db.upsert_budget(&Budget::new(housing, "2024-02".into(), dec!(1800)))
+        .unwrap();
+
+    // Housing is already set in February, so only food is copied
+    assert_eq!(db.copy_budgets("2024-01", "2024-02", false).unwrap(), 1);
+    let feb = db.get_budgets(Some("2024-02")).unwrap();
+    assert_eq!(feb.len(), 2);
+    let limit = |cat: i64| {
+        feb.iter()
+            .find(|b| b.category_id == cat)
+            .unwrap()
+            .limit_amount
+    };
+    assert_eq!(limit(housing), dec!(1800));
+    assert_eq!(limit(food), dec!(400));
+    assert!(feb.iter().find(|b| b.category_id == food).unwrap().rollover);
+
+    assert_eq!(db.copy_budgets("2024-01", "2024-02", true).unwrap(), 2);
+    let feb = db.get_budgets(Some("2024-02")).unwrap();
+    let housing_limit = feb
+        .iter()
+        .find(|b| b.category_id == housing)
+        .unwrap()
+        .limit_amount;
+    assert_eq!(housing_limit, dec!(2000));
+}
+
+#[test]
+fn test_fill_budgets_from_previous_month_once() {
+    let db = Database::open_in_memory().unwrap();
+    let food = category_id(&db, "Food & Dining");
+    db.upsert_budget(&Budget::new(food, "2024-01".into(), dec!(400)))
+        .unwrap();
+
+    // Nothing before January to copy from
+    assert_eq!(
+        db.fill_budgets_from_previous_month("2024-01").unwrap(),
+        None
+    );
+    assert_eq!(
+        db.fill_budgets_from_previous_month("2024-02").unwrap(),
+        Some(("2024-01".to_string(), 1))
+    );
+
+    // Deleting February's budgets doesn't bring them back
+    for budget in db.get_budgets(Some("2024-02")).unwrap() {
+        db.delete_budget(budget.id.unwrap()).unwrap();
+    }
+    assert_eq!(
+        db.fill_budgets_from_previous_month("2024-02").unwrap(),
+        None
+    );
+    assert!(db.get_budgets(Some("2024-02")).unwrap().is_empty());
+}
+
+#[test]
+fn test_budget_include_subcategories() {
+    let db = Database::open_in_memory().unwrap();
+    let cats = db.get_categories().unwrap();
+    let housing_id = Category::find_by_name(&cats, "Housing")
+        .unwrap()
+        .id
+        .unwrap();
+
+    db.upsert_budget(&Budget::new(housing_id, "2024-01".into(), dec!(2000)))
+        .unwrap();
+    let budgets = db.get_budgets(Some("2024-01")).unwrap();
+    assert!(!budgets[0].include_subcategories);
+
+    db.set_budget_include_subcategories(budgets[0].id.unwrap(), true)
+        .unwrap();
+    let budgets = db.get_budgets(Some("2024-01")).unwrap();
+    assert!(budgets[0].include_subcategories);
+
+    // Re-setting the limit keeps the flag
+    db.upsert_budget(&Budget::new(housing_id, "2024-01".into(), dec!(2500)))
+        .unwrap();
+    let budgets = db.get_budgets(Some("2024-01")).unwrap();
+    assert!(budgets[0].include_subcategories);
+    assert_eq!(budgets[0].limit_amount, dec!(2500));
+}
+
+#[test]
+fn test_budget_spent_rolls_up_subcategories() {
+    let db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+    let food = db.insert_category(&Category::new("Food".into())).unwrap();
+    let mut cafe = Category::new("Cafe".into());
+    cafe.parent_id = Some(food);
+    let cafe = db.insert_category(&cafe).unwrap();
+    let base = Transaction {
+        id: None,
+        account_id,
+        date: "2024-01-15".into(),
+        description: "Grocer".into(),
+        original_description: "GROCER".into(),
+        amount: dec!(-40),
+        category_id: Some(food),
+        notes: String::new(),
+        is_transfer: false,
+        import_hash: "a".into(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    db.insert_transaction(&base).unwrap();
+    db.insert_transaction(&Transaction {
+        description: "Corner Cafe".into(),
+        amount: dec!(-4.5),
+        category_id: Some(cafe),
+        import_hash: "b".into(),
+        ..base.clone()
+    })
+    .unwrap();
+    db.insert_transaction(&Transaction {
+        date: "2024-02-01".into(),
+        amount: dec!(-99),
+        import_hash: "c".into(),
+        ..base.clone()
+    })
+    .unwrap();
+
+    db.upsert_budget(&Budget::new(food, "2024-01".into(), dec!(100)))
+        .unwrap();
+    let budget = db.get_budgets(Some("2024-01")).unwrap().remove(0);
+    assert_eq!(db.get_budget_spent(&budget).unwrap(), dec!(40));
+
+    db.set_budget_include_subcategories(budget.id.unwrap(), true)
+        .unwrap();
+    let budget = db.get_budgets(Some("2024-01")).unwrap().remove(0);
+    assert_eq!(db.get_budget_spent(&budget).unwrap(), dec!(44.5));
+}
+
+// ── Import Rule CRUD ──────────────────────────────────────────
+
+#[test]
+fn test_import_rule_crud() {
+    let db = Database::open_in_memory().unwrap();
+    let cats = db.get_categories().unwrap();
+    let shopping_id = cats
+        .iter()
+        .find(|c| c.name == "Shopping")
+        .unwrap()
+        .id
+        .unwrap();
+
+    let rule = ImportRule::new_contains("amazon".into(), shopping_id);
+    let id = db.insert_import_rule(&rule).unwrap();
+    assert!(id > 0);
+
+    let regex_rule = ImportRule::new_regex("^AMZN.*".into(), shopping_id);
+    let regex_id = db.insert_import_rule(&regex_rule).unwrap();
+    assert!(regex_id > 0);
+
+    let rules = db.get_import_rules().unwrap();
+    assert!(rules.len() >= 2);
+
+    db.delete_import_rule(id).unwrap();
+    let rules = db.get_import_rules().unwrap();
+    assert!(rules.iter().all(|r| r.pattern != "amazon"));
+}
+
+#[test]
+fn test_import_rule_source_and_created_at_round_trip() {
+    let db = Database::open_in_memory().unwrap();
+    let shopping_id = db
+        .get_categories()
+        .unwrap()
+        .iter()
+        .find(|c| c.name == "Shopping")
+        .unwrap()
+        .id
+        .unwrap();
+
+    let rule =
+        ImportRule::new_contains("amazon".into(), shopping_id).with_source(RuleSource::Memorized);
+    db.insert_import_rule(&rule).unwrap();
+    db.insert_import_rule(&ImportRule::new_regex("^ebay".into(), shopping_id))
+        .unwrap();
+
+    let rules = db.get_import_rules().unwrap();
+    let amazon = rules.iter().find(|r| r.pattern == "amazon").unwrap();
+    assert_eq!(amazon.source, RuleSource::Memorized);
+    assert_eq!(amazon.created_at, rule.created_at);
+    assert!(amazon.last_matched_at.is_none());
+    let ebay = rules.iter().find(|r| r.pattern == "^ebay").unwrap();
+    assert_eq!(ebay.source, RuleSource::Manual);
+}
+
+#[test]
+fn test_delete_unmatched_rules() {
+    let db = Database::open_in_memory().unwrap();
+    let shopping_id = db
+        .get_categories()
+        .unwrap()
+        .iter()
+        .find(|c| c.name == "Shopping")
+        .unwrap()
+        .id
+        .unwrap();
+    let rule = |pattern: &str, created_at: &str, last_matched_at: Option<&str>| ImportRule {
+        created_at: created_at.into(),
+        last_matched_at: last_matched_at.map(Into::into),
+        ..ImportRule::new_contains(pattern.into(), shopping_id)
+    };
+    for r in [
+        rule(
+            "recent",
+            "2023-01-01T00:00:00+00:00",
+            Some("2024-05-02T10:00:00+00:00"),
+        ),
+        rule(
+            "stale",
+            "2023-01-01T00:00:00+00:00",
+            Some("2024-04-30T23:00:00+00:00"),
+        ),
+        rule("never", "2023-01-01T00:00:00+00:00", None),
+        // Too new to have had a chance to match
+        rule("fresh", "2024-05-10T00:00:00+00:00", None),
+        // From before creation times were kept
+        rule("legacy", "", None),
+    ] {
+        db.insert_import_rule(&r).unwrap();
+    }
+
+    assert_eq!(db.count_unmatched_rules("2024-05-01").unwrap(), 3);
+    assert_eq!(db.delete_unmatched_rules("2024-05-01").unwrap(), 3);
+    let mut left: Vec<String> = db
+        .get_import_rules()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.pattern)
+        .collect();
+    left.sort();
+    assert_eq!(left, ["fresh", "recent"]);
+    assert_eq!(db.count_unmatched_rules("2024-05-01").unwrap(), 0);
+}
+
+#[test]
+fn test_update_import_rule() {
+    let db = Database::open_in_memory().unwrap();
+    let cats = db.get_categories().unwrap();
+    let shopping_id = cats
+        .iter()
+        .find(|c| c.name == "Shopping")
+        .unwrap()
+        .id
+        .unwrap();
+    let food_id = cats
+        .iter()
+        .find(|c| c.name == "Groceries")
+        .unwrap()
+        .id
+        .unwrap();
+
+    let id = db
+        .insert_import_rule(&ImportRule::new_contains("amazn".into(), shopping_id))
+        .unwrap();
+    let mut rule = db
+        .get_import_rules()
+        .unwrap()
+        .into_iter()
+        .find(|r| r.id == Some(id))
+        .unwrap();
+    rule.pattern = "^whole ?foods".into();
+    rule.is_regex = true;
+    rule.whole_word = true;
+    rule.category_id = food_id;
+    db.update_import_rule(&rule).unwrap();
+
+    let rules = db.get_import_rules().unwrap();
+    let updated = rules.iter().find(|r| r.id == Some(id)).unwrap();
+    assert_eq!(updated.pattern, "^whole ?foods");
+    assert!(updated.is_regex);
+    assert!(updated.whole_word);
+    assert_eq!(updated.category_id, food_id);
+    assert!(rules.iter().all(|r| r.pattern != "amazn"));
+
+    assert!(db
+        .update_import_rule(&ImportRule::new_contains("x".into(), food_id))
+        .is_err());
 }
 
 #[test]
@@ -536,6 +1494,129 @@ fn test_import_rules_ordered_by_priority() {
     assert!(high_idx < low_idx);
 }
 
+#[test]
+fn test_recategorize_uncategorized() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let id_of = |name: &str| cats.iter().find(|c| c.name == name).unwrap().id.unwrap();
+    let coffee = id_of("Coffee Shops");
+    let uncategorized = id_of(UNCATEGORIZED);
+    let groceries = id_of("Groceries");
+
+    let txns = db.get_transactions(&TransactionQuery::default()).unwrap();
+    let by_desc = |d: &str| {
+        txns.iter()
+            .find(|t| t.description == d)
+            .unwrap()
+            .id
+            .unwrap()
+    };
+    // Filed under the seed category still counts as uncategorized
+    db.update_transaction_category(by_desc("Starbucks Coffee"), Some(uncategorized))
+        .unwrap();
+    // Already categorized transactions are left alone
+    db.update_transaction_category(by_desc("Amazon Purchase"), Some(groceries))
+        .unwrap();
+    // Transfers never need a category
+    db.set_transaction_transfer(by_desc("Grocery Store"), true)
+        .unwrap();
+    assert_eq!(db.get_uncategorized_transactions().unwrap().len(), 2);
+
+    db.insert_import_rule(&ImportRule::new_contains("starbucks".into(), coffee))
+        .unwrap();
+    db.insert_import_rule(&ImportRule::new_contains("amzn".into(), coffee))
+        .unwrap();
+    db.insert_import_rule(&ImportRule::new_contains("whole foods".into(), groceries))
+        .unwrap();
+
+    assert_eq!(db.recategorize_uncategorized().unwrap(), 1);
+    let txns = db.get_transactions(&TransactionQuery::default()).unwrap();
+    let cat_of = |d: &str| {
+        txns.iter()
+            .find(|t| t.description == d)
+            .unwrap()
+            .category_id
+    };
+    assert_eq!(cat_of("Starbucks Coffee"), Some(coffee));
+    assert_eq!(cat_of("Amazon Purchase"), Some(groceries));
+    assert_eq!(cat_of("Grocery Store"), None);
+    assert_eq!(db.get_uncategorized_transactions().unwrap().len(), 1);
+    assert_eq!(db.recategorize_uncategorized().unwrap(), 0);
+}
+
+#[test]
+fn test_mark_rules_matched() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let id_of = |name: &str| cats.iter().find(|c| c.name == name).unwrap().id.unwrap();
+    let (coffee, shopping) = (id_of("Coffee Shops"), id_of("Shopping"));
+    let starbucks = db
+        .insert_import_rule(&ImportRule::new_contains("starbucks".into(), coffee))
+        .unwrap();
+    for rule in [
+        ImportRule::new_contains("amzn".into(), shopping),
+        ImportRule::new_contains("netflix".into(), shopping),
+    ] {
+        db.insert_import_rule(&rule).unwrap();
+    }
+
+    db.mark_rules_matched(&[starbucks, starbucks]).unwrap();
+
+    let used: Vec<(String, bool)> = db
+        .get_import_rules()
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.pattern, r.last_matched_at.is_some()))
+        .collect();
+    assert!(used.contains(&("starbucks".into(), true)));
+    assert!(used.contains(&("amzn".into(), false)));
+    assert!(used.contains(&("netflix".into(), false)));
+
+    // Categorizing existing transactions counts as a match too
+    db.recategorize_uncategorized().unwrap();
+    let amzn = db
+        .get_import_rules()
+        .unwrap()
+        .into_iter()
+        .find(|r| r.pattern == "amzn")
+        .unwrap();
+    assert!(amzn.last_matched_at.is_some());
+}
+
+#[test]
+fn test_apply_rule_to_existing() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let id_of = |name: &str| cats.iter().find(|c| c.name == name).unwrap().id.unwrap();
+    let shopping = id_of("Shopping");
+    let groceries = id_of("Groceries");
+
+    // Same case-insensitive matching as import: "amzn" hits "AMZN MKTP US"
+    let rule = ImportRule::new_contains("amzn".into(), shopping);
+    assert_eq!(db.apply_rule_to_existing(&rule).unwrap(), 1);
+
+    // Regex rules match the original description; categorized rows are skipped
+    let regex_rule = ImportRule::new_regex("^(amzn|whole) ".into(), groceries);
+    assert_eq!(db.apply_rule_to_existing(&regex_rule).unwrap(), 1);
+
+    let txns = db.get_transactions(&TransactionQuery::default()).unwrap();
+    let cat_of = |d: &str| {
+        txns.iter()
+            .find(|t| t.description == d)
+            .unwrap()
+            .category_id
+    };
+    assert_eq!(cat_of("Amazon Purchase"), Some(shopping));
+    assert_eq!(cat_of("Grocery Store"), Some(groceries));
+    assert_eq!(cat_of("Starbucks Coffee"), None);
+
+    let bad = ImportRule::new_regex("(unclosed".into(), groceries);
+    assert!(db.apply_rule_to_existing(&bad).is_err());
+}
+
 // ── Analytics ─────────────────────────────────────────────────
 
 #[test]
@@ -568,35 +1649,200 @@ fn test_net_worth() {
 }
 
 #[test]
-fn test_net_worth_empty() {
-    let db = Database::open_in_memory().unwrap();
-    let net = db.get_net_worth().unwrap();
-    assert_eq!(net, Decimal::ZERO);
-}
-
-#[test]
-fn test_spending_by_category() {
+fn test_net_worth_converts_foreign_accounts() {
     let mut db = Database::open_in_memory().unwrap();
     setup_test_data(&mut db);
 
-    let spending = db.get_spending_by_category(Some("2024-01")).unwrap();
-    // All uncategorized expenses in January
-    assert!(!spending.is_empty());
-    // All amounts should be negative (expenses)
-    for (_, amount) in &spending {
-        assert!(*amount < Decimal::ZERO);
-    }
-}
+    let mut euro = Account::new("Euro Savings".into(), AccountType::Savings, String::new());
+    euro.currency = "EUR".into();
+    let euro_id = db.insert_account(&euro).unwrap();
+    let txn = Transaction {
+        id: None,
+        account_id: euro_id,
+        date: "2024-01-05".into(),
+        description: "Deposit".into(),
+        original_description: String::new(),
+        amount: dec!(100),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: false,
+        import_hash: "eur-1".into(),
+        created_at: "2024-01-05T00:00:00Z".into(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    db.insert_transaction(&txn).unwrap();
 
-#[test]
-fn test_spending_by_category_empty_month() {
-    let db = Database::open_in_memory().unwrap();
-    let spending = db.get_spending_by_category(Some("2099-01")).unwrap();
-    assert!(spending.is_empty());
+    // No rate yet: EUR balance is excluded and reported separately
+    assert_eq!(db.get_net_worth().unwrap(), dec!(2864.46));
+    assert_eq!(
+        db.get_unconverted_balances().unwrap(),
+        vec![("EUR".to_string(), dec!(100))]
+    );
+
+    db.upsert_exchange_rate(&ExchangeRate::new("EUR", "USD", dec!(1.10)))
+        .unwrap();
+    assert_eq!(db.get_net_worth().unwrap(), dec!(2974.46));
+    assert!(db.get_unconverted_balances().unwrap().is_empty());
+    assert_eq!(
+        db.get_balance_by_account_type(&["Savings"]).unwrap(),
+        dec!(110.00)
+    );
+
+    // Upsert replaces the existing pair
+    db.upsert_exchange_rate(&ExchangeRate::new("eur", "usd", dec!(1.20)))
+        .unwrap();
+    let rates = db.get_exchange_rates().unwrap();
+    assert_eq!(rates.len(), 1);
+    assert_eq!(rates[0].rate, dec!(1.20));
 }
 
 #[test]
-fn test_monthly_trend() {
+fn test_opening_balance_included_in_balances() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+
+    // Defaults to zero, so balances are unchanged
+    assert_eq!(db.get_account_balance(account_id).unwrap(), dec!(2864.46));
+
+    let mut account = db.get_account_by_id(account_id).unwrap().unwrap();
+    account.opening_balance = dec!(2000);
+    db.update_account(&account).unwrap();
+    assert_eq!(
+        db.get_account_by_id(account_id)
+            .unwrap()
+            .unwrap()
+            .opening_balance,
+        dec!(2000)
+    );
+
+    // An account with only an opening balance still counts
+    let mut savings = Account::new("Savings".into(), AccountType::Savings, String::new());
+    savings.opening_balance = dec!(500);
+    db.insert_account(&savings).unwrap();
+
+    assert_eq!(db.get_account_balance(account_id).unwrap(), dec!(4864.46));
+    assert_eq!(db.get_net_worth().unwrap(), dec!(5364.46));
+    assert_eq!(
+        db.get_balance_by_account_type(&["Checking"]).unwrap(),
+        dec!(4864.46)
+    );
+    assert_eq!(
+        db.get_balance_by_account_type(&["Savings"]).unwrap(),
+        dec!(500)
+    );
+}
+
+#[test]
+fn test_net_worth_history() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    let mut account = db.get_account_by_id(account_id).unwrap().unwrap();
+    account.opening_balance = dec!(100);
+    db.update_account(&account).unwrap();
+
+    let history = db.get_net_worth_history_until(5, "2024-04").unwrap();
+    assert_eq!(
+        history,
+        vec![
+            ("2023-12".to_string(), dec!(100)),
+            ("2024-01".to_string(), dec!(3051.76)),
+            ("2024-02".to_string(), dec!(2964.46)),
+            // No transactions: prior balance carries forward
+            ("2024-03".to_string(), dec!(2964.46)),
+            ("2024-04".to_string(), dec!(2964.46)),
+        ]
+    );
+
+    // Later transactions don't leak into earlier months
+    let history = db.get_net_worth_history_until(1, "2024-01").unwrap();
+    assert_eq!(history, vec![("2024-01".to_string(), dec!(3051.76))]);
+
+    assert!(db
+        .get_net_worth_history_until(0, "2024-04")
+        .unwrap()
+        .is_empty());
+    assert_eq!(db.get_net_worth_history(12).unwrap().len(), 12);
+}
+
+#[test]
+fn test_category_spending_delta() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let groceries = cats.iter().find(|c| c.name == "Groceries").unwrap().id;
+    let shopping = cats.iter().find(|c| c.name == "Shopping").unwrap().id;
+
+    let txns = db
+        .get_transactions(&TransactionQuery {
+            limit: Some(100),
+            ..Default::default()
+        })
+        .unwrap();
+    for txn in &txns {
+        let cat = match txn.description.as_str() {
+            "Grocery Store" => groceries,
+            "Amazon Purchase" => shopping,
+            _ => continue,
+        };
+        db.update_transaction_category(txn.id.unwrap(), cat)
+            .unwrap();
+    }
+    // Another $50 of groceries in January, so February is $37.30 higher
+    let mut extra = txns[0].clone();
+    extra.id = None;
+    extra.date = "2024-01-25".into();
+    extra.amount = dec!(-50);
+    extra.category_id = groceries;
+    extra.import_hash = "hash-extra".into();
+    extra.account_id = account_id;
+    db.insert_transaction(&extra).unwrap();
+
+    let deltas = db.get_category_spending_delta("2024-02", None).unwrap();
+    assert_eq!(
+        deltas,
+        vec![("Groceries".to_string(), dec!(87.30), dec!(37.30))]
+    );
+
+    // Shopping is new in January: the full amount counts as the increase
+    let deltas = db.get_category_spending_delta("2024-01", None).unwrap();
+    let shopping_delta = deltas.iter().find(|(n, _, _)| n == "Shopping").unwrap();
+    assert_eq!(shopping_delta.1, dec!(42.99));
+    assert_eq!(shopping_delta.2, dec!(42.99));
+}
+
+#[test]
+fn test_net_worth_empty() {
+    let db = Database::open_in_memory().unwrap();
+    let net = db.get_net_worth().unwrap();
+    assert_eq!(net, Decimal::ZERO);
+}
+
+#[test]
+fn test_spending_by_category() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+
+    let spending = db.get_spending_by_category(Some("2024-01")).unwrap();
+    // All uncategorized expenses in January
+    assert!(!spending.is_empty());
+    // All amounts should be negative (expenses)
+    for (_, amount) in &spending {
+        assert!(*amount < Decimal::ZERO);
+    }
+}
+
+#[test]
+fn test_spending_by_category_empty_month() {
+    let db = Database::open_in_memory().unwrap();
+    let spending = db.get_spending_by_category(Some("2099-01")).unwrap();
+    assert!(spending.is_empty());
+}
+
+#[test]
+fn test_monthly_trend() {
     let mut db = Database::open_in_memory().unwrap();
     setup_test_data(&mut db);
 
@@ -621,6 +1867,71 @@ fn test_monthly_trend_limited() {
     assert_eq!(trend.len(), 1);
 }
 
+#[test]
+fn test_weekly_totals() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+
+    // Jan 10 is a Wednesday; Jan 15 (Monday) and Jan 20 share a week
+    let weeks = db.get_weekly_totals("2024-01").unwrap();
+    assert_eq!(
+        weeks,
+        vec![
+            ("2024-01-08".to_string(), Decimal::ZERO, dec!(-5.25)),
+            ("2024-01-15".to_string(), dec!(3000), dec!(-42.99)),
+        ]
+    );
+    // February's groceries stay in February
+    let weeks = db.get_weekly_totals("2024-02").unwrap();
+    assert_eq!(weeks.len(), 1);
+    assert_eq!(weeks[0].0, "2024-02-05");
+}
+
+#[test]
+fn test_weekly_totals_across_new_year() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+    db.save_ui_state(&UiState {
+        budget_start_day: Some(25),
+        ..UiState::default()
+    })
+    .unwrap();
+    let txns: Vec<Transaction> = ["2024-12-27", "2024-12-31", "2025-01-02", "2025-01-06"]
+        .iter()
+        .map(|date| Transaction {
+            id: None,
+            account_id,
+            date: date.to_string(),
+            description: "SHOP".into(),
+            original_description: "SHOP".into(),
+            amount: dec!(-1),
+            category_id: None,
+            notes: String::new(),
+            is_transfer: false,
+            import_hash: format!("hash-{date}"),
+            created_at: String::new(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
+        })
+        .collect();
+    db.insert_transactions_batch(&txns).unwrap();
+
+    // December's budget month runs to Jan 24; Dec 31 and Jan 2 share the
+    // week of Monday Dec 30 even though the year changes inside it
+    let weeks = db.get_weekly_totals("2024-12").unwrap();
+    assert_eq!(
+        weeks,
+        vec![
+            ("2024-12-23".to_string(), Decimal::ZERO, dec!(-1)),
+            ("2024-12-30".to_string(), Decimal::ZERO, dec!(-2)),
+            ("2025-01-06".to_string(), Decimal::ZERO, dec!(-1)),
+        ]
+    );
+}
+
 #[test]
 fn test_transaction_count() {
     let mut db = Database::open_in_memory().unwrap();
@@ -630,157 +1941,980 @@ fn test_transaction_count() {
     assert_eq!(db.get_transaction_count().unwrap(), 4);
 }
 
-// ── Export ─────────────────────────────────────────────────────
+// ── Running balance ───────────────────────────────────────────
+
+#[test]
+fn test_running_balances_in_date_order() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    let mut account = db.get_account_by_id(account_id).unwrap().unwrap();
+    account.opening_balance = dec!(100);
+    db.update_account(&account).unwrap();
+
+    let txns = db
+        .get_transactions(&TransactionQuery {
+            account_id: Some(account_id),
+            ..Default::default()
+        })
+        .unwrap();
+    let balances = db.get_running_balances(account_id).unwrap();
+    let balance_of = |desc: &str| {
+        let txn = txns
+            .iter()
+            .find(|t| t.original_description == desc)
+            .unwrap();
+        balances[&txn.id.unwrap()]
+    };
+    assert_eq!(balance_of("STARBUCKS #123"), dec!(94.75));
+    assert_eq!(balance_of("AMZN MKTP US"), dec!(51.76));
+    assert_eq!(balance_of("ACME CORP PAYROLL"), dec!(3051.76));
+    assert_eq!(balance_of("WHOLE FOODS #456"), dec!(2964.46));
+}
+
+// ── Export ─────────────────────────────────────────────────────
+
+#[test]
+fn test_export_all() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+
+    let all = db.get_all_transactions_for_export(None, None).unwrap();
+    assert_eq!(all.len(), 4);
+}
+
+#[test]
+fn test_export_by_month() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+
+    let jan = db
+        .get_all_transactions_for_export(Some("2024-01"), None)
+        .unwrap();
+    assert_eq!(jan.len(), 3);
+
+    let feb = db
+        .get_all_transactions_for_export(Some("2024-02"), None)
+        .unwrap();
+    assert_eq!(feb.len(), 1);
+}
+
+#[test]
+fn test_export_by_account() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    let other = Account::new("Savings".into(), AccountType::Savings, String::new());
+    let other_id = db.insert_account(&other).unwrap();
+
+    let mine = db
+        .get_all_transactions_for_export(None, Some(account_id))
+        .unwrap();
+    assert_eq!(mine.len(), 4);
+    let jan = db
+        .get_all_transactions_for_export(Some("2024-01"), Some(account_id))
+        .unwrap();
+    assert_eq!(jan.len(), 3);
+    let none = db
+        .get_all_transactions_for_export(None, Some(other_id))
+        .unwrap();
+    assert!(none.is_empty());
+}
+
+#[test]
+fn test_export_empty() {
+    let db = Database::open_in_memory().unwrap();
+    let all = db.get_all_transactions_for_export(None, None).unwrap();
+    assert!(all.is_empty());
+}
+
+#[test]
+fn test_export_custom_columns() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+
+    let columns = ExportColumn::parse_list("Amount, date,is_transfer").unwrap();
+    let count = db
+        .export_to_csv(path, Some("2024-02"), None, &columns)
+        .unwrap();
+    assert_eq!(count, 1);
+
+    let written = std::fs::read_to_string(path).unwrap();
+    let mut lines = written.lines();
+    assert_eq!(lines.next(), Some("Amount,Date,Transfer"));
+    let row: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(row.len(), 3);
+    assert!(row[1].starts_with("2024-02"));
+    assert_eq!(row[2], "false");
+}
+
+#[test]
+fn test_export_to_qif_round_trips() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+    let savings = Account::new("Savings".into(), AccountType::Savings, String::new());
+    let savings_id = db.insert_account(&savings).unwrap();
+    let food = db.insert_category(&Category::new("Food".into())).unwrap();
+    let mut cafe = Category::new("Cafe".into());
+    cafe.parent_id = Some(food);
+    let cafe = db.insert_category(&cafe).unwrap();
+    let base = Transaction {
+        id: None,
+        account_id,
+        date: "2024-01-15".into(),
+        description: "Corner Cafe".into(),
+        original_description: "CORNER CAFE #12".into(),
+        amount: dec!(-4.5),
+        category_id: Some(cafe),
+        notes: "with Sam\nand Alex".into(),
+        is_transfer: false,
+        import_hash: "a".into(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    db.insert_transaction(&base).unwrap();
+    let out = Transaction {
+        date: "2024-01-20".into(),
+        description: "To savings".into(),
+        amount: dec!(-100),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: true,
+        import_hash: "b".into(),
+        ..base.clone()
+    };
+    let into = Transaction {
+        account_id: savings_id,
+        description: "From checking".into(),
+        amount: dec!(100),
+        import_hash: "c".into(),
+        ..out.clone()
+    };
+    db.insert_transfer_pair(&out, &into).unwrap();
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+
+    assert_eq!(db.export_to_qif(path, None, None).unwrap(), 3);
+
+    // One block per account, each transfer leg naming the other account
+    let written = std::fs::read_to_string(path).unwrap();
+    assert_eq!(
+        written,
+        "!Account\nNSavings\nTBank\n^\n!Type:Bank\n\
+         D01/20/2024\nT100.00\nPFrom checking\nL[Test]\n^\n\
+         !Account\nNTest\nTBank\n^\n!Type:Bank\n\
+         D01/20/2024\nT-100.00\nPTo savings\nL[Savings]\n^\n\
+         D01/15/2024\nT-4.50\nPCorner Cafe\nLFood:Cafe\nMwith Sam and Alex\n^\n"
+    );
+    let categories = db.get_categories().unwrap();
+    let back =
+        crate::import::QifImporter::parse(&written, "%m/%d/%Y", account_id, &categories).unwrap();
+    assert_eq!(back.len(), 3);
+    assert!(back[0].is_transfer);
+    assert!(back[1].is_transfer);
+    assert_eq!(back[2].date, "2024-01-15");
+    assert_eq!(back[2].amount, dec!(-4.50));
+    assert_eq!(back[2].category_id, Some(cafe));
+}
+
+#[test]
+fn test_export_to_qif_leaves_unpaired_transfer_uncategorized() {
+    let db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::CreditCard, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+    db.insert_transaction(&Transaction {
+        id: None,
+        account_id,
+        date: "2024-01-20".into(),
+        description: "Card payment".into(),
+        original_description: "Card payment".into(),
+        amount: dec!(250),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: true,
+        import_hash: "a".into(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    })
+    .unwrap();
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+
+    assert_eq!(db.export_to_qif(path, None, Some(account_id)).unwrap(), 1);
+
+    let written = std::fs::read_to_string(path).unwrap();
+    assert_eq!(
+        written,
+        "!Account\nNTest\nTCCard\n^\n!Type:CCard\n\
+         D01/20/2024\nT250.00\nPCard payment\n^\n"
+    );
+}
+
+#[test]
+fn test_export_columns_reject_unknown_names() {
+    let err = ExportColumn::parse_list("date,payee")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("'payee'"));
+    assert!(err.contains("original_description"));
+    assert!(ExportColumn::parse_list(" , ").is_err());
+}
+
+// ── Backup ────────────────────────────────────────────────────
+
+#[test]
+fn test_backup_round_trip_remaps_ids() {
+    let mut src = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut src);
+    let food = category_id(&src, "Food & Dining");
+    let coffee = src
+        .insert_category(&Category::new("Espresso".into()))
+        .unwrap();
+    src.update_category_parent(coffee, Some(food)).unwrap();
+    src.upsert_budget(&Budget::new(food, "2024-01".into(), dec!(300)))
+        .unwrap();
+    src.insert_import_rule(&ImportRule::new_contains("STARBUCKS".into(), coffee))
+        .unwrap();
+    let doc = src.dump_all().unwrap();
+
+    // Shift ids in the destination so a restore that kept them would break
+    let mut dst = Database::open_in_memory().unwrap();
+    dst.insert_account(&Account::new(
+        "Temp".into(),
+        AccountType::Checking,
+        String::new(),
+    ))
+    .unwrap();
+    assert!(dst.has_user_data().unwrap());
+    dst.load_all(&doc).unwrap();
+
+    assert_eq!(
+        dst.get_accounts(true).unwrap().len(),
+        src.get_accounts(true).unwrap().len()
+    );
+    let cats = dst.get_categories().unwrap();
+    assert_eq!(cats.len(), src.get_categories().unwrap().len());
+    let espresso = Category::find_by_name(&cats, "Espresso").unwrap();
+    let parent = Category::find_by_id(&cats, espresso.parent_id.unwrap()).unwrap();
+    assert_eq!(parent.name, "Food & Dining");
+
+    let rules = dst.get_import_rules().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].category_id, espresso.id.unwrap());
+
+    let budgets = dst.get_budgets(Some("2024-01")).unwrap();
+    assert_eq!(budgets[0].category_id, parent.id.unwrap());
+
+    let before = src
+        .get_all_transactions_for_export(None, Some(account_id))
+        .unwrap();
+    let after = dst.get_all_transactions_for_export(None, None).unwrap();
+    assert_eq!(after.len(), before.len());
+    let checking = dst.get_accounts(true).unwrap();
+    for (a, b) in after.iter().zip(&before) {
+        assert_eq!(a.description, b.description);
+        assert_eq!(a.amount, b.amount);
+        assert_eq!(a.import_hash, b.import_hash);
+        assert!(checking.iter().any(|acct| acct.id == Some(a.account_id)));
+    }
+}
+
+#[test]
+fn test_restore_rejects_bad_documents() {
+    let mut db = Database::open_in_memory().unwrap();
+    assert!(!db.has_user_data().unwrap());
+    assert!(db.load_all(&serde_json::json!({"accounts": []})).is_err());
+
+    // A transaction pointing at an unknown account rolls the whole restore back
+    let doc = serde_json::json!({
+        "budgetui_backup": 1,
+        "accounts": [],
+        "transactions": [{"id": 1, "account_id": 99, "date": "2024-01-01",
+                          "description": "x", "amount": "1", "created_at": ""}],
+    });
+    assert!(db.load_all(&doc).is_err());
+    assert!(!db.get_categories().unwrap().is_empty());
+}
+
+#[test]
+fn test_restore_fills_columns_missing_from_older_backups() {
+    let mut db = Database::open_in_memory().unwrap();
+    let doc = serde_json::json!({
+        "budgetui_backup": 1,
+        "accounts": [{"id": 7, "name": "Old", "account_type": "Checking",
+                      "institution": "", "currency": "USD", "notes": "",
+                      "created_at": "", "opening_balance": "0"}],
+    });
+    db.load_all(&doc).unwrap();
+    let accounts = db.get_accounts(false).unwrap();
+    assert_eq!(accounts.len(), 1);
+    assert!(!accounts[0].archived);
+}
+
+#[test]
+fn test_backup_keeps_amounts_as_decimal_strings() {
+    let mut src = Database::open_in_memory().unwrap();
+    setup_test_data(&mut src);
+    let doc = src.dump_all().unwrap();
+    let amounts: Vec<&str> = doc["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["amount"].as_str().unwrap())
+        .collect();
+    assert_eq!(amounts, vec!["-5.25", "-42.99", "3000.00", "-87.30"]);
+
+    // Older backups wrote the same strings, and numbers restore too
+    let mut dst = Database::open_in_memory().unwrap();
+    let doc = serde_json::json!({
+        "budgetui_backup": 1,
+        "accounts": [{"id": 1, "name": "Old", "created_at": ""}],
+        "transactions": [
+            {"id": 1, "account_id": 1, "date": "2024-01-01", "description": "a",
+             "amount": "1234.5678", "created_at": "", "import_hash": "a"},
+            {"id": 2, "account_id": 1, "date": "2024-01-02", "description": "b",
+             "amount": -12.5, "created_at": "", "import_hash": "b"},
+        ],
+    });
+    dst.load_all(&doc).unwrap();
+    let amounts: Vec<Decimal> = dst
+        .get_all_transactions_for_export(None, None)
+        .unwrap()
+        .iter()
+        .map(|t| t.amount)
+        .collect();
+    assert_eq!(amounts, vec![dec!(-12.5), dec!(1234.5678)]);
+
+    let bad = serde_json::json!({
+        "budgetui_backup": 1,
+        "accounts": [{"id": 1, "name": "Old", "created_at": ""}],
+        "transactions": [{"id": 1, "account_id": 1, "date": "2024-01-01",
+                          "description": "a", "amount": "lots", "created_at": ""}],
+    });
+    assert!(dst.load_all(&bad).is_err());
+}
+
+// ── Batch insert dedup ────────────────────────────────────────
+
+#[test]
+fn test_batch_insert_dedup() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+
+    let txn = Transaction {
+        id: None,
+        account_id,
+        date: "2024-01-15".into(),
+        description: "Coffee".into(),
+        original_description: "COFFEE".into(),
+        amount: dec!(-4.50),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: false,
+        import_hash: "unique-hash".into(),
+        created_at: "2024-01-15T00:00:00Z".into(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+
+    let count1 = db
+        .insert_transactions_batch(std::slice::from_ref(&txn))
+        .unwrap();
+    assert_eq!(count1, 1);
+
+    // Same hash -> skipped
+    let count2 = db
+        .insert_transactions_batch(std::slice::from_ref(&txn))
+        .unwrap();
+    assert_eq!(count2, 0);
+}
+
+#[test]
+fn test_batch_insert_empty_hash_not_deduped() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+
+    let txn = Transaction {
+        id: None,
+        account_id,
+        date: "2024-01-15".into(),
+        description: "Manual Entry".into(),
+        original_description: "Manual".into(),
+        amount: dec!(-10.00),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: false,
+        import_hash: String::new(), // Empty hash
+        created_at: "2024-01-15T00:00:00Z".into(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+
+    let count1 = db
+        .insert_transactions_batch(std::slice::from_ref(&txn))
+        .unwrap();
+    assert_eq!(count1, 1);
+
+    // Empty hash -> should NOT be deduped
+    let count2 = db
+        .insert_transactions_batch(std::slice::from_ref(&txn))
+        .unwrap();
+    assert_eq!(count2, 1);
+
+    assert_eq!(db.get_transaction_count().unwrap(), 2);
+}
+
+#[test]
+fn test_batch_insert_multiple() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+
+    let txns: Vec<Transaction> = (0..10)
+        .map(|i| Transaction {
+            id: None,
+            account_id,
+            date: format!("2024-01-{:02}", i + 1),
+            description: format!("Transaction {i}"),
+            original_description: format!("TXN {i}"),
+            amount: dec!(-10.00),
+            category_id: None,
+            notes: String::new(),
+            is_transfer: false,
+            import_hash: format!("batch-hash-{i}"),
+            created_at: String::new(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
+        })
+        .collect();
+
+    let count = db.insert_transactions_batch(&txns).unwrap();
+    assert_eq!(count, 10);
+    assert_eq!(db.get_transaction_count().unwrap(), 10);
+}
+
+#[test]
+fn test_find_probable_duplicates() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+
+    let incoming = |date: &str, desc: &str, amount: Decimal, hash: &str| Transaction {
+        id: None,
+        account_id,
+        date: date.into(),
+        description: desc.into(),
+        original_description: desc.into(),
+        amount,
+        category_id: None,
+        notes: String::new(),
+        is_transfer: false,
+        import_hash: hash.into(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    let txns = vec![
+        // Same as "STARBUCKS #123" apart from case, punctuation, and spacing
+        incoming("2024-01-10", "starbucks  123", dec!(-5.25), "new-1"),
+        // Different amount
+        incoming("2024-01-15", "AMZN MKTP US", dec!(-43.99), "new-2"),
+        // Different date
+        incoming("2024-02-06", "WHOLE FOODS #456", dec!(-87.30), "new-3"),
+        // Matches the cleaned-up description
+        incoming("2024-02-05", "Grocery Store", dec!(-87.30), "new-4"),
+        // A second identical purchase only has one existing row to match
+        incoming("2024-01-10", "STARBUCKS #123", dec!(-5.25), "new-5"),
+    ];
+    assert_eq!(db.find_probable_duplicates(&txns).unwrap(), vec![0, 3]);
+
+    // Another account never matches
+    let other = db
+        .insert_account(&Account::new(
+            "Other".into(),
+            AccountType::Savings,
+            String::new(),
+        ))
+        .unwrap();
+    let mut elsewhere = txns[0].clone();
+    elsewhere.account_id = other;
+    assert!(db
+        .find_probable_duplicates(&[elsewhere])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_import_batches_numbered_per_insert() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    assert_eq!(db.last_import_batch().unwrap(), None);
+
+    let row = |hash: &str, is_transfer: bool| Transaction {
+        id: None,
+        account_id,
+        date: "2024-03-01".into(),
+        description: hash.into(),
+        original_description: hash.into(),
+        amount: dec!(-1.00),
+        category_id: None,
+        notes: String::new(),
+        is_transfer,
+        import_hash: hash.into(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    db.insert_transactions_batch(&[row("first", false)])
+        .unwrap();
+    db.insert_transactions_batch(&[row("second", false), row("moved", true)])
+        .unwrap();
+    assert_eq!(db.last_import_batch().unwrap(), Some(2));
+
+    let open: Vec<String> = db
+        .get_uncategorized_in_batch(2)
+        .unwrap()
+        .into_iter()
+        .map(|t| t.description)
+        .collect();
+    assert_eq!(open, vec!["second"]);
+    assert_eq!(db.get_uncategorized_in_batch(1).unwrap().len(), 1);
+}
+
+#[test]
+fn test_transfer_pairs_share_an_id() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    let leg = |hash: &str, amount: Decimal| Transaction {
+        id: None,
+        account_id,
+        date: "2024-03-01".into(),
+        description: hash.into(),
+        original_description: hash.into(),
+        amount,
+        category_id: None,
+        notes: String::new(),
+        is_transfer: true,
+        import_hash: hash.into(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    let first = db
+        .insert_transfer_pair(&leg("a-out", dec!(-5)), &leg("a-in", dec!(5)))
+        .unwrap();
+    let second = db
+        .insert_transfer_pair(&leg("b-out", dec!(-7)), &leg("b-in", dec!(7)))
+        .unwrap();
+    assert_eq!((first, second), (1, 2));
+
+    let pair_of = |hash: &str| -> Option<i64> {
+        db.conn
+            .query_row(
+                "SELECT transfer_pair_id FROM transactions WHERE import_hash = ?1",
+                params![hash],
+                |row| row.get(0),
+            )
+            .unwrap()
+    };
+    assert_eq!(pair_of("a-out"), Some(1));
+    assert_eq!(pair_of("a-in"), Some(1));
+    assert_eq!(pair_of("b-in"), Some(2));
+}
+
+// ── Wipe ──────────────────────────────────────────────────────
+
+#[test]
+fn test_wipe_all_data() {
+    let mut db = Database::open_in_memory().unwrap();
+    setup_test_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let food_id = Category::find_by_name(&cats, "Food & Dining")
+        .unwrap()
+        .id
+        .unwrap();
+    db.insert_category(&Category::new("Custom".into())).unwrap();
+    db.upsert_budget(&Budget::new(food_id, "2024-01".into(), dec!(500)))
+        .unwrap();
+    db.insert_import_rule(&ImportRule::new_contains("coffee".into(), food_id))
+        .unwrap();
+    db.push_recent_dir("/tmp/statements").unwrap();
+    db.save_ui_state(&UiState {
+        page_size: Some(200),
+        account_index: Some(1),
+        ..UiState::default()
+    })
+    .unwrap();
+
+    db.wipe_all_data().unwrap();
+
+    assert_eq!(db.get_transaction_count().unwrap(), 0);
+    assert!(db.get_budgets(None).unwrap().is_empty());
+    assert!(db.get_import_rules().unwrap().is_empty());
+    let accounts = db.get_accounts(true).unwrap();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].name, "Default");
+    let cats = db.get_categories().unwrap();
+    assert!(cats.iter().any(|c| c.name == "Income"));
+    assert!(!cats.iter().any(|c| c.name == "Custom"));
+    // Settings and recent directories survive; the account position doesn't
+    let state = db.load_ui_state().unwrap();
+    assert_eq!(state.page_size, Some(200));
+    assert_eq!(state.account_index, None);
+    assert_eq!(db.get_recent_dirs().unwrap(), vec!["/tmp/statements"]);
+}
+
+// ── Schema migration ──────────────────────────────────────────
+
+#[test]
+fn test_schema_version_set() {
+    let db = Database::open_in_memory().unwrap();
+    let version: i32 = db
+        .conn
+        .query_row("SELECT version FROM schema_version LIMIT 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(version, schema::CURRENT_VERSION);
+}
+
+#[test]
+fn test_double_migrate_idempotent() {
+    let mut db = Database::open_in_memory().unwrap();
+    // Running migrate again should not fail
+    db.migrate().unwrap();
+    let version: i32 = db
+        .conn
+        .query_row("SELECT version FROM schema_version LIMIT 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(version, schema::CURRENT_VERSION);
+}
+
+#[test]
+fn test_migrate_converts_text_amounts_to_units() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    // Put the table back the way version 14 left it: decimal TEXT amounts
+    db.conn
+        .execute_batch(
+            "DELETE FROM transactions;
+             ALTER TABLE transactions DROP COLUMN amount;
+             ALTER TABLE transactions ADD COLUMN amount TEXT NOT NULL DEFAULT '0';
+             ALTER TABLE transactions DROP COLUMN import_batch;
+             ALTER TABLE transactions DROP COLUMN transfer_pair_id;
+             ALTER TABLE transactions DROP COLUMN flagged;
+             ALTER TABLE import_rules DROP COLUMN source;
+             ALTER TABLE import_rules DROP COLUMN created_at;
+             ALTER TABLE import_rules DROP COLUMN last_matched_at;
+             UPDATE schema_version SET version = 14;",
+        )
+        .unwrap();
+    for (hash, amount) in [
+        ("a", "1234.5678"),
+        ("b", "-5.25"),
+        ("c", "-0.1"),
+        ("d", "-0.2"),
+    ] {
+        db.conn
+            .execute(
+                "INSERT INTO transactions (account_id, date, description, amount, import_hash, created_at)
+                 VALUES (?1, '2024-01-10', 'Old', ?2, ?3, '')",
+                params![account_id, amount, hash],
+            )
+            .unwrap();
+    }
+
+    db.migrate().unwrap();
+
+    let amounts: Vec<Decimal> = db
+        .get_all_transactions_for_export(None, None)
+        .unwrap()
+        .iter()
+        .map(|t| t.amount)
+        .collect();
+    // Newest id first
+    assert_eq!(
+        amounts,
+        vec![dec!(-0.2), dec!(-0.1), dec!(-5.25), dec!(1234.5678)]
+    );
+    let (income, expenses) = db.get_monthly_totals(Some("2024-01")).unwrap();
+    assert_eq!(income, dec!(1234.5678));
+    assert_eq!(expenses.to_string(), "-5.55");
+    let has_text_column: bool = db
+        .conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM pragma_table_info('transactions') WHERE name = 'amount_text')",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(!has_text_column);
+}
+
+#[test]
+fn test_transaction_queries_use_indexes() {
+    let db = Database::open_in_memory().unwrap();
+    let plan = |sql: &str| -> String {
+        let mut stmt = db
+            .conn
+            .prepare(&format!("EXPLAIN QUERY PLAN {sql}"))
+            .unwrap();
+        stmt.query_map([], |row| row.get::<_, String>(3))
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap()
+            .join("\n")
+    };
+    assert!(
+        plan("SELECT id FROM transactions WHERE date >= '2024-01-01' AND date < '2024-02-01'")
+            .contains("idx_transactions_date")
+    );
+    assert!(plan(
+        "SELECT id FROM transactions WHERE account_id = 1 \
+         AND date >= '2024-01-01' AND date < '2024-02-01' ORDER BY date DESC"
+    )
+    .contains("idx_transactions_account_date"));
+    assert!(plan("SELECT id FROM transactions WHERE category_id = 1")
+        .contains("idx_transactions_category"));
+}
+
+#[test]
+fn test_get_transactions_account_month_uses_index() {
+    let db = Database::open_in_memory().unwrap();
+    let (sql, p) = db
+        .transactions_sql(&TransactionQuery {
+            limit: Some(100),
+            account_id: Some(1),
+            month: Some("2020-06"),
+            ..Default::default()
+        })
+        .unwrap();
+    let refs: Vec<&dyn rusqlite::types::ToSql> = p.iter().map(|v| v.as_ref()).collect();
+    let mut stmt = db
+        .conn
+        .prepare(&format!("EXPLAIN QUERY PLAN {sql}"))
+        .unwrap();
+    let plan = stmt
+        .query_map(refs.as_slice(), |row| row.get::<_, String>(3))
+        .unwrap()
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .unwrap()
+        .join("\n");
+    // A full scan with a sort is what the (account_id, date) index avoids
+    assert!(plan.contains("idx_transactions_account_date"), "{plan}");
+    assert!(!plan.contains("SCAN t"), "{plan}");
+}
+
+// ── UI state ──────────────────────────────────────────────────
 
 #[test]
-fn test_export_all() {
-    let mut db = Database::open_in_memory().unwrap();
-    setup_test_data(&mut db);
-
-    let all = db.get_all_transactions_for_export(None).unwrap();
-    assert_eq!(all.len(), 4);
+fn test_ui_state_missing_is_empty() {
+    let db = Database::open_in_memory().unwrap();
+    assert_eq!(db.load_ui_state().unwrap(), UiState::default());
 }
 
 #[test]
-fn test_export_by_month() {
-    let mut db = Database::open_in_memory().unwrap();
-    setup_test_data(&mut db);
-
-    let jan = db.get_all_transactions_for_export(Some("2024-01")).unwrap();
-    assert_eq!(jan.len(), 3);
+fn test_ui_state_round_trip() {
+    let db = Database::open_in_memory().unwrap();
+    let state = UiState {
+        screen: Some("Budgets".into()),
+        month: Some(Some("2024-03".into())),
+        account_index: Some(2),
+        page_size: Some(500),
+        search_month_only: Some(true),
+        clean_names: Some(true),
+        group_by_day: Some(true),
+        dashboard_scoped: Some(true),
+        budget_start_day: Some(25),
+        confirm_quit: Some(false),
+    };
+    db.save_ui_state(&state).unwrap();
+    assert_eq!(db.load_ui_state().unwrap(), state);
 
-    let feb = db.get_all_transactions_for_export(Some("2024-02")).unwrap();
-    assert_eq!(feb.len(), 1);
+    // All time is saved as an empty month and overwrites the old value
+    db.save_ui_state(&UiState {
+        month: Some(None),
+        ..state.clone()
+    })
+    .unwrap();
+    assert_eq!(db.load_ui_state().unwrap().month, Some(None));
 }
 
 #[test]
-fn test_export_empty() {
+fn test_ui_state_corrupt_values_ignored() {
     let db = Database::open_in_memory().unwrap();
-    let all = db.get_all_transactions_for_export(None).unwrap();
-    assert!(all.is_empty());
+    db.set_app_state("month", "2024-13").unwrap();
+    db.set_app_state("account_index", "-1").unwrap();
+    db.set_app_state("screen", "Nowhere").unwrap();
+    db.set_app_state("page_size", "5").unwrap();
+    db.set_app_state("search_scope", "year").unwrap();
+    db.set_app_state("clean_names", "yes").unwrap();
+    db.set_app_state("group_by_day", "1").unwrap();
+    db.set_app_state("dashboard_scope", "mine").unwrap();
+    db.set_app_state("budget_start_day", "31").unwrap();
+    db.set_app_state("confirm_quit", "maybe").unwrap();
+    let state = db.load_ui_state().unwrap();
+    assert_eq!(state.month, None);
+    assert_eq!(state.account_index, None);
+    assert_eq!(state.page_size, None);
+    assert_eq!(state.search_month_only, None);
+    assert_eq!(state.clean_names, None);
+    assert_eq!(state.group_by_day, None);
+    assert_eq!(state.dashboard_scoped, None);
+    assert_eq!(state.budget_start_day, None);
+    assert_eq!(state.confirm_quit, None);
+    // Screen names are validated by the UI
+    assert_eq!(state.screen.as_deref(), Some("Nowhere"));
 }
 
-// ── Batch insert dedup ────────────────────────────────────────
+// ── Import profiles ───────────────────────────────────────────
 
 #[test]
-fn test_batch_insert_dedup() {
+fn test_account_profiles_round_trip_and_match_by_headers() {
     let mut db = Database::open_in_memory().unwrap();
-    let account = Account::new("Test".into(), AccountType::Checking, String::new());
-    let account_id = db.insert_account(&account).unwrap();
-
-    let txn = Transaction {
-        id: None,
-        account_id,
-        date: "2024-01-15".into(),
-        description: "Coffee".into(),
-        original_description: "COFFEE".into(),
-        amount: dec!(-4.50),
-        category_id: None,
-        notes: String::new(),
-        is_transfer: false,
-        import_hash: "unique-hash".into(),
-        created_at: "2024-01-15T00:00:00Z".into(),
+    let checking = setup_test_data(&mut db);
+    let card = db
+        .insert_account(&Account::new(
+            "Visa".into(),
+            AccountType::CreditCard,
+            String::new(),
+        ))
+        .unwrap();
+    let headers: Vec<String> = vec!["Posted".into(), "Payee".into(), "Out".into(), "In".into()];
+    let profile = CsvProfile {
+        name: "Custom".into(),
+        date_column: 0,
+        description_column: 1,
+        amount_column: None,
+        debit_column: Some(2),
+        credit_column: Some(3),
+        date_format: "%d.%m.%Y".into(),
+        has_header: true,
+        skip_rows: 1,
+        sign_convention: SignConvention::CreditPositive,
+        is_credit_account: true,
+        delimiter: b';',
     };
+    assert!(db.get_account_profile(card).unwrap().is_none());
 
-    let count1 = db
-        .insert_transactions_batch(std::slice::from_ref(&txn))
+    db.save_account_profile(card, &headers, &profile, true)
         .unwrap();
-    assert_eq!(count1, 1);
+    let saved = db.get_account_profile(card).unwrap().unwrap();
+    assert_eq!(saved.headers, headers);
+    assert_eq!(saved.profile.debit_column, Some(2));
+    assert_eq!(saved.profile.date_format, "%d.%m.%Y");
+    assert_eq!(saved.profile.skip_rows, 1);
+    assert_eq!(
+        saved.profile.sign_convention,
+        SignConvention::CreditPositive
+    );
+    assert_eq!(saved.profile.delimiter, b';');
+    assert!(saved.flip_signs);
 
-    // Same hash -> skipped
-    let count2 = db
-        .insert_transactions_batch(std::slice::from_ref(&txn))
+    // The most recently saved account wins a header match
+    db.save_account_profile(checking, &headers, &profile, false)
         .unwrap();
-    assert_eq!(count2, 0);
-}
-
-#[test]
-fn test_batch_insert_empty_hash_not_deduped() {
-    let mut db = Database::open_in_memory().unwrap();
-    let account = Account::new("Test".into(), AccountType::Checking, String::new());
-    let account_id = db.insert_account(&account).unwrap();
-
-    let txn = Transaction {
-        id: None,
-        account_id,
-        date: "2024-01-15".into(),
-        description: "Manual Entry".into(),
-        original_description: "Manual".into(),
-        amount: dec!(-10.00),
-        category_id: None,
-        notes: String::new(),
-        is_transfer: false,
-        import_hash: String::new(), // Empty hash
-        created_at: "2024-01-15T00:00:00Z".into(),
-    };
+    assert_eq!(
+        db.find_account_profile(&headers)
+            .unwrap()
+            .unwrap()
+            .account_id,
+        checking
+    );
+    db.set_account_archived(checking, true).unwrap();
+    assert_eq!(
+        db.find_account_profile(&headers)
+            .unwrap()
+            .unwrap()
+            .account_id,
+        card
+    );
+    assert!(db.find_account_profile(&headers[..3]).unwrap().is_none());
 
-    let count1 = db
-        .insert_transactions_batch(std::slice::from_ref(&txn))
+    // Saving again replaces, and deleting the account forgets it
+    db.save_account_profile(card, &headers[..3], &profile, false)
         .unwrap();
-    assert_eq!(count1, 1);
+    assert!(!db.get_account_profile(card).unwrap().unwrap().flip_signs);
+    db.delete_account(card, true).unwrap();
+    assert!(db.get_account_profile(card).unwrap().is_none());
+}
 
-    // Empty hash -> should NOT be deduped
-    let count2 = db
-        .insert_transactions_batch(std::slice::from_ref(&txn))
-        .unwrap();
-    assert_eq!(count2, 1);
+// ── Date formats ──────────────────────────────────────────────
 
-    assert_eq!(db.get_transaction_count().unwrap(), 2);
+#[test]
+fn test_custom_date_formats_persist_in_order_without_duplicates() {
+    let db = Database::open_in_memory().unwrap();
+    assert!(db.get_custom_date_formats().unwrap().is_empty());
+    assert!(db.add_custom_date_format("%d.%m.%Y").unwrap());
+    assert!(db.add_custom_date_format("%b %d, %Y").unwrap());
+    assert!(!db.add_custom_date_format("%d.%m.%Y").unwrap());
+    assert_eq!(
+        db.get_custom_date_formats().unwrap(),
+        vec!["%d.%m.%Y", "%b %d, %Y"]
+    );
 }
 
-#[test]
-fn test_batch_insert_multiple() {
-    let mut db = Database::open_in_memory().unwrap();
-    let account = Account::new("Test".into(), AccountType::Checking, String::new());
-    let account_id = db.insert_account(&account).unwrap();
+// ── Command history ───────────────────────────────────────────
 
-    let txns: Vec<Transaction> = (0..10)
-        .map(|i| Transaction {
-            id: None,
-            account_id,
-            date: format!("2024-01-{:02}", i + 1),
-            description: format!("Transaction {i}"),
-            original_description: format!("TXN {i}"),
-            amount: dec!(-10.00),
-            category_id: None,
-            notes: String::new(),
-            is_transfer: false,
-            import_hash: format!("batch-hash-{i}"),
-            created_at: String::new(),
-        })
+#[test]
+fn test_command_history_keeps_newest() {
+    let db = Database::open_in_memory().unwrap();
+    assert!(db.get_command_history().unwrap().is_empty());
+    let history: Vec<String> = (0..COMMAND_HISTORY_KEPT + 2)
+        .map(|i| format!("month 2024-{i}"))
         .collect();
-
-    let count = db.insert_transactions_batch(&txns).unwrap();
-    assert_eq!(count, 10);
-    assert_eq!(db.get_transaction_count().unwrap(), 10);
+    db.set_command_history(&history).unwrap();
+    let saved = db.get_command_history().unwrap();
+    assert_eq!(saved.len(), COMMAND_HISTORY_KEPT);
+    assert_eq!(saved[0], "month 2024-2");
+    assert_eq!(saved.last(), history.last());
 }
 
-// ── Schema migration ──────────────────────────────────────────
+// ── Recent directories ────────────────────────────────────────
 
 #[test]
-fn test_schema_version_set() {
+fn test_recent_dirs_newest_first_without_duplicates() {
     let db = Database::open_in_memory().unwrap();
-    let version: i32 = db
-        .conn
-        .query_row("SELECT version FROM schema_version LIMIT 1", [], |row| {
-            row.get(0)
-        })
-        .unwrap();
-    assert_eq!(version, schema::CURRENT_VERSION);
+    assert!(db.get_recent_dirs().unwrap().is_empty());
+
+    db.push_recent_dir("/a").unwrap();
+    db.push_recent_dir("/b").unwrap();
+    db.push_recent_dir("/a").unwrap();
+    assert_eq!(db.get_recent_dirs().unwrap(), vec!["/a", "/b"]);
 }
 
 #[test]
-fn test_double_migrate_idempotent() {
-    let mut db = Database::open_in_memory().unwrap();
-    // Running migrate again should not fail
-    db.migrate().unwrap();
-    let version: i32 = db
-        .conn
-        .query_row("SELECT version FROM schema_version LIMIT 1", [], |row| {
-            row.get(0)
-        })
-        .unwrap();
-    assert_eq!(version, schema::CURRENT_VERSION);
+fn test_recent_dirs_capped() {
+    let db = Database::open_in_memory().unwrap();
+    for i in 0..RECENT_DIRS_KEPT + 2 {
+        db.push_recent_dir(&format!("/dir{i}")).unwrap();
+    }
+    let dirs = db.get_recent_dirs().unwrap();
+    assert_eq!(dirs.len(), RECENT_DIRS_KEPT);
+    assert_eq!(dirs[0], format!("/dir{}", RECENT_DIRS_KEPT + 1));
+    assert!(!dirs.contains(&"/dir0".to_string()));
 }
 
 // ── Account-type-filtered analytics ───────────────────────────
@@ -808,6 +2942,10 @@ fn setup_multi_account_data(db: &mut Database) -> (i64, i64) {
         is_transfer: false,
         import_hash: "chk-1".into(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     })
     .unwrap();
     db.insert_transaction(&Transaction {
@@ -822,6 +2960,10 @@ fn setup_multi_account_data(db: &mut Database) -> (i64, i64) {
         is_transfer: false,
         import_hash: "chk-2".into(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     })
     .unwrap();
 
@@ -838,6 +2980,10 @@ fn setup_multi_account_data(db: &mut Database) -> (i64, i64) {
         is_transfer: false,
         import_hash: "cc-1".into(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     })
     .unwrap();
     db.insert_transaction(&Transaction {
@@ -852,6 +2998,10 @@ fn setup_multi_account_data(db: &mut Database) -> (i64, i64) {
         is_transfer: false,
         import_hash: "cc-2".into(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     })
     .unwrap();
 
@@ -885,6 +3035,67 @@ fn test_monthly_totals_by_account_type_credit() {
     assert_eq!(expenses, dec!(-45.00));
 }
 
+#[test]
+fn test_spending_by_account_category_partitions_spending() {
+    let mut db = Database::open_in_memory().unwrap();
+    let (checking_id, credit_id) = setup_multi_account_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let id_of = |name: &str| cats.iter().find(|c| c.name == name).and_then(|c| c.id);
+    let txns = db.get_all_transactions_for_export(None, None).unwrap();
+    let id_by_desc = |desc: &str| {
+        txns.iter()
+            .find(|t| t.description == desc)
+            .and_then(|t| t.id)
+            .unwrap()
+    };
+    db.update_transaction_category(id_by_desc("Coffee"), id_of("Food & Dining"))
+        .unwrap();
+    db.update_transaction_category(id_by_desc("Amazon"), id_of("Shopping"))
+        .unwrap();
+    // A transfer out of checking is not spending in either view
+    db.insert_transaction(&Transaction {
+        id: None,
+        account_id: checking_id,
+        date: "2024-01-25".into(),
+        description: "To savings".into(),
+        original_description: String::new(),
+        amount: dec!(-200),
+        category_id: id_of("Shopping"),
+        notes: String::new(),
+        is_transfer: true,
+        import_hash: "chk-xfer".into(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    })
+    .unwrap();
+
+    let checking = db
+        .get_spending_by_account_category(checking_id, Some("2024-01"))
+        .unwrap();
+    assert_eq!(checking, vec![("Food & Dining".to_string(), dec!(-5.25))]);
+    let credit = db
+        .get_spending_by_account_category(credit_id, Some("2024-01"))
+        .unwrap();
+    assert_eq!(credit, vec![("Shopping".to_string(), dec!(-45.00))]);
+    assert!(db
+        .get_spending_by_account_category(credit_id, Some("2024-02"))
+        .unwrap()
+        .is_empty());
+
+    // Together the two accounts make up the whole
+    let all = db.get_spending_by_category(Some("2024-01")).unwrap();
+    assert_eq!(
+        all,
+        vec![
+            ("Shopping".to_string(), dec!(-45.00)),
+            ("Food & Dining".to_string(), dec!(-5.25)),
+        ]
+    );
+}
+
 #[test]
 fn test_monthly_totals_by_account_type_empty_month() {
     let mut db = Database::open_in_memory().unwrap();
@@ -897,6 +3108,131 @@ fn test_monthly_totals_by_account_type_empty_month() {
     assert_eq!(expenses, Decimal::ZERO);
 }
 
+#[test]
+fn test_transfers_excluded_from_income_and_expenses() {
+    let mut db = Database::open_in_memory().unwrap();
+    let (checking_id, credit_id) = setup_multi_account_data(&mut db);
+
+    // A $500 card payment: out of checking, into the card
+    for (account_id, amount, hash) in [
+        (checking_id, dec!(-500), "xfer-out"),
+        (credit_id, dec!(500), "xfer-in"),
+    ] {
+        let id = db
+            .insert_transaction(&Transaction {
+                id: None,
+                account_id,
+                date: "2024-01-25".into(),
+                description: "Card Payment".into(),
+                original_description: "CARD PAYMENT".into(),
+                amount,
+                category_id: None,
+                notes: String::new(),
+                is_transfer: false,
+                import_hash: hash.into(),
+                created_at: String::new(),
+                tags: Vec::new(),
+                quantity: None,
+                symbol: None,
+                flagged: false,
+            })
+            .unwrap();
+        db.set_transaction_transfer(id, true).unwrap();
+    }
+
+    let (income, expenses) = db.get_monthly_totals(Some("2024-01")).unwrap();
+    assert_eq!(income, dec!(3045.00));
+    assert_eq!(expenses, dec!(-50.25));
+
+    let debit_types = &["Checking", "Savings", "Cash", "Investment", "Other"];
+    let (income, expenses) = db
+        .get_monthly_totals_by_account_type(Some("2024-01"), debit_types)
+        .unwrap();
+    assert_eq!(income, dec!(3000.00));
+    assert_eq!(expenses, dec!(-5.25));
+
+    let spending = db.get_spending_by_category(Some("2024-01")).unwrap();
+    let total: Decimal = spending.iter().map(|(_, amt)| *amt).sum();
+    assert_eq!(total, dec!(-50.25));
+
+    assert_eq!(db.get_transfer_count(Some("2024-01"), None).unwrap(), 2);
+    assert_eq!(db.get_transfer_count(Some("2024-02"), None).unwrap(), 0);
+
+    // Scoped to one account: only its own spending and its own transfer leg
+    let spending = db
+        .get_spending_by_account_category(checking_id, Some("2024-01"))
+        .unwrap();
+    let total: Decimal = spending.iter().map(|(_, amt)| *amt).sum();
+    assert_eq!(total, dec!(-5.25));
+    assert_eq!(
+        db.get_transfer_count(Some("2024-01"), Some(credit_id))
+            .unwrap(),
+        1
+    );
+
+    // Balances still move: the money did leave checking
+    assert_eq!(db.get_account_balance(checking_id).unwrap(), dec!(2494.75));
+}
+
+#[test]
+fn test_uncategorized_summary() {
+    let mut db = Database::open_in_memory().unwrap();
+    let (checking_id, _) = setup_multi_account_data(&mut db);
+    let cats = db.get_categories().unwrap();
+    let id_of = |name: &str| cats.iter().find(|c| c.name == name).and_then(|c| c.id);
+    let food_id = id_of("Food & Dining").unwrap();
+
+    // Coffee -5.25 and Amazon -45.00 are uncategorized spending
+    assert_eq!(
+        db.get_uncategorized_summary(Some("2024-01"), None).unwrap(),
+        (dec!(50.25), 2)
+    );
+    assert_eq!(
+        db.get_uncategorized_summary(Some("2024-01"), Some(checking_id))
+            .unwrap(),
+        (dec!(5.25), 1)
+    );
+    assert_eq!(
+        db.get_uncategorized_summary(Some("2099-01"), None).unwrap(),
+        (Decimal::ZERO, 0)
+    );
+
+    let txns = db.get_transactions(&TransactionQuery::default()).unwrap();
+    let coffee = txns.iter().find(|t| t.description == "Coffee").unwrap();
+    db.update_transaction_category(coffee.id.unwrap(), Some(food_id))
+        .unwrap();
+    let amazon = txns.iter().find(|t| t.description == "Amazon").unwrap();
+    db.set_transaction_transfer(amazon.id.unwrap(), true)
+        .unwrap();
+    assert_eq!(
+        db.get_uncategorized_summary(None, None).unwrap(),
+        (Decimal::ZERO, 0)
+    );
+
+    // The seed "Uncategorized" category still counts as needing one
+    let coffee_id = coffee.id.unwrap();
+    db.update_transaction_category(coffee_id, id_of(UNCATEGORIZED))
+        .unwrap();
+    assert_eq!(
+        db.get_uncategorized_summary(None, None).unwrap(),
+        (dec!(5.25), 1)
+    );
+
+    // The filter lists every uncategorized non-transfer row, income included
+    let uncategorized = db
+        .get_transactions(&TransactionQuery {
+            account_id: Some(checking_id),
+            category: Some(CategoryFilter::Uncategorized),
+            ..Default::default()
+        })
+        .unwrap();
+    let names: Vec<&str> = uncategorized
+        .iter()
+        .map(|t| t.description.as_str())
+        .collect();
+    assert_eq!(names, vec!["Coffee", "Salary"]);
+}
+
 #[test]
 fn test_balance_by_account_type_debit() {
     let mut db = Database::open_in_memory().unwrap();
@@ -983,6 +3319,90 @@ fn test_account_monthly_totals_empty_month() {
     assert_eq!(expenses, Decimal::ZERO);
 }
 
+#[test]
+fn test_invested_total_counts_positions_only() {
+    let db = Database::open_in_memory().unwrap();
+    let acct = Account::new("Brokerage".into(), AccountType::Investment, String::new());
+    let id = db.insert_account(&acct).unwrap();
+    let deposit = Transaction {
+        id: None,
+        account_id: id,
+        date: "2024-01-02".into(),
+        description: "Deposit".into(),
+        original_description: "Deposit".into(),
+        amount: dec!(1000),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: true,
+        import_hash: "dep".into(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    db.insert_transaction(&deposit).unwrap();
+    db.insert_transaction(&Transaction {
+        description: "Buy 10 VTI".into(),
+        amount: dec!(-150.00),
+        import_hash: "buy".into(),
+        quantity: Some(dec!(10)),
+        symbol: Some("VTI".into()),
+        ..deposit.clone()
+    })
+    .unwrap();
+
+    assert_eq!(db.get_invested_total(id).unwrap(), dec!(150.00));
+    assert_eq!(db.get_account_balance(id).unwrap(), dec!(850.00));
+    let (_, expenses) = db.get_account_monthly_totals(id, None).unwrap();
+    assert_eq!(expenses, Decimal::ZERO);
+
+    let txns = db.get_all_transactions_for_export(None, Some(id)).unwrap();
+    let buy = txns.iter().find(|t| t.symbol.is_some()).unwrap();
+    assert_eq!(buy.quantity, Some(dec!(10)));
+    assert_eq!(buy.symbol.as_deref(), Some("VTI"));
+    assert!(txns.iter().any(|t| t.quantity.is_none()));
+}
+
+#[test]
+fn test_buy_leaves_cash_totals_alone() {
+    let mut db = Database::open_in_memory().unwrap();
+    let account_id = setup_test_data(&mut db);
+    let totals = |db: &Database| {
+        (
+            db.get_monthly_totals(Some("2024-01")).unwrap(),
+            db.get_monthly_totals_by_account_type(Some("2024-01"), &["checking"])
+                .unwrap(),
+            db.get_monthly_trend(12).unwrap(),
+            db.get_weekly_totals("2024-01").unwrap(),
+            db.get_spending_by_category(Some("2024-01")).unwrap(),
+            db.get_uncategorized_summary(Some("2024-01"), None).unwrap(),
+        )
+    };
+    let before = totals(&db);
+
+    // Even a position not marked as a transfer isn't spending
+    db.insert_transaction(&Transaction {
+        id: None,
+        account_id,
+        date: "2024-01-16".into(),
+        description: "Buy 10 VTI @ 15.00".into(),
+        original_description: "Buy 10 VTI @ 15.00".into(),
+        amount: dec!(-150),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: false,
+        import_hash: String::new(),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: Some(dec!(10)),
+        symbol: Some("VTI".into()),
+        flagged: false,
+    })
+    .unwrap();
+    assert_eq!(totals(&db), before);
+}
+
 // ── Decimal precision ─────────────────────────────────────────
 
 #[test]
@@ -1003,11 +3423,18 @@ fn test_decimal_precision_preserved() {
         is_transfer: false,
         import_hash: "precision-test".into(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     };
 
     db.insert_transaction(&txn).unwrap();
     let fetched = db
-        .get_transactions(Some(1), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(1),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(fetched[0].amount, dec!(1234.5678));
 }
@@ -1030,11 +3457,74 @@ fn test_large_transaction_amounts() {
         is_transfer: false,
         import_hash: "large-amount".into(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     };
 
     db.insert_transaction(&txn).unwrap();
     let fetched = db
-        .get_transactions(Some(1), None, None, None, None, None)
+        .get_transactions(&TransactionQuery {
+            limit: Some(1),
+            ..Default::default()
+        })
         .unwrap();
     assert_eq!(fetched[0].amount, dec!(-350000.00));
 }
+
+#[test]
+fn test_amount_units_round_trip() {
+    assert_eq!(amount_to_units(dec!(1234.5678)).unwrap(), 12_345_678);
+    assert_eq!(amount_to_units(dec!(-5.25)).unwrap(), -52_500);
+    assert_eq!(amount_to_units(dec!(1.23456)).unwrap(), 12_346);
+    assert_eq!(amount_from_units(12_345_678).to_string(), "1234.5678");
+    assert_eq!(amount_from_units(-52_500).to_string(), "-5.25");
+    assert_eq!(amount_from_units(-50_000).to_string(), "-5.00");
+    assert_eq!(amount_from_units(0).to_string(), "0.00");
+    assert!(amount_to_units(Decimal::MAX).is_err());
+}
+
+#[test]
+fn test_amount_sums_are_exact() {
+    let db = Database::open_in_memory().unwrap();
+    let account = Account::new("Test".into(), AccountType::Checking, String::new());
+    let account_id = db.insert_account(&account).unwrap();
+    // 0.1 + 0.2 is 0.30000000000000004 in floating point
+    for (i, amount) in [dec!(-0.10), dec!(-0.20), dec!(1234.5678), dec!(0.0001)]
+        .into_iter()
+        .enumerate()
+    {
+        db.insert_transaction(&Transaction {
+            id: None,
+            account_id,
+            date: "2024-01-15".into(),
+            description: format!("Row {i}"),
+            original_description: String::new(),
+            amount,
+            category_id: None,
+            notes: String::new(),
+            is_transfer: false,
+            import_hash: format!("exact-{i}"),
+            created_at: String::new(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
+        })
+        .unwrap();
+    }
+
+    let (income, expenses) = db.get_monthly_totals(Some("2024-01")).unwrap();
+    assert_eq!(income.to_string(), "1234.5679");
+    assert_eq!(expenses.to_string(), "-0.30");
+    assert_eq!(db.get_account_balance(account_id).unwrap(), dec!(1234.2679));
+    let small = db
+        .get_transactions(&TransactionQuery {
+            min_amount: Some(dec!(-0.2)),
+            max_amount: Some(dec!(0.0001)),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(small.len(), 3);
+}
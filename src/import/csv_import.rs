@@ -17,8 +17,89 @@ pub(crate) struct CsvProfile {
     pub(crate) date_format: String,
     pub(crate) has_header: bool,
     pub(crate) skip_rows: usize,
-    pub(crate) negate_amounts: bool,
+    pub(crate) sign_convention: SignConvention,
     pub(crate) is_credit_account: bool,
+    /// Field separator byte: `,`, `;`, or `\t`.
+    pub(crate) delimiter: u8,
+}
+
+/// How the numbers in a statement map onto BudgeTUI's convention, where
+/// spending is negative and money coming in is positive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SignConvention {
+    /// A single amount column is already signed. With separate debit and
+    /// credit columns, the column decides the direction and signs are ignored.
+    AmountSigned,
+    /// Charges are positive and payments negative, as on AmEx statements.
+    /// With separate columns, a negative value flips its column's direction,
+    /// e.g. a refund listed as a negative debit.
+    DebitPositive,
+    /// Every column is signed with money in positive, e.g. a debit column
+    /// holding `-45.00`. Same as `AmountSigned` for a single amount column.
+    CreditPositive,
+}
+
+impl SignConvention {
+    pub(crate) const ALL: [SignConvention; 3] = [
+        SignConvention::AmountSigned,
+        SignConvention::DebitPositive,
+        SignConvention::CreditPositive,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SignConvention::AmountSigned => "Signed (- is spending)",
+            SignConvention::DebitPositive => "Debits positive",
+            SignConvention::CreditPositive => "Credits positive",
+        }
+    }
+
+    /// Stable name for storing the convention.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            SignConvention::AmountSigned => "signed",
+            SignConvention::DebitPositive => "debit-positive",
+            SignConvention::CreditPositive => "credit-positive",
+        }
+    }
+
+    /// Inverse of [`SignConvention::as_str`].
+    pub(crate) fn parse(s: &str) -> Option<SignConvention> {
+        Self::ALL.into_iter().find(|c| c.as_str() == s)
+    }
+
+    /// Step through [`SignConvention::ALL`], wrapping at either end.
+    pub(crate) fn cycle(self, delta: i32) -> SignConvention {
+        let len = Self::ALL.len();
+        let current = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        let next = if delta > 0 {
+            (current + 1) % len
+        } else if current == 0 {
+            len - 1
+        } else {
+            current - 1
+        };
+        Self::ALL[next]
+    }
+}
+
+/// Delimiters offered by sniffing and the column mapper, in cycle order.
+pub(crate) const DELIMITERS: [u8; 3] = [b',', b';', b'\t'];
+
+/// Date formats the column mapper cycles through before any added with
+/// `:add-date-format`.
+pub(crate) const DATE_FORMATS: [&str; 5] =
+    ["%m/%d/%Y", "%Y-%m-%d", "%m-%d-%Y", "%d/%m/%Y", "%m/%d/%y"];
+
+/// Whether `fmt` is a strftime pattern that pins down a whole date, i.e. a
+/// date formatted with it parses back to the same day.
+pub(crate) fn is_valid_date_format(fmt: &str) -> bool {
+    use chrono::format::{Item, StrftimeItems};
+    if StrftimeItems::new(fmt).any(|item| matches!(item, Item::Error)) {
+        return false;
+    }
+    let sample = NaiveDate::from_ymd_opt(2024, 11, 23).unwrap_or_default();
+    NaiveDate::parse_from_str(&sample.format(fmt).to_string(), fmt) == Ok(sample)
 }
 
 impl Default for CsvProfile {
@@ -33,22 +114,238 @@ impl Default for CsvProfile {
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: false,
+            delimiter: b',',
         }
     }
 }
 
 pub(crate) struct CsvImporter;
 
+/// A CSV row left out of an import because its date didn't parse.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SkippedRow {
+    /// 1-based position among the data rows
+    pub(crate) row: usize,
+    pub(crate) date: String,
+    pub(crate) description: String,
+}
+
+/// Read a bank export as text. Files that aren't valid UTF-8 are decoded as
+/// Windows-1252, which covers Latin-1 and is what most banks mean by "ANSI".
+/// A leading UTF-8 byte-order mark is stripped. Paths ending in `.gz` are
+/// decompressed first.
+pub(crate) fn read_text_file(path: &Path) -> Result<String> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if is_gzip_path(path) {
+        return Ok(decode_text(&gunzip(path, &bytes)?));
+    }
+    Ok(decode_text(&bytes))
+}
+
+fn is_gzip_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+}
+
+fn gunzip(path: &Path, bytes: &[u8]) -> Result<Vec<u8>> {
+    if !bytes.starts_with(&[0x1f, 0x8b]) {
+        anyhow::bail!(
+            "{} has a .gz extension but isn't gzip-compressed",
+            path.display()
+        );
+    }
+    let mut out = Vec::new();
+    std::io::Read::read_to_end(&mut flate2::read::MultiGzDecoder::new(bytes), &mut out)
+        .with_context(|| format!("Failed to decompress {}", path.display()))?;
+    Ok(out)
+}
+
+/// Lowercased extension of an import file, looking through a trailing `.gz`:
+/// `May.CSV.gz` gives `csv`.
+pub(crate) fn import_extension(path: &Path) -> Option<String> {
+    let inner = if is_gzip_path(path) {
+        Path::new(path.file_stem()?)
+    } else {
+        path
+    };
+    inner
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+}
+
+/// Account name suggested by an import file's name: the stem split into
+/// words on `_`, `-`, `.` and spaces, title-cased, with trailing dates and
+/// numbers dropped. `chase_checking_2024-01.csv` gives `Chase Checking`.
+/// `None` when nothing is left.
+pub(crate) fn account_name_from_path(path: &Path) -> Option<String> {
+    let inner = if is_gzip_path(path) {
+        Path::new(path.file_stem()?)
+    } else {
+        path
+    };
+    let stem = inner.file_stem()?.to_str()?;
+    let mut words: Vec<&str> = stem
+        .split(['_', '-', '.', ' '])
+        .filter(|w| !w.is_empty())
+        .collect();
+    while words.last().is_some_and(|w| is_date_like(w)) {
+        words.pop();
+    }
+    if words.is_empty() {
+        return None;
+    }
+    let titled: Vec<String> = words
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    Some(titled.join(" "))
+}
+
+/// A file-name word that is part of a date or a running number: all
+/// digits, or a month name such as `jan` or `January`.
+fn is_date_like(word: &str) -> bool {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    let lower = word.to_lowercase();
+    word.chars().all(|c| c.is_ascii_digit())
+        || MONTHS
+            .iter()
+            .any(|m| *m == lower || (lower.len() == 3 && m.starts_with(&lower)))
+}
+
+pub(crate) fn decode_text(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => bytes.iter().map(|&b| cp1252_char(b)).collect(),
+    }
+}
+
+/// Pick the most frequent of `,`, `;`, and tab in the first non-empty line,
+/// ignoring any inside quotes. Ties and lines with none favor comma.
+pub(crate) fn sniff_delimiter(content: &str) -> u8 {
+    let header = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+
+    let mut counts = [0usize; DELIMITERS.len()];
+    let mut in_quotes = false;
+    for b in header.bytes() {
+        if b == b'"' {
+            in_quotes = !in_quotes;
+        } else if !in_quotes {
+            if let Some(i) = DELIMITERS.iter().position(|d| *d == b) {
+                counts[i] += 1;
+            }
+        }
+    }
+
+    let mut best = 0;
+    for (i, count) in counts.iter().enumerate() {
+        if *count > counts[best] {
+            best = i;
+        }
+    }
+    DELIMITERS[best]
+}
+
+/// Labels for the columns of a file without a header row: `col0`, `col1`, ...
+/// numbered like the column mapper's `[0]`, `[1]`.
+pub(crate) fn generic_headers(width: usize) -> Vec<String> {
+    (0..width).map(|i| format!("col{i}")).collect()
+}
+
+/// Whether `headers` were made up by [`generic_headers`] rather than read
+/// from the file.
+pub(crate) fn is_generic_headers(headers: &[String]) -> bool {
+    !headers.is_empty() && headers == generic_headers(headers.len()).as_slice()
+}
+
+/// Display name for a delimiter byte.
+pub(crate) fn delimiter_name(delimiter: u8) -> &'static str {
+    match delimiter {
+        b',' => "Comma (,)",
+        b';' => "Semicolon (;)",
+        b'\t' => "Tab",
+        _ => "Other",
+    }
+}
+
+/// Windows-1252 differs from Latin-1 only in 0x80..=0x9F; the five bytes it
+/// leaves undefined map to the matching C1 control, as Latin-1 would.
+fn cp1252_char(b: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž',
+        '\u{8F}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}',
+        'ž', 'Ÿ',
+    ];
+    match b {
+        0x80..=0x9F => HIGH[usize::from(b - 0x80)],
+        _ => char::from(b),
+    }
+}
+
 impl CsvImporter {
-    /// Read the CSV and return headers + all rows as strings for preview.
+    /// Read the CSV and return headers + all rows as strings for preview,
+    /// using the delimiter found by `detect_delimiter`.
     pub(crate) fn preview(path: &Path) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        let delimiter = Self::detect_delimiter(path)?;
+        Self::preview_with_delimiter(path, delimiter)
+    }
+
+    /// `.tsv` files are tab-separated; anything else is sniffed from its
+    /// first line.
+    pub(crate) fn detect_delimiter(path: &Path) -> Result<u8> {
+        if import_extension(path).as_deref() == Some("tsv") {
+            return Ok(b'\t');
+        }
+        let content = read_text_file(path).context("Failed to open CSV file")?;
+        Ok(sniff_delimiter(&content))
+    }
+
+    pub(crate) fn preview_with_delimiter(
+        path: &Path,
+        delimiter: u8,
+    ) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        let content = read_text_file(path).context("Failed to open CSV file")?;
+        Self::preview_text(&content, delimiter)
+    }
+
+    /// Like `preview`, for CSV already in memory (e.g. piped on stdin).
+    pub(crate) fn preview_str(content: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        Self::preview_text(content, sniff_delimiter(content))
+    }
+
+    fn preview_text(content: &str, delimiter: u8) -> Result<(Vec<String>, Vec<Vec<String>>)> {
         let mut rdr = csv::ReaderBuilder::new()
             .flexible(true)
             .has_headers(false)
-            .from_path(path)
-            .context("Failed to open CSV file")?;
+            .delimiter(delimiter)
+            .from_reader(content.as_bytes());
 
         let mut all_rows: Vec<Vec<String>> = Vec::new();
         for result in rdr.records() {
@@ -60,35 +357,59 @@ impl CsvImporter {
             anyhow::bail!("CSV file is empty");
         }
 
-        // Try to detect if first row is a header
-        let first_row = &all_rows[0];
-        let looks_like_header = first_row.iter().all(|field| {
+        let has_header = Self::looks_like_header(&all_rows[0], None);
+        let mut headers = Vec::new();
+        Self::apply_header(&mut headers, &mut all_rows, has_header);
+        Ok((headers, all_rows))
+    }
+
+    /// Guess whether `first_row` is a header. With an amount column mapped,
+    /// a number there means data. Otherwise (or when that cell is empty) a
+    /// row is a header if none of its fields looks like a date or a number.
+    pub(crate) fn looks_like_header(first_row: &[String], amount_column: Option<usize>) -> bool {
+        if let Some(amount) = amount_column
+            .and_then(|c| first_row.get(c))
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+        {
+            return parse_decimal(amount).is_err();
+        }
+        first_row.iter().all(|field| {
             let trimmed = field.trim();
-            // Headers typically don't parse as dates or numbers
             Decimal::from_str(trimmed.replace(['$', ','], "").trim()).is_err()
                 && NaiveDate::parse_from_str(trimmed, "%m/%d/%Y").is_err()
                 && NaiveDate::parse_from_str(trimmed, "%Y-%m-%d").is_err()
-        });
+        })
+    }
 
-        if looks_like_header {
-            let headers = all_rows.remove(0);
-            Ok((headers, all_rows))
-        } else {
-            // Generate generic column names
-            let headers: Vec<String> = (0..first_row.len())
-                .map(|i| format!("Column {}", i + 1))
-                .collect();
-            Ok((headers, all_rows))
+    /// Move the file's first row between `headers` and `rows` so it matches
+    /// `has_header`. A headerless file gets generic `col0`, `col1`, ... labels.
+    pub(crate) fn apply_header(
+        headers: &mut Vec<String>,
+        rows: &mut Vec<Vec<String>>,
+        has_header: bool,
+    ) {
+        let generic = is_generic_headers(headers);
+        if has_header && (generic || headers.is_empty()) && !rows.is_empty() {
+            *headers = rows.remove(0);
+        } else if !has_header && !generic {
+            if !headers.is_empty() {
+                rows.insert(0, std::mem::take(headers));
+            }
+            let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+            *headers = generic_headers(width);
         }
     }
 
-    /// Parse rows into Transactions using the given profile.
+    /// Parse rows into Transactions using the given profile. Rows whose
+    /// date doesn't parse are returned separately instead of imported.
     pub(crate) fn parse(
         rows: &[Vec<String>],
         profile: &CsvProfile,
         account_id: i64,
-    ) -> Result<Vec<Transaction>> {
+    ) -> Result<(Vec<Transaction>, Vec<SkippedRow>)> {
         let mut transactions = Vec::new();
+        let mut skipped = Vec::new();
         let now = chrono::Utc::now().to_rfc3339();
 
         for (i, row) in rows.iter().enumerate().skip(profile.skip_rows) {
@@ -101,14 +422,23 @@ impl CsvImporter {
                 continue;
             }
 
-            let date = parse_date(&date_str, &profile.date_format)
-                .with_context(|| format!("Row {}: failed to parse date '{}'", i + 1, date_str))?;
-
             let description = row
                 .get(profile.description_column)
                 .map(|s| s.trim().to_string())
                 .unwrap_or_default();
 
+            let date = match parse_date(&date_str, &profile.date_format) {
+                Ok(date) => date,
+                Err(_) => {
+                    skipped.push(SkippedRow {
+                        row: i + 1,
+                        date: date_str,
+                        description,
+                    });
+                    continue;
+                }
+            };
+
             let amount = parse_amount(row, profile)
                 .with_context(|| format!("Row {}: failed to parse amount", i + 1))?;
 
@@ -126,14 +456,18 @@ impl CsvImporter {
                 is_transfer: false,
                 import_hash: hash,
                 created_at: now.clone(),
+                tags: Vec::new(),
+                quantity: None,
+                symbol: None,
+                flagged: false,
             });
         }
 
-        Ok(transactions)
+        Ok((transactions, skipped))
     }
 }
 
-fn parse_date(s: &str, fmt: &str) -> Result<NaiveDate> {
+pub(super) fn parse_date(s: &str, fmt: &str) -> Result<NaiveDate> {
     // Try the specified format first
     if let Ok(d) = NaiveDate::parse_from_str(s, fmt) {
         return Ok(d);
@@ -147,43 +481,52 @@ fn parse_date(s: &str, fmt: &str) -> Result<NaiveDate> {
     anyhow::bail!("Could not parse date: {}", s)
 }
 
-fn parse_amount(row: &[String], profile: &CsvProfile) -> Result<Decimal> {
-    let amount = if let Some(amt_col) = profile.amount_column {
+/// The amount `row` holds under `profile`'s column mapping and sign convention.
+pub(crate) fn parse_amount(row: &[String], profile: &CsvProfile) -> Result<Decimal> {
+    let convention = profile.sign_convention;
+    if let Some(amt_col) = profile.amount_column {
         let raw = row
             .get(amt_col)
             .map(|s| s.trim().to_string())
             .unwrap_or_default();
-        parse_decimal(&raw)?
-    } else {
-        // Separate debit/credit columns
-        let debit = profile
-            .debit_column
-            .and_then(|c| row.get(c))
-            .map(|s| s.trim())
-            .unwrap_or("");
-        let credit = profile
-            .credit_column
-            .and_then(|c| row.get(c))
-            .map(|s| s.trim())
-            .unwrap_or("");
+        let amount = parse_decimal(&raw)?;
+        return Ok(match convention {
+            SignConvention::DebitPositive => -amount,
+            SignConvention::AmountSigned | SignConvention::CreditPositive => amount,
+        });
+    }
 
-        if !debit.is_empty() {
-            -parse_decimal(debit)?.abs()
-        } else if !credit.is_empty() {
-            parse_decimal(credit)?.abs()
-        } else {
-            Decimal::ZERO
-        }
-    };
+    // Separate debit/credit columns
+    let debit = profile
+        .debit_column
+        .and_then(|c| row.get(c))
+        .map(|s| s.trim())
+        .unwrap_or("");
+    let credit = profile
+        .credit_column
+        .and_then(|c| row.get(c))
+        .map(|s| s.trim())
+        .unwrap_or("");
 
-    if profile.negate_amounts {
-        Ok(-amount)
+    if !debit.is_empty() {
+        let amount = parse_decimal(debit)?;
+        Ok(match convention {
+            SignConvention::AmountSigned => -amount.abs(),
+            SignConvention::DebitPositive => -amount,
+            SignConvention::CreditPositive => amount,
+        })
+    } else if !credit.is_empty() {
+        let amount = parse_decimal(credit)?;
+        Ok(match convention {
+            SignConvention::AmountSigned => amount.abs(),
+            SignConvention::DebitPositive | SignConvention::CreditPositive => amount,
+        })
     } else {
-        Ok(amount)
+        Ok(Decimal::ZERO)
     }
 }
 
-fn parse_decimal(s: &str) -> Result<Decimal> {
+pub(super) fn parse_decimal(s: &str) -> Result<Decimal> {
     let cleaned = s
         .replace(['$', ','], "")
         .replace('(', "-")
@@ -203,7 +546,7 @@ fn parse_decimal(s: &str) -> Result<Decimal> {
 /// unlike DefaultHasher which can change between releases.
 /// Includes account_id and row index so duplicate-looking transactions
 /// (same date/description/amount) at different CSV rows are preserved.
-fn compute_hash(
+pub(super) fn compute_hash(
     account_id: i64,
     row_index: usize,
     date: &str,
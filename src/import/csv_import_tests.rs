@@ -70,6 +70,27 @@ fn test_parse_date_iso_format() {
     assert_eq!(d, chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
 }
 
+#[test]
+fn test_parse_date_custom_formats() {
+    let d = parse_date("15.01.2024", "%d.%m.%Y").unwrap();
+    assert_eq!(d, chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+    let d = parse_date("Jan 15, 2024", "%b %d, %Y").unwrap();
+    assert_eq!(d, chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+}
+
+#[test]
+fn test_is_valid_date_format() {
+    assert!(is_valid_date_format("%d.%m.%Y"));
+    assert!(is_valid_date_format("%b %d, %Y"));
+    for fmt in DATE_FORMATS {
+        assert!(is_valid_date_format(fmt), "{fmt}");
+    }
+    // Missing a part of the date, or not strftime at all
+    assert!(!is_valid_date_format("%m/%Y"));
+    assert!(!is_valid_date_format("%Q"));
+    assert!(!is_valid_date_format("date"));
+}
+
 #[test]
 fn test_parse_date_fallback() {
     // Pass wrong primary format, should fallback to try others
@@ -150,16 +171,132 @@ fn test_parse_amount_both_empty_debit_credit() {
     assert_eq!(parse_amount(&row, &profile).unwrap(), Decimal::ZERO);
 }
 
-#[test]
-fn test_parse_amount_negate() {
-    let profile = CsvProfile {
-        negate_amounts: true,
+// ── Sign conventions ──────────────────────────────────────────
+
+fn amount_profile(sign_convention: SignConvention) -> CsvProfile {
+    CsvProfile {
+        sign_convention,
         ..CsvProfile::default()
-    };
+    }
+}
+
+fn split_profile(sign_convention: SignConvention) -> CsvProfile {
+    CsvProfile {
+        amount_column: None,
+        debit_column: Some(2),
+        credit_column: Some(3),
+        sign_convention,
+        ..CsvProfile::default()
+    }
+}
+
+#[test]
+fn test_sign_amount_signed() {
     let row = vec!["01/15/2024".into(), "Coffee".into(), "4.50".into()];
+    let profile = amount_profile(SignConvention::AmountSigned);
+    assert_eq!(parse_amount(&row, &profile).unwrap(), dec!(4.50));
+}
+
+#[test]
+fn test_sign_debit_positive() {
+    let row = vec!["01/15/2024".into(), "Coffee".into(), "4.50".into()];
+    let profile = amount_profile(SignConvention::DebitPositive);
     assert_eq!(parse_amount(&row, &profile).unwrap(), dec!(-4.50));
 }
 
+#[test]
+fn test_sign_credit_positive() {
+    let row = vec!["01/15/2024".into(), "Coffee".into(), "4.50".into()];
+    let profile = amount_profile(SignConvention::CreditPositive);
+    assert_eq!(parse_amount(&row, &profile).unwrap(), dec!(4.50));
+}
+
+#[test]
+fn test_sign_split_columns_signed_debit() {
+    // A debit column holding a negative number, as some banks export.
+    let row = vec![
+        "01/15/2024".into(),
+        "Coffee".into(),
+        "-4.50".into(),
+        "".into(),
+    ];
+    let cases = [
+        (SignConvention::AmountSigned, dec!(-4.50)),
+        (SignConvention::DebitPositive, dec!(4.50)),
+        (SignConvention::CreditPositive, dec!(-4.50)),
+    ];
+    for (convention, expected) in cases {
+        let profile = split_profile(convention);
+        assert_eq!(
+            parse_amount(&row, &profile).unwrap(),
+            expected,
+            "{convention:?}"
+        );
+    }
+}
+
+#[test]
+fn test_sign_split_columns_positive_debit() {
+    let row = vec![
+        "01/15/2024".into(),
+        "Coffee".into(),
+        "4.50".into(),
+        "".into(),
+    ];
+    let cases = [
+        (SignConvention::AmountSigned, dec!(-4.50)),
+        (SignConvention::DebitPositive, dec!(-4.50)),
+        (SignConvention::CreditPositive, dec!(4.50)),
+    ];
+    for (convention, expected) in cases {
+        let profile = split_profile(convention);
+        assert_eq!(
+            parse_amount(&row, &profile).unwrap(),
+            expected,
+            "{convention:?}"
+        );
+    }
+}
+
+#[test]
+fn test_sign_split_columns_credit() {
+    let row = vec![
+        "01/15/2024".into(),
+        "Refund".into(),
+        "".into(),
+        "-20.00".into(),
+    ];
+    let cases = [
+        (SignConvention::AmountSigned, dec!(20.00)),
+        (SignConvention::DebitPositive, dec!(-20.00)),
+        (SignConvention::CreditPositive, dec!(-20.00)),
+    ];
+    for (convention, expected) in cases {
+        let profile = split_profile(convention);
+        assert_eq!(
+            parse_amount(&row, &profile).unwrap(),
+            expected,
+            "{convention:?}"
+        );
+    }
+}
+
+#[test]
+fn test_sign_convention_cycle_wraps() {
+    assert_eq!(
+        SignConvention::AmountSigned.cycle(1),
+        SignConvention::DebitPositive
+    );
+    assert_eq!(
+        SignConvention::CreditPositive.cycle(1),
+        SignConvention::AmountSigned
+    );
+    assert_eq!(
+        SignConvention::AmountSigned.cycle(-1),
+        SignConvention::CreditPositive
+    );
+}
+
 // ── CsvImporter::preview ──────────────────────────────────────
 
 #[test]
@@ -178,10 +315,61 @@ fn test_preview_without_headers() {
     let csv = "01/15/2024,-4.50,*,123,COFFEE SHOP\n01/16/2024,-12.00,*,456,RESTAURANT\n";
     let file = make_csv_file(csv);
     let (headers, rows) = CsvImporter::preview(file.path()).unwrap();
-    assert!(headers[0].starts_with("Column"));
+    assert_eq!(headers, vec!["col0", "col1", "col2", "col3", "col4"]);
     assert_eq!(rows.len(), 2);
 }
 
+#[test]
+fn test_looks_like_header_checks_the_amount_column() {
+    let row = |fields: &[&str]| -> Vec<String> { fields.iter().map(|s| s.to_string()).collect() };
+    // A number under the amount column is data, whatever else the row holds
+    assert!(!CsvImporter::looks_like_header(
+        &row(&["15.01.2024", "Coffee", "-4.50"]),
+        Some(2)
+    ));
+    assert!(CsvImporter::looks_like_header(
+        &row(&["Posted", "Memo", "Amount"]),
+        Some(2)
+    ));
+    // No amount cell to check falls back to looking for dates and numbers
+    assert!(!CsvImporter::looks_like_header(
+        &row(&["01/15/2024", "Coffee", ""]),
+        Some(2)
+    ));
+    assert!(CsvImporter::looks_like_header(
+        &row(&["Date", "Memo"]),
+        Some(2)
+    ));
+}
+
+#[test]
+fn test_apply_header_moves_the_first_row() {
+    let mut headers = vec!["Date".to_string(), "Amount".to_string()];
+    let mut rows = vec![vec!["01/15/2024".to_string(), "-4.50".to_string()]];
+
+    CsvImporter::apply_header(&mut headers, &mut rows, false);
+    assert_eq!(headers, vec!["col0", "col1"]);
+    assert_eq!(rows[0], vec!["Date", "Amount"]);
+    assert_eq!(rows.len(), 2);
+
+    // Already headerless: nothing moves
+    CsvImporter::apply_header(&mut headers, &mut rows, false);
+    assert_eq!(rows.len(), 2);
+
+    CsvImporter::apply_header(&mut headers, &mut rows, true);
+    assert_eq!(headers, vec!["Date", "Amount"]);
+    assert_eq!(rows, vec![vec!["01/15/2024", "-4.50"]]);
+}
+
+#[test]
+fn test_preview_str_sniffs_delimiter_and_headers() {
+    let csv = "Date;Description;Amount\n01/15/2024;Coffee;-4,50\n";
+    let (headers, rows) = CsvImporter::preview_str(csv).unwrap();
+    assert_eq!(headers, vec!["Date", "Description", "Amount"]);
+    assert_eq!(rows, vec![vec!["01/15/2024", "Coffee", "-4,50"]]);
+    assert!(CsvImporter::preview_str("").is_err());
+}
+
 #[test]
 fn test_preview_empty_file() {
     let file = make_csv_file("");
@@ -205,6 +393,182 @@ fn test_preview_quoted_fields() {
     assert_eq!(rows[0][1], "Coffee, Shop");
 }
 
+// ── Delimiters ────────────────────────────────────────────────
+
+#[test]
+fn test_sniff_delimiter() {
+    assert_eq!(sniff_delimiter("Date,Description,Amount\n"), b',');
+    assert_eq!(sniff_delimiter("Datum;Omschrijving;Bedrag\n"), b';');
+    assert_eq!(sniff_delimiter("Date\tDescription\tAmount\n"), b'\t');
+    assert_eq!(sniff_delimiter("\n\nDatum;Bedrag\n"), b';');
+}
+
+#[test]
+fn test_sniff_delimiter_ignores_quoted() {
+    // Three commas inside quotes, two real semicolons
+    assert_eq!(
+        sniff_delimiter("\"Date, posted\";\"Name, first, last\";Amount\n"),
+        b';'
+    );
+}
+
+#[test]
+fn test_sniff_delimiter_defaults_to_comma() {
+    assert_eq!(sniff_delimiter("Amount\n"), b',');
+    assert_eq!(sniff_delimiter(""), b',');
+    assert_eq!(sniff_delimiter("a,b;c\n"), b',');
+}
+
+#[test]
+fn test_preview_semicolon_with_quoted_delimiter() {
+    let csv = "Datum;Omschrijving;Bedrag\n2024-01-15;\"Bakker; de Jong\";-4,50\n";
+    let file = make_csv_file(csv);
+    let (headers, rows) = CsvImporter::preview(file.path()).unwrap();
+    assert_eq!(headers, vec!["Datum", "Omschrijving", "Bedrag"]);
+    assert_eq!(rows[0][1], "Bakker; de Jong");
+    assert_eq!(rows[0][2], "-4,50");
+}
+
+#[test]
+fn test_preview_tsv_defaults_to_tab() {
+    let mut file = tempfile::Builder::new().suffix(".tsv").tempfile().unwrap();
+    // A comma-heavy header would sniff as comma; the extension wins
+    file.write_all(b"Date\tDescription, long\tAmount\n01/15/2024\tCoffee, large\t-4.50\n")
+        .unwrap();
+    assert_eq!(CsvImporter::detect_delimiter(file.path()).unwrap(), b'\t');
+    let (headers, rows) = CsvImporter::preview(file.path()).unwrap();
+    assert_eq!(headers.len(), 3);
+    assert_eq!(rows[0][1], "Coffee, large");
+}
+
+#[test]
+fn test_preview_with_explicit_delimiter() {
+    let file = make_csv_file("Date;Description;Amount\n01/15/2024;Coffee;-4.50\n");
+    let (headers, _) = CsvImporter::preview_with_delimiter(file.path(), b',').unwrap();
+    assert_eq!(headers.len(), 1);
+    let (headers, _) = CsvImporter::preview_with_delimiter(file.path(), b';').unwrap();
+    assert_eq!(headers.len(), 3);
+}
+
+// ── Gzip ──────────────────────────────────────────────────────
+
+fn make_gz_file(suffix: &str, content: &[u8]) -> tempfile::NamedTempFile {
+    let file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+    let mut encoder =
+        flate2::write::GzEncoder::new(file.reopen().unwrap(), flate2::Compression::default());
+    encoder.write_all(content).unwrap();
+    encoder.finish().unwrap();
+    file
+}
+
+#[test]
+fn test_preview_gzip_csv() {
+    let file = make_gz_file(
+        ".csv.gz",
+        b"Transaction Date,Posted Date,Card No.,Description,Category,Debit,Credit\n\
+          2024-01-15,2024-01-16,1234,COFFEE,Dining,4.50,\n",
+    );
+    let (headers, rows) = CsvImporter::preview(file.path()).unwrap();
+    assert_eq!(headers[0], "Transaction Date");
+    assert_eq!(rows[0][3], "COFFEE");
+    let profile = crate::import::detect_bank_format(&headers, &rows[0]).unwrap();
+    assert_eq!(profile.name, "Capital One Credit Card");
+}
+
+#[test]
+fn test_preview_gzip_tsv_uses_tab() {
+    let file = make_gz_file(
+        ".tsv.gz",
+        b"Date\tDescription, long\tAmount\n01/15/2024\tA, B\t-4.50\n",
+    );
+    assert_eq!(CsvImporter::detect_delimiter(file.path()).unwrap(), b'\t');
+}
+
+#[test]
+fn test_preview_fake_gzip_errors() {
+    let mut file = tempfile::Builder::new()
+        .suffix(".csv.gz")
+        .tempfile()
+        .unwrap();
+    file.write_all(b"Date,Description,Amount\n01/15/2024,Coffee,-4.50\n")
+        .unwrap();
+    let err = CsvImporter::preview(file.path()).unwrap_err();
+    assert!(format!("{err:#}").contains("isn't gzip-compressed"));
+}
+
+#[test]
+fn test_import_extension_looks_through_gz() {
+    assert_eq!(
+        import_extension(Path::new("May.CSV.gz")).as_deref(),
+        Some("csv")
+    );
+    assert_eq!(import_extension(Path::new("a.qif")).as_deref(), Some("qif"));
+    assert_eq!(import_extension(Path::new("a.gz")), None);
+}
+
+#[test]
+fn test_account_name_from_path() {
+    let name = |p: &str| account_name_from_path(Path::new(p));
+    assert_eq!(
+        name("/tmp/chase_checking_2024.csv").as_deref(),
+        Some("Chase Checking")
+    );
+    assert_eq!(
+        name("AMEX-gold-card-2024-01-15.csv.gz").as_deref(),
+        Some("Amex Gold Card")
+    );
+    assert_eq!(
+        name("savings statement Jan 2024.CSV").as_deref(),
+        Some("Savings Statement")
+    );
+    // Digits inside a word stay; only whole trailing numbers go
+    assert_eq!(name("visa4421_20240115.qif").as_deref(), Some("Visa4421"));
+    assert_eq!(name("2024-01.csv"), None);
+}
+
+// ── Encoding ──────────────────────────────────────────────────
+
+fn make_csv_bytes(content: &[u8]) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(content).unwrap();
+    file
+}
+
+#[test]
+fn test_preview_windows_1252() {
+    // "Café" and "£" as single cp1252 bytes (0xE9, 0xA3), plus a curly
+    // apostrophe (0x92) from the 0x80..=0x9F range Latin-1 lacks
+    let csv = b"Date,Description,Amount\n01/15/2024,Caf\xE9 Rouge \xA35 off,-4.50\n01/16/2024,Joe\x92s Diner,-12.00\n";
+    let file = make_csv_bytes(csv);
+    let (headers, rows) = CsvImporter::preview(file.path()).unwrap();
+    assert_eq!(headers, vec!["Date", "Description", "Amount"]);
+    assert_eq!(rows[0][1], "Café Rouge £5 off");
+    assert_eq!(rows[1][1], "Joe’s Diner");
+
+    let (txns, _) = CsvImporter::parse(&rows, &CsvProfile::default(), 1).unwrap();
+    assert_eq!(txns[0].description, "Café Rouge £5 off");
+}
+
+#[test]
+fn test_preview_utf8_unchanged() {
+    let file = make_csv_file("Date,Description,Amount\n01/15/2024,Café £,-4.50\n");
+    let (_, rows) = CsvImporter::preview(file.path()).unwrap();
+    assert_eq!(rows[0][1], "Café £");
+}
+
+#[test]
+fn test_preview_strips_bom() {
+    let file = make_csv_bytes(b"\xEF\xBB\xBFDate,Description,Amount\n01/15/2024,Coffee,-4.50\n");
+    let (headers, _) = CsvImporter::preview(file.path()).unwrap();
+    assert_eq!(headers[0], "Date");
+}
+
+#[test]
+fn test_decode_text_latin1_range() {
+    assert_eq!(decode_text(b"\xC0\xFF\x80"), "Àÿ€");
+    assert_eq!(decode_text(b"plain"), "plain");
+}
+
 // ── CsvImporter::parse ────────────────────────────────────────
 
 #[test]
@@ -214,7 +578,7 @@ fn test_parse_basic_rows() {
         vec!["01/15/2024".into(), "Coffee".into(), "-4.50".into()],
         vec!["01/16/2024".into(), "Lunch".into(), "-12.00".into()],
     ];
-    let txns = CsvImporter::parse(&rows, &profile, 1).unwrap();
+    let (txns, _) = CsvImporter::parse(&rows, &profile, 1).unwrap();
     assert_eq!(txns.len(), 2);
     assert_eq!(txns[0].date, "2024-01-15");
     assert_eq!(txns[0].description, "Coffee");
@@ -230,10 +594,37 @@ fn test_parse_skips_empty_dates() {
         vec!["".into(), "".into(), "".into()],
         vec!["01/16/2024".into(), "Lunch".into(), "-12.00".into()],
     ];
-    let txns = CsvImporter::parse(&rows, &profile, 1).unwrap();
+    let (txns, _) = CsvImporter::parse(&rows, &profile, 1).unwrap();
     assert_eq!(txns.len(), 2);
 }
 
+#[test]
+fn test_parse_collects_unparseable_dates() {
+    let profile = CsvProfile::default();
+    let rows = vec![
+        vec!["01/15/2024".into(), "Coffee".into(), "-4.50".into()],
+        vec!["Pending".into(), "Lunch".into(), "-12.00".into()],
+        vec!["13/45/2024".into(), "Gas".into(), "-30.00".into()],
+    ];
+    let (txns, skipped) = CsvImporter::parse(&rows, &profile, 1).unwrap();
+    assert_eq!(txns.len(), 1);
+    assert_eq!(
+        skipped,
+        vec![
+            SkippedRow {
+                row: 2,
+                date: "Pending".into(),
+                description: "Lunch".into(),
+            },
+            SkippedRow {
+                row: 3,
+                date: "13/45/2024".into(),
+                description: "Gas".into(),
+            },
+        ]
+    );
+}
+
 #[test]
 fn test_parse_skip_rows() {
     let profile = CsvProfile {
@@ -244,7 +635,7 @@ fn test_parse_skip_rows() {
         vec!["SKIP THIS ROW".into(), "ignore".into(), "0".into()],
         vec!["01/15/2024".into(), "Coffee".into(), "-4.50".into()],
     ];
-    let txns = CsvImporter::parse(&rows, &profile, 1).unwrap();
+    let (txns, _) = CsvImporter::parse(&rows, &profile, 1).unwrap();
     assert_eq!(txns.len(), 1);
     assert_eq!(txns[0].description, "Coffee");
 }
@@ -256,7 +647,7 @@ fn test_parse_iso_dates() {
         ..CsvProfile::default()
     };
     let rows = vec![vec!["2024-01-15".into(), "Coffee".into(), "-4.50".into()]];
-    let txns = CsvImporter::parse(&rows, &profile, 1).unwrap();
+    let (txns, _) = CsvImporter::parse(&rows, &profile, 1).unwrap();
     assert_eq!(txns[0].date, "2024-01-15");
 }
 
@@ -264,7 +655,7 @@ fn test_parse_iso_dates() {
 fn test_parse_generates_import_hash() {
     let profile = CsvProfile::default();
     let rows = vec![vec!["01/15/2024".into(), "Coffee".into(), "-4.50".into()]];
-    let txns = CsvImporter::parse(&rows, &profile, 1).unwrap();
+    let (txns, _) = CsvImporter::parse(&rows, &profile, 1).unwrap();
     assert!(!txns[0].import_hash.is_empty());
 }
 
@@ -272,7 +663,7 @@ fn test_parse_generates_import_hash() {
 fn test_parse_sets_account_id() {
     let profile = CsvProfile::default();
     let rows = vec![vec!["01/15/2024".into(), "Coffee".into(), "-4.50".into()]];
-    let txns = CsvImporter::parse(&rows, &profile, 42).unwrap();
+    let (txns, _) = CsvImporter::parse(&rows, &profile, 42).unwrap();
     assert_eq!(txns[0].account_id, 42);
 }
 
@@ -280,7 +671,7 @@ fn test_parse_sets_account_id() {
 fn test_parse_empty_rows() {
     let profile = CsvProfile::default();
     let rows: Vec<Vec<String>> = vec![];
-    let txns = CsvImporter::parse(&rows, &profile, 1).unwrap();
+    let (txns, _) = CsvImporter::parse(&rows, &profile, 1).unwrap();
     assert!(txns.is_empty());
 }
 
@@ -1,4 +1,9 @@
-use super::CsvProfile;
+use super::{is_generic_headers, CsvProfile, SignConvention};
+use crate::models::{AccountType, Transaction};
+use rust_decimal::Decimal;
+
+/// Rows an import needs before [`signs_look_flipped`] will judge it.
+const SIGN_CHECK_MIN_ROWS: usize = 5;
 
 /// Known bank CSV fingerprints for auto-detection.
 /// Returns a CsvProfile if the format is recognized, None otherwise.
@@ -9,9 +14,7 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
         .collect();
 
     // Wells Fargo: no real headers, 5 columns, col[2] == "*"
-    // When preview() generates headers for headerless CSVs, they look like "Column 1", etc.
-    let has_generated_headers = h.first().is_some_and(|s| s.starts_with("column "));
-    if (headers.is_empty() || has_generated_headers)
+    if (headers.is_empty() || is_generic_headers(headers))
         && first_row.len() == 5
         && first_row.get(2).map(|s| s.trim()) == Some("*")
     {
@@ -25,8 +28,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: false,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: false,
+            delimiter: b',',
         });
     }
 
@@ -42,8 +46,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: true, // AmEx inverts: charges positive, payments negative
+            sign_convention: SignConvention::DebitPositive, // charges positive, payments negative
             is_credit_account: true,
+            delimiter: b',',
         });
     }
 
@@ -59,8 +64,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: true,
+            delimiter: b',',
         });
     }
 
@@ -76,8 +82,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: false,
+            delimiter: b',',
         });
     }
 
@@ -93,8 +100,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: false,
+            delimiter: b',',
         });
     }
 
@@ -113,8 +121,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned, // credits are listed as negatives
             is_credit_account: true,
+            delimiter: b',',
         });
     }
 
@@ -130,8 +139,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%Y-%m-%d".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: true,
+            delimiter: b',',
         });
     }
 
@@ -149,8 +159,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: false,
+            delimiter: b',',
         });
     }
 
@@ -168,8 +179,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::DebitPositive, // purchases positive, payments negative
             is_credit_account: true,
+            delimiter: b',',
         });
     }
 
@@ -185,8 +197,9 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: false,
+            delimiter: b',',
         });
     }
 
@@ -205,14 +218,38 @@ pub(crate) fn detect_bank_format(headers: &[String], first_row: &[String]) -> Op
             date_format: "%m/%d/%Y".into(),
             has_header: true,
             skip_rows: 0,
-            negate_amounts: false,
+            sign_convention: SignConvention::AmountSigned,
             is_credit_account: true,
+            delimiter: b',',
         });
     }
 
     None
 }
 
+/// Whether an import into an account of `account_type` looks like it has
+/// every sign backwards. Card, checking and cash statements are mostly
+/// spending, which is negative here, so money in outnumbering money out four
+/// to one almost always means the wrong sign convention. Savings, investment
+/// and loan accounts can fairly be mostly positive and are never flagged.
+pub(crate) fn signs_look_flipped(txns: &[Transaction], account_type: &AccountType) -> bool {
+    if !matches!(
+        account_type,
+        AccountType::CreditCard | AccountType::Checking | AccountType::Cash
+    ) {
+        return false;
+    }
+    let (mut money_in, mut money_out) = (0, 0);
+    for txn in txns.iter().filter(|t| !t.is_transfer) {
+        if txn.amount > Decimal::ZERO {
+            money_in += 1;
+        } else if txn.amount < Decimal::ZERO {
+            money_out += 1;
+        }
+    }
+    money_in + money_out >= SIGN_CHECK_MIN_ROWS && money_in >= money_out * 4
+}
+
 fn col_index(headers: &[String], name: &str) -> Option<usize> {
     headers.iter().position(|h| h == name)
 }
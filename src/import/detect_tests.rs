@@ -26,7 +26,7 @@ fn test_detect_amex() {
     let first_row = h(&["01/15/2024", "Coffee Shop", "JOHN DOE", "-4.50"]);
     let profile = detect_bank_format(&headers, &first_row).unwrap();
     assert_eq!(profile.name, "American Express");
-    assert!(profile.negate_amounts);
+    assert_eq!(profile.sign_convention, SignConvention::DebitPositive);
     assert!(profile.is_credit_account);
 }
 
@@ -132,9 +132,10 @@ fn test_detect_discover() {
         "Amount",
         "Category",
     ]);
-    let first_row = h(&["01/15/2024", "01/16/2024", "Coffee", "-4.50", "Food"]);
+    let first_row = h(&["01/15/2024", "01/16/2024", "Coffee", "4.50", "Food"]);
     let profile = detect_bank_format(&headers, &first_row).unwrap();
     assert_eq!(profile.name, "Discover");
+    assert_eq!(profile.sign_convention, SignConvention::DebitPositive);
     assert!(profile.is_credit_account);
 }
 
@@ -227,3 +228,46 @@ fn test_wells_fargo_not_matched_no_star() {
     let first_row = h(&["01/15/2024", "-4.50", "X", "123", "COFFEE SHOP"]);
     assert!(detect_bank_format(&headers, &first_row).is_none());
 }
+
+// ── Flipped signs ─────────────────────────────────────────────
+
+fn txns(amounts: &[&str]) -> Vec<Transaction> {
+    amounts
+        .iter()
+        .map(|a| Transaction {
+            id: None,
+            account_id: 1,
+            date: "2024-01-10".into(),
+            description: "X".into(),
+            original_description: "X".into(),
+            amount: a.parse().unwrap(),
+            category_id: None,
+            notes: String::new(),
+            is_transfer: false,
+            import_hash: String::new(),
+            created_at: String::new(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
+        })
+        .collect()
+}
+
+#[test]
+fn test_signs_look_flipped() {
+    let backwards = txns(&["4.50", "12.00", "60.00", "9.99", "31.20", "-500.00"]);
+    assert!(signs_look_flipped(&backwards, &AccountType::CreditCard));
+    assert!(signs_look_flipped(&backwards, &AccountType::Checking));
+    // Mostly deposits is normal for savings
+    assert!(!signs_look_flipped(&backwards, &AccountType::Savings));
+
+    let normal = txns(&["-4.50", "-12.00", "-60.00", "-9.99", "-31.20", "500.00"]);
+    assert!(!signs_look_flipped(&normal, &AccountType::CreditCard));
+
+    // Too few rows to judge
+    assert!(!signs_look_flipped(
+        &backwards[..4],
+        &AccountType::CreditCard
+    ));
+}
@@ -1,5 +1,11 @@
 mod csv_import;
 mod detect;
+mod qif;
 
-pub(crate) use csv_import::{CsvImporter, CsvProfile};
-pub(crate) use detect::detect_bank_format;
+pub(crate) use csv_import::{
+    account_name_from_path, decode_text, delimiter_name, import_extension, is_generic_headers,
+    is_valid_date_format, parse_amount, read_text_file, CsvImporter, CsvProfile, SignConvention,
+    SkippedRow, DATE_FORMATS, DELIMITERS,
+};
+pub(crate) use detect::{detect_bank_format, signs_look_flipped};
+pub(crate) use qif::QifImporter;
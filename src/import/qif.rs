@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+
+use super::csv_import::{compute_hash, parse_date, parse_decimal};
+use crate::models::{Category, Transaction};
+
+/// QIF account sections we know how to read. Investment sections use a
+/// different field layout and are rejected.
+const SUPPORTED_TYPES: &[&str] = &["bank", "cash", "ccard", "oth a", "oth l"];
+
+pub(crate) struct QifImporter;
+
+impl QifImporter {
+    /// Parse a Quicken QIF export into Transactions.
+    ///
+    /// Records are made of single-letter fields (`D` date, `T` amount, `P` payee,
+    /// `M` memo, `L` category) and end with a `^` line. Dates are parsed with
+    /// `date_format` first, falling back to the usual CSV formats. A category
+    /// named in `L` is matched case-insensitively against `categories`; an `L`
+    /// in square brackets names a transfer account and marks the transaction
+    /// as a transfer instead. `!Account` blocks, which name the register that
+    /// follows in multi-account files, are skipped.
+    pub(crate) fn parse(
+        content: &str,
+        date_format: &str,
+        account_id: i64,
+        categories: &[Category],
+    ) -> Result<Vec<Transaction>> {
+        let mut transactions = Vec::new();
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut record = QifRecord::default();
+        let mut record_index = 0;
+        // `!Account` blocks name the register that follows; their fields
+        // aren't transactions
+        let mut in_account = false;
+
+        for line in content.lines() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('!') {
+                if let Some(kind) = header.strip_prefix("Type:") {
+                    let kind = kind.trim().to_lowercase();
+                    if !SUPPORTED_TYPES.contains(&kind.as_str()) {
+                        anyhow::bail!("Unsupported QIF section: !Type:{}", kind);
+                    }
+                    in_account = false;
+                } else if header.trim().eq_ignore_ascii_case("account") {
+                    in_account = true;
+                }
+                continue;
+            }
+            if in_account {
+                continue;
+            }
+
+            let (code, value) = line.split_at(line.chars().next().map_or(0, char::len_utf8));
+            let value = value.trim();
+            match code {
+                "D" => record.date = Some(value.to_string()),
+                "T" => record.amount = Some(value.to_string()),
+                "P" => record.payee = value.to_string(),
+                "M" => record.memo = value.to_string(),
+                "L" => record.category = value.to_string(),
+                "^" => {
+                    record_index += 1;
+                    let r = std::mem::take(&mut record);
+                    if let Some(txn) =
+                        r.into_transaction(record_index, date_format, account_id, categories, &now)?
+                    {
+                        transactions.push(txn);
+                    }
+                }
+                // Other fields (N check number, C cleared, A address, splits...) are ignored
+                _ => {}
+            }
+        }
+
+        Ok(transactions)
+    }
+}
+
+#[derive(Default)]
+struct QifRecord {
+    date: Option<String>,
+    amount: Option<String>,
+    payee: String,
+    memo: String,
+    category: String,
+}
+
+impl QifRecord {
+    fn into_transaction(
+        self,
+        index: usize,
+        date_format: &str,
+        account_id: i64,
+        categories: &[Category],
+        now: &str,
+    ) -> Result<Option<Transaction>> {
+        // A bare "^" (e.g. after the header) carries no data
+        let date_str = match self.date {
+            Some(d) if !d.is_empty() => d,
+            _ if self.amount.is_none() && self.payee.is_empty() => return Ok(None),
+            _ => anyhow::bail!("Record {index}: missing date"),
+        };
+
+        let date = parse_date(&normalize_qif_date(&date_str), date_format)
+            .with_context(|| format!("Record {index}: failed to parse date '{date_str}'"))?;
+        let amount = parse_decimal(self.amount.as_deref().unwrap_or(""))
+            .with_context(|| format!("Record {index}: failed to parse amount"))?;
+
+        let description = if self.payee.is_empty() {
+            self.memo.clone()
+        } else {
+            self.payee
+        };
+
+        let is_transfer = self.category.starts_with('[') && self.category.ends_with(']');
+        let category_id = if is_transfer || self.category.is_empty() {
+            None
+        } else {
+            // "Parent:Child" categories fall back to the most specific segment
+            Category::find_by_name(categories, &self.category)
+                .or_else(|| {
+                    self.category
+                        .rsplit(':')
+                        .next()
+                        .and_then(|leaf| Category::find_by_name(categories, leaf))
+                })
+                .and_then(|c| c.id)
+        };
+
+        let hash = compute_hash(account_id, index, &date_str, &description, &amount);
+
+        Ok(Some(Transaction {
+            id: None,
+            account_id,
+            date: date.format("%Y-%m-%d").to_string(),
+            description: description.clone(),
+            original_description: description,
+            amount,
+            category_id,
+            notes: self.memo,
+            is_transfer,
+            import_hash: hash,
+            created_at: now.to_string(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
+        }))
+    }
+}
+
+/// Quicken writes dates like `1/15'24` or ` 1/ 5/2024`, where an apostrophe
+/// before a two-digit year means 20xx. Expand that and strip stray padding so
+/// the regular parsers apply.
+fn normalize_qif_date(s: &str) -> String {
+    let s = s.replace(' ', "");
+    match s.split_once('\'') {
+        Some((month_day, year)) if year.len() == 2 => format!("{month_day}/20{year}"),
+        Some((month_day, year)) => format!("{month_day}/{year}"),
+        None => s,
+    }
+}
+
+#[cfg(test)]
+#[path = "qif_tests.rs"]
+mod tests;
@@ -0,0 +1,127 @@
+#![allow(clippy::unwrap_used)]
+
+use super::*;
+use rust_decimal_macros::dec;
+
+fn cats() -> Vec<Category> {
+    vec![
+        Category {
+            id: Some(1),
+            ..Category::new("Groceries".into())
+        },
+        Category {
+            id: Some(2),
+            ..Category::new("Utilities".into())
+        },
+    ]
+}
+
+const SAMPLE: &str = "!Type:Bank
+D01/15/2024
+T-42.50
+PWHOLE FOODS
+Mweekly shop
+Lgroceries
+^
+D01/20/2024
+T1,500.00
+PACME PAYROLL
+LIncome
+^
+";
+
+// ── parse ─────────────────────────────────────────────────────
+
+#[test]
+fn test_parse_basic_records() {
+    let txns = QifImporter::parse(SAMPLE, "%m/%d/%Y", 7, &cats()).unwrap();
+    assert_eq!(txns.len(), 2);
+
+    assert_eq!(txns[0].date, "2024-01-15");
+    assert_eq!(txns[0].amount, dec!(-42.50));
+    assert_eq!(txns[0].description, "WHOLE FOODS");
+    assert_eq!(txns[0].notes, "weekly shop");
+    assert_eq!(txns[0].account_id, 7);
+
+    assert_eq!(txns[1].amount, dec!(1500.00));
+    assert_eq!(txns[1].description, "ACME PAYROLL");
+}
+
+#[test]
+fn test_parse_matches_category_case_insensitive() {
+    let txns = QifImporter::parse(SAMPLE, "%m/%d/%Y", 1, &cats()).unwrap();
+    assert_eq!(txns[0].category_id, Some(1));
+    // "Income" isn't in the supplied categories
+    assert_eq!(txns[1].category_id, None);
+}
+
+#[test]
+fn test_parse_subcategory_falls_back_to_leaf() {
+    let qif = "!Type:Bank\nD1/5/2024\nT-80\nPCity Power\nLHousing:Utilities\n^\n";
+    let txns = QifImporter::parse(qif, "%m/%d/%Y", 1, &cats()).unwrap();
+    assert_eq!(txns[0].category_id, Some(2));
+}
+
+#[test]
+fn test_parse_transfer_category() {
+    let qif = "!Type:Bank\nD1/5/2024\nT-200\nPTo savings\nL[Savings]\n^\n";
+    let txns = QifImporter::parse(qif, "%m/%d/%Y", 1, &cats()).unwrap();
+    assert!(txns[0].is_transfer);
+    assert_eq!(txns[0].category_id, None);
+}
+
+#[test]
+fn test_parse_skips_account_blocks() {
+    let qif = "!Account\nNChecking\nTBank\n^\n!Type:Bank\nD1/5/2024\nT-3.25\nPCoffee\n^\n";
+    let txns = QifImporter::parse(qif, "%m/%d/%Y", 1, &[]).unwrap();
+    assert_eq!(txns.len(), 1);
+    assert_eq!(txns[0].description, "Coffee");
+}
+
+#[test]
+fn test_parse_quicken_apostrophe_year() {
+    let qif = "!Type:Bank\nD 1/ 5'24\nT-3.25\nPCoffee\n^\n";
+    let txns = QifImporter::parse(qif, "%m/%d/%Y", 1, &[]).unwrap();
+    assert_eq!(txns[0].date, "2024-01-05");
+}
+
+#[test]
+fn test_parse_memo_used_when_no_payee() {
+    let qif = "!Type:Bank\nD01/02/2024\nT-10\nMATM withdrawal\n^\n";
+    let txns = QifImporter::parse(qif, "%m/%d/%Y", 1, &[]).unwrap();
+    assert_eq!(txns[0].description, "ATM withdrawal");
+}
+
+#[test]
+fn test_parse_crlf_line_endings() {
+    let qif = SAMPLE.replace('\n', "\r\n");
+    let txns = QifImporter::parse(&qif, "%m/%d/%Y", 1, &cats()).unwrap();
+    assert_eq!(txns.len(), 2);
+    assert_eq!(txns[0].description, "WHOLE FOODS");
+}
+
+#[test]
+fn test_parse_hashes_unique_per_record() {
+    let qif = "!Type:Bank\nD01/02/2024\nT-10\nPSame\n^\nD01/02/2024\nT-10\nPSame\n^\n";
+    let txns = QifImporter::parse(qif, "%m/%d/%Y", 1, &[]).unwrap();
+    assert_eq!(txns.len(), 2);
+    assert_ne!(txns[0].import_hash, txns[1].import_hash);
+}
+
+#[test]
+fn test_parse_missing_date_errors() {
+    let qif = "!Type:Bank\nT-10\nPNo date\n^\n";
+    assert!(QifImporter::parse(qif, "%m/%d/%Y", 1, &[]).is_err());
+}
+
+#[test]
+fn test_parse_rejects_investment_section() {
+    let qif = "!Type:Invst\nD01/02/2024\nNBuy\n^\n";
+    assert!(QifImporter::parse(qif, "%m/%d/%Y", 1, &[]).is_err());
+}
+
+#[test]
+fn test_parse_empty() {
+    let txns = QifImporter::parse("!Type:Bank\n", "%m/%d/%Y", 1, &[]).unwrap();
+    assert!(txns.is_empty());
+}
@@ -2,20 +2,26 @@ mod categorize;
 mod db;
 mod import;
 mod models;
+mod paths;
 mod run;
 mod ui;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 
 fn main() -> Result<()> {
-    let args: Vec<String> = std::env::args().collect();
-    let db_path = get_db_path()?;
+    let mut args: Vec<String> = std::env::args().collect();
+    let db_flag = take_db_flag(&mut args)?;
+    let no_color = take_no_color_flag(&mut args);
+    let db_path = paths::get_db_path(db_flag)?;
     let mut db = db::Database::open(&db_path)?;
-    ensure_default_account(&mut db)?;
+    db.ensure_default_account()?;
 
     match args.len() {
-        1 => run::as_tui(&mut db),
-        2.. => run::as_cli(&args, &mut db),
+        1 => {
+            ui::theme::init(load_theme(no_color));
+            run::as_tui(&mut db, &db_path)
+        }
+        2.. => run::as_cli(&args, &mut db, &db_path),
         _ => {
             eprintln!("Usage: budgetui [command]");
             Ok(())
@@ -23,23 +29,37 @@ fn main() -> Result<()> {
     }
 }
 
-fn ensure_default_account(db: &mut db::Database) -> Result<()> {
-    if db.get_accounts()?.is_empty() {
-        let account = models::Account::new(
-            "Default".into(),
-            models::AccountType::Checking,
-            String::new(),
-        );
-        db.insert_account(&account)?;
+/// Remove `--db <path>` from the arguments, returning the path if given.
+fn take_db_flag(args: &mut Vec<String>) -> Result<Option<String>> {
+    match args.iter().position(|a| a == "--db") {
+        Some(i) => {
+            if i + 1 >= args.len() || args[i + 1].is_empty() {
+                anyhow::bail!("--db requires a path, e.g. --db ~/budgets/household.db");
+            }
+            let path = args.remove(i + 1);
+            args.remove(i);
+            Ok(Some(path))
+        }
+        None => Ok(None),
     }
-    Ok(())
 }
 
-fn get_db_path() -> Result<std::path::PathBuf> {
-    let proj_dirs = directories::ProjectDirs::from("com", "budgetui", "BudgeTUI")
-        .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
-    let data_dir = proj_dirs.data_dir();
-    std::fs::create_dir_all(data_dir)
-        .with_context(|| format!("Failed to create data directory: {}", data_dir.display()))?;
-    Ok(data_dir.join("budgetui.db"))
+/// Remove `--no-color` from the arguments, returning whether it was present.
+fn take_no_color_flag(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|a| a != "--no-color");
+    args.len() != before
+}
+
+/// Pick the color theme: monochrome for `--no-color` or a non-empty
+/// `NO_COLOR`, otherwise `theme.toml` from the config directory.
+fn load_theme(no_color: bool) -> ui::theme::Theme {
+    let no_color_env = std::env::var("NO_COLOR").is_ok_and(|v| !v.is_empty());
+    if no_color || no_color_env {
+        return ui::theme::Theme::monochrome();
+    }
+    match directories::ProjectDirs::from("com", "budgetui", "BudgeTUI") {
+        Some(dirs) => ui::theme::Theme::load(&dirs.config_dir().join("theme.toml")),
+        None => ui::theme::Theme::default(),
+    }
 }
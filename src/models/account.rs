@@ -1,3 +1,5 @@
+use rust_decimal::Decimal;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AccountType {
     Checking,
@@ -76,6 +78,10 @@ pub struct Account {
     pub currency: String,
     pub notes: String,
     pub created_at: String,
+    /// Balance before the first recorded transaction.
+    pub opening_balance: Decimal,
+    /// Hidden from pickers and the Accounts screen, history kept.
+    pub archived: bool,
 }
 
 impl Account {
@@ -85,9 +91,11 @@ impl Account {
             name,
             account_type,
             institution,
-            currency: "USD".to_string(),
+            currency: super::BASE_CURRENCY.to_string(),
             notes: String::new(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            opening_balance: Decimal::ZERO,
+            archived: false,
         }
     }
 }
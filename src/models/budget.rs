@@ -1,12 +1,62 @@
+use chrono::{Datelike, Days, NaiveDate};
 use rust_decimal::Decimal;
 
+/// First day of the budget month labelled `month` (`YYYY-MM`) when months
+/// start on `start_day`, e.g. `2024-01` starting on the 25th is 2024-01-25.
+pub fn budget_month_start(month: &str, start_day: u32) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+        .ok()?
+        .with_day(start_day)
+}
+
+/// Label of the budget month `date` falls in. Budget months are named for
+/// the month they start in, so with a start day of 25, 2024-02-10 is in
+/// `2024-01`.
+pub fn budget_month_of(date: NaiveDate, start_day: u32) -> String {
+    let shifted = date - Days::new(u64::from(start_day.saturating_sub(1)));
+    shifted.format("%Y-%m").to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetPeriod {
+    Monthly,
+    Yearly,
+}
+
+impl BudgetPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Monthly => "Monthly",
+            Self::Yearly => "Yearly",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "yearly" => Self::Yearly,
+            _ => Self::Monthly,
+        }
+    }
+}
+
+impl std::fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Budget {
     pub id: Option<i64>,
     pub category_id: i64,
-    /// Format: "YYYY-MM"
+    /// Format: "YYYY-MM" for monthly budgets, "YYYY" for yearly ones
     pub month: String,
     pub limit_amount: Decimal,
+    /// When set, spend in descendant categories counts against this budget.
+    pub include_subcategories: bool,
+    pub period: BudgetPeriod,
+    /// When set, unspent (or overspent) amount carries into the next month.
+    pub rollover: bool,
 }
 
 impl Budget {
@@ -16,6 +66,16 @@ impl Budget {
             category_id,
             month,
             limit_amount,
+            include_subcategories: false,
+            period: BudgetPeriod::Monthly,
+            rollover: false,
+        }
+    }
+
+    pub fn new_yearly(category_id: i64, year: String, limit_amount: Decimal) -> Self {
+        Self {
+            period: BudgetPeriod::Yearly,
+            ..Self::new(category_id, year, limit_amount)
         }
     }
 }
@@ -1,12 +1,27 @@
+/// Seed category used as the fallback for uncategorized spend.
+pub const UNCATEGORIZED: &str = "Uncategorized";
+
 #[derive(Debug, Clone)]
 pub struct Category {
     pub id: Option<i64>,
     pub name: String,
+    pub parent_id: Option<i64>,
+    /// Shown before the name, e.g. an emoji. Empty for none.
+    pub icon: String,
+    /// Hex (`#ff8800`) or named color for the category's dashboard bar.
+    /// Empty for the theme's default shading.
+    pub color: String,
 }
 
 impl Category {
     pub fn new(name: String) -> Self {
-        Self { id: None, name }
+        Self {
+            id: None,
+            name,
+            parent_id: None,
+            icon: String::new(),
+            color: String::new(),
+        }
     }
 
     /// Find a category by name (case-insensitive) in a slice.
@@ -19,6 +34,24 @@ impl Category {
     pub fn find_by_id(categories: &[Category], id: i64) -> Option<&Category> {
         categories.iter().find(|c| c.id == Some(id))
     }
+
+    /// Collect the IDs of every descendant of `id` (children, grandchildren, ...).
+    /// Guards against parent cycles so a bad hierarchy can't loop forever.
+    pub fn descendant_ids(categories: &[Category], id: i64) -> Vec<i64> {
+        let mut result: Vec<i64> = Vec::new();
+        let mut frontier = vec![id];
+        while let Some(parent) = frontier.pop() {
+            for cat in categories {
+                if let Some(cid) = cat.id {
+                    if cat.parent_id == Some(parent) && cid != id && !result.contains(&cid) {
+                        result.push(cid);
+                        frontier.push(cid);
+                    }
+                }
+            }
+        }
+        result
+    }
 }
 
 impl std::fmt::Display for Category {
@@ -0,0 +1,59 @@
+use rust_decimal::Decimal;
+
+/// Currency that all converted balances are reported in.
+pub const BASE_CURRENCY: &str = "USD";
+
+#[derive(Debug, Clone)]
+pub struct ExchangeRate {
+    pub from_currency: String,
+    pub to_currency: String,
+    pub rate: Decimal,
+    pub as_of: String,
+}
+
+impl ExchangeRate {
+    pub fn new(from_currency: &str, to_currency: &str, rate: Decimal) -> Self {
+        Self {
+            from_currency: from_currency.to_uppercase(),
+            to_currency: to_currency.to_uppercase(),
+            rate,
+            as_of: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    /// Look up the rate for converting `from` into `to`. Falls back to the
+    /// inverse of a stored `to -> from` rate. Same-currency is always 1.
+    pub fn find_rate(rates: &[ExchangeRate], from: &str, to: &str) -> Option<Decimal> {
+        if from.eq_ignore_ascii_case(to) {
+            return Some(Decimal::ONE);
+        }
+        let matches = |r: &ExchangeRate, a: &str, b: &str| {
+            r.from_currency.eq_ignore_ascii_case(a) && r.to_currency.eq_ignore_ascii_case(b)
+        };
+        if let Some(r) = rates.iter().find(|r| matches(r, from, to)) {
+            return Some(r.rate);
+        }
+        rates
+            .iter()
+            .find(|r| matches(r, to, from) && !r.rate.is_zero())
+            .map(|r| Decimal::ONE / r.rate)
+    }
+
+    /// Convert per-currency balances into `base`. Returns the converted total
+    /// and the balances that had no rate and were left out of it.
+    pub fn convert_balances(
+        balances: &[(String, Decimal)],
+        rates: &[ExchangeRate],
+        base: &str,
+    ) -> (Decimal, Vec<(String, Decimal)>) {
+        let mut total = Decimal::ZERO;
+        let mut unconverted = Vec::new();
+        for (currency, amount) in balances {
+            match Self::find_rate(rates, currency, base) {
+                Some(rate) => total += (amount * rate).round_dp(2),
+                None => unconverted.push((currency.clone(), *amount)),
+            }
+        }
+        (total, unconverted)
+    }
+}
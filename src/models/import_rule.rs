@@ -1,3 +1,38 @@
+/// How a rule came to exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSource {
+    /// Typed in with `:rule` or `:regex-rule`.
+    Manual,
+    /// Memorized from a transaction with `M`.
+    Memorized,
+    /// Created while categorizing an import.
+    Import,
+}
+
+impl RuleSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Memorized => "memorized",
+            Self::Import => "import",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "memorized" => Self::Memorized,
+            "import" => Self::Import,
+            _ => Self::Manual,
+        }
+    }
+}
+
+impl std::fmt::Display for RuleSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ImportRule {
     pub id: Option<i64>,
@@ -5,6 +40,13 @@ pub struct ImportRule {
     pub category_id: i64,
     pub is_regex: bool,
     pub priority: i32,
+    /// Only match the pattern at word boundaries.
+    pub whole_word: bool,
+    pub source: RuleSource,
+    /// RFC 3339; empty for rules that predate the column.
+    pub created_at: String,
+    /// RFC 3339 time the rule last categorized a stored transaction.
+    pub last_matched_at: Option<String>,
 }
 
 impl ImportRule {
@@ -15,16 +57,21 @@ impl ImportRule {
             category_id,
             is_regex: false,
             priority: 0,
+            whole_word: false,
+            source: RuleSource::Manual,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            last_matched_at: None,
         }
     }
 
     pub fn new_regex(pattern: String, category_id: i64) -> Self {
         Self {
-            id: None,
-            pattern,
-            category_id,
             is_regex: true,
-            priority: 0,
+            ..Self::new_contains(pattern, category_id)
         }
     }
+
+    pub fn with_source(self, source: RuleSource) -> Self {
+        Self { source, ..self }
+    }
 }
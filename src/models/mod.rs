@@ -1,14 +1,18 @@
 mod account;
 mod budget;
 mod category;
+mod exchange_rate;
 mod import_rule;
 mod transaction;
+mod ui_state;
 
 pub use account::{Account, AccountType};
-pub use budget::Budget;
-pub use category::Category;
-pub use import_rule::ImportRule;
+pub use budget::{budget_month_of, budget_month_start, Budget, BudgetPeriod};
+pub use category::{Category, UNCATEGORIZED};
+pub use exchange_rate::{ExchangeRate, BASE_CURRENCY};
+pub use import_rule::{ImportRule, RuleSource};
 pub use transaction::Transaction;
+pub use ui_state::{UiState, BUDGET_START_DAY_RANGE, DEFAULT_PAGE_SIZE, PAGE_SIZE_RANGE};
 
 #[cfg(test)]
 mod tests;
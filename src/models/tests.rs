@@ -20,9 +20,34 @@ fn make_txn(amount: Decimal) -> Transaction {
         is_transfer: false,
         import_hash: String::new(),
         created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     }
 }
 
+#[test]
+fn test_normalize_tag() {
+    assert_eq!(
+        Transaction::normalize_tag(" #Tax-Deductible "),
+        Some("tax-deductible".into())
+    );
+    assert_eq!(Transaction::normalize_tag("work"), Some("work".into()));
+    assert_eq!(Transaction::normalize_tag("#"), None);
+    assert_eq!(Transaction::normalize_tag("two words"), None);
+    assert_eq!(Transaction::normalize_tag("a,b"), None);
+}
+
+#[test]
+fn test_parse_tags_roundtrip() {
+    let mut txn = make_txn(dec!(-1));
+    assert!(Transaction::parse_tags("").is_empty());
+    txn.tags = Transaction::parse_tags("work, travel");
+    assert_eq!(txn.tags, vec!["work", "travel"]);
+    assert_eq!(txn.tags_string(), "work,travel");
+}
+
 #[test]
 fn test_income() {
     let txn = make_txn(dec!(100.00));
@@ -138,6 +163,36 @@ fn test_category_display() {
     assert_eq!(format!("{cat}"), "Groceries");
 }
 
+fn make_cat(id: i64, name: &str, parent_id: Option<i64>) -> Category {
+    Category {
+        id: Some(id),
+        parent_id,
+        ..Category::new(name.into())
+    }
+}
+
+#[test]
+fn test_category_descendant_ids() {
+    let cats = vec![
+        make_cat(1, "Housing", None),
+        make_cat(2, "Rent", Some(1)),
+        make_cat(3, "Utilities", Some(1)),
+        make_cat(4, "Electric", Some(3)),
+        make_cat(5, "Food", None),
+    ];
+    let mut ids = Category::descendant_ids(&cats, 1);
+    ids.sort();
+    assert_eq!(ids, vec![2, 3, 4]);
+    assert_eq!(Category::descendant_ids(&cats, 3), vec![4]);
+    assert!(Category::descendant_ids(&cats, 5).is_empty());
+}
+
+#[test]
+fn test_category_descendant_ids_cycle() {
+    let cats = vec![make_cat(1, "A", Some(2)), make_cat(2, "B", Some(1))];
+    assert_eq!(Category::descendant_ids(&cats, 1), vec![2]);
+}
+
 // ── Budget ────────────────────────────────────────────────────
 
 #[test]
@@ -147,6 +202,32 @@ fn test_budget_new() {
     assert_eq!(budget.category_id, 1);
     assert_eq!(budget.month, "2024-01");
     assert_eq!(budget.limit_amount, dec!(500));
+    assert!(!budget.include_subcategories);
+    assert_eq!(budget.period, BudgetPeriod::Monthly);
+}
+
+#[test]
+fn test_budget_new_yearly() {
+    let budget = Budget::new_yearly(1, "2024".into(), dec!(6000));
+    assert_eq!(budget.month, "2024");
+    assert_eq!(budget.period, BudgetPeriod::Yearly);
+    assert_eq!(
+        BudgetPeriod::parse(budget.period.as_str()),
+        BudgetPeriod::Yearly
+    );
+}
+
+#[test]
+fn test_budget_month_with_start_day() {
+    let day = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+    assert_eq!(budget_month_start("2024-01", 25), Some(day("2024-01-25")));
+    assert_eq!(budget_month_start("2024-13", 1), None);
+    assert_eq!(budget_month_of(day("2024-02-10"), 1), "2024-02");
+    assert_eq!(budget_month_of(day("2024-02-10"), 25), "2024-01");
+    assert_eq!(budget_month_of(day("2024-02-25"), 25), "2024-02");
+    // The day before the 28th in March still belongs to February
+    assert_eq!(budget_month_of(day("2023-03-27"), 28), "2023-02");
+    assert_eq!(budget_month_of(day("2023-03-28"), 28), "2023-03");
 }
 
 // ── ImportRule ─────────────────────────────────────────────────
@@ -170,3 +251,37 @@ fn test_import_rule_new_regex() {
     assert!(rule.is_regex);
     assert_eq!(rule.priority, 0);
 }
+
+// ── ExchangeRate ──────────────────────────────────────────────
+
+#[test]
+fn test_exchange_rate_find_rate() {
+    let rates = vec![ExchangeRate::new("eur", "usd", dec!(1.25))];
+    assert_eq!(rates[0].from_currency, "EUR");
+    assert_eq!(
+        ExchangeRate::find_rate(&rates, "EUR", "USD"),
+        Some(dec!(1.25))
+    );
+    assert_eq!(
+        ExchangeRate::find_rate(&rates, "USD", "EUR"),
+        Some(dec!(0.8))
+    );
+    assert_eq!(
+        ExchangeRate::find_rate(&rates, "USD", "USD"),
+        Some(Decimal::ONE)
+    );
+    assert_eq!(ExchangeRate::find_rate(&rates, "GBP", "USD"), None);
+}
+
+#[test]
+fn test_exchange_rate_convert_balances() {
+    let rates = vec![ExchangeRate::new("EUR", "USD", dec!(1.10))];
+    let balances = vec![
+        ("EUR".to_string(), dec!(100)),
+        ("GBP".to_string(), dec!(50)),
+        ("USD".to_string(), dec!(20)),
+    ];
+    let (total, unconverted) = ExchangeRate::convert_balances(&balances, &rates, BASE_CURRENCY);
+    assert_eq!(total, dec!(130.00));
+    assert_eq!(unconverted, vec![("GBP".to_string(), dec!(50))]);
+}
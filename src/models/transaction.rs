@@ -13,9 +13,41 @@ pub struct Transaction {
     pub is_transfer: bool,
     pub import_hash: String,
     pub created_at: String,
+    /// Free-form labels, stored as a comma-separated list.
+    pub tags: Vec<String>,
+    /// Shares or units bought, for investment purchases logged with `:buy`.
+    pub quantity: Option<Decimal>,
+    /// Ticker the `quantity` is in, e.g. `VTI`.
+    pub symbol: Option<String>,
+    /// Marked to revisit later, e.g. while reconciling.
+    pub flagged: bool,
 }
 
 impl Transaction {
+    /// Normalize a user-entered tag: trims, drops a leading `#`, lowercases.
+    /// Returns `None` if nothing usable is left or it contains a comma.
+    pub fn normalize_tag(tag: &str) -> Option<String> {
+        let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+        if tag.is_empty() || tag.contains(',') || tag.contains(char::is_whitespace) {
+            None
+        } else {
+            Some(tag)
+        }
+    }
+
+    /// Parse the stored comma-separated tag column.
+    pub fn parse_tags(s: &str) -> Vec<String> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn tags_string(&self) -> String {
+        self.tags.join(",")
+    }
+
     pub fn is_income(&self) -> bool {
         self.amount > Decimal::ZERO
     }
@@ -0,0 +1,34 @@
+use std::ops::RangeInclusive;
+
+/// Transactions loaded into the list at once unless `:set pagesize` says otherwise.
+pub const DEFAULT_PAGE_SIZE: u32 = 200;
+/// Page sizes accepted by `:set pagesize`.
+pub const PAGE_SIZE_RANGE: RangeInclusive<u32> = 10..=10_000;
+/// Days a budget month can start on with `:set budget-start-day`. Capped at
+/// 28 so every month has that day.
+pub const BUDGET_START_DAY_RANGE: RangeInclusive<u32> = 1..=28;
+
+/// Where the TUI was left on quit, restored on the next launch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UiState {
+    /// Screen name as written by `Screen::as_str`.
+    pub screen: Option<String>,
+    /// `None` when nothing was saved; `Some(None)` for "All Time".
+    pub month: Option<Option<String>>,
+    /// Active account on the dashboard, by position in the account list.
+    pub account_index: Option<usize>,
+    /// Transactions list fetch size, within [`PAGE_SIZE_RANGE`].
+    pub page_size: Option<u32>,
+    /// Whether `/` search is limited to the viewed month.
+    pub search_month_only: Option<bool>,
+    /// Whether imports replace descriptions with the cleaned merchant name.
+    pub clean_names: Option<bool>,
+    /// Whether the transactions list shows a header row for each day.
+    pub group_by_day: Option<bool>,
+    /// Whether the dashboard shows only the active account.
+    pub dashboard_scoped: Option<bool>,
+    /// Day of the month budget months start on, within [`BUDGET_START_DAY_RANGE`].
+    pub budget_start_day: Option<u32>,
+    /// Whether quitting with a selection or an open form asks first.
+    pub confirm_quit: Option<bool>,
+}
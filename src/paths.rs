@@ -0,0 +1,38 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Environment variable that overrides the default database location.
+const DB_ENV_VAR: &str = "BUDGETUI_DB";
+
+/// Resolve the database path: `--db` wins over `BUDGETUI_DB`, which wins
+/// over the platform data directory.
+pub(crate) fn get_db_path(flag: Option<String>) -> Result<PathBuf> {
+    let custom = flag.or_else(|| {
+        std::env::var(DB_ENV_VAR)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+    });
+
+    if let Some(custom) = custom {
+        let path = PathBuf::from(crate::run::shellexpand(custom.trim()));
+        if path.is_dir() {
+            anyhow::bail!(
+                "Database path is a directory, expected a file: {}",
+                path.display()
+            );
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create database directory: {}", parent.display())
+            })?;
+        }
+        return Ok(path);
+    }
+
+    let proj_dirs = directories::ProjectDirs::from("com", "budgetui", "BudgeTUI")
+        .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+    let data_dir = proj_dirs.data_dir();
+    std::fs::create_dir_all(data_dir)
+        .with_context(|| format!("Failed to create data directory: {}", data_dir.display()))?;
+    Ok(data_dir.join("budgetui.db"))
+}
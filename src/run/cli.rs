@@ -1,16 +1,29 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{Datelike, Days};
+use std::collections::BTreeMap;
+use std::io::Read;
 use std::path::Path;
 
-use crate::db::Database;
+use crate::db::{Database, ExportColumn};
+use crate::models::{budget_month_of, budget_month_start, BudgetPeriod, Category, BASE_CURRENCY};
+use crate::ui::screens::budgets::{budget_progress, BudgetStatus};
+use crate::ui::util::{format_amount, format_decimal, truncate};
 
-pub(crate) fn as_cli(args: &[String], db: &mut Database) -> Result<()> {
+pub(crate) fn as_cli(args: &[String], db: &mut Database, db_path: &Path) -> Result<()> {
     match args[1].as_str() {
         "import" => cli_import(&args[2..], db),
         "export" => cli_export(&args[2..], db),
         "summary" | "s" => cli_summary(&args[2..], db),
+        "budget" | "budgets" => cli_budget(&args[2..], db),
         "accounts" => cli_accounts(db),
+        "categorize" => cli_categorize(&args[2..], db),
+        "reset" => cli_reset(&args[2..], db),
+        "backup" => cli_backup(&args[2..], db),
+        "restore" => cli_restore(&args[2..], db),
         "--help" | "-h" | "help" => {
             print_usage();
+            println!();
+            println!("Database: {}", db_path.display());
             Ok(())
         }
         "--version" | "-V" | "version" => {
@@ -27,28 +40,51 @@ pub(crate) fn as_cli(args: &[String], db: &mut Database) -> Result<()> {
 fn print_usage() {
     println!("BudgeTUI — local-only personal finance tracker");
     println!();
-    println!("Usage: budgetui [command]");
+    println!("Usage: budgetui [--db <path>] [--no-color] [command]");
     println!();
     println!("Commands:");
     println!("  (none)                        Launch interactive TUI");
-    println!("  import <file.csv>             Import a CSV file (auto-detects bank format)");
+    println!("  import <file.csv|file.qif>    Import a CSV (auto-detects bank format) or QIF file");
     println!("    --account <name>            Account to import into (default: first account)");
-    println!("  export [path]                 Export transactions to CSV");
+    println!("    -                           Read CSV from stdin instead of a file");
+    println!("    --no-categorize             Import as-is, without applying rules");
+    println!("    --categorize-only           Show category counts without importing");
+    println!("  export [path]                 Export transactions to CSV (QIF for .qif)");
     println!("    --month <YYYY-MM>           Month to export (default: current)");
+    println!("    --account <name>            Only export this account");
+    println!(
+        "    --columns <a,b,...>         Columns to write, in order (e.g. date,amount,category)"
+    );
     println!("  summary [YYYY-MM]             Print monthly financial summary");
+    println!("    --weekly                    Break the month down by week instead");
+    println!("  budget [YYYY-MM]              Print budget status (exits 1 if any are over)");
     println!("  accounts                      List all accounts");
+    println!("  categorize                    Apply import rules to uncategorized transactions");
+    println!("    --dry-run                   Show matches without saving them");
+    println!("  reset --yes                   Delete ALL data and start with a fresh database");
+    println!("  backup <file.json>            Save all accounts, transactions, budgets, and rules");
+    println!("  restore <file.json>           Load a backup into an empty database");
+    println!("    --force                     Replace existing data");
     println!("  --help, -h                    Show this help");
     println!("  --version, -V                 Show version");
+    println!();
+    println!("Options:");
+    println!("  --db <path>                   Use this database file (overrides BUDGETUI_DB)");
+    println!("  --no-color                    Black-and-white TUI (also NO_COLOR=1)");
 }
 
 fn cli_import(args: &[String], db: &mut Database) -> Result<()> {
-    if args.is_empty() {
-        anyhow::bail!("Usage: budgetui import <file.csv> [--account <name>]");
-    }
+    let file_path = match import_path(args) {
+        Some(path) => path,
+        None => anyhow::bail!(
+            "Usage: budgetui import <file.csv|-> [--account <name>] [--no-categorize|--categorize-only]"
+        ),
+    };
+    let mode = ImportMode::from_args(args)?;
 
-    let file_path = &args[0];
     let path = Path::new(file_path);
-    if !path.exists() {
+    let from_stdin = file_path == "-";
+    if !from_stdin && !path.exists() {
         anyhow::bail!("File not found: {file_path}");
     }
 
@@ -58,27 +94,15 @@ fn cli_import(args: &[String], db: &mut Database) -> Result<()> {
         .find(|w| w[0] == "--account")
         .map(|w| w[1].as_str());
 
-    // Load and parse CSV
-    let (headers, rows) = crate::import::CsvImporter::preview(path)?;
-    let first_row = rows.first().cloned().unwrap_or_default();
-
-    let profile = if let Some(detected) = crate::import::detect_bank_format(&headers, &first_row) {
-        println!("Detected format: {}", detected.name);
-        detected
-    } else {
-        println!("Using default CSV profile (date=0, desc=1, amount=2)");
-        crate::import::CsvProfile::default()
-    };
-
     let account_id = if let Some(name) = account_name {
-        let accounts = db.get_accounts()?;
+        let accounts = db.get_accounts(true)?;
         accounts
             .iter()
             .find(|a| a.name.to_lowercase() == name.to_lowercase())
             .and_then(|a| a.id)
             .ok_or_else(|| anyhow::anyhow!("Account '{name}' not found"))?
     } else {
-        let accounts = db.get_accounts()?;
+        let accounts = db.get_accounts(false)?;
         if accounts.is_empty() {
             anyhow::bail!("No accounts found. Create one first, or use --account <name>");
         } else if accounts.len() == 1 {
@@ -99,11 +123,119 @@ fn cli_import(args: &[String], db: &mut Database) -> Result<()> {
         }
     };
 
-    let mut txns = crate::import::CsvImporter::parse(&rows, &profile, account_id)?;
+    let is_qif = !from_stdin && crate::import::import_extension(path).as_deref() == Some("qif");
+
+    let mut txns = if is_qif {
+        let content = crate::import::read_text_file(path)?;
+        let categories = db.get_categories()?;
+        let profile = crate::import::CsvProfile::default();
+        crate::import::QifImporter::parse(&content, &profile.date_format, account_id, &categories)?
+    } else {
+        // Load and parse CSV
+        let (headers, rows) = if from_stdin {
+            let mut bytes = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut bytes)
+                .context("Failed to read CSV from stdin")?;
+            crate::import::CsvImporter::preview_str(&crate::import::decode_text(&bytes))?
+        } else {
+            crate::import::CsvImporter::preview(path)?
+        };
+        let first_row = rows.first().cloned().unwrap_or_default();
+
+        let profile =
+            if let Some(detected) = crate::import::detect_bank_format(&headers, &first_row) {
+                println!("Detected format: {}", detected.name);
+                detected
+            } else {
+                println!("Using default CSV profile (date=0, desc=1, amount=2)");
+                crate::import::CsvProfile::default()
+            };
+        let (txns, skipped) = crate::import::CsvImporter::parse(&rows, &profile, account_id)?;
+        if !skipped.is_empty() {
+            eprintln!("Skipped {} rows with unparseable dates:", skipped.len());
+            for row in &skipped {
+                eprintln!("  row {}: '{}' {}", row.row, row.date, row.description);
+            }
+        }
+        txns
+    };
     println!("Parsed {} transactions", txns.len());
 
-    // Auto-categorize
-    let rules = db.get_import_rules()?;
+    if db.load_ui_state()?.clean_names == Some(true) {
+        crate::categorize::clean_descriptions(&mut txns);
+    }
+
+    match import_transactions(&mut txns, mode, db)? {
+        Some(count) => {
+            let dupes = txns.len() - count;
+            println!("Imported {count} new transactions ({dupes} duplicates skipped)");
+        }
+        None => {
+            print_category_counts(&txns, db)?;
+            println!("Categorize only: nothing imported");
+        }
+    }
+
+    Ok(())
+}
+
+/// The file to import: the first argument that isn't a flag or the value
+/// after `--account`, so flags can come before or after it. `-` is stdin.
+fn import_path(args: &[String]) -> Option<&str> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--account" {
+            args.next();
+        } else if !arg.starts_with("--") {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+/// What `budgetui import` does with the parsed file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportMode {
+    /// Auto-categorize, then store.
+    Full,
+    /// `--no-categorize`: store without running the import rules.
+    NoCategorize,
+    /// `--categorize-only`: run the rules and report, storing nothing.
+    CategorizeOnly,
+}
+
+impl ImportMode {
+    fn from_args(args: &[String]) -> Result<Self> {
+        let no_categorize = args.iter().any(|a| a == "--no-categorize");
+        let categorize_only = args.iter().any(|a| a == "--categorize-only");
+        match (no_categorize, categorize_only) {
+            (true, true) => {
+                anyhow::bail!("--no-categorize and --categorize-only can't be used together")
+            }
+            (true, false) => Ok(Self::NoCategorize),
+            (false, true) => Ok(Self::CategorizeOnly),
+            (false, false) => Ok(Self::Full),
+        }
+    }
+}
+
+/// Auto-categorize `txns` unless `mode` skips it, then store them unless
+/// it's `CategorizeOnly`. Rules that categorized rows are stamped as
+/// matched once something is stored. Returns how many rows were inserted,
+/// or `None` when nothing was stored.
+fn import_transactions(
+    txns: &mut [crate::models::Transaction],
+    mode: ImportMode,
+    db: &mut Database,
+) -> Result<Option<usize>> {
+    let rules = if mode == ImportMode::NoCategorize {
+        println!("Skipping auto-categorization (--no-categorize)");
+        Vec::new()
+    } else {
+        db.get_import_rules()?
+    };
+    let mut matched_rules = Vec::new();
     if !rules.is_empty() {
         let (categorizer, bad_patterns) = crate::categorize::Categorizer::new(&rules);
         if !bad_patterns.is_empty() {
@@ -112,16 +244,96 @@ fn cli_import(args: &[String], db: &mut Database) -> Result<()> {
                 bad_patterns.join(", ")
             );
         }
-        categorizer.categorize_batch(&mut txns);
+        matched_rules = categorizer
+            .categorize_batch(txns)
+            .into_iter()
+            .flatten()
+            .filter_map(|m| m.rule_id)
+            .collect();
         let categorized = txns.iter().filter(|t| t.category_id.is_some()).count();
         println!("Auto-categorized {categorized}/{} transactions", txns.len());
     }
 
-    // Insert
-    let count = db.insert_transactions_batch(&txns)?;
-    let dupes = txns.len() - count;
-    println!("Imported {count} new transactions ({dupes} duplicates skipped)");
+    if mode == ImportMode::CategorizeOnly {
+        return Ok(None);
+    }
+    let count = db.insert_transactions_batch(txns)?;
+    if count > 0 {
+        db.mark_rules_matched(&matched_rules)?;
+    }
+    Ok(Some(count))
+}
 
+/// How many parsed transactions landed in each category, busiest first.
+fn print_category_counts(txns: &[crate::models::Transaction], db: &Database) -> Result<()> {
+    let categories = db.get_categories()?;
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for txn in txns {
+        let name = txn
+            .category_id
+            .and_then(|id| categories.iter().find(|c| c.id == Some(id)))
+            .map(|c| c.name.as_str())
+            .unwrap_or("Uncategorized");
+        *counts.entry(name).or_default() += 1;
+    }
+    // Stable, so ties stay in name order
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    for (name, count) in counts {
+        println!("  {:<30} {count:>5}", truncate(name, 30));
+    }
+    Ok(())
+}
+
+fn cli_categorize(args: &[String], db: &mut Database) -> Result<()> {
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+
+    let rules = db.get_import_rules()?;
+    let (categorizer, bad_patterns) = crate::categorize::Categorizer::new(&rules);
+    if !bad_patterns.is_empty() {
+        eprintln!(
+            "Warning: invalid regex rule(s): {}",
+            bad_patterns.join(", ")
+        );
+    }
+
+    let txns = db.get_uncategorized_transactions()?;
+    if txns.is_empty() {
+        println!("No uncategorized transactions");
+        return Ok(());
+    }
+
+    if !dry_run {
+        let count = db.recategorize_uncategorized()?;
+        println!(
+            "Categorized {count}/{} uncategorized transactions",
+            txns.len()
+        );
+        return Ok(());
+    }
+
+    let categories = db.get_categories()?;
+    let mut matched = 0;
+    for txn in &txns {
+        if let Some((category_id, rule)) = categorizer.categorize(&txn.original_description) {
+            let category = categories
+                .iter()
+                .find(|c| c.id == Some(category_id))
+                .map(|c| c.name.as_str())
+                .unwrap_or("?");
+            println!(
+                "  {}  {:<40} → {category} (rule '{}')",
+                txn.date,
+                truncate(&txn.description, 40),
+                rule.pattern
+            );
+            matched += 1;
+        }
+    }
+    println!(
+        "Would categorize {matched}/{} uncategorized transactions (dry run, nothing saved)",
+        txns.len()
+    );
     Ok(())
 }
 
@@ -131,33 +343,173 @@ fn cli_export(args: &[String], db: &mut Database) -> Result<()> {
         .windows(2)
         .find(|w| w[0] == "--month")
         .map(|w| w[1].clone())
-        .unwrap_or_else(|| chrono::Local::now().format("%Y-%m").to_string());
+        .unwrap_or_else(|| {
+            budget_month_of(chrono::Local::now().date_naive(), db.budget_start_day())
+        });
 
-    // Output path is the first non-flag argument
+    let columns = match args.windows(2).find(|w| w[0] == "--columns") {
+        Some(w) => ExportColumn::parse_list(&w[1])?,
+        None if args.iter().any(|a| a == "--columns") => {
+            anyhow::bail!("--columns needs a list, e.g. --columns date,amount,category")
+        }
+        None => ExportColumn::DEFAULT.to_vec(),
+    };
+
+    // Output path is the first argument that isn't a flag or a flag's value
     let output_path = args
-        .first()
-        .filter(|a| !a.starts_with('-'))
-        .map(|a| shellexpand(a))
+        .iter()
+        .enumerate()
+        .find(|(i, a)| {
+            let after_flag = i.checked_sub(1).is_some_and(|prev| {
+                matches!(args[prev].as_str(), "--month" | "--account" | "--columns")
+            });
+            !a.starts_with('-') && !after_flag
+        })
+        .map(|(_, a)| shellexpand(a))
         .unwrap_or_else(|| {
             let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
             format!("{home}/budgetui-export-{month}.csv")
         });
 
-    let count = db.export_to_csv(&output_path, Some(&month))?;
+    // Parse --account flag
+    let account = match args.windows(2).find(|w| w[0] == "--account") {
+        Some(w) => {
+            let name = &w[1];
+            let accounts = db.get_accounts(true)?;
+            let acct = accounts
+                .into_iter()
+                .find(|a| a.name.to_lowercase() == name.to_lowercase())
+                .ok_or_else(|| anyhow::anyhow!("Account '{name}' not found"))?;
+            Some(acct)
+        }
+        None => None,
+    };
+    let from = account
+        .as_ref()
+        .map(|a| format!(" from {}", a.name))
+        .unwrap_or_default();
+
+    let account_id = account.as_ref().and_then(|a| a.id);
+    let count = if output_path.to_lowercase().ends_with(".qif") {
+        if args.iter().any(|a| a == "--columns") {
+            anyhow::bail!("--columns only applies to CSV exports");
+        }
+        db.export_to_qif(&output_path, Some(&month), account_id)?
+    } else {
+        db.export_to_csv(&output_path, Some(&month), account_id, &columns)?
+    };
     if count == 0 {
-        println!("No transactions for {month}");
+        println!("No transactions for {month}{from}");
     } else {
-        println!("Exported {count} transactions to {output_path}");
+        println!("Exported {count} transactions{from} to {output_path}");
     }
     Ok(())
 }
 
-fn cli_summary(args: &[String], db: &mut Database) -> Result<()> {
+fn cli_budget(args: &[String], db: &mut Database) -> Result<()> {
     let month = args
         .first()
         .filter(|a| !a.starts_with('-'))
         .cloned()
-        .unwrap_or_else(|| chrono::Local::now().format("%Y-%m").to_string());
+        .unwrap_or_else(|| {
+            budget_month_of(chrono::Local::now().date_naive(), db.budget_start_day())
+        });
+    let year = match chrono::NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d") {
+        Ok(date) => date.year().to_string(),
+        Err(_) => anyhow::bail!("Invalid month '{month}'. Use YYYY-MM (e.g. 2024-01)"),
+    };
+
+    let categories = db.get_categories()?;
+    let spending = db.get_spending_by_category(Some(&month))?;
+    let mut budgets = db.get_budgets(Some(&month))?;
+    budgets.extend(db.get_budgets(Some(&year))?);
+
+    let fmt = |amount| format_amount(amount, BASE_CURRENCY);
+
+    println!("BudgeTUI budgets — {month}");
+    println!("{}", "─".repeat(60));
+    if budgets.is_empty() {
+        println!("  No budgets set (use :budget <category> <amount> in the TUI)");
+    }
+
+    // Category ids whose spending is counted by some budget
+    let mut covered: Vec<i64> = Vec::new();
+    let mut over = 0;
+    for budget in &budgets {
+        let name = Category::find_by_id(&categories, budget.category_id)
+            .map(|c| c.name.as_str())
+            .unwrap_or("Unknown");
+        covered.push(budget.category_id);
+        if budget.include_subcategories {
+            covered.extend(Category::descendant_ids(&categories, budget.category_id));
+        }
+
+        let is_yearly = budget.period == BudgetPeriod::Yearly;
+        let spent = db.get_budget_spent(budget)?;
+        let available = if budget.rollover {
+            budget.limit_amount + db.get_budget_rollover(budget.category_id, &budget.month)?
+        } else {
+            budget.limit_amount
+        };
+        let progress = budget_progress(spent, available);
+        let is_over = progress.status == BudgetStatus::Over;
+        if is_over {
+            over += 1;
+        }
+        let label = match (budget.include_subcategories, is_yearly) {
+            (true, true) => format!("{name}+ ({year})"),
+            (true, false) => format!("{name}+"),
+            (false, true) => format!("{name} ({year})"),
+            (false, false) => name.to_string(),
+        };
+        println!(
+            "  {:<24} {:>12} / {:<12} {:>9}{}",
+            truncate(&label, 24),
+            fmt(spent),
+            fmt(available),
+            progress.label(),
+            if is_over { "  OVER" } else { "" }
+        );
+    }
+
+    let unbudgeted: Vec<_> = spending
+        .iter()
+        .filter(|(name, _)| {
+            categories
+                .iter()
+                .find(|c| c.name == *name)
+                .and_then(|c| c.id)
+                .is_none_or(|id| !covered.contains(&id))
+        })
+        .collect();
+    if !unbudgeted.is_empty() {
+        println!();
+        println!("  Unbudgeted:");
+        for (name, amount) in unbudgeted {
+            println!("    {:<22} {:>12}", truncate(name, 22), fmt(amount.abs()));
+        }
+    }
+
+    if over > 0 {
+        anyhow::bail!(
+            "{over} budget{} over the limit for {month}",
+            if over == 1 { "" } else { "s" }
+        );
+    }
+    Ok(())
+}
+
+fn cli_summary(args: &[String], db: &mut Database) -> Result<()> {
+    let month = args
+        .iter()
+        .find(|a| !a.starts_with('-'))
+        .cloned()
+        .unwrap_or_else(|| {
+            budget_month_of(chrono::Local::now().date_naive(), db.budget_start_day())
+        });
+    if args.iter().any(|a| a == "--weekly") {
+        return cli_weekly_summary(&month, db);
+    }
 
     let (income, expenses) = db.get_monthly_totals(Some(&month))?;
     let net = income + expenses;
@@ -165,27 +517,80 @@ fn cli_summary(args: &[String], db: &mut Database) -> Result<()> {
     let spending = db.get_spending_by_category(Some(&month))?;
     let txn_count = db.get_transaction_count()?;
 
+    // Totals are in the base currency
+    let fmt = |amount| format_amount(amount, BASE_CURRENCY);
+
     println!("BudgeTUI — {month}");
     println!("{}", "─".repeat(40));
-    println!("  Income:     ${:.2}", income);
-    println!("  Expenses:   ${:.2}", expenses.abs());
-    println!("  Net:        ${:.2}", net);
-    println!("  Net Worth:  ${:.2}", net_worth);
+    println!("  Income:     {}", fmt(income));
+    println!("  Expenses:   {}", fmt(expenses.abs()));
+    println!("  Net:        {}", fmt(net));
+    println!("  Net Worth:  {}", fmt(net_worth));
+    for (currency, amount) in db.get_unconverted_balances()? {
+        println!(
+            "    (excluded {currency} {} — no exchange rate, set one with :rate)",
+            format_decimal(amount, 2, true)
+        );
+    }
     println!("  Total Txns: {txn_count}");
 
     if !spending.is_empty() {
         println!();
         println!("Spending by Category:");
         for (name, amount) in &spending {
-            println!("  {name:<24} ${:.2}", amount.abs());
+            println!("  {name:<24} {}", fmt(amount.abs()));
         }
     }
 
     Ok(())
 }
 
+fn cli_weekly_summary(month: &str, db: &mut Database) -> Result<()> {
+    let first = budget_month_start(month, db.budget_start_day())
+        .with_context(|| format!("Invalid month '{month}', expected YYYY-MM"))?;
+    let last = first
+        .checked_add_months(chrono::Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .context("Month out of range")?;
+    let totals = db.get_weekly_totals(month)?;
+    let fmt = |amount| format_amount(amount, BASE_CURRENCY);
+
+    println!("BudgeTUI — {month} by week");
+    println!("{}", "─".repeat(52));
+    println!(
+        "  {:<13} {:>12} {:>12} {:>12}",
+        "Week", "Income", "Expenses", "Net"
+    );
+    // Every week of the month, Monday first, including quiet ones
+    let mut monday = first - Days::new(u64::from(first.weekday().num_days_from_monday()));
+    while monday <= last {
+        let start = monday.max(first);
+        let end = (monday + Days::new(6)).min(last);
+        let key = monday.format("%Y-%m-%d").to_string();
+        let (income, expenses) = totals
+            .iter()
+            .find(|(week, _, _)| *week == key)
+            .map(|(_, i, e)| (*i, *e))
+            .unwrap_or_default();
+        let week = if start.month() == end.month() {
+            format!("{} {}–{}", start.format("%b"), start.day(), end.day())
+        } else {
+            format!("{}–{}", start.format("%b %-d"), end.format("%b %-d"))
+        };
+        println!(
+            "  {:<13} {:>12} {:>12} {:>12}",
+            week,
+            fmt(income),
+            fmt(expenses.abs()),
+            fmt(income + expenses)
+        );
+        monday = monday + Days::new(7);
+    }
+    Ok(())
+}
+
 fn cli_accounts(db: &mut Database) -> Result<()> {
-    let accounts = db.get_accounts()?;
+    let accounts = db.get_accounts(true)?;
     if accounts.is_empty() {
         println!("No accounts");
         return Ok(());
@@ -195,16 +600,74 @@ fn cli_accounts(db: &mut Database) -> Result<()> {
     println!("{}", "─".repeat(55));
     for acct in &accounts {
         println!(
-            "{:<4} {:<20} {:<15} {}",
+            "{:<4} {:<20} {:<15} {}{}",
             acct.id.unwrap_or(0),
             acct.name,
             acct.account_type,
             acct.institution,
+            if acct.archived { "  (archived)" } else { "" },
         );
     }
     Ok(())
 }
 
+fn cli_reset(args: &[String], db: &mut Database) -> Result<()> {
+    if !args.iter().any(|a| a == "--yes") {
+        anyhow::bail!(
+            "This deletes ALL transactions, accounts, budgets, and rules.\n\
+             Re-run with `budgetui reset --yes` to confirm."
+        );
+    }
+
+    db.wipe_all_data()?;
+    println!("All data wiped. Default categories and account restored.");
+    Ok(())
+}
+
+fn cli_backup(args: &[String], db: &mut Database) -> Result<()> {
+    let path = match args.first().filter(|a| !a.starts_with('-')) {
+        Some(p) => shellexpand(p),
+        None => anyhow::bail!("Usage: budgetui backup <file.json>"),
+    };
+
+    let doc = db.dump_all()?;
+    let file = std::fs::File::create(&path)
+        .with_context(|| format!("Failed to create backup file: {path}"))?;
+    serde_json::to_writer_pretty(std::io::BufWriter::new(file), &doc)
+        .with_context(|| format!("Failed to write backup file: {path}"))?;
+    println!("Backed up to {path}");
+    Ok(())
+}
+
+fn cli_restore(args: &[String], db: &mut Database) -> Result<()> {
+    let path = match args.first().filter(|a| !a.starts_with('-')) {
+        Some(p) => shellexpand(p),
+        None => anyhow::bail!("Usage: budgetui restore <file.json> [--force]"),
+    };
+    let force = args.iter().any(|a| a == "--force");
+
+    if !force && db.has_user_data()? {
+        anyhow::bail!(
+            "This database already has data, which a restore would replace.\n\
+             Back it up first, then re-run with `budgetui restore {path} --force`."
+        );
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read backup file: {path}"))?;
+    let doc: serde_json::Value =
+        serde_json::from_str(&content).with_context(|| format!("{path} is not valid JSON"))?;
+    let counts = db.load_all(&doc)?;
+    db.ensure_default_account()?;
+
+    let summary: Vec<String> = counts
+        .iter()
+        .map(|(table, n)| format!("{n} {}", table.replace('_', " ")))
+        .collect();
+    println!("Restored {}", summary.join(", "));
+    Ok(())
+}
+
 pub(crate) fn shellexpand(path: &str) -> String {
     if let Some(rest) = path.strip_prefix("~/") {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
@@ -213,3 +676,7 @@ pub(crate) fn shellexpand(path: &str) -> String {
         path.to_string()
     }
 }
+
+#[cfg(test)]
+#[path = "cli_tests.rs"]
+mod tests;
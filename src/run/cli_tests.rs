@@ -0,0 +1,106 @@
+#![allow(clippy::unwrap_used)]
+
+use super::*;
+use crate::db::TransactionQuery;
+use crate::models::{ImportRule, Transaction};
+use rust_decimal_macros::dec;
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|a| a.to_string()).collect()
+}
+
+/// A database with one account and a `COFFEE` rule, plus two parsed rows
+/// for it: one the rule matches and one it doesn't.
+fn setup() -> (Database, Vec<Transaction>) {
+    let db = Database::open_in_memory().unwrap();
+    db.ensure_default_account().unwrap();
+    let account_id = db.get_accounts(false).unwrap()[0].id.unwrap();
+    let category_id = db.get_categories().unwrap()[0].id.unwrap();
+    db.insert_import_rule(&ImportRule::new_contains("COFFEE".into(), category_id))
+        .unwrap();
+    let row = |description: &str| Transaction {
+        id: None,
+        account_id,
+        date: "2024-01-10".into(),
+        description: description.into(),
+        original_description: description.into(),
+        amount: dec!(-5.25),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: false,
+        import_hash: format!("hash-{description}"),
+        created_at: String::new(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    (db, vec![row("COFFEE SHOP"), row("GAS")])
+}
+
+fn coffee_rule_matched(db: &Database) -> bool {
+    db.get_import_rules().unwrap()[0].last_matched_at.is_some()
+}
+
+// ── Import ────────────────────────────────────────────────────
+
+#[test]
+fn test_import_path_skips_flags_before_it() {
+    assert_eq!(import_path(&args(&["--account", "Chase", "-"])), Some("-"));
+    assert_eq!(
+        import_path(&args(&["--no-categorize", "a.csv", "--account", "Chase"])),
+        Some("a.csv")
+    );
+    assert_eq!(import_path(&args(&["-", "--account", "Chase"])), Some("-"));
+    assert_eq!(import_path(&args(&["--account", "Chase"])), None);
+}
+
+#[test]
+fn test_import_mode_from_args() {
+    assert_eq!(
+        ImportMode::from_args(&args(&["a.csv"])).unwrap(),
+        ImportMode::Full
+    );
+    assert_eq!(
+        ImportMode::from_args(&args(&["a.csv", "--no-categorize"])).unwrap(),
+        ImportMode::NoCategorize
+    );
+    assert_eq!(
+        ImportMode::from_args(&args(&["a.csv", "--categorize-only"])).unwrap(),
+        ImportMode::CategorizeOnly
+    );
+    let err = ImportMode::from_args(&args(&["a.csv", "--no-categorize", "--categorize-only"]))
+        .unwrap_err();
+    assert!(err.to_string().contains("can't be used together"));
+}
+
+#[test]
+fn test_import_categorizes_stores_and_stamps_rules() {
+    let (mut db, mut txns) = setup();
+    let count = import_transactions(&mut txns, ImportMode::Full, &mut db).unwrap();
+    assert_eq!(count, Some(2));
+    let stored = db.get_transactions(&TransactionQuery::default()).unwrap();
+    assert_eq!(stored.iter().filter(|t| t.category_id.is_some()).count(), 1);
+    assert!(coffee_rule_matched(&db));
+}
+
+#[test]
+fn test_import_no_categorize_stores_rows_as_parsed() {
+    let (mut db, mut txns) = setup();
+    let count = import_transactions(&mut txns, ImportMode::NoCategorize, &mut db).unwrap();
+    assert_eq!(count, Some(2));
+    let stored = db.get_transactions(&TransactionQuery::default()).unwrap();
+    assert!(stored.iter().all(|t| t.category_id.is_none()));
+    assert!(!coffee_rule_matched(&db));
+}
+
+#[test]
+fn test_import_categorize_only_stores_nothing() {
+    let (mut db, mut txns) = setup();
+    let count = import_transactions(&mut txns, ImportMode::CategorizeOnly, &mut db).unwrap();
+    assert_eq!(count, None);
+    // The rows are categorized in memory for the report
+    assert!(txns[0].category_id.is_some());
+    assert_eq!(db.get_transaction_count().unwrap(), 0);
+    assert!(!coffee_rule_matched(&db));
+}
@@ -7,16 +7,47 @@ use crossterm::{
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 use crate::db::Database;
-use crate::models::{Account, AccountType};
-use crate::ui::app::{App, ImportStep, InputMode, PendingAction, Screen};
+use crate::import::{delimiter_name, SignConvention, DELIMITERS};
+use crate::models::{Account, AccountType, Transaction};
+use crate::ui::app::{
+    App, ImportReport, ImportStep, InputMode, InterruptedPrompt, PendingAction, Screen,
+    TXN_FORM_FIELDS,
+};
 use crate::ui::commands;
-use crate::ui::util::{scroll_down, scroll_to_bottom, scroll_to_top, scroll_up};
+use crate::ui::util::{
+    first_starting_with, scroll_down, scroll_to, scroll_to_bottom, scroll_to_top, scroll_up,
+};
 
-pub(crate) fn as_tui(db: &mut Database) -> Result<()> {
+pub(crate) fn as_tui(db: &mut Database, db_path: &Path) -> Result<()> {
     let mut app = App::new();
+    // The file exists once the database is open, so a relative `--db` can be
+    // shown as the full path
+    app.db_path = std::fs::canonicalize(db_path).unwrap_or_else(|_| db_path.to_path_buf());
+    // A corrupt or missing saved state just means starting from the defaults
+    if let Ok(state) = db.load_ui_state() {
+        app.restore_ui_state(state);
+    }
+    // Same for the import history: without it the file browser opens at home
+    if let Ok(dirs) = db.get_recent_dirs() {
+        app.set_recent_dirs(dirs);
+        if let Some(dir) = app.file_browser_recent.first() {
+            app.file_browser_path = dir.clone();
+        }
+    }
+    if let Ok(formats) = db.get_custom_date_formats() {
+        app.custom_date_formats = formats;
+    }
+    if let Ok(history) = db.get_command_history() {
+        app.command_history = history;
+    }
     app.refresh_all(db)?;
+    if app.account_index >= app.accounts.len() {
+        app.account_index = 0;
+    }
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -25,6 +56,7 @@ pub(crate) fn as_tui(db: &mut Database) -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     let result = run_app(&mut terminal, &mut app, db);
+    let saved = db.save_ui_state(&app.ui_state());
 
     disable_raw_mode()?;
     execute!(
@@ -37,43 +69,81 @@ pub(crate) fn as_tui(db: &mut Database) -> Result<()> {
     if let Err(ref e) = result {
         eprintln!("Error: {e:?}");
     }
+    if let Err(e) = saved {
+        eprintln!("Warning: could not save UI state: {e}");
+    }
 
     result
 }
 
+/// How long the event loop waits for input before redrawing anyway, so
+/// time-based UI like the status-bar clock updates while idle. Only cells
+/// that changed are written to the terminal, so an idle redraw is cheap.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
     db: &mut Database,
 ) -> Result<()> {
     while app.running {
+        app.expire_status(Instant::now());
         terminal.draw(|f| {
             let content_height = f.area().height.saturating_sub(3) as usize;
             app.visible_rows = content_height.max(1);
             crate::ui::render::render(f, app);
         })?;
 
+        if !event::poll(REDRAW_INTERVAL)? {
+            continue;
+        }
+        // Anything but a key press, a resize included, just needs the
+        // redraw at the top of the loop; `draw` picks up the new size
         if let Event::Key(key) = event::read()? {
-            if app.show_help {
-                app.show_help = false;
-                continue;
-            }
-            if app.show_nav {
-                handle_nav_input(key, app, db)?;
-                continue;
-            }
-            match app.input_mode {
-                InputMode::Normal => handle_normal_input(key, app, db)?,
-                InputMode::Command => handle_command_input(key, app, db)?,
-                InputMode::Search => handle_search_input(key, app, db)?,
-                InputMode::Editing => handle_editing_input(key, app, db)?,
-                InputMode::Confirm => handle_confirm_input(key, app, db)?,
-            }
+            handle_key(key, app, db)?;
         }
     }
     Ok(())
 }
 
+fn handle_key(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
+    app.dismiss_error();
+    if app.show_help {
+        app.show_help = false;
+        return Ok(());
+    }
+    if app.show_net_worth_history {
+        app.show_net_worth_history = false;
+        return Ok(());
+    }
+    if app.show_transaction_detail {
+        app.show_transaction_detail = false;
+        return Ok(());
+    }
+    if app.show_nav {
+        return handle_nav_input(key, app, db);
+    }
+    if app.show_month_picker {
+        return handle_month_picker_input(key, app, db);
+    }
+    // Checked ahead of every mode so an open form or prompt can't swallow it
+    if is_quit_key(key) {
+        if matches!(app.pending_action, Some(PendingAction::Quit)) {
+            app.running = false;
+        } else {
+            request_quit(app);
+        }
+        return Ok(());
+    }
+    match app.input_mode {
+        InputMode::Normal => handle_normal_input(key, app, db),
+        InputMode::Command => handle_command_input(key, app, db),
+        InputMode::Search => handle_search_input(key, app, db),
+        InputMode::Editing => handle_editing_input(key, app, db),
+        InputMode::Confirm => handle_confirm_input(key, app, db),
+    }
+}
+
 // ── Input handlers ───────────────────────────────────────────
 
 fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
@@ -81,7 +151,7 @@ fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
         && app.import_step == ImportStep::SelectFile
         && app.file_browser_input_focused
     {
-        return handle_file_browser_input(key, app);
+        return handle_file_browser_input(key, app, db);
     }
 
     if app.screen == Screen::Import && app.import_step == ImportStep::Categorize {
@@ -92,6 +162,22 @@ fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
         return handle_select_account_input(key, app, db);
     }
 
+    if app.screen == Screen::Accounts && app.account_editing {
+        return handle_account_edit_input(key, app, db);
+    }
+
+    if app.screen == Screen::Categories && app.rule_editing {
+        return handle_rule_edit_input(key, app, db);
+    }
+
+    if app.screen == Screen::Transactions && app.txn_adding {
+        return handle_add_transaction_input(key, app, db);
+    }
+
+    if app.screen == Screen::Transactions && app.txn_recat {
+        return handle_recat_input(key, app, db);
+    }
+
     match key.code {
         KeyCode::Char(':') => {
             app.input_mode = InputMode::Command;
@@ -101,11 +187,6 @@ fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
             app.input_mode = InputMode::Search;
             app.search_input.clear();
         }
-        KeyCode::Char('q') | KeyCode::Char('c')
-            if key.modifiers.contains(KeyModifiers::CONTROL) =>
-        {
-            app.running = false;
-        }
         KeyCode::Char('j') | KeyCode::Down => handle_move_down(app),
         KeyCode::Char('k') | KeyCode::Up => handle_move_up(app),
         KeyCode::Char('1') => switch_screen(app, db, Screen::Dashboard)?,
@@ -132,9 +213,25 @@ fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
             switch_screen(app, db, screens[prev])?;
         }
         KeyCode::Enter => handle_enter(app, db)?,
-        KeyCode::Esc => handle_escape(app),
+        KeyCode::Esc => {
+            handle_escape(app);
+            // Show the rows a cleared filter or search was hiding
+            if app.screen == Screen::Transactions {
+                app.refresh_transactions(db)?;
+            }
+        }
         KeyCode::Char('+') | KeyCode::Char('=') => handle_adjust_field(app, 1),
         KeyCode::Char('-') => handle_adjust_field(app, -1),
+        KeyCode::Char('~')
+            if app.screen == Screen::Import && app.import_step == ImportStep::MapColumns =>
+        {
+            app.import_flip_signs = !app.import_flip_signs;
+            app.set_status(if app.import_flip_signs {
+                "Every amount will be flipped — ~ to undo"
+            } else {
+                "Amounts are read as mapped — ~ to flip them"
+            });
+        }
         KeyCode::Char('.')
             if app.screen == Screen::Import && app.import_step == ImportStep::SelectFile =>
         {
@@ -149,24 +246,51 @@ fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
         KeyCode::Char('r') if app.screen == Screen::Categories => {
             app.category_view_rules = !app.category_view_rules;
         }
-        KeyCode::Char('n') if app.screen == Screen::Dashboard => {
-            if !app.accounts.is_empty() {
-                app.account_index = (app.account_index + 1) % app.accounts.len();
-                let name = &app.accounts[app.account_index].name;
-                app.set_status(format!("Active account: {name}"));
+        KeyCode::Char('x') if app.screen == Screen::Categories => {
+            let cmd = if app.category_view_rules {
+                "delete-rule"
+            } else {
+                "delete-category"
+            };
+            commands::handle_command(cmd, app, db)?;
+        }
+        KeyCode::Char('e') if app.screen == Screen::Categories && app.category_view_rules => {
+            if let Some(rule) = app.import_rules.get(app.rule_index) {
+                app.rule_edit_pattern = rule.pattern.clone();
+                app.rule_edit_regex = rule.is_regex;
+                app.rule_edit_whole_word = rule.whole_word;
+                app.rule_edit_category = app
+                    .categories
+                    .iter()
+                    .position(|c| c.id == Some(rule.category_id))
+                    .unwrap_or(0);
+                app.rule_editing = true;
             }
         }
-        KeyCode::Char('p') if app.screen == Screen::Dashboard => {
-            if !app.accounts.is_empty() {
-                app.account_index = if app.account_index == 0 {
-                    app.accounts.len() - 1
-                } else {
-                    app.account_index - 1
-                };
-                let name = &app.accounts[app.account_index].name;
-                app.set_status(format!("Active account: {name}"));
-            }
+        KeyCode::Char('n') if app.screen == Screen::Dashboard && !app.accounts.is_empty() => {
+            app.account_index = (app.account_index + 1) % app.accounts.len();
+            switch_active_account(app, db)?;
         }
+        KeyCode::Char('p') if app.screen == Screen::Dashboard && !app.accounts.is_empty() => {
+            app.account_index = if app.account_index == 0 {
+                app.accounts.len() - 1
+            } else {
+                app.account_index - 1
+            };
+            switch_active_account(app, db)?;
+        }
+        KeyCode::Char('A') if app.screen == Screen::Dashboard && !app.accounts.is_empty() => {
+            app.dashboard_scoped = !app.dashboard_scoped;
+            app.refresh_dashboard(db)?;
+            app.set_status(match app.dashboard_account() {
+                Some(account) => format!(
+                    "Dashboard: {} only — n/p to switch account, A for all accounts",
+                    account.name
+                ),
+                None => "Dashboard: all accounts — A to show only the active account".to_string(),
+            });
+        }
+        KeyCode::Char('m') => app.open_month_picker(),
         KeyCode::Char('H') => {
             commands::handle_command("prev-month", app, db)?;
         }
@@ -185,6 +309,28 @@ fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
                 handle_move_up(app);
             }
         }
+        KeyCode::Char('u')
+            if app.screen == Screen::Dashboard || app.screen == Screen::Transactions =>
+        {
+            commands::handle_command("uncategorized", app, db)?;
+        }
+        KeyCode::Char('z') if app.screen == Screen::Transactions => {
+            app.transaction_group_by_day = !app.transaction_group_by_day;
+            app.set_status(if app.transaction_group_by_day {
+                "Grouping transactions by day — z to show a flat list"
+            } else {
+                "Showing a flat list — z to group by day"
+            });
+        }
+        KeyCode::Char('f') if app.screen == Screen::Transactions => {
+            toggle_flagged(app, db)?;
+        }
+        KeyCode::Char('[') if app.screen == Screen::Transactions => {
+            jump_to_flagged(app, false);
+        }
+        KeyCode::Char(']') if app.screen == Screen::Transactions => {
+            jump_to_flagged(app, true);
+        }
         KeyCode::Char('D') if app.screen == Screen::Transactions => {
             if app.selected_transactions.is_empty() {
                 commands::handle_command("delete-txn", app, db)?;
@@ -199,6 +345,114 @@ fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
                 app.input_mode = InputMode::Confirm;
             }
         }
+        KeyCode::Char(' ')
+            if app.screen == Screen::Import && app.import_step == ImportStep::Preview =>
+        {
+            let index = app.import_preview_index;
+            if index < app.import_preview.len() && !app.import_preview_selected.remove(&index) {
+                app.import_preview_selected.insert(index);
+            }
+            handle_move_down(app);
+        }
+        KeyCode::Char('t')
+            if app.screen == Screen::Import && app.import_step == ImportStep::Preview =>
+        {
+            let count = app.import_preview_targets().len();
+            if count > 0 {
+                let marked = app.toggle_import_preview_transfer();
+                let rows = if count == 1 { "row" } else { "rows" };
+                app.set_status(if marked {
+                    format!("Marked {count} {rows} as transfers")
+                } else {
+                    format!("Unmarked {count} {rows} as transfers")
+                });
+            }
+        }
+        KeyCode::Char('f')
+            if app.screen == Screen::Import && app.import_step == ImportStep::Preview =>
+        {
+            // Regenerating drops marks and notes, but they were made against
+            // amounts that were wrong anyway
+            app.import_flip_signs = !app.import_flip_signs;
+            app.generate_import_preview(db)?;
+            if !app.import_signs_suspect {
+                app.set_status(if app.import_flip_signs {
+                    "Flipped every sign — f to put them back"
+                } else {
+                    "Signs are as in the file — f to flip them"
+                });
+            }
+        }
+        KeyCode::Char('n')
+            if app.screen == Screen::Import && app.import_step == ImportStep::Preview =>
+        {
+            let targets = app.import_preview_targets();
+            if let [only] = targets[..] {
+                app.command_input = app.import_preview[only].notes.clone();
+                app.input_mode = InputMode::Editing;
+            } else if !targets.is_empty() {
+                app.command_input.clear();
+                app.input_mode = InputMode::Editing;
+            }
+        }
+        KeyCode::Char('s')
+            if app.screen == Screen::Import
+                && app.import_step == ImportStep::Preview
+                && !app.import_duplicates.is_empty() =>
+        {
+            app.import_skip_duplicates = !app.import_skip_duplicates;
+            let dupes = app.import_duplicates.len();
+            app.set_status(if app.import_skip_duplicates {
+                format!("Skipping {dupes} probable duplicates")
+            } else {
+                format!("Including {dupes} probable duplicates")
+            });
+        }
+        KeyCode::Char('t') if app.screen == Screen::Transactions => {
+            commands::handle_command("transfer", app, db)?;
+        }
+        KeyCode::Char('a') if app.screen == Screen::Transactions => {
+            app.open_add_transaction();
+        }
+        KeyCode::Char('M') if app.screen == Screen::Transactions => {
+            commands::handle_command("memorize", app, db)?;
+        }
+        KeyCode::Char('c') if app.screen == Screen::Transactions => {
+            if app.transactions.is_empty() {
+                app.set_status("No transaction selected");
+            } else {
+                app.open_recat_picker();
+            }
+        }
+        KeyCode::Char('e') if app.screen == Screen::Accounts => {
+            if let Some(snap) = app.account_snapshots.get(app.accounts_tab_index) {
+                app.account_edit_name = snap.account.name.clone();
+                app.account_edit_type = AccountType::all()
+                    .iter()
+                    .position(|t| *t == snap.account.account_type)
+                    .unwrap_or(0);
+                app.account_editing = true;
+            }
+        }
+        KeyCode::Char('x') if app.screen == Screen::Accounts => {
+            commands::handle_command("delete-account", app, db)?;
+        }
+        KeyCode::Char('A') if app.screen == Screen::Accounts => {
+            app.show_archived = !app.show_archived;
+            app.refresh_accounts_tab(db)?;
+            if app.accounts_tab_index >= app.account_snapshots.len() {
+                app.accounts_tab_index = app.account_snapshots.len().saturating_sub(1);
+            }
+            app.set_status(if app.show_archived {
+                let count = app.archived_accounts.len();
+                format!(
+                    "Showing {count} archived account{}",
+                    if count == 1 { "" } else { "s" }
+                )
+            } else {
+                "Hiding archived accounts".to_string()
+            });
+        }
         KeyCode::Char(' ') if app.screen == Screen::Transactions => {
             if let Some(txn) = app.transactions.get(app.transaction_index) {
                 if let Some(id) = txn.id {
@@ -209,18 +463,112 @@ fn handle_normal_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
             }
             handle_move_down(app);
         }
+        KeyCode::Char('i')
+            if app.screen == Screen::Transactions && !app.transactions.is_empty() =>
+        {
+            app.show_transaction_detail = true;
+        }
         KeyCode::Char('i')
             if app.screen == Screen::Import && app.import_step == ImportStep::Complete =>
         {
             app.import_step = ImportStep::SelectFile;
             app.refresh_file_browser();
         }
+        // Any letter not bound above jumps to the first matching entry
+        KeyCode::Char(c) if app.screen == Screen::Categories && c.is_alphanumeric() => {
+            jump_to_letter(app, c);
+        }
         _ => {}
     }
     Ok(())
 }
 
-fn handle_file_browser_input(key: event::KeyEvent, app: &mut App) -> Result<()> {
+/// Dashboard: after `n`/`p` moved the active account, announce it and, when
+/// the dashboard is scoped to it, reload the figures.
+fn switch_active_account(app: &mut App, db: &mut Database) -> Result<()> {
+    if app.dashboard_scoped {
+        app.refresh_dashboard(db)?;
+    }
+    let name = &app.accounts[app.account_index].name;
+    app.set_status(format!("Active account: {name}"));
+    Ok(())
+}
+
+/// Transactions screen: flag the row under the cursor for review, or clear
+/// its flag.
+fn toggle_flagged(app: &mut App, db: &mut Database) -> Result<()> {
+    let (id, flagged) = match app.transactions.get(app.transaction_index) {
+        Some(txn) => (txn.id, !txn.flagged),
+        None => {
+            app.set_status("No transaction selected");
+            return Ok(());
+        }
+    };
+    if let Some(id) = id {
+        db.set_transaction_flagged(id, flagged)?;
+        app.refresh_transactions(db)?;
+        app.set_status(if flagged {
+            "Flagged for review — [ and ] jump between flagged rows"
+        } else {
+            "Flag cleared"
+        });
+    }
+    Ok(())
+}
+
+/// Transactions screen: move to the next (or previous) flagged row in the
+/// list, wrapping around at the ends.
+fn jump_to_flagged(app: &mut App, forward: bool) {
+    let len = app.transactions.len();
+    let target = (1..len)
+        .map(|step| {
+            if forward {
+                (app.transaction_index + step) % len
+            } else {
+                (app.transaction_index + len - step) % len
+            }
+        })
+        .find(|&i| app.transactions[i].flagged);
+    match target {
+        Some(idx) => {
+            let page = app.transaction_page();
+            scroll_to(
+                &mut app.transaction_index,
+                &mut app.transaction_scroll,
+                idx,
+                page,
+            );
+        }
+        None if app
+            .transactions
+            .get(app.transaction_index)
+            .is_some_and(|t| t.flagged) =>
+        {
+            app.set_status("No other flagged transactions in this list");
+        }
+        None => app.set_status("No flagged transactions in this list — f to flag one"),
+    }
+}
+
+/// Categories screen: move to the first category (or rule pattern, in the
+/// rules view) starting with `c`.
+fn jump_to_letter(app: &mut App, c: char) {
+    if app.category_view_rules {
+        let page = app.rule_page();
+        let patterns = app.import_rules.iter().map(|r| r.pattern.as_str());
+        if let Some(idx) = first_starting_with(patterns, c) {
+            scroll_to(&mut app.rule_index, &mut app.rule_scroll, idx, page);
+        }
+    } else {
+        let page = app.category_page();
+        let names = app.categories.iter().map(|cat| cat.name.as_str());
+        if let Some(idx) = first_starting_with(names, c) {
+            scroll_to(&mut app.category_index, &mut app.category_scroll, idx, page);
+        }
+    }
+}
+
+fn handle_file_browser_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
     match key.code {
         KeyCode::Char(c) => {
             app.file_browser_filter.push(c);
@@ -257,10 +605,7 @@ fn handle_file_browser_input(key: event::KeyEvent, app: &mut App) -> Result<()>
                     app.file_browser_path = path;
                     app.refresh_file_browser();
                 } else {
-                    app.import_path = path.display().to_string();
-                    if let Err(e) = app.load_import_file() {
-                        app.set_status(format!("Error loading file: {e}"));
-                    }
+                    open_import_file(app, db, &path)?;
                 }
             } else {
                 app.file_browser_input_focused = false;
@@ -271,16 +616,258 @@ fn handle_file_browser_input(key: event::KeyEvent, app: &mut App) -> Result<()>
     Ok(())
 }
 
+fn handle_account_edit_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
+    let types = AccountType::all();
+    match key.code {
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Tab => {
+            app.account_edit_type = (app.account_edit_type + 1) % types.len();
+        }
+        KeyCode::Char('-') | KeyCode::BackTab => {
+            app.account_edit_type = if app.account_edit_type == 0 {
+                types.len() - 1
+            } else {
+                app.account_edit_type - 1
+            };
+        }
+        KeyCode::Char(c) => {
+            app.account_edit_name.push(c);
+        }
+        KeyCode::Backspace => {
+            app.account_edit_name.pop();
+        }
+        KeyCode::Esc => {
+            app.account_editing = false;
+            app.account_edit_name.clear();
+            app.set_status("Edit cancelled");
+        }
+        KeyCode::Enter => {
+            let name = app.account_edit_name.trim().to_string();
+            if name.is_empty() {
+                app.set_error("Account name cannot be empty");
+                return Ok(());
+            }
+            if let Some(snap) = app.account_snapshots.get(app.accounts_tab_index) {
+                let mut account = snap.account.clone();
+                account.name = name.clone();
+                account.account_type = types
+                    .get(app.account_edit_type)
+                    .cloned()
+                    .unwrap_or(AccountType::Checking);
+                db.update_account(&account)?;
+                app.refresh_accounts(db)?;
+                app.refresh_accounts_tab(db)?;
+                app.refresh_dashboard(db)?;
+                app.set_status(format!("Updated account: {name}"));
+            }
+            app.account_editing = false;
+            app.account_edit_name.clear();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_add_transaction_input(
+    key: event::KeyEvent,
+    app: &mut App,
+    db: &mut Database,
+) -> Result<()> {
+    let field_count = TXN_FORM_FIELDS.len();
+    // Category choices include "none" ahead of the categories themselves
+    let cat_choices = app.categories.len() + 1;
+    let acct_choices = app.accounts.len().max(1);
+    match key.code {
+        KeyCode::Tab | KeyCode::Down => {
+            app.txn_add_field = (app.txn_add_field + 1) % field_count;
+        }
+        KeyCode::BackTab | KeyCode::Up => {
+            app.txn_add_field = (app.txn_add_field + field_count - 1) % field_count;
+        }
+        KeyCode::Right if app.txn_add_field == 3 => {
+            app.txn_add_category = (app.txn_add_category + 1) % cat_choices;
+        }
+        KeyCode::Left if app.txn_add_field == 3 => {
+            app.txn_add_category = (app.txn_add_category + cat_choices - 1) % cat_choices;
+        }
+        KeyCode::Right if app.txn_add_field == 4 => {
+            app.txn_add_account = (app.txn_add_account + 1) % acct_choices;
+        }
+        KeyCode::Left if app.txn_add_field == 4 => {
+            app.txn_add_account = (app.txn_add_account + acct_choices - 1) % acct_choices;
+        }
+        KeyCode::Char(c) => match app.txn_add_field {
+            0 => app.txn_add_date.push(c),
+            1 => app.txn_add_description.push(c),
+            2 => app.txn_add_amount.push(c),
+            _ => {}
+        },
+        KeyCode::Backspace => match app.txn_add_field {
+            0 => {
+                app.txn_add_date.pop();
+            }
+            1 => {
+                app.txn_add_description.pop();
+            }
+            2 => {
+                app.txn_add_amount.pop();
+            }
+            _ => {}
+        },
+        KeyCode::Esc => {
+            app.txn_adding = false;
+            app.set_status("Add transaction cancelled");
+        }
+        KeyCode::Enter => {
+            let date = app.txn_add_date.trim().to_string();
+            if chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+                app.txn_add_field = 0;
+                app.set_error(format!("Invalid date '{date}'. Use YYYY-MM-DD"));
+                return Ok(());
+            }
+            let description = app.txn_add_description.trim().to_string();
+            if description.is_empty() {
+                app.txn_add_field = 1;
+                app.set_error("Description cannot be empty");
+                return Ok(());
+            }
+            let amount_input = app.txn_add_amount.trim().replace([',', '$'], "");
+            let amount = match amount_input.parse::<rust_decimal::Decimal>() {
+                Ok(a) => a,
+                Err(_) => {
+                    app.txn_add_field = 2;
+                    app.set_error(format!(
+                        "Invalid amount '{}'. Use e.g. -4.50 for spending",
+                        app.txn_add_amount.trim()
+                    ));
+                    return Ok(());
+                }
+            };
+            let account = match app.accounts.get(app.txn_add_account) {
+                Some(a) => a.clone(),
+                None => {
+                    app.set_status("No account found. Create one with :account <name>");
+                    return Ok(());
+                }
+            };
+            let category_id = app
+                .txn_add_category
+                .checked_sub(1)
+                .and_then(|i| app.categories.get(i))
+                .and_then(|c| c.id);
+
+            let created_at = chrono::Utc::now().to_rfc3339();
+            let txn = Transaction {
+                id: None,
+                account_id: account.id.unwrap_or(0),
+                date: date.clone(),
+                description: description.clone(),
+                original_description: description.clone(),
+                amount,
+                category_id,
+                notes: String::new(),
+                is_transfer: false,
+                // Timestamped so entering the same purchase twice isn't a duplicate
+                import_hash: format!("manual-{date}-{description}-{amount}-{created_at}"),
+                created_at,
+                tags: Vec::new(),
+                quantity: None,
+                symbol: None,
+                flagged: false,
+            };
+            db.insert_transaction(&txn)?;
+            app.txn_adding = false;
+            app.refresh_transactions(db)?;
+            app.refresh_dashboard(db)?;
+            app.set_status(format!(
+                "Added transaction: {description} {} to {}",
+                crate::ui::util::format_amount(amount, &account.currency),
+                account.name
+            ));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_rule_edit_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
+    let cat_count = app.categories.len().max(1);
+    match key.code {
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.rule_edit_regex = !app.rule_edit_regex;
+        }
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.rule_edit_whole_word = !app.rule_edit_whole_word;
+        }
+        KeyCode::Tab => {
+            app.rule_edit_category = (app.rule_edit_category + 1) % cat_count;
+        }
+        KeyCode::BackTab => {
+            app.rule_edit_category = if app.rule_edit_category == 0 {
+                cat_count - 1
+            } else {
+                app.rule_edit_category - 1
+            };
+        }
+        KeyCode::Char(c) => {
+            app.rule_edit_pattern.push(c);
+        }
+        KeyCode::Backspace => {
+            app.rule_edit_pattern.pop();
+        }
+        KeyCode::Esc => {
+            app.rule_editing = false;
+            app.rule_edit_pattern.clear();
+            app.set_status("Edit cancelled");
+        }
+        KeyCode::Enter => {
+            let pattern = if app.rule_edit_regex {
+                app.rule_edit_pattern.trim().to_string()
+            } else {
+                app.rule_edit_pattern.trim().to_lowercase()
+            };
+            if pattern.is_empty() {
+                app.set_error("Rule pattern cannot be empty");
+                return Ok(());
+            }
+            if app.rule_edit_regex && regex::Regex::new(&pattern).is_err() {
+                app.set_error(format!("Invalid regex: {pattern}"));
+                return Ok(());
+            }
+            let category = match app.categories.get(app.rule_edit_category) {
+                Some(c) => c,
+                None => {
+                    app.set_status("No category selected");
+                    return Ok(());
+                }
+            };
+            if let (Some(rule), Some(cat_id)) = (app.import_rules.get(app.rule_index), category.id)
+            {
+                let mut rule = rule.clone();
+                let cat_name = category.name.clone();
+                rule.pattern = pattern.clone();
+                rule.is_regex = app.rule_edit_regex;
+                rule.whole_word = app.rule_edit_whole_word;
+                rule.category_id = cat_id;
+                db.update_import_rule(&rule)?;
+                app.refresh_categories(db)?;
+                app.set_status(format!("Updated rule: '{pattern}' -> {cat_name}"));
+            }
+            app.rule_editing = false;
+            app.rule_edit_pattern.clear();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 fn handle_categorize_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
     if app.import_cat_creating {
         match key.code {
             KeyCode::Char(c) => {
                 app.import_cat_new_name.push(c);
             }
-            KeyCode::Backspace => {
-                if app.import_cat_new_name.pop().is_none() {
-                    app.import_cat_creating = false;
-                }
+            KeyCode::Backspace if app.import_cat_new_name.pop().is_none() => {
+                app.import_cat_creating = false;
             }
             KeyCode::Esc => {
                 app.import_cat_creating = false;
@@ -294,12 +881,14 @@ fn handle_categorize_input(key: event::KeyEvent, app: &mut App, db: &mut Databas
 
                     if let Some((desc, _)) = app.import_cat_descriptions.get(app.import_cat_index) {
                         if let Ok(pattern) = crate::categorize::suggest_rule(desc) {
-                            let rule = crate::models::ImportRule::new_contains(pattern, cat_id);
-                            db.insert_import_rule(&rule)?;
+                            let rule = crate::models::ImportRule::new_contains(pattern, cat_id)
+                                .with_source(crate::models::RuleSource::Import);
+                            let rule_id = db.insert_import_rule(&rule)?;
+                            app.import_matched_rules.push(rule_id);
                         }
                     }
 
-                    app.apply_category_to_current(cat_id);
+                    assign_import_category(app, db, cat_id)?;
                     app.refresh_categories(db)?;
 
                     let count = app
@@ -316,7 +905,7 @@ fn handle_categorize_input(key: event::KeyEvent, app: &mut App, db: &mut Databas
                     app.import_cat_new_name.clear();
 
                     if !app.advance_categorize() {
-                        commit_import(app, db)?;
+                        finish_categorize(app, db)?;
                     }
                 }
             }
@@ -325,40 +914,16 @@ fn handle_categorize_input(key: event::KeyEvent, app: &mut App, db: &mut Databas
         return Ok(());
     }
 
-    let page = app.categorize_visible_rows();
-    let cat_len = app.categories.len();
     match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
-            scroll_down(
-                &mut app.import_cat_selected,
-                &mut app.import_cat_scroll,
-                cat_len,
-                page,
-            );
-        }
-        KeyCode::Char('k') | KeyCode::Up => {
-            scroll_up(&mut app.import_cat_selected, &mut app.import_cat_scroll);
-        }
-        KeyCode::Char('g') => {
-            scroll_to_top(&mut app.import_cat_selected, &mut app.import_cat_scroll);
-        }
-        KeyCode::Char('G') => {
-            scroll_to_bottom(
-                &mut app.import_cat_selected,
-                &mut app.import_cat_scroll,
-                cat_len,
-                page,
-            );
-        }
         KeyCode::Char('s') => {
             if !app.advance_categorize() {
-                commit_import(app, db)?;
+                finish_categorize(app, db)?;
             } else {
                 app.set_status("Skipped — moving to next");
             }
         }
         KeyCode::Char('S') => {
-            commit_import(app, db)?;
+            finish_categorize(app, db)?;
         }
         KeyCode::Char('n') => {
             app.import_cat_creating = true;
@@ -372,13 +937,15 @@ fn handle_categorize_input(key: event::KeyEvent, app: &mut App, db: &mut Databas
                     if let Some((desc, _)) = app.import_cat_descriptions.get(app.import_cat_index) {
                         if let Ok(pattern) = crate::categorize::suggest_rule(desc) {
                             let rule =
-                                crate::models::ImportRule::new_contains(pattern.clone(), cat_id);
-                            db.insert_import_rule(&rule)?;
+                                crate::models::ImportRule::new_contains(pattern.clone(), cat_id)
+                                    .with_source(crate::models::RuleSource::Import);
+                            let rule_id = db.insert_import_rule(&rule)?;
+                            app.import_matched_rules.push(rule_id);
                             app.refresh_categories(db)?;
                         }
                     }
 
-                    app.apply_category_to_current(cat_id);
+                    assign_import_category(app, db, cat_id)?;
 
                     let count = app
                         .import_cat_descriptions
@@ -391,14 +958,98 @@ fn handle_categorize_input(key: event::KeyEvent, app: &mut App, db: &mut Databas
                     ));
 
                     if !app.advance_categorize() {
-                        commit_import(app, db)?;
+                        finish_categorize(app, db)?;
                     }
                 }
             }
         }
-        KeyCode::Esc => {
-            app.import_step = ImportStep::Preview;
-            app.set_status("Back to preview — categories already assigned will be kept");
+        KeyCode::Esc if app.import_recategorizing => {
+            finish_categorize(app, db)?;
+        }
+        KeyCode::Esc => {
+            app.import_step = ImportStep::Preview;
+            app.import_preview_index = 0;
+            app.import_preview_scroll = 0;
+            app.set_status("Back to preview — categories already assigned will be kept");
+        }
+        _ => {
+            let page = app.categorize_visible_rows();
+            handle_category_picker_nav(key, app, page);
+        }
+    }
+    Ok(())
+}
+
+/// Give the current description's rows `category_id`. When revisiting an
+/// earlier import the rows are already stored, so they're updated in place.
+fn assign_import_category(app: &mut App, db: &mut Database, category_id: i64) -> Result<()> {
+    app.apply_category_to_current(category_id);
+    if app.import_recategorizing {
+        if let Some((desc, _)) = app.import_cat_descriptions.get(app.import_cat_index) {
+            for txn in app
+                .import_preview
+                .iter()
+                .filter(|t| t.original_description == *desc)
+            {
+                if let Some(id) = txn.id {
+                    db.update_transaction_category(id, txn.category_id)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Leave the categorize step: commit a fresh import, or wrap up a
+/// `:categorize-last` pass whose assignments are already saved.
+fn finish_categorize(app: &mut App, db: &mut Database) -> Result<()> {
+    if !app.import_recategorizing {
+        return commit_import(app, db);
+    }
+    app.import_recategorizing = false;
+    let done = app
+        .import_preview
+        .iter()
+        .filter(|t| t.category_id.is_some())
+        .count();
+    let total = app.import_preview.len();
+    app.import_preview.clear();
+    app.import_step = ImportStep::Complete;
+    app.import_summary =
+        format!("Categorized {done} of {total} transactions left over from the last import");
+    app.import_report = None;
+    app.set_status(app.import_summary.clone());
+    app.refresh_all(db)?;
+    Ok(())
+}
+
+/// Movement in a category picker (`import_cat_selected`): j/k, g/G,
+/// Ctrl-d/Ctrl-u, and any other letter jumps to the first category
+/// starting with it.
+fn handle_category_picker_nav(key: event::KeyEvent, app: &mut App, page: usize) {
+    let cat_len = app.categories.len();
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            scroll_down(
+                &mut app.import_cat_selected,
+                &mut app.import_cat_scroll,
+                cat_len,
+                page,
+            );
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            scroll_up(&mut app.import_cat_selected, &mut app.import_cat_scroll);
+        }
+        KeyCode::Char('g') => {
+            scroll_to_top(&mut app.import_cat_selected, &mut app.import_cat_scroll);
+        }
+        KeyCode::Char('G') => {
+            scroll_to_bottom(
+                &mut app.import_cat_selected,
+                &mut app.import_cat_scroll,
+                cat_len,
+                page,
+            );
         }
         KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             for _ in 0..page / 2 {
@@ -416,21 +1067,95 @@ fn handle_categorize_input(key: event::KeyEvent, app: &mut App, db: &mut Databas
             }
         }
         KeyCode::Char(c) => {
-            let lower = c.to_ascii_lowercase();
-            if let Some(idx) = app.categories.iter().position(|cat| {
-                cat.name
-                    .starts_with(|ch: char| ch.to_ascii_lowercase() == lower)
-            }) {
-                app.import_cat_selected = idx;
-                if idx < app.import_cat_scroll {
-                    app.import_cat_scroll = idx;
-                } else if idx >= app.import_cat_scroll + page {
-                    app.import_cat_scroll = idx.saturating_sub(page.saturating_sub(1));
-                }
+            if let Some(idx) =
+                first_starting_with(app.categories.iter().map(|cat| cat.name.as_str()), c)
+            {
+                scroll_to(
+                    &mut app.import_cat_selected,
+                    &mut app.import_cat_scroll,
+                    idx,
+                    page,
+                );
             }
         }
         _ => {}
     }
+}
+
+/// The category picker opened with `c` on a transaction.
+fn handle_recat_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
+    if app.import_cat_creating {
+        match key.code {
+            KeyCode::Char(c) => app.import_cat_new_name.push(c),
+            KeyCode::Backspace if app.import_cat_new_name.pop().is_none() => {
+                app.import_cat_creating = false;
+            }
+            KeyCode::Esc => {
+                app.import_cat_creating = false;
+                app.import_cat_new_name.clear();
+            }
+            KeyCode::Enter => {
+                let name = app.import_cat_new_name.trim().to_string();
+                if !name.is_empty() {
+                    let cat_id = match crate::models::Category::find_by_name(&app.categories, &name)
+                        .and_then(|c| c.id)
+                    {
+                        Some(id) => id,
+                        None => db.insert_category(&crate::models::Category::new(name))?,
+                    };
+                    app.refresh_categories(db)?;
+                    assign_recat(app, db, cat_id)?;
+                }
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Enter => {
+            if let Some(cat_id) = app
+                .categories
+                .get(app.import_cat_selected)
+                .and_then(|c| c.id)
+            {
+                assign_recat(app, db, cat_id)?;
+            }
+        }
+        KeyCode::Char('n') => {
+            app.import_cat_creating = true;
+            app.import_cat_new_name.clear();
+        }
+        KeyCode::Esc => {
+            app.txn_recat = false;
+            app.set_status("Cancelled");
+        }
+        _ => {
+            let page = app.recat_visible_rows();
+            handle_category_picker_nav(key, app, page);
+        }
+    }
+    Ok(())
+}
+
+/// Give the selected transaction `category_id` and close the picker.
+fn assign_recat(app: &mut App, db: &mut Database, category_id: i64) -> Result<()> {
+    app.txn_recat = false;
+    app.import_cat_creating = false;
+    app.import_cat_new_name.clear();
+    let txn_id = app
+        .transactions
+        .get(app.transaction_index)
+        .and_then(|t| t.id);
+    if let Some(txn_id) = txn_id {
+        db.update_transaction_category(txn_id, Some(category_id))?;
+        app.refresh_transactions(db)?;
+        app.refresh_dashboard(db)?;
+        let name = crate::models::Category::find_by_id(&app.categories, category_id)
+            .map(|c| c.name.clone())
+            .unwrap_or_default();
+        app.set_status(format!("Categorized as: {name}"));
+    }
     Ok(())
 }
 
@@ -460,10 +1185,8 @@ fn handle_select_account_input(
             KeyCode::Char(c) => {
                 app.import_new_account_name.push(c);
             }
-            KeyCode::Backspace => {
-                if app.import_new_account_name.pop().is_none() {
-                    app.import_creating_account = false;
-                }
+            KeyCode::Backspace if app.import_new_account_name.pop().is_none() => {
+                app.import_creating_account = false;
             }
             KeyCode::Esc => {
                 app.import_creating_account = false;
@@ -483,16 +1206,16 @@ fn handle_select_account_input(
                     app.refresh_accounts(db)?;
 
                     app.import_profile.is_credit_account = is_credit;
-                    if app.import_detected_bank.is_none() {
-                        app.import_profile.negate_amounts = is_credit;
+                    if app.import_detected_bank.is_none() && !app.import_sign_chosen {
+                        app.import_profile.sign_convention = default_sign_convention(is_credit);
                     }
 
                     app.import_creating_account = false;
                     app.import_new_account_name.clear();
                     app.set_status(format!("Created account: {name}"));
 
-                    if let Err(e) = app.generate_import_preview() {
-                        app.set_status(format!("Error generating preview: {e}"));
+                    if let Err(e) = app.generate_import_preview(db) {
+                        app.set_error(format!("Error generating preview: {e}"));
                     }
                 }
             }
@@ -531,30 +1254,37 @@ fn handle_select_account_input(
                 page,
             );
         }
-        KeyCode::Char('n') => {
-            app.import_creating_account = true;
-            app.import_new_account_name.clear();
-        }
+        KeyCode::Char('n') => open_import_account_form(app),
         KeyCode::Enter => {
             if let Some(acct) = app.accounts.get(app.import_account_index) {
                 app.import_account_id = acct.id;
                 let is_credit = acct.account_type.is_credit();
                 app.import_profile.is_credit_account = is_credit;
-                if app.import_detected_bank.is_none() {
-                    app.import_profile.negate_amounts = is_credit;
+                if app.import_detected_bank.is_none() && !app.import_sign_chosen {
+                    app.import_profile.sign_convention = default_sign_convention(is_credit);
                 }
                 let name = acct.name.clone();
+                let columns_changed = match acct.id {
+                    Some(id) if app.import_qif_content.is_none() => db
+                        .get_account_profile(id)?
+                        .is_some_and(|saved| saved.headers != app.import_headers),
+                    _ => false,
+                };
                 app.set_status(format!("Using account: {name}"));
-                if let Err(e) = app.generate_import_preview() {
-                    app.set_status(format!("Error generating preview: {e}"));
+                if let Err(e) = app.generate_import_preview(db) {
+                    app.set_error(format!("Error generating preview: {e}"));
+                } else if columns_changed && !app.import_signs_suspect {
+                    app.set_warning(format!(
+                        "This file's columns differ from your last import into {name}, \
+                         so its saved mapping wasn't used — check the preview"
+                    ));
                 }
             } else if app.accounts.is_empty() {
-                app.import_creating_account = true;
-                app.import_new_account_name.clear();
+                open_import_account_form(app);
             }
         }
         KeyCode::Esc => {
-            app.import_step = ImportStep::MapColumns;
+            app.import_step = step_before_select_account(app);
         }
         KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             for _ in 0..page / 2 {
@@ -580,28 +1310,43 @@ fn handle_select_account_input(
 }
 
 fn handle_command_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
+    if !matches!(key.code, KeyCode::Tab | KeyCode::BackTab) {
+        app.command_completion_index = None;
+    }
     match key.code {
+        KeyCode::Tab => complete_command(app, db, true)?,
+        KeyCode::BackTab => complete_command(app, db, false)?,
+        KeyCode::Up => app.command_history_prev(),
+        KeyCode::Down => app.command_history_next(),
         KeyCode::Enter => {
             let input = app.command_input.clone();
             app.input_mode = InputMode::Normal;
             app.command_input.clear();
+            if app.record_command(&input) {
+                db.set_command_history(&app.command_history)?;
+            }
             commands::handle_command(&input, app, db)?;
         }
+        // Esc backs out of history recall first, then out of the bar
+        KeyCode::Esc if app.command_history_index.is_some() => app.restore_command_draft(),
         KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
             app.command_input.clear();
         }
         KeyCode::Backspace => {
+            app.command_history_index = None;
             app.command_input.pop();
             if app.command_input.is_empty() {
                 app.input_mode = InputMode::Normal;
             }
         }
         KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.command_history_index = None;
             app.command_input.clear();
             app.input_mode = InputMode::Normal;
         }
         KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.command_history_index = None;
             let trimmed = app.command_input.trim_end();
             if let Some(pos) = trimmed.rfind(' ') {
                 app.command_input.truncate(pos + 1);
@@ -611,6 +1356,7 @@ fn handle_command_input(key: event::KeyEvent, app: &mut App, db: &mut Database)
             }
         }
         KeyCode::Char(c) => {
+            app.command_history_index = None;
             app.command_input.push(c);
         }
         _ => {}
@@ -618,6 +1364,66 @@ fn handle_command_input(key: event::KeyEvent, app: &mut App, db: &mut Database)
     Ok(())
 }
 
+/// Tab completion for the command bar. A single match is filled in; several
+/// are first narrowed to their common prefix, then cycled through, with the
+/// choices listed in the status bar.
+fn complete_command(app: &mut App, db: &Database, forward: bool) -> Result<()> {
+    app.command_history_index = None;
+    if app.command_completion_index.is_none() {
+        let (stem, options) = commands::completions(&app.command_input, db)?;
+        match options.len() {
+            0 => {
+                app.set_warning("No completions");
+                return Ok(());
+            }
+            1 => {
+                app.command_input = format!("{stem}{}", options[0]);
+                // A finished command name is ready for its argument
+                if stem.is_empty() {
+                    app.command_input.push(' ');
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+        app.set_status(format!("{} matches: {}", options.len(), options.join("  ")));
+        let lines: Vec<String> = options.iter().map(|o| format!("{stem}{o}")).collect();
+        let common = common_prefix(&lines);
+        if common.chars().count() > app.command_input.trim_start().chars().count() {
+            app.command_input = common;
+            return Ok(());
+        }
+        app.command_completions = lines;
+    }
+    let len = app.command_completions.len();
+    let index = match (app.command_completion_index, forward) {
+        (None, true) => 0,
+        (None, false) => len - 1,
+        (Some(i), true) => (i + 1) % len,
+        (Some(i), false) => (i + len - 1) % len,
+    };
+    app.command_completion_index = Some(index);
+    app.command_input = app.command_completions[index].clone();
+    Ok(())
+}
+
+/// The longest prefix every line shares.
+fn common_prefix(lines: &[String]) -> String {
+    let mut prefix: Vec<char> = lines
+        .first()
+        .map(|l| l.chars().collect())
+        .unwrap_or_default();
+    for line in lines.iter().skip(1) {
+        let shared = prefix
+            .iter()
+            .zip(line.chars())
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        prefix.truncate(shared);
+    }
+    prefix.into_iter().collect()
+}
+
 fn handle_search_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
     match key.code {
         KeyCode::Enter => {
@@ -651,6 +1457,22 @@ fn handle_search_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -
 
 fn handle_editing_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
     match key.code {
+        KeyCode::Enter if app.screen == Screen::Import => {
+            // Note for the targeted import preview rows; empty clears it
+            let note = app.command_input.trim().to_string();
+            let targets = app.import_preview_targets();
+            for &i in &targets {
+                app.import_preview[i].notes = note.clone();
+            }
+            let rows = if targets.len() == 1 { "row" } else { "rows" };
+            app.set_status(if note.is_empty() {
+                format!("Cleared the note on {} {rows}", targets.len())
+            } else {
+                format!("Noted {} {rows}: {note}", targets.len())
+            });
+            app.command_input.clear();
+            app.input_mode = InputMode::Normal;
+        }
         KeyCode::Enter => {
             let new_name = app.command_input.clone();
             if !new_name.is_empty() {
@@ -712,7 +1534,86 @@ fn handle_nav_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> R
     Ok(())
 }
 
+fn handle_month_picker_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
+    let idx = app.month_picker_index;
+    match key.code {
+        KeyCode::Char('l') | KeyCode::Right if (1..12).contains(&idx) => {
+            app.month_picker_index += 1;
+        }
+        KeyCode::Char('h') | KeyCode::Left if idx > 1 => {
+            app.month_picker_index -= 1;
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.month_picker_index = match idx {
+                0 => 1,
+                i if i + 3 <= 12 => i + 3,
+                i => i,
+            };
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.month_picker_index = match idx {
+                1..=3 => 0,
+                i if i > 3 => i - 3,
+                i => i,
+            };
+        }
+        KeyCode::Char('[') => app.month_picker_year -= 1,
+        KeyCode::Char(']') => app.month_picker_year += 1,
+        KeyCode::Enter => {
+            app.show_month_picker = false;
+            if idx == 0 {
+                commands::set_month(app, db, None)?;
+                app.set_status("Showing all time");
+            } else {
+                let m = format!("{}-{idx:02}", app.month_picker_year);
+                app.set_status(format!("Switched to month: {m}"));
+                commands::set_month(app, db, Some(m))?;
+            }
+        }
+        // h/l at the edge of a row stay put rather than closing
+        KeyCode::Char('h' | 'l') | KeyCode::Left | KeyCode::Right => {}
+        _ => {
+            app.show_month_picker = false;
+        }
+    }
+    Ok(())
+}
+
+/// Ctrl-Q or Ctrl-C.
+fn is_quit_key(key: event::KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('q') | KeyCode::Char('c'))
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Quit, unless `:set confirm-quit` is on and quitting would throw away a
+/// selection, an open form or another pending confirmation, in which case
+/// ask first. A second Ctrl-Q at the prompt quits regardless.
+fn request_quit(app: &mut App) {
+    if !app.confirm_quit || !app.has_pending_edits() {
+        app.running = false;
+        return;
+    }
+    app.quit_interrupted = Some(InterruptedPrompt {
+        input_mode: app.input_mode,
+        pending_action: app.pending_action.take(),
+        confirm_message: std::mem::take(&mut app.confirm_message),
+        command_input: std::mem::take(&mut app.command_input),
+    });
+    let selected = app.selected_transactions.len();
+    app.confirm_message = if selected > 0 {
+        format!("Quit with {selected} transactions selected? Ctrl-Q again to force")
+    } else {
+        "Quit and discard unsaved edits? Ctrl-Q again to force".to_string()
+    };
+    app.pending_action = Some(PendingAction::Quit);
+    app.input_mode = InputMode::Confirm;
+}
+
 fn handle_confirm_input(key: event::KeyEvent, app: &mut App, db: &mut Database) -> Result<()> {
+    if let Some(phrase) = app.pending_action.as_ref().and_then(|a| a.confirm_phrase()) {
+        return handle_confirm_phrase_input(key, app, db, phrase);
+    }
+
     match key.code {
         KeyCode::Char('y') | KeyCode::Char('Y') => {
             if let Some(action) = app.pending_action.take() {
@@ -756,45 +1657,145 @@ fn handle_confirm_input(key: event::KeyEvent, app: &mut App, db: &mut Database)
                         }
                         app.set_status(format!("Deleted rule: '{pattern}'"));
                     }
-                    PendingAction::ImportCommit => {
-                        let rules = db.get_import_rules()?;
-                        let (categorizer, bad_patterns) =
-                            crate::categorize::Categorizer::new(&rules);
-                        if !bad_patterns.is_empty() {
-                            app.set_status(format!(
-                                "Warning: invalid regex rule(s): {}",
-                                bad_patterns.join(", ")
-                            ));
+                    PendingAction::PruneRules { before } => {
+                        let count = db.delete_unmatched_rules(&before)?;
+                        app.refresh_categories(db)?;
+                        if app.rule_index >= app.import_rules.len() {
+                            app.rule_index = app.import_rules.len().saturating_sub(1);
+                        }
+                        app.rule_scroll = app.rule_scroll.min(app.rule_index);
+                        app.set_status(format!(
+                            "Deleted {count} rule{} with no match since {before}",
+                            if count == 1 { "" } else { "s" }
+                        ));
+                    }
+                    PendingAction::DeleteAccount { id, name, force } => {
+                        db.delete_account(id, force)?;
+                        if app.transaction_filter_account == Some(id) {
+                            app.transaction_filter_account = None;
+                        }
+                        app.refresh_all(db)?;
+                        if app.accounts_tab_index >= app.account_snapshots.len() {
+                            app.accounts_tab_index = app.account_snapshots.len().saturating_sub(1);
                         }
-                        categorizer.categorize_batch(&mut app.import_preview);
-
-                        if app.prepare_categorize_step() {
-                            let total = app.import_cat_descriptions.len();
-                            app.import_step = ImportStep::Categorize;
-                            app.set_status(format!(
-                                "{total} unique description{} to categorize",
-                                if total == 1 { "" } else { "s" }
-                            ));
-                        } else {
-                            commit_import(app, db)?;
+                        if app.account_index >= app.accounts.len() {
+                            app.account_index = 0;
+                        }
+                        app.set_status(format!("Deleted account: {name}"));
+                    }
+                    PendingAction::DeleteCategory { id, name } => {
+                        db.delete_category(id)?;
+                        app.refresh_categories(db)?;
+                        app.refresh_budgets(db)?;
+                        app.refresh_transactions(db)?;
+                        app.refresh_dashboard(db)?;
+                        if app.category_index >= app.categories.len() {
+                            app.category_index = app.categories.len().saturating_sub(1);
                         }
+                        app.set_status(format!("Deleted category: {name}"));
                     }
+                    PendingAction::ImportCommit => begin_import_commit(app, db)?,
+                    PendingAction::WipeAllData => wipe_all_data(app, db)?,
+                    PendingAction::Quit => app.running = false,
                 }
             }
             app.input_mode = InputMode::Normal;
             app.confirm_message.clear();
         }
         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            match app.quit_interrupted.take() {
+                // Declining to quit goes back to the edit or prompt it replaced
+                Some(prev) if matches!(app.pending_action, Some(PendingAction::Quit)) => {
+                    app.input_mode = prev.input_mode;
+                    app.pending_action = prev.pending_action;
+                    app.confirm_message = prev.confirm_message;
+                    app.command_input = prev.command_input;
+                    app.set_status("Quit cancelled");
+                }
+                _ => {
+                    app.pending_action = None;
+                    app.input_mode = InputMode::Normal;
+                    app.confirm_message.clear();
+                    app.set_status("Cancelled");
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Confirmation for destructive actions that require typing a phrase rather
+/// than pressing `y`. Anything other than an exact match cancels.
+fn handle_confirm_phrase_input(
+    key: event::KeyEvent,
+    app: &mut App,
+    db: &mut Database,
+    phrase: &str,
+) -> Result<()> {
+    match key.code {
+        KeyCode::Enter => {
+            let typed = app.command_input.trim().to_string();
+            let action = app.pending_action.take();
+            app.command_input.clear();
+            app.input_mode = InputMode::Normal;
+            app.confirm_message.clear();
+            if typed == phrase {
+                if let Some(PendingAction::WipeAllData) = action {
+                    wipe_all_data(app, db)?;
+                }
+            } else {
+                app.set_status("Confirmation phrase did not match — nothing was deleted");
+            }
+        }
+        KeyCode::Esc => {
             app.pending_action = None;
+            app.command_input.clear();
             app.input_mode = InputMode::Normal;
             app.confirm_message.clear();
             app.set_status("Cancelled");
         }
+        KeyCode::Backspace => {
+            app.command_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.command_input.push(c);
+        }
         _ => {}
     }
     Ok(())
 }
 
+fn wipe_all_data(app: &mut App, db: &mut Database) -> Result<()> {
+    db.wipe_all_data()?;
+    app.budget_start_day = db.budget_start_day();
+    app.clear_selections();
+    app.search_input.clear();
+    app.command_history.clear();
+    app.transaction_filter_account = None;
+    app.transaction_filter_uncategorized = false;
+    app.transaction_filter_flagged = false;
+    if app.import_recategorizing {
+        app.import_recategorizing = false;
+        app.import_preview.clear();
+        app.import_step = ImportStep::SelectFile;
+    }
+    app.account_index = 0;
+    app.transaction_index = 0;
+    app.transaction_scroll = 0;
+    app.category_index = 0;
+    app.category_scroll = 0;
+    app.rule_index = 0;
+    app.rule_scroll = 0;
+    app.budget_index = 0;
+    app.budget_scroll = 0;
+    app.accounts_tab_index = 0;
+    app.accounts_tab_scroll = 0;
+    app.refresh_all(db)?;
+    app.set_status("All data wiped — starting fresh");
+    Ok(())
+}
+
 // ── Navigation helpers ───────────────────────────────────────
 
 fn switch_screen(app: &mut App, db: &mut Database, screen: Screen) -> Result<()> {
@@ -812,6 +1813,7 @@ fn switch_screen(app: &mut App, db: &mut Database, screen: Screen) -> Result<()>
             app.import_creating_account = false;
             app.import_new_account_name.clear();
             app.import_detected_bank = None;
+            app.import_qif_content = None;
             app.refresh_file_browser();
         }
         Screen::Categories => app.refresh_categories(db)?,
@@ -871,10 +1873,17 @@ fn handle_move_down(app: &mut App) {
                     page,
                 );
             }
-            ImportStep::MapColumns => {
-                if app.import_selected_field < 6 {
-                    app.import_selected_field += 1;
-                }
+            ImportStep::MapColumns if app.import_selected_field < 8 => {
+                app.import_selected_field += 1;
+            }
+            ImportStep::Preview => {
+                let page = app.import_preview_page();
+                scroll_down(
+                    &mut app.import_preview_index,
+                    &mut app.import_preview_scroll,
+                    app.import_preview.len(),
+                    page,
+                );
             }
             _ => {}
         },
@@ -913,6 +1922,12 @@ fn handle_move_up(app: &mut App) {
             ImportStep::MapColumns => {
                 app.import_selected_field = app.import_selected_field.saturating_sub(1);
             }
+            ImportStep::Preview => {
+                scroll_up(
+                    &mut app.import_preview_index,
+                    &mut app.import_preview_scroll,
+                );
+            }
             _ => {}
         },
         Screen::Budgets => scroll_up(&mut app.budget_index, &mut app.budget_scroll),
@@ -921,6 +1936,11 @@ fn handle_move_up(app: &mut App) {
 }
 
 fn handle_enter(app: &mut App, db: &mut Database) -> Result<()> {
+    if app.screen == Screen::Transactions {
+        app.show_transaction_detail = !app.transactions.is_empty();
+        return Ok(());
+    }
+
     if app.screen == Screen::Accounts {
         if let Some(snap) = app.account_snapshots.get(app.accounts_tab_index) {
             let account_id = snap.account.id;
@@ -945,45 +1965,20 @@ fn handle_enter(app: &mut App, db: &mut Database) -> Result<()> {
                         app.file_browser_path = path;
                         app.refresh_file_browser();
                     } else {
-                        app.import_path = path.display().to_string();
-                        if let Err(e) = app.load_import_file() {
-                            app.set_status(format!("Error loading file: {e}"));
-                        }
-                    }
-                }
-            }
-            ImportStep::MapColumns => {
-                app.refresh_accounts(db)?;
-                app.import_account_index = 0;
-                app.import_account_scroll = 0;
-                app.import_creating_account = false;
-                app.import_new_account_name.clear();
-
-                if let Some(ref bank) = app.import_detected_bank {
-                    let lower = bank.to_lowercase();
-                    if let Some(pos) = app
-                        .accounts
-                        .iter()
-                        .position(|a| a.name.to_lowercase() == lower)
-                    {
-                        app.import_account_index = pos;
+                        open_import_file(app, db, &path)?;
                     }
                 }
-
-                if app.import_profile.is_credit_account {
-                    app.import_new_account_type = AccountType::all()
-                        .iter()
-                        .position(|t| *t == AccountType::CreditCard)
-                        .unwrap_or(0);
-                } else {
-                    app.import_new_account_type = 0;
-                }
-
-                app.import_step = ImportStep::SelectAccount;
             }
+            ImportStep::MapColumns => begin_select_account(app, db)?,
             ImportStep::SelectAccount => {}
             ImportStep::Preview => {
-                app.confirm_message = format!("Import {} transactions?", app.import_preview.len());
+                app.confirm_message = match app.import_duplicates.len() {
+                    dupes if dupes > 0 && app.import_skip_duplicates => format!(
+                        "Import {} transactions, skipping {dupes} probable duplicates?",
+                        app.import_commit_count()
+                    ),
+                    _ => format!("Import {} transactions?", app.import_commit_count()),
+                };
                 app.pending_action = Some(crate::ui::app::PendingAction::ImportCommit);
                 app.input_mode = InputMode::Confirm;
             }
@@ -997,6 +1992,81 @@ fn handle_enter(app: &mut App, db: &mut Database) -> Result<()> {
     Ok(())
 }
 
+/// Load the chosen import file. QIF files skip column mapping entirely, as
+/// do CSVs with a mapping saved from an earlier import.
+fn open_import_file(app: &mut App, db: &mut Database, path: &std::path::Path) -> Result<()> {
+    app.import_path = path.display().to_string();
+    if let Err(e) = app.load_import_file(db) {
+        app.set_error(format!("Error loading file: {e}"));
+    } else if app.import_qif_content.is_some() || app.import_saved_account.is_some() {
+        let status = app.status.clone();
+        begin_select_account(app, db)?;
+        app.status = status;
+    }
+    Ok(())
+}
+
+fn begin_select_account(app: &mut App, db: &mut Database) -> Result<()> {
+    app.refresh_accounts(db)?;
+    app.import_account_index = 0;
+    app.import_account_scroll = 0;
+    app.import_creating_account = false;
+    app.import_new_account_name.clear();
+
+    if let Some(ref bank) = app.import_detected_bank {
+        let lower = bank.to_lowercase();
+        if let Some(pos) = app
+            .accounts
+            .iter()
+            .position(|a| a.name.to_lowercase() == lower)
+        {
+            app.import_account_index = pos;
+        }
+    }
+    if let Some(id) = app.import_saved_account {
+        if let Some(pos) = app.accounts.iter().position(|a| a.id == Some(id)) {
+            app.import_account_index = pos;
+        }
+    }
+
+    // A type named in the file (`..._savings.csv`) is used unless a
+    // recognised bank format already says what kind of account it is
+    let named_type = app
+        .import_suggested_account_name
+        .split(' ')
+        .map(AccountType::parse)
+        .find(|t| *t != AccountType::Other);
+    let new_type = match named_type {
+        Some(t) if app.import_detected_bank.is_none() => t,
+        _ if app.import_profile.is_credit_account => AccountType::CreditCard,
+        _ => AccountType::Checking,
+    };
+    app.import_new_account_type = AccountType::all()
+        .iter()
+        .position(|t| *t == new_type)
+        .unwrap_or(0);
+
+    app.import_step = ImportStep::SelectAccount;
+    Ok(())
+}
+
+/// Open the new-account form under account selection, named after the
+/// import file when its name suggests one.
+fn open_import_account_form(app: &mut App) {
+    app.import_creating_account = true;
+    app.import_new_account_name = app.import_suggested_account_name.clone();
+}
+
+/// Step to return to when backing out of account selection. QIF imports
+/// never visited the column mapper.
+fn step_before_select_account(app: &App) -> ImportStep {
+    if app.import_qif_content.is_some() {
+        ImportStep::SelectFile
+    } else {
+        ImportStep::MapColumns
+    }
+}
+
 fn handle_escape(app: &mut App) {
     match app.screen {
         Screen::Import => match app.import_step {
@@ -1013,7 +2083,11 @@ fn handle_escape(app: &mut App) {
                 app.import_step = ImportStep::SelectFile;
             }
             ImportStep::SelectAccount => {
-                app.import_step = ImportStep::MapColumns;
+                app.import_step = step_before_select_account(app);
+            }
+            ImportStep::Preview if !app.import_preview_selected.is_empty() => {
+                app.import_preview_selected.clear();
+                app.set_status("Selection cleared");
             }
             ImportStep::Preview => {
                 app.import_step = ImportStep::SelectAccount;
@@ -1029,12 +2103,20 @@ fn handle_escape(app: &mut App) {
             app.clear_selections();
             app.set_status("Selection cleared");
         }
+        Screen::Transactions if app.transaction_filter_uncategorized => {
+            app.transaction_filter_uncategorized = false;
+            app.set_status("Uncategorized filter cleared");
+        }
+        Screen::Transactions if app.transaction_filter_flagged => {
+            app.transaction_filter_flagged = false;
+            app.set_status("Flagged filter cleared");
+        }
         Screen::Transactions if app.transaction_filter_account.is_some() => {
             app.transaction_filter_account = None;
             app.set_status("Account filter cleared");
         }
         _ => {
-            app.status_message.clear();
+            app.clear_status();
             app.search_input.clear();
         }
     }
@@ -1069,7 +2151,7 @@ fn handle_adjust_field(app: &mut App, delta: i32) {
                 adjust_optional(app.import_profile.credit_column, delta, max_col);
         }
         5 => {
-            let formats = ["%m/%d/%Y", "%Y-%m-%d", "%m-%d-%Y", "%d/%m/%Y", "%m/%d/%y"];
+            let formats = app.date_formats();
             let current = formats
                 .iter()
                 .position(|f| *f == app.import_profile.date_format)
@@ -1085,11 +2167,53 @@ fn handle_adjust_field(app: &mut App, delta: i32) {
         }
         6 => {
             app.import_profile.has_header = !app.import_profile.has_header;
+            app.apply_import_header();
+            app.set_status(if app.import_profile.has_header {
+                "The first row is the header"
+            } else {
+                "The first row is data — columns are labelled col0, col1, ..."
+            });
+        }
+        7 => {
+            let current = DELIMITERS
+                .iter()
+                .position(|d| *d == app.import_profile.delimiter)
+                .unwrap_or(0);
+            let next = if delta > 0 {
+                (current + 1) % DELIMITERS.len()
+            } else if current == 0 {
+                DELIMITERS.len() - 1
+            } else {
+                current - 1
+            };
+            app.import_profile.delimiter = DELIMITERS[next];
+            match app.reload_import_rows() {
+                Ok(()) => app.set_status(format!(
+                    "Delimiter: {} ({} columns)",
+                    delimiter_name(app.import_profile.delimiter),
+                    app.import_headers.len()
+                )),
+                Err(e) => app.set_error(format!("Failed to re-read file: {e}")),
+            }
+        }
+        8 => {
+            app.import_profile.sign_convention = app.import_profile.sign_convention.cycle(delta);
+            app.import_sign_chosen = true;
         }
         _ => {}
     }
 }
 
+/// Sign convention for an unrecognized CSV: credit card and loan statements
+/// usually list charges as positive numbers.
+fn default_sign_convention(is_credit: bool) -> SignConvention {
+    if is_credit {
+        SignConvention::DebitPositive
+    } else {
+        SignConvention::AmountSigned
+    }
+}
+
 fn adjust_usize(val: usize, delta: i32, max: usize) -> usize {
     let new_val = val as i32 + delta;
     if new_val < 0 {
@@ -1119,14 +2243,94 @@ fn adjust_optional(val: Option<usize>, delta: i32, max: usize) -> Option<usize>
     }
 }
 
+/// Auto-categorize the confirmed import, then either hand the leftovers to
+/// the categorize step or write everything straight away. Invalid regex
+/// rules are reported once, after whichever status the next step sets.
+fn begin_import_commit(app: &mut App, db: &mut Database) -> Result<()> {
+    app.import_preview_selected.clear();
+    app.remove_skipped_duplicates();
+    let rules = db.get_import_rules()?;
+    let (categorizer, bad_patterns) = crate::categorize::Categorizer::new(&rules);
+    let categorized = |txns: &[crate::models::Transaction]| {
+        txns.iter().filter(|t| t.category_id.is_some()).count()
+    };
+    let before = categorized(&app.import_preview);
+    app.import_matched_rules = categorizer
+        .categorize_batch(&mut app.import_preview)
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m.rule_id)
+        .collect();
+    app.import_report = Some(ImportReport {
+        auto_categorized: categorized(&app.import_preview) - before,
+        ..ImportReport::default()
+    });
+
+    if app.prepare_categorize_step() {
+        let total = app.import_cat_descriptions.len();
+        app.import_step = ImportStep::Categorize;
+        app.set_status(format!(
+            "{total} unique description{} to categorize",
+            if total == 1 { "" } else { "s" }
+        ));
+    } else {
+        commit_import(app, db)?;
+    }
+
+    if !bad_patterns.is_empty() {
+        app.set_warning(format!(
+            "{} | Warning: invalid regex rule(s): {}",
+            app.status.text,
+            bad_patterns.join(", ")
+        ));
+    }
+    Ok(())
+}
+
 fn commit_import(app: &mut App, db: &mut Database) -> Result<()> {
     let txns = &app.import_preview;
     let count = db.insert_transactions_batch(txns)?;
-    let dupes = txns.len() - count;
+    if count > 0 {
+        db.mark_rules_matched(&app.import_matched_rules)?;
+    }
+    app.import_matched_rules.clear();
+    let dupes = txns.len() - count + app.import_duplicates_skipped;
+    let first = txns.iter().map(|t| t.date.as_str()).min();
+    let last = txns.iter().map(|t| t.date.as_str()).max();
+    // Counted from what was stored, so rows already imported don't count
+    let uncategorized = match db.last_import_batch()? {
+        Some(batch) if count > 0 => db.get_uncategorized_in_batch(batch)?.len(),
+        _ => 0,
+    };
+    let report = ImportReport {
+        parsed: txns.len() + app.import_duplicates_skipped,
+        bad_dates: app.import_skipped.len(),
+        inserted: count,
+        duplicates: dupes,
+        uncategorized,
+        date_range: first.zip(last).map(|(a, b)| (a.to_string(), b.to_string())),
+        ..app.import_report.take().unwrap_or_default()
+    };
+    app.import_report = Some(report);
+    // Headerless files all look alike, so only a real header row is a key
+    if app.import_qif_content.is_none() && app.import_profile.has_header {
+        if let Some(account_id) = app.import_account_id {
+            db.save_account_profile(
+                account_id,
+                &app.import_headers,
+                &app.import_profile,
+                app.import_flip_signs,
+            )?;
+        }
+    }
+    if let Some(dir) = std::path::Path::new(&app.import_path).parent() {
+        let dir = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+        db.push_recent_dir(&dir.to_string_lossy())?;
+        app.set_recent_dirs(db.get_recent_dirs()?);
+    }
     app.import_step = ImportStep::Complete;
-    app.set_status(format!(
-        "Imported {count} new transactions ({dupes} duplicates skipped)"
-    ));
+    app.import_summary = format!("Imported {count} new transactions ({dupes} duplicates skipped)");
+    app.set_status(app.import_summary.clone());
     app.refresh_all(db)?;
     Ok(())
 }
@@ -1150,6 +2354,12 @@ fn handle_goto_top(app: &mut App) {
         Screen::Import if app.import_step == ImportStep::SelectFile => {
             scroll_to_top(&mut app.file_browser_index, &mut app.file_browser_scroll);
         }
+        Screen::Import if app.import_step == ImportStep::Preview => {
+            scroll_to_top(
+                &mut app.import_preview_index,
+                &mut app.import_preview_scroll,
+            );
+        }
         _ => {}
     }
 }
@@ -1212,6 +2422,19 @@ fn handle_goto_bottom(app: &mut App) {
                 page,
             );
         }
+        Screen::Import if app.import_step == ImportStep::Preview => {
+            let page = app.import_preview_page();
+            scroll_to_bottom(
+                &mut app.import_preview_index,
+                &mut app.import_preview_scroll,
+                app.import_preview.len(),
+                page,
+            );
+        }
         _ => {}
     }
 }
+
+#[cfg(test)]
+#[path = "tui_tests.rs"]
+mod tests;
@@ -0,0 +1,1474 @@
+#![allow(clippy::unwrap_used)]
+
+use super::*;
+use crate::db::TransactionQuery;
+use crate::models::{Category, ImportRule, RuleSource};
+use chrono::Datelike;
+use rust_decimal_macros::dec;
+
+fn setup() -> (App, Database) {
+    let db = Database::open_in_memory().unwrap();
+    db.ensure_default_account().unwrap();
+    let mut app = App::new();
+    app.refresh_all(&db).unwrap();
+    (app, db)
+}
+
+fn import_row(app: &App, description: &str) -> Transaction {
+    Transaction {
+        id: None,
+        account_id: app.accounts[0].id.unwrap(),
+        date: "2024-01-10".into(),
+        description: description.into(),
+        original_description: description.into(),
+        amount: dec!(-5.25),
+        category_id: None,
+        notes: String::new(),
+        is_transfer: false,
+        import_hash: format!("hash-{description}"),
+        created_at: "2024-01-10T00:00:00Z".into(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    }
+}
+
+fn confirm_import(app: &mut App, db: &mut Database) {
+    app.screen = Screen::Import;
+    app.pending_action = Some(PendingAction::ImportCommit);
+    app.input_mode = InputMode::Confirm;
+    let key = event::KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+    handle_confirm_input(key, app, db).unwrap();
+}
+
+fn add_rules(db: &Database) {
+    let category_id = db.get_categories().unwrap()[0].id.unwrap();
+    db.insert_import_rule(&ImportRule::new_contains("COFFEE".into(), category_id))
+        .unwrap();
+    db.insert_import_rule(&ImportRule::new_regex("([unclosed".into(), category_id))
+        .unwrap();
+}
+
+// ── Import commit ─────────────────────────────────────────────
+
+#[test]
+fn test_import_commit_warns_bad_regex_once_when_committing() {
+    let (mut app, mut db) = setup();
+    add_rules(&db);
+    app.import_preview = vec![import_row(&app, "COFFEE SHOP")];
+
+    confirm_import(&mut app, &mut db);
+
+    assert_eq!(app.import_step, ImportStep::Complete);
+    assert!(app.status.text.starts_with("Imported 1 new transactions"));
+    assert_eq!(app.status.text.matches("([unclosed").count(), 1);
+}
+
+#[test]
+fn test_import_commit_warns_bad_regex_once_when_categorizing() {
+    let (mut app, mut db) = setup();
+    add_rules(&db);
+    app.import_preview = vec![import_row(&app, "COFFEE SHOP"), import_row(&app, "GAS")];
+
+    confirm_import(&mut app, &mut db);
+
+    assert_eq!(app.import_step, ImportStep::Categorize);
+    assert!(app
+        .status
+        .text
+        .starts_with("1 unique description to categorize"));
+    assert_eq!(app.status.text.matches("([unclosed").count(), 1);
+}
+
+#[test]
+fn test_import_commit_without_bad_rules_has_no_warning() {
+    let (mut app, mut db) = setup();
+    app.import_preview = vec![import_row(&app, "GAS")];
+
+    confirm_import(&mut app, &mut db);
+
+    assert!(!app.status.text.contains("Warning"));
+}
+
+#[test]
+fn test_import_report_counts_what_the_commit_did() {
+    let (mut app, mut db) = setup();
+    add_rules(&db);
+    let rows = vec![
+        import_row(&app, "COFFEE SHOP"),
+        Transaction {
+            date: "2024-01-20".into(),
+            ..import_row(&app, "GAS")
+        },
+    ];
+    app.import_preview = rows.clone();
+    confirm_import(&mut app, &mut db);
+    press(&mut app, &mut db, KeyCode::Char('S'));
+    assert_eq!(app.import_step, ImportStep::Complete);
+    assert_eq!(
+        app.import_report,
+        Some(ImportReport {
+            parsed: 2,
+            bad_dates: 0,
+            inserted: 2,
+            duplicates: 0,
+            auto_categorized: 1,
+            uncategorized: 1,
+            date_range: Some(("2024-01-10".into(), "2024-01-20".into())),
+        })
+    );
+
+    // The same rows again are all duplicates, so nothing is left to file
+    app.import_preview = rows;
+    confirm_import(&mut app, &mut db);
+    press(&mut app, &mut db, KeyCode::Char('S'));
+    let report = app.import_report.clone().unwrap();
+    assert_eq!((report.inserted, report.duplicates), (0, 2));
+    assert_eq!(report.uncategorized, 0);
+}
+
+#[test]
+fn test_import_commit_records_rule_matches() {
+    let (mut app, mut db) = setup();
+    add_rules(&db);
+    app.import_preview = vec![import_row(&app, "COFFEE SHOP"), import_row(&app, "GAS")];
+    confirm_import(&mut app, &mut db);
+    // Filing GAS in the categorize step creates a rule for it
+    press(&mut app, &mut db, KeyCode::Enter);
+    assert_eq!(app.import_step, ImportStep::Complete);
+
+    let rules = db.get_import_rules().unwrap();
+    let rule = |pattern: &str| rules.iter().find(|r| r.pattern == pattern).unwrap();
+    assert!(rule("COFFEE").last_matched_at.is_some());
+    assert_eq!(rule("COFFEE").source, RuleSource::Manual);
+    assert!(rule("gas").last_matched_at.is_some());
+    assert_eq!(rule("gas").source, RuleSource::Import);
+    assert!(rule("([unclosed").last_matched_at.is_none());
+}
+
+#[test]
+fn test_duplicate_only_import_records_no_rule_matches() {
+    let (mut app, mut db) = setup();
+    add_rules(&db);
+    db.insert_transaction(&import_row(&app, "COFFEE SHOP"))
+        .unwrap();
+    app.import_preview = vec![import_row(&app, "COFFEE SHOP")];
+    confirm_import(&mut app, &mut db);
+    assert_eq!(app.import_step, ImportStep::Complete);
+
+    let rules = db.get_import_rules().unwrap();
+    let coffee = rules.iter().find(|r| r.pattern == "COFFEE").unwrap();
+    assert!(coffee.last_matched_at.is_none());
+}
+
+#[test]
+fn test_categorize_last_updates_stored_rows() {
+    let (mut app, mut db) = setup();
+    app.import_preview = vec![import_row(&app, "GAS"), import_row(&app, "TOLL")];
+    confirm_import(&mut app, &mut db);
+    assert_eq!(app.import_step, ImportStep::Categorize);
+    press(&mut app, &mut db, KeyCode::Char('S'));
+    assert_eq!(app.import_step, ImportStep::Complete);
+
+    commands::handle_command("categorize-last", &mut app, &mut db).unwrap();
+    assert_eq!(app.import_step, ImportStep::Categorize);
+    assert_eq!(app.import_cat_descriptions.len(), 2);
+    let category_id = app.categories[0].id;
+    press(&mut app, &mut db, KeyCode::Enter);
+    press(&mut app, &mut db, KeyCode::Esc);
+    assert_eq!(app.import_step, ImportStep::Complete);
+    assert_eq!(
+        app.status.text,
+        "Categorized 1 of 2 transactions left over from the last import"
+    );
+    let stored = db.get_all_transactions_for_export(None, None).unwrap();
+    let gas = stored.iter().find(|t| t.description == "GAS").unwrap();
+    assert_eq!(gas.category_id, category_id);
+
+    // Only what's still open comes back, and a newer import takes over
+    commands::handle_command("categorize-last", &mut app, &mut db).unwrap();
+    assert_eq!(app.import_cat_descriptions[0].0, "TOLL");
+    press(&mut app, &mut db, KeyCode::Char('S'));
+    app.import_preview = vec![import_row(&app, "PARKING")];
+    confirm_import(&mut app, &mut db);
+    press(&mut app, &mut db, KeyCode::Char('S'));
+    commands::handle_command("categorize-last", &mut app, &mut db).unwrap();
+    assert_eq!(
+        app.import_cat_descriptions,
+        vec![("PARKING".to_string(), 1)]
+    );
+}
+
+// ── Category picker ───────────────────────────────────────────
+
+fn press(app: &mut App, db: &mut Database, code: KeyCode) {
+    let key = event::KeyEvent::new(code, KeyModifiers::NONE);
+    handle_normal_input(key, app, db).unwrap();
+}
+
+fn setup_with_transaction() -> (App, Database) {
+    let (mut app, db) = setup();
+    db.insert_transaction(&import_row(&app, "GAS")).unwrap();
+    app.screen = Screen::Transactions;
+    app.current_month = None;
+    app.refresh_transactions(&db).unwrap();
+    (app, db)
+}
+
+#[test]
+fn test_recat_picker_assigns_selected_category() {
+    let (mut app, mut db) = setup_with_transaction();
+    let target = app.categories[2].clone();
+
+    press(&mut app, &mut db, KeyCode::Char('c'));
+    assert!(app.txn_recat);
+    press(&mut app, &mut db, KeyCode::Char('j'));
+    press(&mut app, &mut db, KeyCode::Char('j'));
+    press(&mut app, &mut db, KeyCode::Enter);
+
+    assert!(!app.txn_recat);
+    assert_eq!(app.transactions[0].category_id, target.id);
+    assert_eq!(app.status.text, format!("Categorized as: {}", target.name));
+}
+
+#[test]
+fn test_recat_picker_creates_new_category() {
+    let (mut app, mut db) = setup_with_transaction();
+
+    press(&mut app, &mut db, KeyCode::Char('c'));
+    press(&mut app, &mut db, KeyCode::Char('n'));
+    for c in "Pets".chars() {
+        press(&mut app, &mut db, KeyCode::Char(c));
+    }
+    press(&mut app, &mut db, KeyCode::Enter);
+
+    let pets = crate::models::Category::find_by_name(&app.categories, "Pets").unwrap();
+    assert_eq!(app.transactions[0].category_id, pets.id);
+}
+
+#[test]
+fn test_recat_picker_escape_leaves_category_alone() {
+    let (mut app, mut db) = setup_with_transaction();
+
+    press(&mut app, &mut db, KeyCode::Char('c'));
+    press(&mut app, &mut db, KeyCode::Enter);
+    press(&mut app, &mut db, KeyCode::Char('c'));
+    press(&mut app, &mut db, KeyCode::Char('j'));
+    press(&mut app, &mut db, KeyCode::Esc);
+
+    assert!(!app.txn_recat);
+    assert_eq!(app.transactions[0].category_id, app.categories[0].id);
+}
+
+// ── Search scope ──────────────────────────────────────────────
+
+#[test]
+fn test_search_scope_month_follows_viewed_month() {
+    let (mut app, mut db) = setup();
+    for date in ["2024-01-10", "2024-02-10"] {
+        let txn = Transaction {
+            date: date.into(),
+            import_hash: format!("hash-{date}"),
+            ..import_row(&app, "COFFEE")
+        };
+        db.insert_transaction(&txn).unwrap();
+    }
+    app.search_input = "coffee".into();
+    crate::ui::commands::set_month(&mut app, &mut db, Some("2024-02".into())).unwrap();
+    app.refresh_transactions(&db).unwrap();
+    assert_eq!(app.transactions.len(), 2);
+    assert_eq!(app.substring_search.as_deref(), Some("coffee"));
+    assert_eq!(app.fuzzy_search, None);
+
+    crate::ui::commands::handle_command("set search-scope month", &mut app, &mut db).unwrap();
+    assert_eq!(app.transactions.len(), 1);
+    assert_eq!(app.transactions[0].date, "2024-02-10");
+
+    crate::ui::commands::set_month(&mut app, &mut db, Some("2024-01".into())).unwrap();
+    assert_eq!(app.transactions[0].date, "2024-01-10");
+    assert_eq!(app.search_scope_label(), "2024-01");
+}
+
+#[test]
+fn test_strict_amount_bound_fills_the_page() {
+    let (mut app, db) = setup();
+    app.current_month = None;
+    app.page_size = 3;
+    // Newer rows sit exactly on the bound and must not eat the page
+    for day in 10..16 {
+        let date = format!("2024-01-{day}");
+        let txn = Transaction {
+            amount: dec!(50),
+            import_hash: format!("hash-{date}"),
+            date,
+            ..import_row(&app, "ON BOUND")
+        };
+        db.insert_transaction(&txn).unwrap();
+    }
+    for day in 1..5 {
+        let date = format!("2024-01-0{day}");
+        let txn = Transaction {
+            amount: dec!(60),
+            import_hash: format!("hash-{date}"),
+            date,
+            ..import_row(&app, "ABOVE")
+        };
+        db.insert_transaction(&txn).unwrap();
+    }
+    app.search_input = "amount>50".into();
+    app.refresh_transactions(&db).unwrap();
+    assert_eq!(app.transactions.len(), 3);
+    assert!(app.transactions.iter().all(|t| t.amount == dec!(60)));
+    assert!(app.transactions_truncated);
+}
+
+// ── Import preview selection ──────────────────────────────────
+
+fn setup_preview() -> (App, Database) {
+    let (mut app, db) = setup();
+    app.import_preview = ["RENT", "CARD PAYMENT", "XFER TO SAVINGS"]
+        .iter()
+        .map(|d| import_row(&app, d))
+        .collect();
+    app.screen = Screen::Import;
+    app.import_step = ImportStep::Preview;
+    (app, db)
+}
+
+#[test]
+fn test_preview_bulk_transfer_and_note() {
+    let (mut app, mut db) = setup_preview();
+
+    press(&mut app, &mut db, KeyCode::Char('j'));
+    press(&mut app, &mut db, KeyCode::Char(' '));
+    press(&mut app, &mut db, KeyCode::Char(' '));
+    assert_eq!(app.import_preview_targets(), vec![1, 2]);
+
+    press(&mut app, &mut db, KeyCode::Char('t'));
+    press(&mut app, &mut db, KeyCode::Char('n'));
+    assert_eq!(app.input_mode, InputMode::Editing);
+    for c in "moving money".chars() {
+        let key = event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        handle_editing_input(key, &mut app, &mut db).unwrap();
+    }
+    let enter = event::KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    handle_editing_input(enter, &mut app, &mut db).unwrap();
+
+    let flags: Vec<(bool, &str)> = app
+        .import_preview
+        .iter()
+        .map(|t| (t.is_transfer, t.notes.as_str()))
+        .collect();
+    assert_eq!(
+        flags,
+        vec![(false, ""), (true, "moving money"), (true, "moving money")]
+    );
+
+    confirm_import(&mut app, &mut db);
+    assert!(app.import_preview_selected.is_empty());
+    press(&mut app, &mut db, KeyCode::Char('S'));
+    assert_eq!(app.import_step, ImportStep::Complete);
+    let saved = db.get_transactions(&TransactionQuery::default()).unwrap();
+    assert_eq!(saved.iter().filter(|t| t.is_transfer).count(), 2);
+    assert_eq!(
+        saved.iter().filter(|t| t.notes == "moving money").count(),
+        2
+    );
+}
+
+#[test]
+fn test_preview_transfer_toggles_cursor_row_and_escape_clears_selection() {
+    let (mut app, mut db) = setup_preview();
+
+    press(&mut app, &mut db, KeyCode::Char('t'));
+    assert!(app.import_preview[0].is_transfer);
+    press(&mut app, &mut db, KeyCode::Char('t'));
+    assert!(!app.import_preview[0].is_transfer);
+
+    press(&mut app, &mut db, KeyCode::Char(' '));
+    handle_escape(&mut app);
+    assert!(app.import_preview_selected.is_empty());
+    assert_eq!(app.import_step, ImportStep::Preview);
+    handle_escape(&mut app);
+    assert_eq!(app.import_step, ImportStep::SelectAccount);
+}
+
+#[test]
+fn test_preview_scrolls_to_every_row() {
+    let (mut app, mut db) = setup();
+    app.import_preview = (0..200)
+        .map(|i| import_row(&app, &format!("ROW {i}")))
+        .collect();
+    app.screen = Screen::Import;
+    app.import_step = ImportStep::Preview;
+    app.visible_rows = 20;
+    let page = app.import_preview_page();
+
+    for _ in 0..120 {
+        press(&mut app, &mut db, KeyCode::Char('j'));
+    }
+    assert_eq!(app.import_preview_index, 120);
+    assert!(app.import_preview_scroll <= 120 && 120 < app.import_preview_scroll + page);
+
+    press(&mut app, &mut db, KeyCode::Char('G'));
+    assert_eq!(app.import_preview_index, 199);
+    assert_eq!(app.import_preview_scroll, 200 - page);
+    press(&mut app, &mut db, KeyCode::Char('t'));
+    assert!(app.import_preview[199].is_transfer);
+}
+
+#[test]
+fn test_preview_warns_about_flipped_signs_and_f_flips_them() {
+    let (mut app, mut db) = setup();
+    app.import_rows = ["4.50", "12.00", "60.00", "9.99", "-500.00"]
+        .iter()
+        .map(|a| vec!["01/10/2024".to_string(), "SHOP".to_string(), a.to_string()])
+        .collect();
+    app.import_account_id = app.accounts[0].id;
+    app.screen = Screen::Import;
+    app.generate_import_preview(&db).unwrap();
+    assert!(app.import_signs_suspect);
+    assert!(app.status.text.ends_with("f to flip every sign"));
+
+    press(&mut app, &mut db, KeyCode::Char('f'));
+    assert!(!app.import_signs_suspect);
+    assert_eq!(app.import_preview[0].amount, dec!(-4.50));
+    assert_eq!(app.import_preview[4].amount, dec!(500.00));
+    assert_eq!(app.status.text, "Flipped every sign — f to put them back");
+
+    press(&mut app, &mut db, KeyCode::Char('f'));
+    assert!(app.import_signs_suspect);
+    assert_eq!(app.import_preview[0].amount, dec!(4.50));
+}
+
+#[test]
+fn test_tilde_flips_sample_amounts_in_column_mapper() {
+    let (mut app, mut db) = setup();
+    app.import_rows = vec![
+        vec!["01/10/2024".into(), "SHOP".into(), "4.50".into()],
+        vec!["01/11/2024".into(), "PAYMENT".into(), "-20".into()],
+        vec!["01/12/2024".into(), "JUNK".into(), "n/a".into()],
+    ];
+    app.screen = Screen::Import;
+    app.import_step = ImportStep::MapColumns;
+    assert_eq!(
+        app.sample_amounts(),
+        vec![Some(dec!(4.50)), Some(dec!(-20)), None]
+    );
+
+    press(&mut app, &mut db, KeyCode::Char('~'));
+    assert!(app.import_flip_signs);
+    assert_eq!(
+        app.sample_amounts(),
+        vec![Some(dec!(-4.50)), Some(dec!(20)), None]
+    );
+
+    // The flip carries into the preview
+    app.import_rows.pop();
+    app.import_account_id = app.accounts[0].id;
+    app.generate_import_preview(&db).unwrap();
+    assert_eq!(app.import_preview[0].amount, dec!(-4.50));
+}
+
+#[test]
+fn test_preview_names_the_mapped_columns() {
+    let (mut app, _db) = setup_preview();
+    app.import_headers = vec!["Posted".into(), "Memo".into(), "Out".into(), "In".into()];
+    app.import_profile.description_column = 1;
+    app.import_profile.amount_column = None;
+    app.import_profile.debit_column = Some(2);
+    app.import_profile.credit_column = Some(3);
+    app.import_flip_signs = true;
+    assert_eq!(
+        app.import_mapping_summary().unwrap(),
+        format!(
+            "Date ← [0] Posted  Description ← [1] Memo  Debit ← [2] Out  Credit ← [3] In  Signs: {}, flipped",
+            app.import_profile.sign_convention.label()
+        )
+    );
+
+    app.import_qif_content = Some(String::new());
+    assert_eq!(app.import_mapping_summary(), None);
+}
+
+#[test]
+fn test_mapping_remembered_for_the_account() {
+    let (mut app, mut db) = setup();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("card.csv");
+    std::fs::write(&path, "When,What,Cost\n2024-01-10,SHOP,4.50\n").unwrap();
+    let card = Account::new("Visa".into(), AccountType::CreditCard, String::new());
+    let card_id = db.insert_account(&card).unwrap();
+
+    // First import: map by hand, then commit into the card
+    open_import_file(&mut app, &mut db, &path).unwrap();
+    assert_eq!(app.import_step, ImportStep::MapColumns);
+    app.import_profile.date_format = "%Y-%m-%d".into();
+    app.import_profile.sign_convention = SignConvention::DebitPositive;
+    app.import_sign_chosen = true;
+    begin_select_account(&mut app, &mut db).unwrap();
+    app.import_account_index = app
+        .accounts
+        .iter()
+        .position(|a| a.id == Some(card_id))
+        .unwrap();
+    handle_select_account_input(
+        event::KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        &mut app,
+        &mut db,
+    )
+    .unwrap();
+    assert_eq!(app.import_preview[0].amount, dec!(-4.50));
+    confirm_import(&mut app, &mut db);
+    finish_categorize(&mut app, &mut db).unwrap();
+
+    // The next file with those headers skips the mapper
+    app.import_profile = Default::default();
+    std::fs::write(&path, "When,What,Cost\n2024-02-10,CAFE,3.00\n").unwrap();
+    open_import_file(&mut app, &mut db, &path).unwrap();
+    assert_eq!(app.import_step, ImportStep::SelectAccount);
+    assert_eq!(app.accounts[app.import_account_index].id, Some(card_id));
+    assert_eq!(app.import_profile.date_format, "%Y-%m-%d");
+    assert!(app
+        .status
+        .text
+        .starts_with("Using the column mapping from your last import into Visa"));
+
+    // Different headers fall back to mapping by hand
+    std::fs::write(&path, "Date,Payee,Amount\n2024-02-10,CAFE,3.00\n").unwrap();
+    open_import_file(&mut app, &mut db, &path).unwrap();
+    assert_eq!(app.import_step, ImportStep::MapColumns);
+}
+
+#[test]
+fn test_headerless_csv_keeps_its_first_row() {
+    let (mut app, mut db) = setup();
+    app.screen = Screen::Import;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bank.csv");
+    std::fs::write(&path, "2024-01-10,SHOP,-4.50\n2024-01-11,CAFE,-3.00\n").unwrap();
+
+    open_import_file(&mut app, &mut db, &path).unwrap();
+    assert!(!app.import_profile.has_header);
+    assert_eq!(app.import_headers, vec!["col0", "col1", "col2"]);
+    assert_eq!(app.import_rows.len(), 2);
+
+    // Toggling Has Header in the mapper moves the first row either way
+    app.import_selected_field = 6;
+    handle_adjust_field(&mut app, 1);
+    assert!(app.import_profile.has_header);
+    assert_eq!(app.import_headers, vec!["2024-01-10", "SHOP", "-4.50"]);
+    assert_eq!(app.import_rows.len(), 1);
+    handle_adjust_field(&mut app, 1);
+    assert_eq!(app.import_headers, vec!["col0", "col1", "col2"]);
+    assert_eq!(app.import_rows[0][1], "SHOP");
+}
+
+#[test]
+fn test_new_account_form_is_named_after_the_file() {
+    let (mut app, mut db) = setup();
+    app.screen = Screen::Import;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ally_savings_2024-03.csv");
+    std::fs::write(&path, "Date,Description,Amount\n2024-03-01,INTEREST,1.20\n").unwrap();
+
+    open_import_file(&mut app, &mut db, &path).unwrap();
+    begin_select_account(&mut app, &mut db).unwrap();
+    handle_select_account_input(
+        event::KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+        &mut app,
+        &mut db,
+    )
+    .unwrap();
+    assert!(app.import_creating_account);
+    assert_eq!(app.import_new_account_name, "Ally Savings");
+    assert_eq!(
+        AccountType::all()[app.import_new_account_type],
+        AccountType::Savings
+    );
+
+    // A recognised bank format decides the type over the file name
+    let path = dir.path().join("amex_checking.csv");
+    std::fs::write(
+        &path,
+        "Date,Description,Card Member,Amount\n01/05/2024,SHOP,A B,9.99\n",
+    )
+    .unwrap();
+    open_import_file(&mut app, &mut db, &path).unwrap();
+    begin_select_account(&mut app, &mut db).unwrap();
+    assert_eq!(app.import_suggested_account_name, "Amex Checking");
+    assert_eq!(
+        AccountType::all()[app.import_new_account_type],
+        AccountType::CreditCard
+    );
+}
+
+// ── Date formats ──────────────────────────────────────────────
+
+#[test]
+fn test_add_date_format_joins_cycle_and_parses_sample() {
+    let (mut app, mut db) = setup();
+    app.screen = Screen::Import;
+    app.import_step = ImportStep::MapColumns;
+    app.import_rows = vec![vec!["15.01.2024".into(), "Bakery".into(), "-3.20".into()]];
+    assert_eq!(app.sample_date_parse(), Some(Err("15.01.2024".into())));
+
+    commands::handle_command("add-date-format %d.%m.%Y", &mut app, &mut db).unwrap();
+    assert_eq!(app.import_profile.date_format, "%d.%m.%Y");
+    assert_eq!(app.sample_date_parse(), Some(Ok("2024-01-15".into())));
+    assert_eq!(db.get_custom_date_formats().unwrap(), vec!["%d.%m.%Y"]);
+
+    // Cycling wraps from the custom format back to the first built-in
+    app.import_selected_field = 5;
+    handle_adjust_field(&mut app, 1);
+    assert_eq!(app.import_profile.date_format, "%m/%d/%Y");
+    handle_adjust_field(&mut app, -1);
+    assert_eq!(app.import_profile.date_format, "%d.%m.%Y");
+
+    commands::handle_command("add-date-format %m/%Y", &mut app, &mut db).unwrap();
+    assert!(app.status.text.starts_with("Not a usable date format"));
+    assert_eq!(app.custom_date_formats.len(), 1);
+}
+
+// ── Memorize ──────────────────────────────────────────────────
+
+#[test]
+fn test_memorize_creates_rule_from_categorized_transaction() {
+    let (mut app, mut db) = setup();
+    db.insert_transaction(&import_row(&app, "SQ *COFFEE 0012345"))
+        .unwrap();
+    app.screen = Screen::Transactions;
+    app.refresh_transactions(&db).unwrap();
+
+    press(&mut app, &mut db, KeyCode::Char('M'));
+    assert!(app
+        .status
+        .text
+        .starts_with("Categorize this transaction first"));
+    assert!(db.get_import_rules().unwrap().is_empty());
+
+    let category = app.categories[1].clone();
+    db.update_transaction_category(app.transactions[0].id.unwrap(), category.id)
+        .unwrap();
+    app.refresh_transactions(&db).unwrap();
+    press(&mut app, &mut db, KeyCode::Char('M'));
+
+    let rules = db.get_import_rules().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].pattern, "coffee");
+    assert_eq!(Some(rules[0].category_id), category.id);
+    assert_eq!(rules[0].source, RuleSource::Memorized);
+    assert_eq!(
+        app.status.text,
+        format!("Memorized rule: 'coffee' -> {}", category.name)
+    );
+
+    // A second press doesn't add a duplicate
+    press(&mut app, &mut db, KeyCode::Char('M'));
+    assert_eq!(db.get_import_rules().unwrap().len(), 1);
+    assert!(app.status.text.contains("already exists"));
+}
+
+// ── Import rules ──────────────────────────────────────────────
+
+#[test]
+fn test_prune_rules_confirms_then_deletes_unused() {
+    let (mut app, mut db) = setup();
+    let category_id = app.categories[0].id.unwrap();
+    let old = "2000-01-01T00:00:00+00:00";
+    let rule = |pattern: &str, last_matched_at: Option<String>| ImportRule {
+        created_at: old.into(),
+        last_matched_at,
+        ..ImportRule::new_contains(pattern.into(), category_id)
+    };
+    db.insert_import_rule(&rule("kept", Some(chrono::Utc::now().to_rfc3339())))
+        .unwrap();
+    db.insert_import_rule(&rule("stale", Some(old.into())))
+        .unwrap();
+    db.insert_import_rule(&rule("unused", None)).unwrap();
+    app.refresh_categories(&db).unwrap();
+
+    run_command(&mut app, &mut db, "prune-rules 0");
+    assert!(app.status.text.starts_with("Usage: :prune-rules [months]"));
+
+    run_command(&mut app, &mut db, "prune-rules");
+    assert_eq!(app.input_mode, InputMode::Confirm);
+    assert!(app
+        .confirm_message
+        .starts_with("Delete 2 rules with no match since "));
+    let key = event::KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+    handle_confirm_input(key, &mut app, &mut db).unwrap();
+    assert!(app.status.text.starts_with("Deleted 2 rules"));
+    let patterns: Vec<&str> = app
+        .import_rules
+        .iter()
+        .map(|r| r.pattern.as_str())
+        .collect();
+    assert_eq!(patterns, ["kept"]);
+
+    run_command(&mut app, &mut db, "prune-rules 12");
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.status.text.starts_with("No rules unused since "));
+}
+
+// ── Uncategorized review ──────────────────────────────────────
+
+#[test]
+fn test_review_uncategorized_from_dashboard() {
+    let (mut app, mut db) = setup();
+    let category = app
+        .categories
+        .iter()
+        .find(|c| c.name != crate::models::UNCATEGORIZED)
+        .unwrap()
+        .clone();
+    db.insert_transaction(&import_row(&app, "GAS")).unwrap();
+    db.insert_transaction(&Transaction {
+        category_id: category.id,
+        ..import_row(&app, "COFFEE")
+    })
+    .unwrap();
+    app.current_month = Some("2024-01".into());
+    app.refresh_dashboard(&db).unwrap();
+    assert_eq!(app.uncategorized_summary, (dec!(5.25), 1));
+
+    press(&mut app, &mut db, KeyCode::Char('u'));
+    assert_eq!(app.screen, Screen::Transactions);
+    let names: Vec<&str> = app
+        .transactions
+        .iter()
+        .map(|t| t.description.as_str())
+        .collect();
+    assert_eq!(names, vec!["GAS"]);
+
+    // Categorizing a row drops it from the list
+    db.update_transaction_category(app.transactions[0].id.unwrap(), category.id)
+        .unwrap();
+    app.refresh_transactions(&db).unwrap();
+    assert!(app.transactions.is_empty());
+
+    press(&mut app, &mut db, KeyCode::Esc);
+    assert!(!app.transaction_filter_uncategorized);
+    assert_eq!(app.transactions.len(), 2);
+}
+
+#[test]
+fn test_uncategorized_toggle_follows_month() {
+    let (mut app, mut db) = setup();
+    db.insert_transaction(&import_row(&app, "GAS")).unwrap();
+    db.insert_transaction(&Transaction {
+        date: "2024-02-03".into(),
+        ..import_row(&app, "PARKING")
+    })
+    .unwrap();
+    app.screen = Screen::Transactions;
+    app.current_month = Some("2024-01".into());
+
+    press(&mut app, &mut db, KeyCode::Char('u'));
+    assert!(app.transaction_filter_uncategorized);
+    assert_eq!(app.transactions[0].description, "GAS");
+    assert!(app
+        .status
+        .text
+        .starts_with("1 uncategorized transaction in 2024-01"));
+
+    // Moving to the next month cleans that one up instead
+    commands::handle_command("next-month", &mut app, &mut db).unwrap();
+    let names: Vec<&str> = app
+        .transactions
+        .iter()
+        .map(|t| t.description.as_str())
+        .collect();
+    assert_eq!(names, vec!["PARKING"]);
+
+    // Ctrl-u still pages instead of toggling
+    let key = event::KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+    handle_normal_input(key, &mut app, &mut db).unwrap();
+    assert!(app.transaction_filter_uncategorized);
+
+    press(&mut app, &mut db, KeyCode::Char('u'));
+    assert!(!app.transaction_filter_uncategorized);
+    assert_eq!(app.transactions.len(), 2);
+}
+
+// ── Dashboard budgets ─────────────────────────────────────────
+
+#[test]
+fn test_dashboard_flags_over_budget_categories() {
+    let (mut app, mut db) = setup();
+    let category = |name: &str| {
+        Category::find_by_name(&app.categories, name)
+            .and_then(|c| c.id)
+            .unwrap()
+    };
+    let (groceries, travel) = (category("Groceries"), category("Travel"));
+    for (desc, category_id, amount) in [
+        ("MARKET", groceries, dec!(-60)),
+        ("TRAIN", travel, dec!(-20)),
+    ] {
+        db.insert_transaction(&Transaction {
+            category_id: Some(category_id),
+            amount,
+            ..import_row(&app, desc)
+        })
+        .unwrap();
+    }
+    app.current_month = Some("2024-01".into());
+    commands::handle_command("budget Groceries 50", &mut app, &mut db).unwrap();
+    commands::handle_command("budget Travel 100", &mut app, &mut db).unwrap();
+
+    app.refresh_dashboard(&db).unwrap();
+    assert_eq!(app.over_budget.len(), 1);
+    assert_eq!(app.over_budget["Groceries"], "120%");
+
+    // Only the viewed month's budgets count
+    app.current_month = None;
+    app.refresh_dashboard(&db).unwrap();
+    assert!(app.over_budget.is_empty());
+}
+
+#[test]
+fn test_budget_without_amount_prefills_a_suggestion() {
+    let (mut app, mut db) = setup();
+    let groceries = Category::find_by_name(&app.categories, "Groceries")
+        .and_then(|c| c.id)
+        .unwrap();
+    let last_month = chrono::Local::now()
+        .date_naive()
+        .with_day(10)
+        .and_then(|d| d.checked_sub_months(chrono::Months::new(1)))
+        .unwrap();
+    db.insert_transaction(&Transaction {
+        date: last_month.format("%Y-%m-%d").to_string(),
+        category_id: Some(groceries),
+        amount: dec!(-90),
+        ..import_row(&app, "MARKET")
+    })
+    .unwrap();
+
+    run_command(&mut app, &mut db, "budget Groceries");
+    assert_eq!(app.input_mode, InputMode::Command);
+    assert_eq!(app.command_input, "budget Groceries 30.00");
+    assert_eq!(
+        app.status.text,
+        "Groceries: avg last 3 months: $30.00 — Enter to use it"
+    );
+    assert!(db.get_budgets(None).unwrap().is_empty());
+
+    type_command(&mut app, &mut db, KeyCode::Enter);
+    assert_eq!(db.get_budgets(None).unwrap()[0].limit_amount, dec!(30));
+
+    run_command(&mut app, &mut db, "budget Travel");
+    assert_eq!(app.command_input, "budget Travel 0.00");
+}
+
+// ── Pending edits ─────────────────────────────────────────────
+
+#[test]
+fn test_pending_edits_follow_selection_and_forms() {
+    let (mut app, mut db) = setup_with_transaction();
+    assert!(!app.has_pending_edits());
+
+    press(&mut app, &mut db, KeyCode::Char(' '));
+    assert!(app.has_pending_edits());
+    press(&mut app, &mut db, KeyCode::Esc);
+    assert!(!app.has_pending_edits());
+
+    press(&mut app, &mut db, KeyCode::Char('a'));
+    assert!(app.has_pending_edits());
+}
+
+fn press_ctrl_q(app: &mut App, db: &mut Database) {
+    let key = event::KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL);
+    handle_key(key, app, db).unwrap();
+}
+
+#[test]
+fn test_quit_confirms_when_a_selection_would_be_lost() {
+    let (mut app, mut db) = setup_with_transaction();
+    press(&mut app, &mut db, KeyCode::Char(' '));
+
+    press_ctrl_q(&mut app, &mut db);
+    assert!(app.running);
+    assert!(matches!(app.pending_action, Some(PendingAction::Quit)));
+    assert!(app.confirm_message.contains("1 transactions selected"));
+
+    let key = event::KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+    handle_confirm_input(key, &mut app, &mut db).unwrap();
+    assert!(app.running);
+    assert_eq!(app.selected_transactions.len(), 1);
+
+    // A second Ctrl-Q at the prompt forces the quit
+    press_ctrl_q(&mut app, &mut db);
+    press_ctrl_q(&mut app, &mut db);
+    assert!(!app.running);
+}
+
+#[test]
+fn test_quit_reaches_open_forms_and_can_skip_confirmation() {
+    let (mut app, mut db) = setup_with_transaction();
+    press(&mut app, &mut db, KeyCode::Char('a'));
+    press_ctrl_q(&mut app, &mut db);
+    assert!(matches!(app.pending_action, Some(PendingAction::Quit)));
+    let key = event::KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+    handle_confirm_input(key, &mut app, &mut db).unwrap();
+    assert!(!app.running);
+
+    let (mut app, mut db) = setup_with_transaction();
+    run_command(&mut app, &mut db, "set confirm-quit off");
+    assert_eq!(db.load_ui_state().unwrap().confirm_quit, Some(false));
+    press(&mut app, &mut db, KeyCode::Char(' '));
+    press_ctrl_q(&mut app, &mut db);
+    assert!(!app.running);
+
+    // Nothing to lose quits straight away even with the setting on
+    let (mut app, mut db) = setup_with_transaction();
+    press_ctrl_q(&mut app, &mut db);
+    assert!(!app.running);
+}
+
+#[test]
+fn test_quit_while_editing_asks_and_keeps_the_edit() {
+    let (mut app, mut db) = setup_with_transaction();
+    run_command(&mut app, &mut db, "rename");
+    assert_eq!(app.input_mode, InputMode::Editing);
+    app.command_input = "GAS STATION".into();
+
+    press_ctrl_q(&mut app, &mut db);
+    assert!(app.running);
+    assert!(matches!(app.pending_action, Some(PendingAction::Quit)));
+
+    let key = event::KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+    handle_key(key, &mut app, &mut db).unwrap();
+    assert_eq!(app.input_mode, InputMode::Editing);
+    assert_eq!(app.command_input, "GAS STATION");
+
+    press_ctrl_q(&mut app, &mut db);
+    press_ctrl_q(&mut app, &mut db);
+    assert!(!app.running);
+}
+
+#[test]
+fn test_declining_quit_restores_the_interrupted_confirmation() {
+    let (mut app, mut db) = setup();
+    run_command(&mut app, &mut db, "wipe");
+    assert!(matches!(
+        app.pending_action,
+        Some(PendingAction::WipeAllData)
+    ));
+    let wipe_message = app.confirm_message.clone();
+
+    press_ctrl_q(&mut app, &mut db);
+    assert!(matches!(app.pending_action, Some(PendingAction::Quit)));
+
+    let key = event::KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+    handle_key(key, &mut app, &mut db).unwrap();
+    assert!(app.running);
+    assert_eq!(app.input_mode, InputMode::Confirm);
+    assert!(matches!(
+        app.pending_action,
+        Some(PendingAction::WipeAllData)
+    ));
+    assert_eq!(app.confirm_message, wipe_message);
+    assert_eq!(app.status.text, "Quit cancelled");
+}
+
+// ── Command history ───────────────────────────────────────────
+
+fn type_command(app: &mut App, db: &mut Database, code: KeyCode) {
+    let key = event::KeyEvent::new(code, KeyModifiers::NONE);
+    handle_command_input(key, app, db).unwrap();
+}
+
+fn run_command(app: &mut App, db: &mut Database, command: &str) {
+    press(app, db, KeyCode::Char(':'));
+    for c in command.chars() {
+        type_command(app, db, KeyCode::Char(c));
+    }
+    type_command(app, db, KeyCode::Enter);
+}
+
+#[test]
+fn test_command_history_recall() {
+    let (mut app, mut db) = setup();
+    run_command(&mut app, &mut db, "t");
+    run_command(&mut app, &mut db, "c");
+    run_command(&mut app, &mut db, "c");
+    assert_eq!(app.command_history, vec!["t", "c"]);
+    assert_eq!(db.get_command_history().unwrap(), vec!["t", "c"]);
+
+    press(&mut app, &mut db, KeyCode::Char(':'));
+    type_command(&mut app, &mut db, KeyCode::Char('b'));
+    type_command(&mut app, &mut db, KeyCode::Up);
+    assert_eq!(app.command_input, "c");
+    type_command(&mut app, &mut db, KeyCode::Up);
+    type_command(&mut app, &mut db, KeyCode::Up);
+    assert_eq!(app.command_input, "t");
+    type_command(&mut app, &mut db, KeyCode::Down);
+    assert_eq!(app.command_input, "c");
+    type_command(&mut app, &mut db, KeyCode::Down);
+    assert_eq!(app.command_input, "b");
+
+    // Esc during recall brings back the typed line, then leaves the bar
+    type_command(&mut app, &mut db, KeyCode::Up);
+    type_command(&mut app, &mut db, KeyCode::Esc);
+    assert_eq!(app.command_input, "b");
+    assert_eq!(app.input_mode, InputMode::Command);
+    type_command(&mut app, &mut db, KeyCode::Esc);
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    // A recalled line can be edited and run
+    press(&mut app, &mut db, KeyCode::Char(':'));
+    type_command(&mut app, &mut db, KeyCode::Up);
+    type_command(&mut app, &mut db, KeyCode::Up);
+    type_command(&mut app, &mut db, KeyCode::Char('x'));
+    type_command(&mut app, &mut db, KeyCode::Enter);
+    assert_eq!(app.command_history, vec!["t", "c", "tx"]);
+}
+
+#[test]
+fn test_tab_completes_commands_and_arguments() {
+    let (mut app, mut db) = setup();
+    press(&mut app, &mut db, KeyCode::Char(':'));
+    for c in "nex".chars() {
+        type_command(&mut app, &mut db, KeyCode::Char(c));
+    }
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "next-month ");
+
+    // Several matches narrow to what they share, then cycle
+    app.command_input = "bud".into();
+    type_command(&mut app, &mut db, KeyCode::Char('g'));
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "budget");
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "budget");
+    assert!(app
+        .status
+        .text
+        .starts_with("5 matches: budget  budget-rollover"));
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "budget-rollover");
+    type_command(&mut app, &mut db, KeyCode::BackTab);
+    type_command(&mut app, &mut db, KeyCode::BackTab);
+    assert_eq!(app.command_input, "budgets");
+
+    // Category and account arguments, matched without case
+    app.command_input = "recat tra".into();
+    type_command(&mut app, &mut db, KeyCode::Char('n'));
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "recat Trans");
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "recat Transfer");
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "recat Transportation");
+    app.command_input = "fa de".into();
+    type_command(&mut app, &mut db, KeyCode::Char('f'));
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "fa Default");
+
+    app.command_input = "rename ".into();
+    type_command(&mut app, &mut db, KeyCode::Char('x'));
+    type_command(&mut app, &mut db, KeyCode::Tab);
+    assert_eq!(app.command_input, "rename x");
+    assert_eq!(app.status.text, "No completions");
+}
+
+// ── Status messages ───────────────────────────────────────────
+
+#[test]
+fn test_status_messages_expire_by_level() {
+    use crate::ui::app::{StatusLevel, STATUS_TIMEOUT, WARNING_TIMEOUT};
+
+    let (mut app, mut db) = setup();
+    app.set_status("Saved");
+    let set_at = app.status.set_at;
+    app.expire_status(set_at + Duration::from_secs(1));
+    assert_eq!(app.status.text, "Saved");
+    app.expire_status(set_at + STATUS_TIMEOUT);
+    assert!(app.status.text.is_empty());
+
+    app.set_warning("Careful");
+    let set_at = app.status.set_at;
+    app.expire_status(set_at + STATUS_TIMEOUT);
+    assert_eq!(app.status.text, "Careful");
+    app.expire_status(set_at + WARNING_TIMEOUT);
+    assert!(app.status.text.is_empty());
+
+    // Errors wait for a key press instead
+    commands::handle_command("month 2024-13", &mut app, &mut db).unwrap();
+    assert_eq!(app.status.level, StatusLevel::Error);
+    app.expire_status(app.status.set_at + Duration::from_secs(3600));
+    assert!(app.status.text.starts_with("Invalid month"));
+    app.dismiss_error();
+    assert!(app.status.text.is_empty());
+}
+
+// ── Transfers ─────────────────────────────────────────────────
+
+#[test]
+fn test_transfer_command_creates_both_legs() {
+    let (mut app, mut db) = setup();
+    let savings = Account::new("Rainy Day Fund".into(), AccountType::Savings, String::new());
+    let savings_id = db.insert_account(&savings).unwrap();
+    app.refresh_accounts(&db).unwrap();
+
+    commands::handle_command("transfer default rainy day fund $250", &mut app, &mut db).unwrap();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    assert_eq!(
+        app.status.text,
+        format!("Moved $250.00 from Default to Rainy Day Fund on {today}")
+    );
+    commands::handle_command(
+        "transfer Rainy Day Fund Default 40 2024-02-03",
+        &mut app,
+        &mut db,
+    )
+    .unwrap();
+
+    let legs = db
+        .get_transactions(&TransactionQuery {
+            account_id: Some(savings_id),
+            ..Default::default()
+        })
+        .unwrap();
+    let legs: Vec<(&str, rust_decimal::Decimal, bool)> = legs
+        .iter()
+        .map(|t| (t.description.as_str(), t.amount, t.is_transfer))
+        .collect();
+    assert_eq!(
+        legs,
+        vec![
+            ("Transfer from Default", dec!(250), true),
+            ("Transfer to Default", dec!(-40), true),
+        ]
+    );
+
+    for (args, error) in [
+        ("transfer Default Default 5", "Pick two different accounts"),
+        ("transfer Default Savings 5", "Couldn't find two accounts"),
+        ("transfer Default Rainy Day Fund -5", "Invalid amount: -5"),
+        (
+            "transfer Default Rainy Day Fund 5 03/02/2024",
+            "Invalid date",
+        ),
+    ] {
+        commands::handle_command(args, &mut app, &mut db).unwrap();
+        assert!(
+            app.status.text.starts_with(error),
+            "{args}: {}",
+            app.status.text
+        );
+    }
+}
+
+// ── Investments ───────────────────────────────────────────────
+
+#[test]
+fn test_buy_logs_position_in_investment_account() {
+    let (mut app, mut db) = setup();
+    commands::handle_command("buy VTI 10 15", &mut app, &mut db).unwrap();
+    assert_eq!(app.status.level, crate::ui::app::StatusLevel::Error);
+
+    let brokerage = Account::new("Brokerage".into(), AccountType::Investment, String::new());
+    let id = db.insert_account(&brokerage).unwrap();
+    app.transaction_filter_account = Some(id);
+    app.refresh_accounts(&db).unwrap();
+
+    commands::handle_command("buy vti 10 $15", &mut app, &mut db).unwrap();
+    assert_eq!(
+        app.status.text,
+        "Bought 10 VTI at $15.00 ($150.00) in Brokerage"
+    );
+    let buy = &app.transactions[0];
+    assert_eq!(buy.amount, dec!(-150.00));
+    assert!(buy.is_transfer);
+    assert_eq!(buy.quantity, Some(dec!(10)));
+    assert_eq!(db.get_invested_total(id).unwrap(), dec!(150.00));
+
+    // Kept out of spending
+    app.current_month = None;
+    app.refresh_dashboard(&db).unwrap();
+    assert_eq!(app.monthly_expenses, dec!(0));
+}
+
+// ── Transaction detail ────────────────────────────────────────
+
+#[test]
+fn test_enter_opens_transaction_detail() {
+    let (mut app, mut db) = setup();
+    app.screen = Screen::Transactions;
+    press(&mut app, &mut db, KeyCode::Enter);
+    assert!(!app.show_transaction_detail);
+
+    let savings = Account::new("Savings".into(), AccountType::Savings, String::new());
+    db.insert_account(&savings).unwrap();
+    app.refresh_accounts(&db).unwrap();
+    commands::handle_command("transfer Default Savings 5 2024-02-03", &mut app, &mut db).unwrap();
+    app.current_month = None;
+    app.transaction_filter_account = app.accounts[0].id;
+    app.refresh_transactions(&db).unwrap();
+
+    press(&mut app, &mut db, KeyCode::Enter);
+    assert!(app.show_transaction_detail);
+    let details = app.transaction_details().unwrap();
+    let field = |name: &str| {
+        details
+            .iter()
+            .find(|(label, _)| *label == name)
+            .map(|(_, v)| v.as_str())
+            .unwrap()
+    };
+    assert_eq!(field("Description"), "Transfer to Savings");
+    assert_eq!(field("Account"), "Default");
+    assert_eq!(field("Transfer"), "yes");
+    assert_eq!(field("Source"), "Added by hand");
+    assert_ne!(field("Added"), "—");
+    assert!(field("Import hash").starts_with("manual-transfer-"));
+}
+
+// ── Budget start day ──────────────────────────────────────────
+
+#[test]
+fn test_set_budget_start_day_shifts_the_viewed_month() {
+    let (mut app, mut db) = setup();
+    for date in ["2024-01-20", "2024-02-10"] {
+        let txn = Transaction {
+            date: date.into(),
+            import_hash: format!("hash-{date}"),
+            ..import_row(&app, "SHOP")
+        };
+        db.insert_transaction(&txn).unwrap();
+    }
+    commands::set_month(&mut app, &mut db, Some("2024-01".into())).unwrap();
+    assert_eq!(app.monthly_expenses, dec!(-5.25));
+
+    run_command(&mut app, &mut db, "set budget-start-day 15");
+    assert_eq!(
+        app.status.text,
+        "budget-start-day set to 15: each month runs from the 15th to the 14th of the next"
+    );
+    assert_eq!(app.monthly_expenses, dec!(-10.50));
+    assert_eq!(db.load_ui_state().unwrap().budget_start_day, Some(15));
+
+    run_command(&mut app, &mut db, "set budget-start-day 29");
+    assert_eq!(
+        app.status.text,
+        "budget-start-day must be a number from 1 to 28"
+    );
+    assert_eq!(app.budget_start_day, 15);
+}
+
+// ── Database location ─────────────────────────────────────────
+
+#[test]
+fn test_where_shows_the_database_path() {
+    let (mut app, mut db) = setup();
+    app.db_path = std::path::PathBuf::from("/data/budgetui.db");
+    run_command(&mut app, &mut db, "where");
+    assert_eq!(app.status.text, "Database: /data/budgetui.db");
+}
+
+// ── Goto ──────────────────────────────────────────────────────
+
+#[test]
+fn test_goto_jumps_to_a_row_in_the_current_list() {
+    let (mut app, mut db) = setup();
+    for i in 0..30 {
+        db.insert_transaction(&Transaction {
+            import_hash: format!("goto-{i}"),
+            ..import_row(&app, &format!("SHOP {i}"))
+        })
+        .unwrap();
+    }
+    app.screen = Screen::Transactions;
+    app.current_month = None;
+    app.refresh_transactions(&db).unwrap();
+
+    run_command(&mut app, &mut db, "goto 20");
+    assert_eq!(app.transaction_index, 19);
+    assert_eq!(app.status.text, "Row 20 of 30");
+    assert!(app.transaction_scroll <= 19);
+
+    run_command(&mut app, &mut db, "goto 999");
+    assert_eq!(app.transaction_index, 29);
+    assert_eq!(app.status.text, "Row 30 of 30 (the last)");
+
+    run_command(&mut app, &mut db, "goto 0");
+    assert_eq!(app.transaction_index, 29);
+    assert!(app.status.text.starts_with("Usage: :goto"));
+
+    // Dispatches on the screen: categories move their own cursor
+    app.screen = Screen::Categories;
+    run_command(&mut app, &mut db, "goto 3");
+    assert_eq!(app.category_index, 2);
+    assert_eq!(app.transaction_index, 29);
+}
+
+// ── Flagged transactions ──────────────────────────────────────
+
+#[test]
+fn test_flag_jump_and_filter_flagged_rows() {
+    let (mut app, mut db) = setup();
+    for name in ["A", "B", "C", "D"] {
+        db.insert_transaction(&import_row(&app, name)).unwrap();
+    }
+    app.screen = Screen::Transactions;
+    app.current_month = None;
+    app.refresh_transactions(&db).unwrap();
+    // Newest first: D, C, B, A
+    press(&mut app, &mut db, KeyCode::Char('j'));
+    press(&mut app, &mut db, KeyCode::Char('f'));
+    assert!(app.transactions[1].flagged);
+    assert!(app.status.text.starts_with("Flagged for review"));
+    press(&mut app, &mut db, KeyCode::Char('G'));
+    press(&mut app, &mut db, KeyCode::Char('f'));
+
+    press(&mut app, &mut db, KeyCode::Char(']'));
+    assert_eq!(app.transactions[app.transaction_index].description, "C");
+    press(&mut app, &mut db, KeyCode::Char(']'));
+    assert_eq!(app.transactions[app.transaction_index].description, "A");
+    press(&mut app, &mut db, KeyCode::Char('['));
+    assert_eq!(app.transactions[app.transaction_index].description, "C");
+
+    run_command(&mut app, &mut db, "flagged");
+    let shown: Vec<&str> = app
+        .transactions
+        .iter()
+        .map(|t| t.description.as_str())
+        .collect();
+    assert_eq!(shown, ["C", "A"]);
+    assert_eq!(
+        app.status.text,
+        "2 flagged transactions — f to clear a flag, Esc to show all"
+    );
+
+    press(&mut app, &mut db, KeyCode::Char('f'));
+    assert_eq!(app.transactions.len(), 1);
+    assert_eq!(app.status.text, "Flag cleared");
+
+    press(&mut app, &mut db, KeyCode::Esc);
+    assert!(!app.transaction_filter_flagged);
+    app.refresh_transactions(&db).unwrap();
+    assert_eq!(app.transactions.len(), 4);
+}
+
+// ── Dashboard scope ───────────────────────────────────────────
+
+#[test]
+fn test_dashboard_scopes_to_the_active_account() {
+    let (mut app, mut db) = setup();
+    let card = Account::new("Visa".into(), AccountType::CreditCard, String::new());
+    let card_id = db.insert_account(&card).unwrap();
+    db.insert_transaction(&import_row(&app, "GROCER")).unwrap();
+    db.insert_transaction(&Transaction {
+        account_id: card_id,
+        amount: dec!(-40),
+        ..import_row(&app, "SHOES")
+    })
+    .unwrap();
+    app.current_month = Some("2024-01".into());
+    app.screen = Screen::Dashboard;
+    app.refresh_all(&db).unwrap();
+    assert_eq!(app.monthly_expenses, dec!(-45.25));
+
+    press(&mut app, &mut db, KeyCode::Char('A'));
+    assert_eq!(app.ui_state().dashboard_scoped, Some(true));
+    assert_eq!(
+        app.status.text,
+        "Dashboard: Default only — n/p to switch account, A for all accounts"
+    );
+    assert_eq!(app.monthly_expenses, dec!(-5.25));
+
+    press(&mut app, &mut db, KeyCode::Char('n'));
+    assert_eq!(app.dashboard_account().and_then(|a| a.id), Some(card_id));
+    assert_eq!(app.monthly_expenses, dec!(-40));
+    assert_eq!(app.credit_charges, dec!(-40));
+    assert_eq!(app.debit_expenses, dec!(0));
+    assert_eq!(app.net_worth, dec!(-40));
+    assert_eq!(app.dashboard_spending().len(), 1);
+    // Budgets keep reading spending across every account
+    let total: rust_decimal::Decimal = app.spending_by_category.iter().map(|(_, a)| *a).sum();
+    assert_eq!(total, dec!(-45.25));
+
+    press(&mut app, &mut db, KeyCode::Char('A'));
+    assert!(app.dashboard_account().is_none());
+    assert_eq!(app.monthly_expenses, dec!(-45.25));
+    assert_eq!(
+        app.status.text,
+        "Dashboard: all accounts — A to show only the active account"
+    );
+}
+
+#[test]
+fn test_scoped_dashboard_leaves_out_transfer_legs() {
+    let (mut app, mut db) = setup();
+    db.insert_transaction(&import_row(&app, "GROCER")).unwrap();
+    db.insert_transaction(&Transaction {
+        amount: dec!(-100),
+        is_transfer: true,
+        ..import_row(&app, "TO SAVINGS")
+    })
+    .unwrap();
+    app.current_month = Some("2024-01".into());
+    app.screen = Screen::Dashboard;
+    app.refresh_all(&db).unwrap();
+    assert_eq!(app.monthly_expenses, dec!(-5.25));
+
+    // Scoped to the same account, the totals agree with the combined view
+    press(&mut app, &mut db, KeyCode::Char('A'));
+    assert!(app.dashboard_account().is_some());
+    assert_eq!(app.monthly_expenses, dec!(-5.25));
+    assert_eq!(app.monthly_income, dec!(0));
+}
+
+// ── Cloning accounts ──────────────────────────────────────────
+
+#[test]
+fn test_clone_account_copies_settings_only() {
+    let (mut app, mut db) = setup();
+    let mut visa = crate::models::Account::new(
+        "Chase Visa".into(),
+        crate::models::AccountType::CreditCard,
+        "Chase".into(),
+    );
+    visa.currency = "EUR".into();
+    visa.notes = "autopay on the 3rd".into();
+    visa.opening_balance = dec!(-250);
+    let visa_id = db.insert_account(&visa).unwrap();
+    db.insert_transaction(&Transaction {
+        account_id: visa_id,
+        ..import_row(&app, "COFFEE")
+    })
+    .unwrap();
+
+    commands::handle_command("clone-account chase visa Amex Gold", &mut app, &mut db).unwrap();
+    assert_eq!(
+        app.status.text,
+        "Created account: Amex Gold (Credit Card, EUR) from Chase Visa"
+    );
+    let accounts = db.get_accounts(true).unwrap();
+    let amex = accounts.iter().find(|a| a.name == "Amex Gold").unwrap();
+    assert_eq!(amex.account_type, crate::models::AccountType::CreditCard);
+    assert_eq!(amex.institution, "Chase");
+    assert_eq!(amex.currency, "EUR");
+    assert_eq!(amex.notes, "autopay on the 3rd");
+    assert_eq!(amex.opening_balance, dec!(0));
+    assert_eq!(db.get_account_balance(amex.id.unwrap()).unwrap(), dec!(0));
+
+    commands::handle_command("clone-account Discover It", &mut app, &mut db).unwrap();
+    assert!(app
+        .status
+        .text
+        .starts_with("Account not found. Available: "));
+    assert!(app.status.text.contains("Chase Visa"));
+
+    commands::handle_command("clone-account Chase Visa amex gold", &mut app, &mut db).unwrap();
+    assert_eq!(app.status.text, "Account 'amex gold' already exists");
+    commands::handle_command("clone-account Chase Visa", &mut app, &mut db).unwrap();
+    assert_eq!(
+        app.status.text,
+        "Usage: :clone-account <existing name> <new name>"
+    );
+}
@@ -1,11 +1,19 @@
-use std::collections::HashSet;
-use std::path::PathBuf;
-
-use anyhow::Result;
-
-use crate::db::Database;
-use crate::import::{CsvImporter, CsvProfile};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::Datelike;
+
+use crate::db::{CategoryFilter, Database, TransactionQuery};
+use crate::import::{
+    account_name_from_path, import_extension, is_generic_headers, parse_amount, read_text_file,
+    signs_look_flipped, CsvImporter, CsvProfile, QifImporter, SkippedRow, DATE_FORMATS,
+};
 use crate::models::*;
+use crate::ui::screens::budgets::{budget_progress, BudgetStatus};
+use crate::ui::screens::dashboard::{spending_pace, SpendingPace};
+use crate::ui::util::{fuzzy_match, scroll_to, SearchQuery};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Screen {
@@ -28,18 +36,30 @@ impl Screen {
             Self::Budgets,
         ]
     }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dashboard => "Dashboard",
+            Self::Accounts => "Accounts",
+            Self::Transactions => "Transactions",
+            Self::Import => "Import",
+            Self::Categories => "Categories",
+            Self::Budgets => "Budgets",
+        }
+    }
+
+    /// Inverse of `as_str`; `None` for anything unrecognized.
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|screen| screen.as_str() == s)
+    }
 }
 
 impl std::fmt::Display for Screen {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Dashboard => write!(f, "Dashboard"),
-            Self::Accounts => write!(f, "Accounts"),
-            Self::Transactions => write!(f, "Transactions"),
-            Self::Import => write!(f, "Import"),
-            Self::Categories => write!(f, "Categories"),
-            Self::Budgets => write!(f, "Budgets"),
-        }
+        write!(f, "{}", self.as_str())
     }
 }
 
@@ -64,6 +84,47 @@ impl std::fmt::Display for InputMode {
     }
 }
 
+/// How long an info message stays in the command bar.
+pub(crate) const STATUS_TIMEOUT: Duration = Duration::from_secs(4);
+/// Warnings stay up longer so there's time to read them.
+pub(crate) const WARNING_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StatusLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// The message in the command bar. Info and warnings clear themselves after
+/// a few seconds; errors stay until the next key press.
+#[derive(Debug, Clone)]
+pub(crate) struct StatusMessage {
+    pub(crate) text: String,
+    pub(crate) level: StatusLevel,
+    pub(crate) set_at: Instant,
+}
+
+impl StatusMessage {
+    fn new(text: String, level: StatusLevel) -> Self {
+        Self {
+            text,
+            level,
+            set_at: Instant::now(),
+        }
+    }
+
+    /// Whether the message has been up long enough to clear at `now`.
+    pub(crate) fn expired(&self, now: Instant) -> bool {
+        let timeout = match self.level {
+            StatusLevel::Info => STATUS_TIMEOUT,
+            StatusLevel::Warning => WARNING_TIMEOUT,
+            StatusLevel::Error => return false,
+        };
+        now.saturating_duration_since(self.set_at) >= timeout
+    }
+}
+
 /// Pending action that requires user confirmation.
 #[derive(Debug, Clone)]
 pub(crate) enum PendingAction {
@@ -71,7 +132,49 @@ pub(crate) enum PendingAction {
     DeleteTransactions { ids: Vec<i64>, count: usize },
     DeleteBudget { id: i64, name: String },
     DeleteRule { id: i64, pattern: String },
+    PruneRules { before: String },
+    DeleteAccount { id: i64, name: String, force: bool },
+    DeleteCategory { id: i64, name: String },
     ImportCommit,
+    WipeAllData,
+    Quit,
+}
+
+/// What the quit prompt took over, put back when the user declines to quit.
+#[derive(Debug, Clone)]
+pub(crate) struct InterruptedPrompt {
+    pub(crate) input_mode: InputMode,
+    pub(crate) pending_action: Option<PendingAction>,
+    pub(crate) confirm_message: String,
+    pub(crate) command_input: String,
+}
+
+/// Phrase that must be typed in full to confirm wiping all data.
+pub(crate) const WIPE_CONFIRM_PHRASE: &str = "wipe everything";
+
+/// Fields of the new-transaction form, in Tab order.
+pub(crate) const TXN_FORM_FIELDS: [&str; 5] =
+    ["Date", "Description", "Amount", "Category", "Account"];
+
+/// CSV rows shown under the column mapper.
+pub(crate) const SAMPLE_ROWS: usize = 5;
+
+/// Height of the category picker opened under the transactions table.
+pub(crate) const RECAT_PICKER_HEIGHT: u16 = 12;
+/// A substring search with fewer hits than this is retried as a fuzzy search.
+const FUZZY_FALLBACK_HITS: usize = 3;
+/// Transactions scored when fuzzy ranking.
+const FUZZY_CANDIDATES: u32 = 2000;
+
+impl PendingAction {
+    /// Actions that are too destructive for a single `y` keypress return the
+    /// phrase the user must type instead.
+    pub(crate) fn confirm_phrase(&self) -> Option<&'static str> {
+        match self {
+            Self::WipeAllData => Some(WIPE_CONFIRM_PHRASE),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -97,12 +200,32 @@ impl std::fmt::Display for ImportStep {
     }
 }
 
+/// What the last committed import did, listed on the Done step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ImportReport {
+    /// Rows in the preview, probable duplicates included.
+    pub(crate) parsed: usize,
+    /// Rows left out because their date didn't parse.
+    pub(crate) bad_dates: usize,
+    pub(crate) inserted: usize,
+    /// Probable duplicates skipped plus rows already imported before.
+    pub(crate) duplicates: usize,
+    /// Rows given a category by import rules.
+    pub(crate) auto_categorized: usize,
+    /// Rows committed without a category.
+    pub(crate) uncategorized: usize,
+    /// Earliest and latest transaction dates committed.
+    pub(crate) date_range: Option<(String, String)>,
+}
+
 /// Per-account snapshot for the Accounts tab.
 pub(crate) struct AccountSnapshot {
     pub(crate) account: Account,
     pub(crate) month_income: rust_decimal::Decimal,
     pub(crate) month_expenses: rust_decimal::Decimal,
     pub(crate) balance: rust_decimal::Decimal,
+    /// Cost of positions bought with `:buy`, for investment accounts.
+    pub(crate) invested: Option<rust_decimal::Decimal>,
 }
 
 pub(crate) struct App {
@@ -110,18 +233,57 @@ pub(crate) struct App {
     pub(crate) screen: Screen,
     pub(crate) input_mode: InputMode,
     pub(crate) command_input: String,
+    /// Commands run from the command bar, oldest first.
+    pub(crate) command_history: Vec<String>,
+    /// Position in `command_history` while recalling with Up/Down.
+    pub(crate) command_history_index: Option<usize>,
+    /// The line being typed before history recall replaced it.
+    pub(crate) command_draft: String,
+    /// Full command lines Tab is cycling through.
+    pub(crate) command_completions: Vec<String>,
+    /// Position in `command_completions`, while cycling.
+    pub(crate) command_completion_index: Option<usize>,
     pub(crate) search_input: String,
-    pub(crate) status_message: String,
+    /// Search text the transaction list was fuzzy-ranked by, if any.
+    pub(crate) fuzzy_search: Option<String>,
+    /// Search text the list was filtered by as a plain substring, if any.
+    pub(crate) substring_search: Option<String>,
+    pub(crate) status: StatusMessage,
     pub(crate) show_help: bool,
     pub(crate) show_nav: bool,
     pub(crate) nav_index: usize,
+    pub(crate) show_month_picker: bool,
+    pub(crate) month_picker_year: i32,
+    /// 0 is "All time", 1-12 are January-December.
+    pub(crate) month_picker_index: usize,
+    pub(crate) show_net_worth_history: bool,
+    /// Overlay listing every stored field of the selected transaction.
+    pub(crate) show_transaction_detail: bool,
+    pub(crate) net_worth_history: Vec<(String, rust_decimal::Decimal)>,
+    /// Resolved location of the open database, shown by `:where`.
+    pub(crate) db_path: PathBuf,
     pub(crate) current_month: Option<String>,
 
-    // Dashboard — totals (all accounts)
+    /// Dashboard shows only the active account (`A` toggles) instead of
+    /// every account.
+    pub(crate) dashboard_scoped: bool,
+    // Dashboard — totals (all accounts, or the active one when scoped)
     pub(crate) monthly_income: rust_decimal::Decimal,
     pub(crate) monthly_expenses: rust_decimal::Decimal,
+    /// Daily average and month-end projection for the viewed month
+    pub(crate) spending_pace: Option<SpendingPace>,
     pub(crate) net_worth: rust_decimal::Decimal,
+    pub(crate) unconverted_balances: Vec<(String, rust_decimal::Decimal)>,
     pub(crate) spending_by_category: Vec<(String, rust_decimal::Decimal)>,
+    /// The active account's share of `spending_by_category`, filled only
+    /// while the dashboard is scoped. Budgets always use the full figures.
+    pub(crate) account_spending_by_category: Vec<(String, rust_decimal::Decimal)>,
+    /// Change in spending from the previous month, keyed by category name.
+    /// Empty when viewing all time.
+    pub(crate) spending_deltas: HashMap<String, rust_decimal::Decimal>,
+    /// Categories spent past their budget for the viewed month, by name,
+    /// with the percentage used (e.g. `"120%"`)
+    pub(crate) over_budget: HashMap<String, String>,
     pub(crate) monthly_trend: Vec<(String, rust_decimal::Decimal, rust_decimal::Decimal)>,
 
     // Dashboard — debit accounts (Checking, Savings, Cash, Investment, Other)
@@ -139,8 +301,47 @@ pub(crate) struct App {
     pub(crate) transaction_index: usize,
     pub(crate) transaction_scroll: usize,
     pub(crate) transaction_filter_account: Option<i64>,
+    /// Only rows still needing a category, in the viewed month (`u` on the
+    /// dashboard).
+    pub(crate) transaction_filter_uncategorized: bool,
+    /// Only rows flagged for review (`:flagged`), across every month.
+    pub(crate) transaction_filter_flagged: bool,
+    /// Balance after each transaction, by id. Only filled while the list is
+    /// filtered to one account; a combined balance across accounts means nothing.
+    pub(crate) running_balances: HashMap<i64, rust_decimal::Decimal>,
     pub(crate) transaction_count: i64,
+    /// Most transactions loaded into the list at once (`:set pagesize`).
+    pub(crate) page_size: u32,
+    /// `:set search-scope month` limits `/` search to `current_month`.
+    pub(crate) search_month_only: bool,
+    /// `:set clean-names on` imports merchant names instead of raw descriptions.
+    pub(crate) clean_names: bool,
+    /// `z` on Transactions puts a header row above each day's transactions.
+    pub(crate) transaction_group_by_day: bool,
+    /// `:set budget-start-day` shifts months to start on this day; mirrors
+    /// `Database::budget_start_day` for code without the database at hand.
+    pub(crate) budget_start_day: u32,
+    /// `:set confirm-quit on` asks before Ctrl-Q throws away a selection or
+    /// an open form.
+    pub(crate) confirm_quit: bool,
+    /// More transactions matched than `page_size` let into the list.
+    pub(crate) transactions_truncated: bool,
+    /// Transfers in the viewed month, left out of the dashboard totals.
+    pub(crate) transfer_count: usize,
+    /// Uncategorized spending in the viewed month and how many
+    /// transactions it spans.
+    pub(crate) uncategorized_summary: (rust_decimal::Decimal, i64),
     pub(crate) selected_transactions: HashSet<i64>,
+    pub(crate) txn_adding: bool,
+    /// Category picker open for the selected transaction. Shares the
+    /// import categorize step's `import_cat_*` selection state.
+    pub(crate) txn_recat: bool,
+    pub(crate) txn_add_field: usize, // index into TXN_FORM_FIELDS
+    pub(crate) txn_add_date: String,
+    pub(crate) txn_add_description: String,
+    pub(crate) txn_add_amount: String,
+    pub(crate) txn_add_category: usize, // 0 = none, otherwise index + 1 into categories
+    pub(crate) txn_add_account: usize,  // index into accounts
 
     // Categories
     pub(crate) categories: Vec<Category>,
@@ -150,18 +351,36 @@ pub(crate) struct App {
     pub(crate) rule_index: usize,
     pub(crate) rule_scroll: usize,
     pub(crate) category_view_rules: bool,
+    pub(crate) rule_editing: bool,
+    pub(crate) rule_edit_pattern: String,
+    pub(crate) rule_edit_regex: bool,
+    pub(crate) rule_edit_whole_word: bool,
+    pub(crate) rule_edit_category: usize, // index into categories
 
     // Accounts tab
+    /// Active accounts, offered in pickers and cycled on the dashboard.
     pub(crate) accounts: Vec<Account>,
+    pub(crate) archived_accounts: Vec<Account>,
+    /// Accounts screen lists archived accounts after the active ones.
+    pub(crate) show_archived: bool,
     pub(crate) account_index: usize,
     pub(crate) accounts_tab_index: usize,
     pub(crate) accounts_tab_scroll: usize,
     pub(crate) account_snapshots: Vec<AccountSnapshot>,
+    pub(crate) account_editing: bool,
+    pub(crate) account_edit_name: String,
+    pub(crate) account_edit_type: usize, // index into AccountType::all()
 
     // Budgets
     pub(crate) budgets: Vec<Budget>,
     pub(crate) budget_index: usize,
     pub(crate) budget_scroll: usize,
+    /// Year shown for yearly budgets, derived from `current_month`.
+    pub(crate) budget_year: String,
+    /// Amount carried in from earlier months, keyed by budget id.
+    pub(crate) budget_rollovers: HashMap<i64, rust_decimal::Decimal>,
+    /// Spend counted against each budget in `budgets`, by budget id.
+    pub(crate) budget_spent: HashMap<i64, rust_decimal::Decimal>,
 
     // Import state
     pub(crate) import_step: ImportStep,
@@ -170,9 +389,40 @@ pub(crate) struct App {
     pub(crate) import_rows: Vec<Vec<String>>,
     pub(crate) import_profile: CsvProfile,
     pub(crate) import_preview: Vec<Transaction>,
+    /// Preview rows that look like transactions already in the database.
+    pub(crate) import_duplicates: Vec<usize>,
+    /// Leave `import_duplicates` out when committing (toggled with `s`).
+    pub(crate) import_skip_duplicates: bool,
+    /// Probable duplicates dropped from this import, for the summary.
+    pub(crate) import_duplicates_skipped: usize,
+    /// Rules that categorized rows of the pending import, including ones
+    /// made in the categorize step; stamped as matched once it is stored.
+    pub(crate) import_matched_rules: Vec<i64>,
+    /// What the last commit imported, shown on the Done step.
+    pub(crate) import_summary: String,
+    /// Breakdown of the last commit; `None` after a `:categorize-last` pass.
+    pub(crate) import_report: Option<ImportReport>,
+    /// CSV rows left out of the preview because their date didn't parse
+    pub(crate) import_skipped: Vec<SkippedRow>,
+    /// Date formats added with `:add-date-format`, after the built-ins
+    pub(crate) custom_date_formats: Vec<String>,
+    pub(crate) import_preview_index: usize,
+    pub(crate) import_preview_scroll: usize,
+    /// Preview rows marked with Space, by index into `import_preview`
+    pub(crate) import_preview_selected: HashSet<usize>,
     pub(crate) import_selected_field: usize,
     pub(crate) import_account_id: Option<i64>,
     pub(crate) import_detected_bank: Option<String>,
+    /// Set once the sign convention is changed by hand, so picking a credit
+    /// account doesn't override it.
+    pub(crate) import_sign_chosen: bool,
+    /// Account whose saved column mapping was applied to the file
+    pub(crate) import_saved_account: Option<i64>,
+    /// Negate every amount in the preview, toggled with `f`
+    pub(crate) import_flip_signs: bool,
+    /// The preview looks like it has every sign backwards
+    pub(crate) import_signs_suspect: bool,
+    pub(crate) import_qif_content: Option<String>, // raw QIF text; None for CSV imports
 
     // Import account picker (SelectAccount step)
     pub(crate) import_account_index: usize,
@@ -180,6 +430,9 @@ pub(crate) struct App {
     pub(crate) import_new_account_name: String,
     pub(crate) import_new_account_type: usize, // index into AccountType::all()
     pub(crate) import_creating_account: bool,
+    /// Name the new-account form starts with, taken from the import file's
+    /// name. Empty when the name gives nothing usable.
+    pub(crate) import_suggested_account_name: String,
 
     // Interactive categorization (import wizard)
     pub(crate) import_cat_descriptions: Vec<(String, usize)>, // (description, count of matching txns)
@@ -188,6 +441,10 @@ pub(crate) struct App {
     pub(crate) import_cat_scroll: usize,                      // category list viewport scroll
     pub(crate) import_cat_new_name: String, // inline new-category input (empty = not typing)
     pub(crate) import_cat_creating: bool,   // whether we're typing a new category name
+    /// The categorize step is revisiting rows already in the database
+    /// (`:categorize-last`) rather than an import preview, so assignments
+    /// are saved as they're made and nothing is committed at the end.
+    pub(crate) import_recategorizing: bool,
 
     // File browser
     pub(crate) file_browser_path: PathBuf,
@@ -197,10 +454,16 @@ pub(crate) struct App {
     pub(crate) file_browser_filter: String,
     pub(crate) file_browser_show_hidden: bool,
     pub(crate) file_browser_input_focused: bool,
+    /// Remembered import directories that still exist, newest first.
+    pub(crate) file_browser_recent: Vec<PathBuf>,
+    /// How many recent directories are listed after `..` in the entries.
+    pub(crate) file_browser_recent_count: usize,
 
     // Confirmation
     pub(crate) pending_action: Option<PendingAction>,
     pub(crate) confirm_message: String,
+    /// Set while the quit prompt is up over an edit or another prompt.
+    pub(crate) quit_interrupted: Option<InterruptedPrompt>,
 
     // Layout (updated each render frame)
     pub(crate) visible_rows: usize,
@@ -213,17 +476,37 @@ impl App {
             screen: Screen::Dashboard,
             input_mode: InputMode::Normal,
             command_input: String::new(),
+            command_history: Vec::new(),
+            command_history_index: None,
+            command_draft: String::new(),
+            command_completions: Vec::new(),
+            command_completion_index: None,
             search_input: String::new(),
-            status_message: String::new(),
+            fuzzy_search: None,
+            substring_search: None,
+            status: StatusMessage::new(String::new(), StatusLevel::Info),
             show_help: false,
             show_nav: false,
             nav_index: 0,
+            show_month_picker: false,
+            month_picker_year: 0,
+            month_picker_index: 0,
+            show_net_worth_history: false,
+            show_transaction_detail: false,
+            net_worth_history: Vec::new(),
+            db_path: PathBuf::new(),
             current_month: None,
 
+            dashboard_scoped: false,
             monthly_income: rust_decimal::Decimal::ZERO,
             monthly_expenses: rust_decimal::Decimal::ZERO,
+            spending_pace: None,
             net_worth: rust_decimal::Decimal::ZERO,
+            unconverted_balances: Vec::new(),
             spending_by_category: Vec::new(),
+            account_spending_by_category: Vec::new(),
+            spending_deltas: HashMap::new(),
+            over_budget: HashMap::new(),
             monthly_trend: Vec::new(),
 
             debit_income: rust_decimal::Decimal::ZERO,
@@ -237,8 +520,28 @@ impl App {
             transaction_index: 0,
             transaction_scroll: 0,
             transaction_filter_account: None,
+            transaction_filter_uncategorized: false,
+            transaction_filter_flagged: false,
+            running_balances: HashMap::new(),
             transaction_count: 0,
+            page_size: DEFAULT_PAGE_SIZE,
+            budget_start_day: 1,
+            search_month_only: false,
+            clean_names: false,
+            confirm_quit: true,
+            transaction_group_by_day: false,
+            transactions_truncated: false,
+            transfer_count: 0,
+            uncategorized_summary: (rust_decimal::Decimal::ZERO, 0),
             selected_transactions: HashSet::new(),
+            txn_adding: false,
+            txn_recat: false,
+            txn_add_field: 0,
+            txn_add_date: String::new(),
+            txn_add_description: String::new(),
+            txn_add_amount: String::new(),
+            txn_add_category: 0,
+            txn_add_account: 0,
 
             categories: Vec::new(),
             category_index: 0,
@@ -247,16 +550,29 @@ impl App {
             rule_index: 0,
             rule_scroll: 0,
             category_view_rules: false,
+            rule_editing: false,
+            rule_edit_pattern: String::new(),
+            rule_edit_regex: false,
+            rule_edit_whole_word: false,
+            rule_edit_category: 0,
 
             accounts: Vec::new(),
+            archived_accounts: Vec::new(),
+            show_archived: false,
             account_index: 0,
             accounts_tab_index: 0,
             accounts_tab_scroll: 0,
             account_snapshots: Vec::new(),
+            account_editing: false,
+            account_edit_name: String::new(),
+            account_edit_type: 0,
 
             budgets: Vec::new(),
             budget_index: 0,
             budget_scroll: 0,
+            budget_year: String::new(),
+            budget_rollovers: HashMap::new(),
+            budget_spent: HashMap::new(),
 
             import_step: ImportStep::SelectFile,
             import_path: String::new(),
@@ -264,15 +580,32 @@ impl App {
             import_rows: Vec::new(),
             import_profile: CsvProfile::default(),
             import_preview: Vec::new(),
+            import_duplicates: Vec::new(),
+            import_skip_duplicates: true,
+            import_duplicates_skipped: 0,
+            import_matched_rules: Vec::new(),
+            import_summary: String::new(),
+            import_report: None,
+            import_skipped: Vec::new(),
+            custom_date_formats: Vec::new(),
+            import_preview_index: 0,
+            import_preview_scroll: 0,
+            import_preview_selected: HashSet::new(),
             import_selected_field: 0,
             import_account_id: None,
             import_detected_bank: None,
+            import_sign_chosen: false,
+            import_saved_account: None,
+            import_flip_signs: false,
+            import_signs_suspect: false,
+            import_qif_content: None,
 
             import_account_index: 0,
             import_account_scroll: 0,
             import_new_account_name: String::new(),
             import_new_account_type: 0,
             import_creating_account: false,
+            import_suggested_account_name: String::new(),
 
             import_cat_descriptions: Vec::new(),
             import_cat_index: 0,
@@ -280,6 +613,7 @@ impl App {
             import_cat_scroll: 0,
             import_cat_new_name: String::new(),
             import_cat_creating: false,
+            import_recategorizing: false,
 
             file_browser_path: directories::UserDirs::new()
                 .map(|d| d.home_dir().to_path_buf())
@@ -290,55 +624,248 @@ impl App {
             file_browser_filter: String::new(),
             file_browser_show_hidden: false,
             file_browser_input_focused: false,
+            file_browser_recent: Vec::new(),
+            file_browser_recent_count: 0,
 
             pending_action: None,
             confirm_message: String::new(),
+            quit_interrupted: None,
 
             visible_rows: 20,
         }
     }
 
+    /// Monthly budgets for `month` that spending has gone past, judged as
+    /// the Budgets screen does: subcategories and rollover included.
+    fn over_budget_categories(
+        &self,
+        db: &Database,
+        month: &str,
+    ) -> Result<HashMap<String, String>> {
+        let categories = db.get_categories()?;
+        let mut over = HashMap::new();
+        for budget in db.get_budgets(Some(month))? {
+            let name = match Category::find_by_id(&categories, budget.category_id) {
+                Some(c) => c.name.clone(),
+                None => continue,
+            };
+            let spent = db.get_budget_spent(&budget)?;
+            let rollover = if budget.rollover {
+                db.get_budget_rollover(budget.category_id, &budget.month)?
+            } else {
+                rust_decimal::Decimal::ZERO
+            };
+            let progress = budget_progress(spent, budget.limit_amount + rollover);
+            if progress.status == BudgetStatus::Over {
+                over.insert(name, progress.label());
+            }
+        }
+        Ok(over)
+    }
+
+    /// The account the dashboard is limited to, when it's scoped.
+    pub(crate) fn dashboard_account(&self) -> Option<&Account> {
+        if self.dashboard_scoped {
+            self.accounts.get(self.account_index)
+        } else {
+            None
+        }
+    }
+
+    /// Currency of the dashboard's figures: the scoped account's, otherwise
+    /// the base currency.
+    pub(crate) fn dashboard_currency(&self) -> &str {
+        self.dashboard_account()
+            .map(|a| a.currency.as_str())
+            .unwrap_or(BASE_CURRENCY)
+    }
+
+    /// Spending by category as the dashboard shows it: the active account's
+    /// when scoped, otherwise every account's.
+    pub(crate) fn dashboard_spending(&self) -> &[(String, rust_decimal::Decimal)] {
+        if self.dashboard_account().is_some() {
+            &self.account_spending_by_category
+        } else {
+            &self.spending_by_category
+        }
+    }
+
     pub(crate) fn refresh_dashboard(&mut self, db: &Database) -> Result<()> {
         let month = self.current_month.as_deref();
-        let (income, expenses) = db.get_monthly_totals(month)?;
+        let scope = self
+            .dashboard_account()
+            .and_then(|a| a.id.map(|id| (id, a.account_type.is_credit())));
+        let scope_id = scope.map(|(id, _)| id);
+        let (income, expenses) = match scope_id {
+            Some(id) => db.get_account_monthly_totals(id, month)?,
+            None => db.get_monthly_totals(month)?,
+        };
         self.monthly_income = income;
         self.monthly_expenses = expenses;
-        self.net_worth = db.get_net_worth()?;
+        let today = chrono::Local::now().date_naive();
+        self.spending_pace =
+            month.and_then(|m| spending_pace(expenses.abs(), m, today, self.budget_start_day));
+        match scope_id {
+            Some(id) => {
+                self.net_worth = db.get_account_balance(id)?;
+                self.unconverted_balances = Vec::new();
+            }
+            None => {
+                self.net_worth = db.get_net_worth()?;
+                self.unconverted_balances = db.get_unconverted_balances()?;
+            }
+        }
+        // Budgets read this, so it always covers every account
         self.spending_by_category = db.get_spending_by_category(month)?;
+        self.account_spending_by_category = match scope_id {
+            Some(id) => db.get_spending_by_account_category(id, month)?,
+            None => Vec::new(),
+        };
+        self.spending_deltas = match month {
+            Some(m) => db
+                .get_category_spending_delta(m, scope_id)?
+                .into_iter()
+                .map(|(name, _, change)| (name, change))
+                .collect(),
+            None => HashMap::new(),
+        };
+        self.over_budget = match month {
+            Some(m) => self.over_budget_categories(db, m)?,
+            None => HashMap::new(),
+        };
         self.monthly_trend = db.get_monthly_trend(12)?;
         self.transaction_count = db.get_transaction_count()?;
+        self.transfer_count = db.get_transfer_count(month, scope_id)?;
+        self.uncategorized_summary = db.get_uncategorized_summary(month, scope_id)?;
+
+        match scope {
+            // A scoped dashboard fills only the row for the account's kind
+            Some((_, is_credit)) => {
+                let balance = self.net_worth;
+                let zero = rust_decimal::Decimal::ZERO;
+                if is_credit {
+                    (self.debit_income, self.debit_expenses, self.debit_balance) =
+                        (zero, zero, zero);
+                    (
+                        self.credit_payments,
+                        self.credit_charges,
+                        self.credit_balance,
+                    ) = (income, expenses, balance);
+                } else {
+                    (self.debit_income, self.debit_expenses, self.debit_balance) =
+                        (income, expenses, balance);
+                    (
+                        self.credit_payments,
+                        self.credit_charges,
+                        self.credit_balance,
+                    ) = (zero, zero, zero);
+                }
+            }
+            None => {
+                // Debit accounts (Checking, Savings, Cash, Investment, Other)
+                let debit_types = AccountType::debit_type_strs();
+                let (di, de) = db.get_monthly_totals_by_account_type(month, debit_types)?;
+                self.debit_income = di;
+                self.debit_expenses = de;
+                self.debit_balance = db.get_balance_by_account_type(debit_types)?;
+
+                // Credit accounts (CreditCard, Loan)
+                let credit_types = AccountType::credit_type_strs();
+                let (cp, cc) = db.get_monthly_totals_by_account_type(month, credit_types)?;
+                self.credit_payments = cp; // positive = payments made to card
+                self.credit_charges = cc; // negative = charges/purchases
+                self.credit_balance = db.get_balance_by_account_type(credit_types)?;
+            }
+        }
 
-        // Debit accounts (Checking, Savings, Cash, Investment, Other)
-        let debit_types = AccountType::debit_type_strs();
-        let (di, de) = db.get_monthly_totals_by_account_type(month, debit_types)?;
-        self.debit_income = di;
-        self.debit_expenses = de;
-        self.debit_balance = db.get_balance_by_account_type(debit_types)?;
+        self.refresh_budget_spent(db)?;
+        Ok(())
+    }
 
-        // Credit accounts (CreditCard, Loan)
-        let credit_types = AccountType::credit_type_strs();
-        let (cp, cc) = db.get_monthly_totals_by_account_type(month, credit_types)?;
-        self.credit_payments = cp; // positive = payments made to card
-        self.credit_charges = cc; // negative = charges/purchases
-        self.credit_balance = db.get_balance_by_account_type(credit_types)?;
+    /// The month the list is limited to: the viewed month when reviewing
+    /// uncategorized rows or when the search scope is "month" and a search
+    /// is active, otherwise none.
+    pub(crate) fn list_month(&self) -> Option<&str> {
+        if self.transaction_filter_uncategorized
+            || (self.search_month_only && !self.search_input.is_empty())
+        {
+            self.current_month.as_deref()
+        } else {
+            None
+        }
+    }
 
-        Ok(())
+    /// What a search covers, for the search bar and list title.
+    pub(crate) fn search_scope_label(&self) -> String {
+        match self.current_month.as_deref() {
+            Some(month) if self.search_month_only || self.transaction_filter_uncategorized => {
+                month.to_string()
+            }
+            _ => "all months".into(),
+        }
     }
 
     pub(crate) fn refresh_transactions(&mut self, db: &Database) -> Result<()> {
-        let search = if self.search_input.is_empty() {
-            None
+        let query = SearchQuery::parse(&self.search_input);
+        let month = self.list_month().map(str::to_string);
+        let category = self
+            .transaction_filter_uncategorized
+            .then_some(CategoryFilter::Uncategorized);
+        let fetch = |limit: u32, text: Option<&str>| -> Result<Vec<Transaction>> {
+            db.get_transactions(&TransactionQuery {
+                limit: Some(limit),
+                account_id: self.transaction_filter_account,
+                category,
+                search: text,
+                month: month.as_deref(),
+                min_amount: query.min_amount,
+                max_amount: query.max_amount,
+                min_exclusive: query.min_exclusive,
+                max_exclusive: query.max_exclusive,
+                date_from: query.date_from.as_deref(),
+                date_to: query.date_to.as_deref(),
+                tags: &query.tags,
+                flagged_only: self.transaction_filter_flagged,
+                ..Default::default()
+            })
+        };
+
+        let page_size = self.page_size as usize;
+        self.fuzzy_search = None;
+        self.substring_search = query.text.clone().filter(|_| !query.fuzzy);
+        // One extra row tells whether the page cut anything off
+        self.transactions = if query.fuzzy {
+            Vec::new()
         } else {
-            Some(self.search_input.as_str())
+            fetch(self.page_size + 1, query.text.as_deref())?
+        };
+        self.transactions_truncated = self.transactions.len() > page_size;
+        self.transactions.truncate(page_size);
+
+        // Fall back to fuzzy ranking when the substring search comes up
+        // short. The other predicates still narrow the candidates in SQL.
+        if let Some(text) = query.text.as_deref() {
+            if query.fuzzy || self.transactions.len() < FUZZY_FALLBACK_HITS {
+                let candidates = FUZZY_CANDIDATES.max(self.page_size);
+                let mut ranked: Vec<(i64, Transaction)> = fetch(candidates, None)?
+                    .into_iter()
+                    .filter_map(|t| fuzzy_match(text, &t.description).map(|m| (m.score, t)))
+                    .collect();
+                // Stable sort keeps newest-first among equal scores
+                ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+                if query.fuzzy || ranked.len() > self.transactions.len() {
+                    self.transactions_truncated = ranked.len() > page_size;
+                    self.transactions =
+                        ranked.into_iter().take(page_size).map(|(_, t)| t).collect();
+                    self.fuzzy_search = Some(text.to_string());
+                    self.substring_search = None;
+                }
+            }
+        }
+        self.running_balances = match self.transaction_filter_account {
+            Some(account_id) => db.get_running_balances(account_id)?,
+            None => HashMap::new(),
         };
-        self.transactions = db.get_transactions(
-            Some(200),
-            None,
-            self.transaction_filter_account,
-            None,
-            search,
-            None,
-        )?;
         self.transaction_count = db.get_transaction_count()?;
         if self.transaction_index >= self.transactions.len() && !self.transactions.is_empty() {
             self.transaction_index = self.transactions.len() - 1;
@@ -353,34 +880,144 @@ impl App {
     }
 
     pub(crate) fn refresh_budgets(&mut self, db: &Database) -> Result<()> {
+        if let Some(month) = self.current_month.as_deref() {
+            if let Some((from, count)) = db.fill_budgets_from_previous_month(month)? {
+                self.set_status(format!("Copied {count} budgets from {from}"));
+            }
+        }
         self.budgets = db.get_budgets(self.current_month.as_deref())?;
+        self.budget_year = self.budget_year_for_month();
+        if self.current_month.is_some() {
+            // Yearly budgets for the viewed month's year sit below the monthly ones
+            self.budgets
+                .extend(db.get_budgets(Some(&self.budget_year))?);
+        }
+        self.budget_rollovers.clear();
+        for budget in &self.budgets {
+            if let (Some(id), true) = (budget.id, budget.rollover) {
+                let carried = db.get_budget_rollover(budget.category_id, &budget.month)?;
+                self.budget_rollovers.insert(id, carried);
+            }
+        }
+        self.refresh_budget_spent(db)
+    }
+
+    /// Recompute `budget_spent` for the loaded budgets. Spending changes
+    /// whenever the dashboard does, so both refreshes call this.
+    fn refresh_budget_spent(&mut self, db: &Database) -> Result<()> {
+        self.budget_spent.clear();
+        for budget in &self.budgets {
+            if let Some(id) = budget.id {
+                self.budget_spent.insert(id, db.get_budget_spent(budget)?);
+            }
+        }
         Ok(())
     }
 
+    /// Year (`YYYY`) of the viewed month, or the current year for "All Time".
+    pub(crate) fn budget_year_for_month(&self) -> String {
+        self.current_month
+            .as_deref()
+            .and_then(|m| m.get(..4))
+            .map(str::to_string)
+            .unwrap_or_else(|| chrono::Local::now().format("%Y").to_string())
+    }
+
     pub(crate) fn refresh_accounts(&mut self, db: &Database) -> Result<()> {
-        self.accounts = db.get_accounts()?;
+        let (archived, active) = db.get_accounts(true)?.into_iter().partition(|a| a.archived);
+        self.accounts = active;
+        self.archived_accounts = archived;
         Ok(())
     }
 
     pub(crate) fn refresh_accounts_tab(&mut self, db: &Database) -> Result<()> {
-        self.accounts = db.get_accounts()?;
+        self.refresh_accounts(db)?;
         let month = self.current_month.as_deref();
-        let mut snapshots = Vec::with_capacity(self.accounts.len());
-        for account in &self.accounts {
+        let archived: &[Account] = if self.show_archived {
+            &self.archived_accounts
+        } else {
+            &[]
+        };
+        let mut snapshots = Vec::with_capacity(self.accounts.len() + archived.len());
+        for account in self.accounts.iter().chain(archived) {
             let aid = account.id.unwrap_or(0);
             let (income, expenses) = db.get_account_monthly_totals(aid, month)?;
             let balance = db.get_account_balance(aid)?;
+            let invested = if account.account_type == AccountType::Investment {
+                Some(db.get_invested_total(aid)?)
+            } else {
+                None
+            };
             snapshots.push(AccountSnapshot {
                 account: account.clone(),
                 month_income: income,
                 month_expenses: expenses,
                 balance,
+                invested,
             });
         }
         self.account_snapshots = snapshots;
         Ok(())
     }
 
+    /// Snapshot of the screen, month, and active account to save on quit.
+    pub(crate) fn ui_state(&self) -> UiState {
+        UiState {
+            screen: Some(self.screen.as_str().to_string()),
+            month: Some(self.current_month.clone()),
+            account_index: Some(self.account_index),
+            page_size: Some(self.page_size),
+            search_month_only: Some(self.search_month_only),
+            clean_names: Some(self.clean_names),
+            group_by_day: Some(self.transaction_group_by_day),
+            dashboard_scoped: Some(self.dashboard_scoped),
+            budget_start_day: Some(self.budget_start_day),
+            confirm_quit: Some(self.confirm_quit),
+        }
+    }
+
+    /// Apply state saved by a previous session. The import wizard can't be
+    /// resumed, so it falls back to the Dashboard. Call before `refresh_all`.
+    pub(crate) fn restore_ui_state(&mut self, state: UiState) {
+        if let Some(screen) = state.screen.as_deref().and_then(Screen::parse) {
+            if screen != Screen::Import {
+                self.screen = screen;
+            }
+        }
+        if let Some(month) = state.month {
+            self.current_month = month;
+        }
+        if let Some(index) = state.account_index {
+            self.account_index = index;
+        }
+        if let Some(size) = state.page_size {
+            self.page_size = size;
+        }
+        if let Some(month_only) = state.search_month_only {
+            self.search_month_only = month_only;
+        }
+        if let Some(clean) = state.clean_names {
+            self.clean_names = clean;
+        }
+        if let Some(group) = state.group_by_day {
+            self.transaction_group_by_day = group;
+        }
+        if let Some(scoped) = state.dashboard_scoped {
+            self.dashboard_scoped = scoped;
+        }
+        if let Some(day) = state.budget_start_day {
+            self.budget_start_day = day;
+        }
+        if let Some(confirm) = state.confirm_quit {
+            self.confirm_quit = confirm;
+        }
+    }
+
+    /// Label of the budget month today falls in.
+    pub(crate) fn this_month(&self) -> String {
+        budget_month_of(chrono::Local::now().date_naive(), self.budget_start_day)
+    }
+
     pub(crate) fn refresh_all(&mut self, db: &Database) -> Result<()> {
         self.refresh_dashboard(db)?;
         self.refresh_transactions(db)?;
@@ -391,46 +1028,237 @@ impl App {
         Ok(())
     }
 
-    pub(crate) fn load_import_file(&mut self) -> Result<()> {
+    pub(crate) fn load_import_file(&mut self, db: &Database) -> Result<()> {
         let path = std::path::Path::new(&self.import_path);
-        let (headers, rows) = CsvImporter::preview(path)?;
+        self.import_flip_signs = false;
+        self.import_saved_account = None;
+        self.import_suggested_account_name = account_name_from_path(path).unwrap_or_default();
+        if import_extension(path).as_deref() == Some("qif") {
+            return self.load_qif_file();
+        }
+
+        self.import_qif_content = None;
+        self.import_sign_chosen = false;
+        let delimiter = CsvImporter::detect_delimiter(path)?;
+        let (mut headers, mut rows) = CsvImporter::preview_with_delimiter(path, delimiter)?;
 
         // Try to auto-detect bank format
         let first_row = rows.first().cloned().unwrap_or_default();
         if let Some(profile) = crate::import::detect_bank_format(&headers, &first_row) {
             self.import_detected_bank = Some(profile.name.clone());
             self.import_profile = profile;
+        } else {
+            // A number where the amount should be means the file starts with data
+            let file_first_row = if is_generic_headers(&headers) {
+                &first_row
+            } else {
+                &headers
+            };
+            self.import_profile.has_header =
+                CsvImporter::looks_like_header(file_first_row, self.import_profile.amount_column);
+        }
+        self.import_profile.delimiter = delimiter;
+        CsvImporter::apply_header(&mut headers, &mut rows, self.import_profile.has_header);
+
+        // A file laid out like one imported before reuses that mapping
+        if let Some(saved) = db.find_account_profile(&headers)? {
+            self.import_profile = saved.profile;
+            self.import_flip_signs = saved.flip_signs;
+            self.import_sign_chosen = true;
+            self.import_saved_account = Some(saved.account_id);
         }
 
         self.import_headers = headers;
         self.import_rows = rows;
         self.import_step = ImportStep::MapColumns;
-        self.status_message = if let Some(ref bank) = self.import_detected_bank {
+        let saved_into = self
+            .import_saved_account
+            .and_then(|id| self.find_account(id))
+            .map(|a| a.name.clone());
+        let status = if let Some(name) = saved_into {
+            format!("Using the column mapping from your last import into {name} — Esc to re-map")
+        } else if let Some(ref bank) = self.import_detected_bank {
             format!("Detected format: {bank}")
         } else {
             "Custom CSV - map columns manually".into()
         };
+        self.set_status(status);
 
         Ok(())
     }
 
-    pub(crate) fn generate_import_preview(&mut self) -> Result<()> {
+    /// Re-read the CSV after the delimiter is changed in the column mapper.
+    pub(crate) fn reload_import_rows(&mut self) -> Result<()> {
+        let (headers, rows) = CsvImporter::preview_with_delimiter(
+            Path::new(&self.import_path),
+            self.import_profile.delimiter,
+        )?;
+        self.import_headers = headers;
+        self.import_rows = rows;
+        self.apply_import_header();
+        Ok(())
+    }
+
+    /// Split the file's first row off as the header, or fold it back into
+    /// the data, to match `has_header` in the column mapper.
+    pub(crate) fn apply_import_header(&mut self) {
+        CsvImporter::apply_header(
+            &mut self.import_headers,
+            &mut self.import_rows,
+            self.import_profile.has_header,
+        );
+    }
+
+    /// QIF files carry their own field layout, so there is nothing to map:
+    /// parse once to surface errors early, then go straight to account selection.
+    fn load_qif_file(&mut self) -> Result<()> {
+        let content = read_text_file(Path::new(&self.import_path))
+            .with_context(|| format!("Failed to read QIF file: {}", self.import_path))?;
+        let txns = QifImporter::parse(
+            &content,
+            &self.import_profile.date_format,
+            0,
+            &self.categories,
+        )?;
+        if txns.is_empty() {
+            anyhow::bail!("QIF file contains no transactions");
+        }
+
+        self.import_qif_content = Some(content);
+        self.import_detected_bank = None;
+        self.import_headers.clear();
+        self.import_rows.clear();
+        self.import_profile.is_credit_account = false;
+        self.import_step = ImportStep::SelectAccount;
+        self.set_status(format!("QIF file with {} transactions", txns.len()));
+        Ok(())
+    }
+
+    pub(crate) fn generate_import_preview(&mut self, db: &Database) -> Result<()> {
         let account_id = self.import_account_id.unwrap_or(1);
-        self.import_preview =
-            CsvImporter::parse(&self.import_rows, &self.import_profile, account_id)?;
+        (self.import_preview, self.import_skipped) = match &self.import_qif_content {
+            Some(content) => (
+                QifImporter::parse(
+                    content,
+                    &self.import_profile.date_format,
+                    account_id,
+                    &self.categories,
+                )?,
+                Vec::new(),
+            ),
+            None => CsvImporter::parse(&self.import_rows, &self.import_profile, account_id)?,
+        };
+        if self.import_flip_signs {
+            for txn in &mut self.import_preview {
+                txn.amount = -txn.amount;
+            }
+        }
+        if self.clean_names {
+            crate::categorize::clean_descriptions(&mut self.import_preview);
+        }
+        self.import_signs_suspect = self
+            .accounts
+            .iter()
+            .find(|a| a.id == Some(account_id))
+            .is_some_and(|a| signs_look_flipped(&self.import_preview, &a.account_type));
+        self.import_duplicates = db.find_probable_duplicates(&self.import_preview)?;
+        self.import_preview_index = 0;
+        self.import_preview_scroll = 0;
+        self.import_preview_selected.clear();
+        self.import_skip_duplicates = true;
+        self.import_duplicates_skipped = 0;
         self.import_step = ImportStep::Preview;
-        self.status_message = format!("{} transactions ready to import", self.import_preview.len());
+        let status = match self.import_duplicates.len() {
+            0 => format!("{} transactions ready to import", self.import_preview.len()),
+            dupes => format!(
+                "{} transactions ready to import, {dupes} look like duplicates (s to keep them)",
+                self.import_preview.len()
+            ),
+        };
+        if self.import_signs_suspect {
+            self.set_warning(format!(
+                "{status}, but most amounts are money in — f to flip every sign"
+            ));
+        } else if self.import_skipped.is_empty() {
+            self.set_status(status);
+        } else {
+            self.set_warning(format!(
+                "{status}, {} rows skipped (unparseable date)",
+                self.import_skipped.len()
+            ));
+        }
         Ok(())
     }
 
+    /// Preview rows a bulk action applies to: the Space-selected rows, or
+    /// the row under the cursor when nothing is selected.
+    pub(crate) fn import_preview_targets(&self) -> Vec<usize> {
+        if self.import_preview_selected.is_empty() {
+            if self.import_preview_index < self.import_preview.len() {
+                vec![self.import_preview_index]
+            } else {
+                Vec::new()
+            }
+        } else {
+            let mut targets: Vec<usize> = self.import_preview_selected.iter().copied().collect();
+            targets.sort_unstable();
+            targets
+        }
+    }
+
+    /// Mark the target preview rows as transfers, or unmark them if they
+    /// all are already. Returns whether they are now transfers.
+    pub(crate) fn toggle_import_preview_transfer(&mut self) -> bool {
+        let targets = self.import_preview_targets();
+        let make_transfer = targets.iter().any(|&i| !self.import_preview[i].is_transfer);
+        for i in targets {
+            self.import_preview[i].is_transfer = make_transfer;
+        }
+        make_transfer
+    }
+
+    /// Drop the flagged probable duplicates from the preview if skipping is on.
+    pub(crate) fn remove_skipped_duplicates(&mut self) {
+        if self.import_skip_duplicates && !self.import_duplicates.is_empty() {
+            let flagged: HashSet<usize> = self.import_duplicates.drain(..).collect();
+            self.import_duplicates_skipped += flagged.len();
+            let mut index = 0;
+            self.import_preview.retain(|_| {
+                let keep = !flagged.contains(&index);
+                index += 1;
+                keep
+            });
+        }
+    }
+
+    /// Rows that will be written when the import is committed.
+    pub(crate) fn import_commit_count(&self) -> usize {
+        if self.import_skip_duplicates {
+            self.import_preview.len() - self.import_duplicates.len()
+        } else {
+            self.import_preview.len()
+        }
+    }
+
     pub(crate) fn refresh_file_browser(&mut self) {
         let mut entries: Vec<PathBuf> = Vec::new();
 
         // Add parent directory
-        if let Some(parent) = self.file_browser_path.parent() {
-            entries.push(parent.to_path_buf());
+        let parent = self.file_browser_path.parent().map(Path::to_path_buf);
+        if let Some(ref parent) = parent {
+            entries.push(parent.clone());
         }
 
+        // Recent import directories, except ones already reachable as . or ..
+        let recent: Vec<PathBuf> = self
+            .file_browser_recent
+            .iter()
+            .filter(|p| **p != self.file_browser_path && Some(*p) != parent.as_ref())
+            .cloned()
+            .collect();
+        self.file_browser_recent_count = recent.len();
+        entries.extend(recent);
+
         if let Ok(read_dir) = std::fs::read_dir(&self.file_browser_path) {
             let is_hidden = |p: &PathBuf| {
                 p.file_name()
@@ -444,9 +1272,8 @@ impl App {
                 .filter(|p| {
                     (self.file_browser_show_hidden || !is_hidden(p))
                         && (p.is_dir()
-                            || p.extension().and_then(|e| e.to_str()).is_some_and(|ext| {
-                                matches!(ext.to_ascii_lowercase().as_str(), "csv" | "tsv")
-                            }))
+                            || import_extension(p)
+                                .is_some_and(|ext| matches!(ext.as_str(), "csv" | "tsv" | "qif")))
                 })
                 .collect();
 
@@ -466,8 +1293,152 @@ impl App {
         self.file_browser_input_focused = false;
     }
 
+    /// Whether entry `i` is one of the recent directories listed after `..`.
+    pub(crate) fn is_recent_entry(&self, i: usize) -> bool {
+        let start = usize::from(self.file_browser_path.parent().is_some());
+        (start..start + self.file_browser_recent_count).contains(&i)
+    }
+
+    /// Formats the column mapper cycles through: built-ins, then custom.
+    pub(crate) fn date_formats(&self) -> Vec<&str> {
+        DATE_FORMATS
+            .iter()
+            .copied()
+            .chain(self.custom_date_formats.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// The first data row's date as the column mapper would read it with the
+    /// selected format alone (no fallbacks): `Some(Ok(date))`, `Some(Err(raw))`
+    /// when it doesn't parse, or `None` without a non-empty date cell.
+    pub(crate) fn sample_date_parse(&self) -> Option<std::result::Result<String, String>> {
+        let profile = &self.import_profile;
+        let raw = self
+            .import_rows
+            .iter()
+            .skip(profile.skip_rows)
+            .filter_map(|row| row.get(profile.date_column))
+            .map(|s| s.trim())
+            .find(|s| !s.is_empty())?;
+        Some(
+            chrono::NaiveDate::parse_from_str(raw, &profile.date_format)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .map_err(|_| raw.to_string()),
+        )
+    }
+
+    /// Amounts of the column mapper's sample rows as the import would read
+    /// them, including a pending sign flip. `None` where a cell doesn't parse.
+    pub(crate) fn sample_amounts(&self) -> Vec<Option<rust_decimal::Decimal>> {
+        self.import_rows
+            .iter()
+            .take(SAMPLE_ROWS)
+            .map(|row| {
+                parse_amount(row, &self.import_profile).ok().map(|a| {
+                    if self.import_flip_signs {
+                        -a
+                    } else {
+                        a
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// One line naming the source column behind each field, as the mapper
+    /// labels them (`[4] Amount`). `None` for QIF, which has no columns.
+    pub(crate) fn import_mapping_summary(&self) -> Option<String> {
+        if self.import_qif_content.is_some() {
+            return None;
+        }
+        let column = |i: usize| match self.import_headers.get(i) {
+            Some(h) => format!("[{i}] {h}"),
+            None => format!("[{i}]"),
+        };
+        let p = &self.import_profile;
+        let mut parts = vec![
+            format!("Date ← {}", column(p.date_column)),
+            format!("Description ← {}", column(p.description_column)),
+        ];
+        if let Some(i) = p.amount_column {
+            parts.push(format!("Amount ← {}", column(i)));
+        }
+        if let Some(i) = p.debit_column {
+            parts.push(format!("Debit ← {}", column(i)));
+        }
+        if let Some(i) = p.credit_column {
+            parts.push(format!("Credit ← {}", column(i)));
+        }
+        let signs = if self.import_flip_signs {
+            format!("Signs: {}, flipped", p.sign_convention.label())
+        } else {
+            format!("Signs: {}", p.sign_convention.label())
+        };
+        parts.push(signs);
+        Some(parts.join("  "))
+    }
+
+    /// Recall the previous command into the command bar, saving the line
+    /// being typed the first time so it can be brought back.
+    pub(crate) fn command_history_prev(&mut self) {
+        let index = match self.command_history_index {
+            Some(0) => return,
+            Some(i) => i - 1,
+            None if self.command_history.is_empty() => return,
+            None => {
+                self.command_draft = self.command_input.clone();
+                self.command_history.len() - 1
+            }
+        };
+        self.command_history_index = Some(index);
+        self.command_input = self.command_history[index].clone();
+    }
+
+    /// Step forward through the command history, ending back at the line
+    /// that was being typed.
+    pub(crate) fn command_history_next(&mut self) {
+        if let Some(i) = self.command_history_index {
+            if i + 1 < self.command_history.len() {
+                self.command_history_index = Some(i + 1);
+                self.command_input = self.command_history[i + 1].clone();
+            } else {
+                self.restore_command_draft();
+            }
+        }
+    }
+
+    /// Stop recalling history and put back the line that was being typed.
+    pub(crate) fn restore_command_draft(&mut self) {
+        if self.command_history_index.take().is_some() {
+            self.command_input = std::mem::take(&mut self.command_draft);
+        }
+    }
+
+    /// Add a command to the history unless it repeats the last one.
+    /// Returns false if nothing was added.
+    pub(crate) fn record_command(&mut self, input: &str) -> bool {
+        self.command_history_index = None;
+        self.command_draft.clear();
+        let input = input.trim();
+        if input.is_empty() || self.command_history.last().is_some_and(|c| c == input) {
+            return false;
+        }
+        self.command_history.push(input.to_string());
+        true
+    }
+
+    /// Keep the remembered import directories that still exist.
+    pub(crate) fn set_recent_dirs(&mut self, dirs: Vec<String>) {
+        self.file_browser_recent = dirs
+            .into_iter()
+            .map(PathBuf::from)
+            .filter(|p| p.is_dir())
+            .collect();
+    }
+
     /// Returns filtered file browser entries (indices into `file_browser_entries`).
-    /// When filter is empty, returns all. The `..` entry always passes.
+    /// When filter is empty, returns all. The `..` entry always passes; recent
+    /// directories only show while the filter is empty.
     pub(crate) fn file_browser_filtered(&self) -> Vec<usize> {
         if self.file_browser_filter.is_empty() {
             return (0..self.file_browser_entries.len()).collect();
@@ -476,7 +1447,10 @@ impl App {
         self.file_browser_entries
             .iter()
             .enumerate()
-            .filter(|(_, path)| {
+            .filter(|(i, path)| {
+                if self.is_recent_entry(*i) {
+                    return false;
+                }
                 // Parent (..) always passes
                 if Some(path.as_path()) == self.file_browser_path.parent() {
                     return true;
@@ -558,6 +1532,20 @@ impl App {
         self.visible_rows.saturating_sub(6).max(1)
     }
 
+    /// Effective visible rows for the import preview table. Step indicator
+    /// (1) + table borders and header (3), plus the skipped-rows panel.
+    pub(crate) fn import_preview_page(&self) -> usize {
+        let skipped = match self.import_skipped.len() {
+            0 => 0,
+            n => n.min(5) + 2,
+        };
+        let warning = usize::from(self.import_signs_suspect);
+        let mapping = usize::from(self.import_qif_content.is_none());
+        self.visible_rows
+            .saturating_sub(4 + skipped + warning + mapping)
+            .max(1)
+    }
+
     /// Effective visible rows for the category list (borders = 2).
     pub(crate) fn category_page(&self) -> usize {
         self.visible_rows.saturating_sub(2).max(1)
@@ -591,8 +1579,180 @@ impl App {
         self.visible_rows.saturating_sub(8).max(1)
     }
 
+    /// Open the month picker on the viewed month, or "All time" in this
+    /// year's grid when no month is set.
+    pub(crate) fn open_month_picker(&mut self) {
+        let viewed = self
+            .current_month
+            .as_deref()
+            .and_then(|m| chrono::NaiveDate::parse_from_str(&format!("{m}-01"), "%Y-%m-%d").ok());
+        match viewed {
+            Some(date) => {
+                self.month_picker_year = date.year();
+                self.month_picker_index = date.month() as usize;
+            }
+            None => {
+                self.month_picker_year = chrono::Local::now().year();
+                self.month_picker_index = 0;
+            }
+        }
+        self.show_month_picker = true;
+    }
+
+    /// Edits that haven't reached the database yet: a selection waiting on
+    /// a batch action, an open form, picker or prompt, or an import
+    /// preview that hasn't been committed.
+    pub(crate) fn has_pending_edits(&self) -> bool {
+        !self.selected_transactions.is_empty()
+            || self.txn_adding
+            || self.txn_recat
+            || self.account_editing
+            || self.rule_editing
+            || matches!(self.input_mode, InputMode::Editing | InputMode::Confirm)
+            || (self.screen == Screen::Import
+                && self.import_step == ImportStep::Preview
+                && !self.import_preview.is_empty())
+    }
+
+    /// Open the new-transaction form dated today, on the account the list is
+    /// filtered to or else the active dashboard account.
+    pub(crate) fn open_add_transaction(&mut self) {
+        self.txn_add_field = 0;
+        self.txn_add_date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        self.txn_add_description.clear();
+        self.txn_add_amount.clear();
+        self.txn_add_category = 0;
+        self.txn_add_account = self
+            .transaction_filter_account
+            .and_then(|id| self.accounts.iter().position(|a| a.id == Some(id)))
+            .unwrap_or(self.account_index);
+        self.txn_adding = true;
+    }
+
+    /// Open the category picker on the selected transaction, starting at
+    /// its current category.
+    pub(crate) fn open_recat_picker(&mut self) {
+        let current = self
+            .transactions
+            .get(self.transaction_index)
+            .and_then(|t| t.category_id)
+            .and_then(|id| self.categories.iter().position(|c| c.id == Some(id)))
+            .unwrap_or(0);
+        let page = self.recat_visible_rows();
+        self.import_cat_scroll = 0;
+        scroll_to(
+            &mut self.import_cat_selected,
+            &mut self.import_cat_scroll,
+            current,
+            page,
+        );
+        self.import_cat_creating = false;
+        self.import_cat_new_name.clear();
+        self.txn_recat = true;
+    }
+
+    /// Labelled fields of the selected transaction for the detail overlay,
+    /// including the ones the table leaves out.
+    pub(crate) fn transaction_details(&self) -> Option<Vec<(&'static str, String)>> {
+        let txn = self.transactions.get(self.transaction_index)?;
+        let or_dash = |s: &str| {
+            if s.is_empty() {
+                "—".to_string()
+            } else {
+                s.to_string()
+            }
+        };
+        let account = self
+            .find_account(txn.account_id)
+            .map(|a| a.name.clone())
+            .unwrap_or_else(|| format!("#{}", txn.account_id));
+        let category = txn
+            .category_id
+            .and_then(|id| Category::find_by_id(&self.categories, id))
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| "—".into());
+        let added = match chrono::DateTime::parse_from_rfc3339(&txn.created_at) {
+            Ok(t) => t
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            Err(_) => or_dash(&txn.created_at),
+        };
+        let source = if txn.import_hash.starts_with("manual-") {
+            "Added by hand"
+        } else {
+            "Imported"
+        };
+        Some(vec![
+            ("Date", txn.date.clone()),
+            ("Description", txn.description.clone()),
+            ("Original", or_dash(&txn.original_description)),
+            (
+                "Amount",
+                crate::ui::util::format_amount(txn.amount, self.account_currency(txn.account_id)),
+            ),
+            ("Account", account),
+            ("Category", category),
+            (
+                "Transfer",
+                if txn.is_transfer { "yes" } else { "no" }.into(),
+            ),
+            ("Notes", or_dash(&txn.notes)),
+            ("Tags", or_dash(&txn.tags.join(", "))),
+            ("Source", source.into()),
+            ("Added", added),
+            ("Import hash", or_dash(&txn.import_hash)),
+        ])
+    }
+
+    /// Categories visible at once in the transactions category picker.
+    pub(crate) fn recat_visible_rows(&self) -> usize {
+        usize::from(RECAT_PICKER_HEIGHT.saturating_sub(2))
+    }
+
+    /// Any account by id, archived or not.
+    pub(crate) fn find_account(&self, account_id: i64) -> Option<&Account> {
+        self.accounts
+            .iter()
+            .chain(&self.archived_accounts)
+            .find(|a| a.id == Some(account_id))
+    }
+
+    /// Currency of the given account, or the base currency if it's unknown.
+    pub(crate) fn account_currency(&self, account_id: i64) -> &str {
+        self.find_account(account_id)
+            .map(|a| a.currency.as_str())
+            .unwrap_or(BASE_CURRENCY)
+    }
+
     pub(crate) fn set_status(&mut self, msg: impl Into<String>) {
-        self.status_message = msg.into();
+        self.status = StatusMessage::new(msg.into(), StatusLevel::Info);
+    }
+
+    pub(crate) fn set_warning(&mut self, msg: impl Into<String>) {
+        self.status = StatusMessage::new(msg.into(), StatusLevel::Warning);
+    }
+
+    pub(crate) fn set_error(&mut self, msg: impl Into<String>) {
+        self.status = StatusMessage::new(msg.into(), StatusLevel::Error);
+    }
+
+    pub(crate) fn clear_status(&mut self) {
+        self.status.text.clear();
+    }
+
+    /// Clear an info or warning message that has been up long enough.
+    pub(crate) fn expire_status(&mut self, now: Instant) {
+        if self.status.expired(now) {
+            self.clear_status();
+        }
+    }
+
+    /// Errors stay up until the user does something next.
+    pub(crate) fn dismiss_error(&mut self) {
+        if self.status.level == StatusLevel::Error {
+            self.clear_status();
+        }
     }
 
     pub(crate) fn clear_selections(&mut self) {
@@ -4,9 +4,19 @@ use std::sync::LazyLock;
 use rust_decimal::Decimal;
 use std::str::FromStr;
 
-use super::app::{App, InputMode, PendingAction, Screen};
-use crate::db::Database;
-use crate::models::{Account, AccountType, Budget, Category, ImportRule};
+use super::app::{App, ImportStep, InputMode, PendingAction, Screen, WIPE_CONFIRM_PHRASE};
+use super::util::{format_amount, format_decimal, scroll_to};
+use crate::db::{Database, ExportColumn};
+use crate::models::{
+    Account, AccountType, Budget, BudgetPeriod, Category, ExchangeRate, ImportRule, RuleSource,
+    Transaction, UiState, BASE_CURRENCY, BUDGET_START_DAY_RANGE, PAGE_SIZE_RANGE, UNCATEGORIZED,
+};
+
+/// Months without a match after which `:prune-rules` counts a rule unused.
+const PRUNE_RULES_MONTHS: u32 = 6;
+
+/// Months `:budget <category>` averages over to suggest an amount.
+const BUDGET_SUGGESTION_MONTHS: usize = 3;
 
 pub(crate) struct Command {
     pub(crate) description: &'static str,
@@ -42,8 +52,26 @@ pub(crate) static COMMANDS: LazyLock<HashMap<&str, Command>> = LazyLock::new(||
     register_command!("budgets", "Go to Budgets", cmd_budgets, r);
     register_command!("help", "Show available commands", cmd_help, r);
     register_command!("h", "Show available commands", cmd_help, r);
+    register_command!(
+        "goto",
+        "Jump to a row of the current list (e.g. :goto 120)",
+        cmd_goto,
+        r
+    );
     register_command!("month", "Set month (e.g. :month 2024-01)", cmd_month, r);
     register_command!("m", "Set month (e.g. :m 2024-01)", cmd_month, r);
+    register_command!(
+        "set",
+        "Change a setting (e.g. :set pagesize 500)",
+        cmd_set,
+        r
+    );
+    register_command!(
+        "add-date-format",
+        "Add a date format to the import column mapper (e.g. :add-date-format %d.%m.%Y)",
+        cmd_add_date_format,
+        r
+    );
     register_command!(
         "account",
         "Create account (e.g. :account Chase Checking)",
@@ -62,12 +90,30 @@ pub(crate) static COMMANDS: LazyLock<HashMap<&str, Command>> = LazyLock::new(||
         cmd_rule,
         r
     );
+    register_command!(
+        "memorize",
+        "Add a rule from the selected transaction's merchant and category",
+        cmd_memorize,
+        r
+    );
     register_command!(
         "r",
         "Add categorization rule (e.g. :r amazon Shopping)",
         cmd_rule,
         r
     );
+    register_command!(
+        "rule!",
+        "Add rule and apply it to uncategorized transactions",
+        cmd_rule_apply,
+        r
+    );
+    register_command!(
+        "r!",
+        "Add rule and apply it to uncategorized transactions",
+        cmd_rule_apply,
+        r
+    );
     register_command!(
         "search",
         "Search transactions (e.g. :search coffee)",
@@ -75,39 +121,201 @@ pub(crate) static COMMANDS: LazyLock<HashMap<&str, Command>> = LazyLock::new(||
         r
     );
     register_command!("s", "Search transactions (e.g. :s coffee)", cmd_search, r);
+    register_command!(
+        "uncategorized",
+        "Toggle showing only transactions that need a category",
+        cmd_uncategorized,
+        r
+    );
+    register_command!(
+        "flagged",
+        "Toggle showing only transactions flagged for review",
+        cmd_flagged,
+        r
+    );
+    register_command!(
+        "categorize-last",
+        "Categorize what the last import left uncategorized",
+        cmd_categorize_last,
+        r
+    );
     register_command!(
         "budget",
-        "Set budget (e.g. :budget Food & Dining 500)",
+        "Set budget (e.g. :budget Food & Dining 500), or suggest one from recent spending",
         cmd_budget,
         r
     );
+    register_command!(
+        "budget-year",
+        "Set yearly budget (e.g. :budget-year Groceries 6000)",
+        cmd_budget_year,
+        r
+    );
     register_command!(
         "delete-budget",
         "Delete selected budget",
         cmd_delete_budget,
         r
     );
+    register_command!(
+        "copy-budgets",
+        "Copy a month's budgets into this one (e.g. :copy-budgets 2024-02 [--force])",
+        cmd_copy_budgets,
+        r
+    );
+    register_command!(
+        "budget-subcats",
+        "Toggle counting subcategory spend in selected budget",
+        cmd_budget_subcats,
+        r
+    );
+    register_command!(
+        "budget-rollover",
+        "Carry unspent budget into next month (e.g. :budget-rollover on)",
+        cmd_budget_rollover,
+        r
+    );
     register_command!(
         "category",
         "Create category (e.g. :category Subscriptions)",
         cmd_category,
         r
     );
+    register_command!(
+        "set-icon",
+        "Show an icon before the selected category (e.g. :set-icon 🍔, empty to clear)",
+        cmd_set_icon,
+        r
+    );
+    register_command!(
+        "set-color",
+        "Color the selected category's dashboard bar (e.g. :set-color #ff8800)",
+        cmd_set_color,
+        r
+    );
+    register_command!(
+        "delete-category",
+        "Delete selected category (transactions become uncategorized)",
+        cmd_delete_category,
+        r
+    );
+    register_command!(
+        "category-parent",
+        "Set parent of selected category (e.g. :category-parent Housing)",
+        cmd_category_parent,
+        r
+    );
     register_command!(
         "delete-rule",
         "Delete selected import rule",
         cmd_delete_rule,
         r
     );
+    register_command!(
+        "prune-rules",
+        "Delete rules unmatched in N months (e.g. :prune-rules 12, default 6)",
+        cmd_prune_rules,
+        r
+    );
     register_command!(
         "regex-rule",
         "Add regex rule (e.g. :regex-rule ^AMZ.* Shopping)",
         cmd_regex_rule,
         r
     );
+    register_command!(
+        "regex-rule!",
+        "Add regex rule and apply it to uncategorized transactions",
+        cmd_regex_rule_apply,
+        r
+    );
     register_command!("rename", "Rename selected transaction", cmd_rename, r);
     register_command!("recat", "Re-categorize selected transaction", cmd_recat, r);
+    register_command!(
+        "transfer",
+        "Mark selected transaction as a transfer, or move money (e.g. :transfer Checking Savings 500)",
+        cmd_transfer,
+        r
+    );
+    register_command!(
+        "why",
+        "Show which rule categorizes selected transaction",
+        cmd_why,
+        r
+    );
+    register_command!(
+        "setamount",
+        "Set amount of selected transaction (e.g. :setamount -42.10)",
+        cmd_setamount,
+        r
+    );
+    register_command!(
+        "setdate",
+        "Set date of selected transaction (e.g. :setdate 2024-01-15)",
+        cmd_setdate,
+        r
+    );
+    register_command!(
+        "tag",
+        "Tag selected transaction (e.g. :tag reimbursable)",
+        cmd_tag,
+        r
+    );
+    register_command!(
+        "untag",
+        "Remove a tag from selected transaction",
+        cmd_untag,
+        r
+    );
     register_command!("accounts", "Go to Accounts", cmd_accounts, r);
+    register_command!(
+        "clone-account",
+        "Create an account with another's settings (e.g. :clone-account Chase Visa Amex)",
+        cmd_clone_account,
+        r
+    );
+    register_command!(
+        "opening-balance",
+        "Set opening balance of selected account (e.g. :opening-balance 2000)",
+        cmd_opening_balance,
+        r
+    );
+    register_command!(
+        "delete-account",
+        "Delete selected account (:delete-account force to include its transactions)",
+        cmd_delete_account,
+        r
+    );
+    register_command!(
+        "archive",
+        "Hide selected account from pickers, keeping its history",
+        cmd_archive,
+        r
+    );
+    register_command!(
+        "unarchive",
+        "Restore selected archived account",
+        cmd_unarchive,
+        r
+    );
+    register_command!(
+        "rate",
+        "Set exchange rate (e.g. :rate EUR USD 1.08)",
+        cmd_rate,
+        r
+    );
+    register_command!(
+        "account-currency",
+        "Set currency of selected account (e.g. :account-currency EUR)",
+        cmd_account_currency,
+        r
+    );
+    register_command!(
+        "buy",
+        "Log an investment purchase (e.g. :buy VTI 10 215.40)",
+        cmd_buy,
+        r
+    );
     register_command!(
         "add-txn",
         "Add manual transaction (e.g. :add-txn 2024-01-15 Coffee -4.50)",
@@ -122,7 +330,7 @@ pub(crate) static COMMANDS: LazyLock<HashMap<&str, Command>> = LazyLock::new(||
     );
     register_command!(
         "export",
-        "Export transactions to CSV (e.g. :export ~/budget.csv)",
+        "Export transactions to CSV, or QIF for a .qif path (e.g. :export ~/budget.qif)",
         cmd_export,
         r
     );
@@ -141,6 +349,19 @@ pub(crate) static COMMANDS: LazyLock<HashMap<&str, Command>> = LazyLock::new(||
     register_command!("next-month", "Go to next month", cmd_next_month, r);
     register_command!("prev-month", "Go to previous month", cmd_prev_month, r);
     register_command!("nav", "Open screen navigator", cmd_nav, r);
+    register_command!(
+        "networth",
+        "Show month-end net worth history (e.g. :networth 24)",
+        cmd_networth,
+        r
+    );
+    register_command!("where", "Show where the database file lives", cmd_where, r);
+    register_command!(
+        "wipe",
+        "Delete ALL data and start over (typed confirmation)",
+        cmd_wipe,
+        r
+    );
     register_command!(
         "delete-selected",
         "Delete all selected transactions",
@@ -162,7 +383,7 @@ pub(crate) fn handle_command(input: &str, app: &mut App, db: &mut Database) -> a
     } else {
         // Try fuzzy match
         let suggestion = find_closest(cmd_name);
-        app.set_status(format!(
+        app.set_error(format!(
             "Unknown command: :{cmd_name}. Did you mean :{suggestion}?"
         ));
     }
@@ -170,6 +391,41 @@ pub(crate) fn handle_command(input: &str, app: &mut App, db: &mut Database) -> a
     Ok(())
 }
 
+/// What Tab can complete `input` to: the stem kept as typed and the
+/// sorted candidates that can follow it. The first word completes against
+/// command names; commands that take a category or account name complete
+/// their argument against the names in the database.
+pub(crate) fn completions(input: &str, db: &Database) -> anyhow::Result<(String, Vec<String>)> {
+    let input = input.trim_start();
+    let (cmd, arg) = match input.split_once(' ') {
+        Some(split) => split,
+        None => {
+            let mut names: Vec<String> = COMMANDS
+                .keys()
+                .filter(|k| k.starts_with(input))
+                .map(|k| k.to_string())
+                .collect();
+            names.sort();
+            return Ok((String::new(), names));
+        }
+    };
+    let names: Vec<String> = match cmd {
+        "recat" | "category-parent" | "budget" | "budget-year" => {
+            db.get_categories()?.into_iter().map(|c| c.name).collect()
+        }
+        "filter-account" | "fa" => db.get_accounts(true)?.into_iter().map(|a| a.name).collect(),
+        _ => Vec::new(),
+    };
+    let typed = arg.trim_start().to_lowercase();
+    let mut names: Vec<String> = names
+        .into_iter()
+        .filter(|n| n.to_lowercase().starts_with(&typed))
+        .collect();
+    names.sort_by_key(|n| n.to_lowercase());
+    names.dedup();
+    Ok((format!("{cmd} "), names))
+}
+
 fn find_closest(input: &str) -> String {
     COMMANDS
         .keys()
@@ -217,7 +473,8 @@ fn cmd_transactions(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Re
 
 fn cmd_import(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::Result<()> {
     app.screen = Screen::Import;
-    app.import_step = super::app::ImportStep::SelectFile;
+    app.import_step = ImportStep::SelectFile;
+    app.import_recategorizing = false;
     app.refresh_file_browser();
     Ok(())
 }
@@ -248,13 +505,110 @@ fn cmd_nav(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::Result<()>
     Ok(())
 }
 
+fn cmd_where(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::Result<()> {
+    app.set_status(format!("Database: {}", app.db_path.display()));
+    Ok(())
+}
+
+/// Move the cursor of the screen's list to a 1-based row, clamped to the
+/// list, scrolling just enough to show it.
+fn cmd_goto(args: &str, app: &mut App, _db: &mut Database) -> anyhow::Result<()> {
+    let row = match args.trim().parse::<usize>() {
+        Ok(n) if n > 0 => n,
+        _ => {
+            app.set_error("Usage: :goto <row> (e.g. :goto 120)");
+            return Ok(());
+        }
+    };
+
+    let (index, scroll, len, page) = match app.screen {
+        Screen::Accounts => {
+            let page = app.accounts_page();
+            (
+                &mut app.accounts_tab_index,
+                &mut app.accounts_tab_scroll,
+                app.account_snapshots.len(),
+                page,
+            )
+        }
+        Screen::Transactions => {
+            let page = app.transaction_page();
+            (
+                &mut app.transaction_index,
+                &mut app.transaction_scroll,
+                app.transactions.len(),
+                page,
+            )
+        }
+        Screen::Categories if app.category_view_rules => {
+            let page = app.rule_page();
+            (
+                &mut app.rule_index,
+                &mut app.rule_scroll,
+                app.import_rules.len(),
+                page,
+            )
+        }
+        Screen::Categories => {
+            let page = app.category_page();
+            (
+                &mut app.category_index,
+                &mut app.category_scroll,
+                app.categories.len(),
+                page,
+            )
+        }
+        Screen::Budgets => {
+            let page = app.budget_page();
+            (
+                &mut app.budget_index,
+                &mut app.budget_scroll,
+                app.budgets.len(),
+                page,
+            )
+        }
+        Screen::Dashboard | Screen::Import => {
+            app.set_status("No list to jump in here. Use :goto on Accounts, Transactions, Categories or Budgets");
+            return Ok(());
+        }
+    };
+    if len == 0 {
+        app.set_status("The list is empty");
+        return Ok(());
+    }
+
+    let target = row.min(len);
+    scroll_to(index, scroll, target - 1, page);
+    app.set_status(if target < row {
+        format!("Row {target} of {len} (the last)")
+    } else {
+        format!("Row {target} of {len}")
+    });
+    Ok(())
+}
+
+fn cmd_networth(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let months = if args.is_empty() {
+        12
+    } else {
+        match args.parse::<usize>() {
+            Ok(n) if (1..=120).contains(&n) => n,
+            _ => {
+                app.set_error("Usage: :networth [months] (1-120, default 12)");
+                return Ok(());
+            }
+        }
+    };
+
+    app.net_worth_history = db.get_net_worth_history(months)?;
+    app.show_net_worth_history = true;
+    Ok(())
+}
+
 fn cmd_month(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
     if args.is_empty() {
         // No args → reset to all-time
-        app.current_month = None;
-        app.refresh_dashboard(db)?;
-        app.refresh_budgets(db)?;
-        app.refresh_accounts_tab(db)?;
+        set_month(app, db, None)?;
         app.set_status("Showing all time");
         return Ok(());
     }
@@ -274,21 +628,182 @@ fn cmd_month(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()>
     if chrono::NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d").is_ok() {
         let m = month[..7].to_string();
         app.set_status(format!("Switched to month: {m}"));
-        app.current_month = Some(m);
-        app.refresh_dashboard(db)?;
-        app.refresh_budgets(db)?;
-        app.refresh_accounts_tab(db)?;
+        set_month(app, db, Some(m))?;
     } else {
-        app.set_status("Invalid month format. Use YYYY-MM (e.g. 2024-01)");
+        app.set_error("Invalid month format. Use YYYY-MM (e.g. 2024-01)");
+    }
+
+    Ok(())
+}
+
+fn cmd_set(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let mut parts = args.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("pagesize"), None) => {
+            app.set_status(format!(
+                "pagesize is {} (:set pagesize <{}-{}>)",
+                app.page_size,
+                PAGE_SIZE_RANGE.start(),
+                PAGE_SIZE_RANGE.end()
+            ));
+        }
+        (Some("pagesize"), Some(value)) => match value.parse::<u32>() {
+            Ok(size) if PAGE_SIZE_RANGE.contains(&size) => {
+                app.page_size = size;
+                db.save_ui_state(&UiState {
+                    page_size: Some(size),
+                    ..UiState::default()
+                })?;
+                app.refresh_transactions(db)?;
+                app.set_status(format!("pagesize set to {size}"));
+            }
+            _ => app.set_status(format!(
+                "pagesize must be a number from {} to {}",
+                PAGE_SIZE_RANGE.start(),
+                PAGE_SIZE_RANGE.end()
+            )),
+        },
+        (Some("search-scope"), None) => {
+            let scope = if app.search_month_only {
+                "month"
+            } else {
+                "all"
+            };
+            app.set_status(format!(
+                "search-scope is {scope} (:set search-scope month|all)"
+            ));
+        }
+        (Some("search-scope"), Some(value @ ("month" | "all"))) => {
+            app.search_month_only = value == "month";
+            db.save_ui_state(&UiState {
+                search_month_only: Some(app.search_month_only),
+                ..UiState::default()
+            })?;
+            app.refresh_transactions(db)?;
+            app.set_status(format!(
+                "search-scope set to {value}: / searches {}",
+                app.search_scope_label()
+            ));
+        }
+        (Some("search-scope"), Some(_)) => {
+            app.set_status("search-scope must be month or all");
+        }
+        (Some("clean-names"), None) => {
+            let state = if app.clean_names { "on" } else { "off" };
+            app.set_status(format!("clean-names is {state} (:set clean-names on|off)"));
+        }
+        (Some("clean-names"), Some(value @ ("on" | "off"))) => {
+            app.clean_names = value == "on";
+            db.save_ui_state(&UiState {
+                clean_names: Some(app.clean_names),
+                ..UiState::default()
+            })?;
+            app.set_status(if app.clean_names {
+                "clean-names on: imports use the merchant name as the description"
+            } else {
+                "clean-names off: imports keep the bank's description"
+            });
+        }
+        (Some("clean-names"), Some(_)) => app.set_status("clean-names must be on or off"),
+        (Some("budget-start-day"), None) => {
+            app.set_status(format!(
+                "budget-start-day is {} (:set budget-start-day <{}-{}>)",
+                app.budget_start_day,
+                BUDGET_START_DAY_RANGE.start(),
+                BUDGET_START_DAY_RANGE.end()
+            ));
+        }
+        (Some("budget-start-day"), Some(value)) => match value.parse::<u32>() {
+            Ok(day) if BUDGET_START_DAY_RANGE.contains(&day) => {
+                app.budget_start_day = day;
+                db.save_ui_state(&UiState {
+                    budget_start_day: Some(day),
+                    ..UiState::default()
+                })?;
+                // The viewed month keeps its label but now covers new dates
+                let month = app.current_month.clone();
+                set_month(app, db, month)?;
+                app.set_status(if day == 1 {
+                    "budget-start-day set to 1: months follow the calendar".to_string()
+                } else {
+                    format!(
+                        "budget-start-day set to {day}: each month runs from the {} to the {} of the next",
+                        ordinal(day),
+                        ordinal(day - 1)
+                    )
+                });
+            }
+            _ => app.set_status(format!(
+                "budget-start-day must be a number from {} to {}",
+                BUDGET_START_DAY_RANGE.start(),
+                BUDGET_START_DAY_RANGE.end()
+            )),
+        },
+        (Some("confirm-quit"), None) => {
+            let state = if app.confirm_quit { "on" } else { "off" };
+            app.set_status(format!("confirm-quit is {state} (:set confirm-quit on|off)"));
+        }
+        (Some("confirm-quit"), Some(value @ ("on" | "off"))) => {
+            app.confirm_quit = value == "on";
+            db.save_ui_state(&UiState {
+                confirm_quit: Some(app.confirm_quit),
+                ..UiState::default()
+            })?;
+            app.set_status(if app.confirm_quit {
+                "confirm-quit on: Ctrl-Q asks first when a selection or edit would be lost"
+            } else {
+                "confirm-quit off: Ctrl-Q always quits straight away"
+            });
+        }
+        (Some("confirm-quit"), Some(_)) => app.set_status("confirm-quit must be on or off"),
+        _ => app.set_error(
+            "Usage: :set pagesize <n> | :set search-scope month|all | :set clean-names on|off | :set budget-start-day <1-28> | :set confirm-quit on|off",
+        ),
     }
+    Ok(())
+}
+
+/// `1st`, `2nd`, `3rd`, `4th`, ..., `11th`, ..., `21st`.
+fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
 
+fn cmd_add_date_format(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if args.is_empty() {
+        app.set_error("Usage: :add-date-format <strftime format> (e.g. :add-date-format %d.%m.%Y)");
+        return Ok(());
+    }
+    if !crate::import::is_valid_date_format(args) {
+        app.set_status(format!(
+            "Not a usable date format: {args} (needs a day, month, and year, e.g. %d.%m.%Y)"
+        ));
+        return Ok(());
+    }
+    if app.date_formats().contains(&args) {
+        app.set_status(format!("{args} is already in the date format list"));
+    } else {
+        db.add_custom_date_format(args)?;
+        app.custom_date_formats.push(args.to_string());
+        app.set_status(format!("Added date format {args}"));
+    }
+    // Pick it straight away when mapping columns
+    if app.screen == Screen::Import && app.import_step == ImportStep::MapColumns {
+        app.import_profile.date_format = args.to_string();
+    }
     Ok(())
 }
 
 fn cmd_account(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
     if args.is_empty() {
         let types: Vec<&str> = AccountType::all().iter().map(|t| t.as_str()).collect();
-        app.set_status(format!(
+        app.set_error(format!(
             "Usage: :account <name> [type]. Types: {}",
             types.join(", ")
         ));
@@ -323,82 +838,504 @@ fn cmd_account(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<(
     Ok(())
 }
 
-fn cmd_rule(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
-    if args.is_empty() {
-        app.set_status("Usage: :rule <pattern> <category_name>");
-        return Ok(());
-    }
+/// Create `<new name>` with the type, currency, institution, and notes of
+/// `<existing name>`. Names can contain spaces, so the longest run of leading
+/// words that names an account is the source and the rest is the new name.
+fn cmd_clone_account(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let usage = "Usage: :clone-account <existing name> <new name>";
+    let accounts = db.get_accounts(true)?;
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let find = |name: &str| {
+        accounts
+            .iter()
+            .find(|a| a.name.to_lowercase() == name.to_lowercase())
+    };
+    let split = (1..words.len())
+        .rev()
+        .find_map(|n| find(&words[..n].join(" ")).map(|a| (a, words[n..].join(" "))));
+
+    let (source, new_name) = match split {
+        Some(found) => found,
+        // Only one name given: nothing to call the new account
+        None if words.len() < 2 || find(&words.join(" ")).is_some() => {
+            app.set_error(usage);
+            return Ok(());
+        }
+        None => {
+            let names: Vec<&str> = accounts.iter().map(|a| a.name.as_str()).collect();
+            app.set_error(format!(
+                "Account not found. Available: {}",
+                names.join(", ")
+            ));
+            return Ok(());
+        }
+    };
 
-    let parts: Vec<&str> = args.rsplitn(2, ' ').collect();
-    if parts.len() < 2 {
-        app.set_status("Usage: :rule <pattern> <category_name>");
+    if accounts
+        .iter()
+        .any(|a| a.name.to_lowercase() == new_name.to_lowercase())
+    {
+        app.set_error(format!("Account '{new_name}' already exists"));
         return Ok(());
     }
 
-    let category_name = parts[0];
-    let pattern = parts[1].to_lowercase();
-
-    let categories = db.get_categories()?;
-    if let Some(cat) = Category::find_by_name(&categories, category_name) {
-        let cat_id = match cat.id {
-            Some(id) => id,
-            None => {
-                app.set_status("Category has no ID (this shouldn't happen)");
-                return Ok(());
-            }
-        };
-        let rule = ImportRule::new_contains(pattern.clone(), cat_id);
-        db.insert_import_rule(&rule)?;
-        app.refresh_categories(db)?;
-        app.set_status(format!("Added rule: '{pattern}' -> {}", cat.name));
-    } else {
-        app.set_status(format!("Category '{category_name}' not found"));
-    }
-
+    // Settings only: the opening balance and transactions stay behind
+    let account = Account {
+        currency: source.currency.clone(),
+        notes: source.notes.clone(),
+        ..Account::new(
+            new_name.clone(),
+            source.account_type.clone(),
+            source.institution.clone(),
+        )
+    };
+    db.insert_account(&account)?;
+    app.refresh_accounts(db)?;
+    app.set_status(format!(
+        "Created account: {new_name} ({}, {}) from {}",
+        account.account_type, account.currency, source.name
+    ));
     Ok(())
 }
 
-fn cmd_search(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
-    app.search_input = args.to_string();
-    app.screen = Screen::Transactions;
-    app.refresh_transactions(db)?;
+fn cmd_opening_balance(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Accounts || app.account_snapshots.is_empty() {
+        app.set_status("Navigate to Accounts and select one first");
+        return Ok(());
+    }
 
     if args.is_empty() {
-        app.set_status("Search cleared");
-    } else {
-        app.set_status(format!("Searching: {args}"));
+        app.set_error("Usage: :opening-balance <amount> (e.g. :opening-balance 2000)");
+        return Ok(());
+    }
+
+    let amount = match Decimal::from_str(args) {
+        Ok(a) => a,
+        Err(_) => {
+            app.set_error(format!("Invalid amount: {args}"));
+            return Ok(());
+        }
+    };
+
+    if let Some(snap) = app.account_snapshots.get(app.accounts_tab_index) {
+        let mut account = snap.account.clone();
+        account.opening_balance = amount;
+        db.update_account(&account)?;
+        app.refresh_accounts(db)?;
+        app.refresh_accounts_tab(db)?;
+        app.refresh_dashboard(db)?;
+        app.set_status(format!(
+            "Opening balance for {} set to {amount}",
+            account.name
+        ));
     }
 
     Ok(())
 }
 
-fn cmd_budget(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
-    if args.is_empty() {
-        app.set_status(
-            "Usage: :budget <category_name> <amount>. Example: :budget Food & Dining 500",
-        );
+fn cmd_delete_account(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Accounts || app.account_snapshots.is_empty() {
+        app.set_status("Navigate to Accounts and select one first");
         return Ok(());
     }
 
-    // Last token is the amount, everything before is the category name
-    let parts: Vec<&str> = args.rsplitn(2, ' ').collect();
-    if parts.len() < 2 {
-        app.set_status("Usage: :budget <category_name> <amount>");
+    let force = args.eq_ignore_ascii_case("force");
+    if !args.is_empty() && !force {
+        app.set_error("Usage: :delete-account [force]");
         return Ok(());
     }
 
-    let amount_str = parts[0];
-    let category_name = parts[1];
+    if let Some(snap) = app.account_snapshots.get(app.accounts_tab_index) {
+        if let Some(id) = snap.account.id {
+            let name = snap.account.name.clone();
+            let txn_count = db.get_account_transaction_count(id)?;
+            if txn_count > 0 && !force {
+                app.set_status(format!(
+                    "'{name}' has {txn_count} transactions. Use :delete-account force to delete them too"
+                ));
+                return Ok(());
+            }
+            app.confirm_message = if txn_count > 0 {
+                format!("Delete account '{name}' and its {txn_count} transactions?")
+            } else {
+                format!("Delete account '{name}'?")
+            };
+            app.pending_action = Some(PendingAction::DeleteAccount { id, name, force });
+            app.input_mode = InputMode::Confirm;
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_archive(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    set_selected_archived(app, db, true)
+}
+
+fn cmd_unarchive(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    set_selected_archived(app, db, false)
+}
+
+fn set_selected_archived(app: &mut App, db: &mut Database, archived: bool) -> anyhow::Result<()> {
+    if app.screen != Screen::Accounts || app.account_snapshots.is_empty() {
+        app.set_status("Navigate to Accounts and select one first");
+        return Ok(());
+    }
+
+    if let Some(snap) = app.account_snapshots.get(app.accounts_tab_index) {
+        if let Some(id) = snap.account.id {
+            let name = snap.account.name.clone();
+            if snap.account.archived == archived {
+                app.set_status(format!(
+                    "{name} is already {}",
+                    if archived { "archived" } else { "active" }
+                ));
+                return Ok(());
+            }
+            db.set_account_archived(id, archived)?;
+            app.refresh_accounts_tab(db)?;
+            app.refresh_dashboard(db)?;
+            if app.accounts_tab_index >= app.account_snapshots.len() {
+                app.accounts_tab_index = app.account_snapshots.len().saturating_sub(1);
+            }
+            if app.account_index >= app.accounts.len() {
+                app.account_index = 0;
+            }
+            app.set_status(if archived {
+                format!("Archived {name} (A shows archived accounts)")
+            } else {
+                format!("Restored {name}")
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn cmd_rate(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+    if parts.len() != 3 {
+        app.set_error("Usage: :rate <from> <to> <rate>. Example: :rate EUR USD 1.08");
+        return Ok(());
+    }
+
+    let (from, to) = (parts[0], parts[1]);
+    if !is_currency_code(from) || !is_currency_code(to) {
+        app.set_status("Currencies must be 3-letter codes (e.g. USD, EUR)");
+        return Ok(());
+    }
+
+    let rate = match Decimal::from_str(parts[2]) {
+        Ok(r) if r > Decimal::ZERO => r,
+        _ => {
+            app.set_error(format!("Invalid rate: {}", parts[2]));
+            return Ok(());
+        }
+    };
+
+    let rate = ExchangeRate::new(from, to, rate);
+    db.upsert_exchange_rate(&rate)?;
+    app.refresh_dashboard(db)?;
+    app.set_status(format!(
+        "Rate set: 1 {} = {} {}",
+        rate.from_currency, rate.rate, rate.to_currency
+    ));
+    Ok(())
+}
+
+fn cmd_account_currency(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Accounts || app.account_snapshots.is_empty() {
+        app.set_status("Navigate to Accounts and select one first");
+        return Ok(());
+    }
+
+    if !is_currency_code(args) {
+        app.set_error("Usage: :account-currency <code> (e.g. :account-currency EUR)");
+        return Ok(());
+    }
+
+    let currency = args.to_uppercase();
+    if let Some(snap) = app.account_snapshots.get(app.accounts_tab_index) {
+        if let Some(id) = snap.account.id {
+            let name = snap.account.name.clone();
+            db.update_account_currency(id, &currency)?;
+            app.refresh_accounts(db)?;
+            app.refresh_accounts_tab(db)?;
+            app.refresh_dashboard(db)?;
+            app.set_status(format!("{name} now uses {currency}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_rule(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    add_rule(args, app, db, false)
+}
+
+fn cmd_rule_apply(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    add_rule(args, app, db, true)
+}
+
+/// Add a contains rule. With `apply`, existing uncategorized transactions
+/// that match are categorized right away.
+fn add_rule(args: &str, app: &mut App, db: &mut Database, apply: bool) -> anyhow::Result<()> {
+    let usage = if apply {
+        "Usage: :rule! <pattern> <category_name>"
+    } else {
+        "Usage: :rule <pattern> <category_name>"
+    };
+    if args.is_empty() {
+        app.set_error(usage);
+        return Ok(());
+    }
+
+    let parts: Vec<&str> = args.rsplitn(2, ' ').collect();
+    if parts.len() < 2 {
+        app.set_error(usage);
+        return Ok(());
+    }
+
+    let category_name = parts[0];
+    let pattern = parts[1].to_lowercase();
+
+    let categories = db.get_categories()?;
+    if let Some(cat) = Category::find_by_name(&categories, category_name) {
+        let cat_id = match cat.id {
+            Some(id) => id,
+            None => {
+                app.set_status("Category has no ID (this shouldn't happen)");
+                return Ok(());
+            }
+        };
+        let rule = ImportRule::new_contains(pattern.clone(), cat_id);
+        db.insert_import_rule(&rule)?;
+        let added = format!("Added rule: '{pattern}' -> {}", cat.name);
+        finish_add_rule(app, db, &rule, apply, added)?;
+    } else {
+        app.set_error(format!("Category '{category_name}' not found"));
+    }
+
+    Ok(())
+}
+
+/// Turn the selected transaction into a contains rule: the pattern comes
+/// from `suggest_rule` on its original description, the category is its own.
+fn cmd_memorize(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Transactions || app.transactions.is_empty() {
+        app.set_status("Navigate to Transactions and select one first");
+        return Ok(());
+    }
+
+    let (description, category) = match app.transactions.get(app.transaction_index) {
+        Some(txn) => (
+            txn.original_description.clone(),
+            txn.category_id
+                .and_then(|id| Category::find_by_id(&app.categories, id))
+                .cloned(),
+        ),
+        None => return Ok(()),
+    };
+    let (cat_id, cat_name) = match category {
+        Some(Category {
+            id: Some(id), name, ..
+        }) => (id, name),
+        _ => {
+            app.set_status("Categorize this transaction first (c), then press M to memorize it");
+            return Ok(());
+        }
+    };
+
+    let pattern = crate::categorize::suggest_rule(&description)?;
+    if let Some(existing) = app
+        .import_rules
+        .iter()
+        .find(|r| !r.is_regex && r.pattern.eq_ignore_ascii_case(&pattern))
+    {
+        let target = Category::find_by_id(&app.categories, existing.category_id)
+            .map(|c| c.name.as_str())
+            .unwrap_or("?");
+        app.set_status(format!("Rule '{pattern}' already exists -> {target}"));
+        return Ok(());
+    }
+
+    db.insert_import_rule(
+        &ImportRule::new_contains(pattern.clone(), cat_id).with_source(RuleSource::Memorized),
+    )?;
+    app.refresh_categories(db)?;
+    app.set_status(format!("Memorized rule: '{pattern}' -> {cat_name}"));
+    Ok(())
+}
+
+fn cmd_search(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    app.search_input = args.to_string();
+    app.screen = Screen::Transactions;
+    app.refresh_transactions(db)?;
+
+    if args.is_empty() {
+        app.set_status("Search cleared");
+    } else {
+        app.set_status(format!("Searching: {args}"));
+    }
+
+    Ok(())
+}
+
+/// Show only the transactions flagged for review, or go back to the full
+/// list if that's already what's showing.
+fn cmd_flagged(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    app.clear_selections();
+    if app.screen == Screen::Transactions && app.transaction_filter_flagged {
+        app.transaction_filter_flagged = false;
+        app.refresh_transactions(db)?;
+        app.set_status("Flagged filter cleared");
+        return Ok(());
+    }
+
+    app.transaction_filter_flagged = true;
+    app.transaction_index = 0;
+    app.transaction_scroll = 0;
+    app.screen = Screen::Transactions;
+    app.refresh_transactions(db)?;
+    let count = app.transactions.len();
+    let noun = if count == 1 {
+        "transaction"
+    } else {
+        "transactions"
+    };
+    app.set_status(format!(
+        "{count}{} flagged {noun} — f to clear a flag, Esc to show all",
+        if app.transactions_truncated { "+" } else { "" },
+    ));
+    Ok(())
+}
+
+/// Show only the viewed month's transactions that still need a category,
+/// or go back to the full list if that's already what's showing.
+fn cmd_uncategorized(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    app.clear_selections();
+    if app.screen == Screen::Transactions && app.transaction_filter_uncategorized {
+        app.transaction_filter_uncategorized = false;
+        app.refresh_transactions(db)?;
+        app.set_status("Uncategorized filter cleared");
+        return Ok(());
+    }
+
+    app.transaction_filter_uncategorized = true;
+    app.transaction_index = 0;
+    app.transaction_scroll = 0;
+    app.screen = Screen::Transactions;
+    app.refresh_transactions(db)?;
+    let count = app.transactions.len();
+    let noun = if count == 1 {
+        "transaction"
+    } else {
+        "transactions"
+    };
+    app.set_status(format!(
+        "{count}{} uncategorized {noun} in {} — c to categorize, u or Esc to show all",
+        if app.transactions_truncated { "+" } else { "" },
+        app.search_scope_label()
+    ));
+    Ok(())
+}
+
+fn cmd_categorize_last(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if !app.import_recategorizing
+        && matches!(
+            app.import_step,
+            ImportStep::Preview | ImportStep::Categorize
+        )
+    {
+        app.set_error("An import is in progress — finish it, or :import to start over");
+        return Ok(());
+    }
+    let batch = match db.last_import_batch()? {
+        Some(batch) => batch,
+        None => {
+            app.set_error("Nothing has been imported yet");
+            return Ok(());
+        }
+    };
+    let mut txns = db.get_uncategorized_in_batch(batch)?;
+    if txns.is_empty() {
+        app.set_status("Everything from the last import is categorized");
+        return Ok(());
+    }
+    // Rows filed under the seed "Uncategorized" category count as open too
+    for txn in &mut txns {
+        txn.category_id = None;
+    }
+
+    app.clear_selections();
+    app.import_preview = txns;
+    app.import_recategorizing = true;
+    app.prepare_categorize_step();
+    app.screen = Screen::Import;
+    app.import_step = ImportStep::Categorize;
+    let total = app.import_cat_descriptions.len();
+    app.set_status(format!(
+        "{total} unique description{} left uncategorized by the last import",
+        if total == 1 { "" } else { "s" }
+    ));
+    Ok(())
+}
+
+fn cmd_budget(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    set_budget(args, app, db, BudgetPeriod::Monthly)
+}
+
+fn cmd_budget_year(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    set_budget(args, app, db, BudgetPeriod::Yearly)
+}
+
+fn set_budget(
+    args: &str,
+    app: &mut App,
+    db: &mut Database,
+    period: BudgetPeriod,
+) -> anyhow::Result<()> {
+    let cmd = match period {
+        BudgetPeriod::Monthly => "budget",
+        BudgetPeriod::Yearly => "budget-year",
+    };
+    if args.is_empty() {
+        app.set_error(format!(
+            "Usage: :{cmd} <category_name> [amount]. Example: :{cmd} Food & Dining 500"
+        ));
+        return Ok(());
+    }
+
+    let categories = db.get_categories()?;
+    // A bare category name asks for a suggestion instead of setting a budget
+    if let Some(cat) = Category::find_by_name(&categories, args) {
+        if let Some(id) = cat.id {
+            return suggest_budget(app, db, period, id, &cat.name);
+        }
+    }
+
+    // Last token is the amount, everything before is the category name
+    let parts: Vec<&str> = args.rsplitn(2, ' ').collect();
+    if parts.len() < 2 {
+        app.set_error(format!("Usage: :{cmd} <category_name> <amount>"));
+        return Ok(());
+    }
+
+    let amount_str = parts[0];
+    let category_name = parts[1];
 
     let amount = match Decimal::from_str(amount_str) {
         Ok(a) => a,
         Err(_) => {
-            app.set_status(format!("Invalid amount: {amount_str}"));
+            app.set_error(format!("Invalid amount: {amount_str}"));
             return Ok(());
         }
     };
 
-    let categories = db.get_categories()?;
     if let Some(cat) = Category::find_by_name(&categories, category_name) {
         let cat_id = match cat.id {
             Some(id) => id,
@@ -407,25 +1344,75 @@ fn cmd_budget(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()
                 return Ok(());
             }
         };
-        let budget_month = app
-            .current_month
-            .clone()
-            .unwrap_or_else(|| chrono::Local::now().format("%Y-%m").to_string());
-        let budget = Budget::new(cat_id, budget_month.clone(), amount);
+        let (budget, period_label) = match period {
+            BudgetPeriod::Monthly => {
+                let budget_month = app
+                    .current_month
+                    .clone()
+                    .unwrap_or_else(|| app.this_month());
+                (
+                    Budget::new(cat_id, budget_month.clone(), amount),
+                    budget_month,
+                )
+            }
+            BudgetPeriod::Yearly => {
+                let year = app.budget_year_for_month();
+                (
+                    Budget::new_yearly(cat_id, year.clone(), amount),
+                    format!("year {year}"),
+                )
+            }
+        };
         db.upsert_budget(&budget)?;
         app.refresh_budgets(db)?;
         app.screen = Screen::Budgets;
         app.set_status(format!(
-            "Budget set: {} = ${amount} for {budget_month}",
+            "Budget set: {} = ${amount} for {period_label}",
             cat.name
         ));
     } else {
-        app.set_status(format!("Category '{category_name}' not found"));
+        app.set_error(format!("Category '{category_name}' not found"));
     }
 
     Ok(())
 }
 
+/// Show what a category has averaged lately and open the command bar with
+/// a `:budget` (or `:budget-year`) for that amount, ready to accept or edit.
+fn suggest_budget(
+    app: &mut App,
+    db: &Database,
+    period: BudgetPeriod,
+    category_id: i64,
+    name: &str,
+) -> anyhow::Result<()> {
+    let (cmd, suggestion, label) = match period {
+        BudgetPeriod::Monthly => {
+            let avg = db.suggest_budget(category_id, BUDGET_SUGGESTION_MONTHS)?;
+            (
+                "budget",
+                avg,
+                format!(
+                    "avg last {BUDGET_SUGGESTION_MONTHS} months: {}",
+                    format_amount(avg, BASE_CURRENCY)
+                ),
+            )
+        }
+        BudgetPeriod::Yearly => {
+            let year = db.suggest_budget(category_id, 12)? * Decimal::from(12);
+            (
+                "budget-year",
+                year,
+                format!("last 12 months: {}", format_amount(year, BASE_CURRENCY)),
+            )
+        }
+    };
+    app.command_input = format!("{cmd} {name} {}", format_decimal(suggestion, 2, false));
+    app.input_mode = InputMode::Command;
+    app.set_status(format!("{name}: {label} — Enter to use it"));
+    Ok(())
+}
+
 fn cmd_delete_budget(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::Result<()> {
     if app.budgets.is_empty() {
         app.set_status("No budgets to delete");
@@ -442,23 +1429,262 @@ fn cmd_delete_budget(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::
                 id,
                 name: cat_name.to_string(),
             });
-            app.input_mode = InputMode::Confirm;
+            app.input_mode = InputMode::Confirm;
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_copy_budgets(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let mut force = false;
+    let mut from = None;
+    for arg in args.split_whitespace() {
+        if arg == "--force" {
+            force = true;
+        } else {
+            from = Some(arg);
+        }
+    }
+    let from = match from {
+        Some(m) if chrono::NaiveDate::parse_from_str(&format!("{m}-01"), "%Y-%m-%d").is_ok() => m,
+        _ => {
+            app.set_error("Usage: :copy-budgets <YYYY-MM> [--force]");
+            return Ok(());
+        }
+    };
+    let to = app
+        .current_month
+        .clone()
+        .unwrap_or_else(|| app.this_month());
+    if from == to {
+        app.set_status(format!(
+            "Already viewing {to}; pick another month to copy from"
+        ));
+        return Ok(());
+    }
+
+    let available = db
+        .get_budgets(Some(from))?
+        .iter()
+        .filter(|b| b.period == BudgetPeriod::Monthly)
+        .count();
+    if available == 0 {
+        app.set_status(format!("No monthly budgets in {from}"));
+        return Ok(());
+    }
+
+    let copied = db.copy_budgets(from, &to, force)?;
+    app.refresh_budgets(db)?;
+    app.screen = Screen::Budgets;
+    let kept = available - copied;
+    app.set_status(if kept > 0 {
+        format!("Copied {copied} budgets from {from} into {to} ({kept} already set, kept; --force to overwrite)")
+    } else {
+        format!("Copied {copied} budgets from {from} into {to}")
+    });
+    Ok(())
+}
+
+fn cmd_budget_subcats(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Budgets || app.budgets.is_empty() {
+        app.set_status("Navigate to Budgets and select one first");
+        return Ok(());
+    }
+
+    if let Some(budget) = app.budgets.get(app.budget_index) {
+        if let Some(id) = budget.id {
+            let include = !budget.include_subcategories;
+            let cat_name = Category::find_by_id(&app.categories, budget.category_id)
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| "Unknown".into());
+            db.set_budget_include_subcategories(id, include)?;
+            app.refresh_budgets(db)?;
+            app.set_status(if include {
+                format!("Budget for '{cat_name}' now includes subcategories")
+            } else {
+                format!("Budget for '{cat_name}' now excludes subcategories")
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_budget_rollover(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Budgets || app.budgets.is_empty() {
+        app.set_status("Navigate to Budgets and select one first");
+        return Ok(());
+    }
+
+    if let Some(budget) = app.budgets.get(app.budget_index) {
+        if budget.period != BudgetPeriod::Monthly {
+            app.set_status("Rollover only applies to monthly budgets");
+            return Ok(());
+        }
+        let rollover = match args.to_lowercase().as_str() {
+            "" => !budget.rollover,
+            "on" => true,
+            "off" => false,
+            _ => {
+                app.set_error("Usage: :budget-rollover [on|off]");
+                return Ok(());
+            }
+        };
+        if let Some(id) = budget.id {
+            let cat_name = Category::find_by_id(&app.categories, budget.category_id)
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| "Unknown".into());
+            db.set_budget_rollover(id, rollover)?;
+            app.refresh_budgets(db)?;
+            app.set_status(if rollover {
+                format!("Budget for '{cat_name}' now rolls over")
+            } else {
+                format!("Budget for '{cat_name}' no longer rolls over")
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_category(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if args.is_empty() {
+        app.set_error("Usage: :category <name>. Creates a new top-level category");
+        return Ok(());
+    }
+
+    let cat = Category::new(args.to_string());
+    db.insert_category(&cat)?;
+    app.refresh_categories(db)?;
+    app.set_status(format!("Created category: {args}"));
+    Ok(())
+}
+
+fn cmd_category_parent(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Categories || app.categories.is_empty() || app.category_view_rules {
+        app.set_status("Navigate to Categories and select one first");
+        return Ok(());
+    }
+
+    let (cat_id, cat_name) = match app.categories.get(app.category_index) {
+        Some(Category {
+            id: Some(id), name, ..
+        }) => (*id, name.clone()),
+        _ => return Ok(()),
+    };
+
+    if args.is_empty() {
+        db.update_category_parent(cat_id, None)?;
+        app.refresh_categories(db)?;
+        app.set_status(format!("'{cat_name}' is now a top-level category"));
+        return Ok(());
+    }
+
+    let (parent_id, parent_name) = match Category::find_by_name(&app.categories, args) {
+        Some(Category {
+            id: Some(id), name, ..
+        }) => (*id, name.clone()),
+        _ => {
+            app.set_error(format!("Category '{args}' not found"));
+            return Ok(());
+        }
+    };
+
+    // A category can't sit under itself or one of its own descendants
+    if parent_id == cat_id || Category::descendant_ids(&app.categories, cat_id).contains(&parent_id)
+    {
+        app.set_error(format!(
+            "'{cat_name}' can't be placed under its own subcategory"
+        ));
+        return Ok(());
+    }
+
+    db.update_category_parent(cat_id, Some(parent_id))?;
+    app.refresh_categories(db)?;
+    app.set_status(format!("'{cat_name}' is now under '{parent_name}'"));
+    Ok(())
+}
+
+fn cmd_set_icon(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Categories || app.categories.is_empty() {
+        app.set_status("Navigate to Categories and select one first");
+        return Ok(());
+    }
+
+    if let Some(cat) = app.categories.get(app.category_index) {
+        if let Some(id) = cat.id {
+            let name = cat.name.clone();
+            db.update_category_style(id, args, &cat.color)?;
+            app.refresh_categories(db)?;
+            app.set_status(if args.is_empty() {
+                format!("Cleared icon for {name}")
+            } else {
+                format!("Icon for {name} set to {args}")
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_set_color(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Categories || app.categories.is_empty() {
+        app.set_status("Navigate to Categories and select one first");
+        return Ok(());
+    }
+
+    let clear = args.is_empty() || args.eq_ignore_ascii_case("none");
+    if !clear && ratatui::style::Color::from_str(args).is_err() {
+        app.set_error(format!(
+            "Unknown color '{args}'. Use #rrggbb or a name like red, lightblue"
+        ));
+        return Ok(());
+    }
+
+    if let Some(cat) = app.categories.get(app.category_index) {
+        if let Some(id) = cat.id {
+            let name = cat.name.clone();
+            let color = if clear { "" } else { args };
+            db.update_category_style(id, &cat.icon, color)?;
+            app.refresh_categories(db)?;
+            app.set_status(if clear {
+                format!("Cleared color for {name}")
+            } else {
+                format!("Color for {name} set to {color}")
+            });
         }
     }
 
     Ok(())
 }
 
-fn cmd_category(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
-    if args.is_empty() {
-        app.set_status("Usage: :category <name>. Creates a new top-level category");
+fn cmd_delete_category(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Categories || app.categories.is_empty() {
+        app.set_status("Navigate to Categories and select one first");
         return Ok(());
     }
 
-    let cat = Category::new(args.to_string());
-    db.insert_category(&cat)?;
-    app.refresh_categories(db)?;
-    app.set_status(format!("Created category: {args}"));
+    if let Some(cat) = app.categories.get(app.category_index) {
+        if cat.name == UNCATEGORIZED {
+            app.set_error(format!(
+                "'{UNCATEGORIZED}' is required and cannot be deleted"
+            ));
+            return Ok(());
+        }
+        if let Some(id) = cat.id {
+            let name = cat.name.clone();
+            let txn_count = db.get_category_transaction_count(id)?;
+            app.confirm_message = if txn_count > 0 {
+                format!("Delete category '{name}'? {txn_count} transactions become uncategorized")
+            } else {
+                format!("Delete category '{name}'?")
+            };
+            app.pending_action = Some(PendingAction::DeleteCategory { id, name });
+            app.input_mode = InputMode::Confirm;
+        }
+    }
+
     Ok(())
 }
 
@@ -480,15 +1706,62 @@ fn cmd_delete_rule(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::Re
     Ok(())
 }
 
+/// Offer to delete every rule that hasn't matched a saved transaction in
+/// the last N months, or ever.
+fn cmd_prune_rules(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let months = if args.is_empty() {
+        PRUNE_RULES_MONTHS
+    } else {
+        match args.parse::<u32>() {
+            Ok(n) if (1..=120).contains(&n) => n,
+            _ => {
+                app.set_error(format!(
+                    "Usage: :prune-rules [months] (1-120, default {PRUNE_RULES_MONTHS})"
+                ));
+                return Ok(());
+            }
+        }
+    };
+    let today = chrono::Local::now().date_naive();
+    let before = match today.checked_sub_months(chrono::Months::new(months)) {
+        Some(date) => date.format("%Y-%m-%d").to_string(),
+        None => return Ok(()),
+    };
+
+    let count = db.count_unmatched_rules(&before)?;
+    if count == 0 {
+        app.set_status(format!("No rules unused since {before}"));
+        return Ok(());
+    }
+    let noun = if count == 1 { "rule" } else { "rules" };
+    app.confirm_message = format!("Delete {count} {noun} with no match since {before}?");
+    app.pending_action = Some(PendingAction::PruneRules { before });
+    app.input_mode = InputMode::Confirm;
+    Ok(())
+}
+
 fn cmd_regex_rule(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    add_regex_rule(args, app, db, false)
+}
+
+fn cmd_regex_rule_apply(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    add_regex_rule(args, app, db, true)
+}
+
+fn add_regex_rule(args: &str, app: &mut App, db: &mut Database, apply: bool) -> anyhow::Result<()> {
+    let usage = if apply {
+        "Usage: :regex-rule! <pattern> <category_name>"
+    } else {
+        "Usage: :regex-rule <pattern> <category_name>"
+    };
     if args.is_empty() {
-        app.set_status("Usage: :regex-rule <pattern> <category_name>");
+        app.set_error(usage);
         return Ok(());
     }
 
     let parts: Vec<&str> = args.rsplitn(2, ' ').collect();
     if parts.len() < 2 {
-        app.set_status("Usage: :regex-rule <pattern> <category_name>");
+        app.set_error(usage);
         return Ok(());
     }
 
@@ -497,7 +1770,7 @@ fn cmd_regex_rule(args: &str, app: &mut App, db: &mut Database) -> anyhow::Resul
 
     // Validate regex
     if regex::Regex::new(&pattern).is_err() {
-        app.set_status(format!("Invalid regex: {pattern}"));
+        app.set_error(format!("Invalid regex: {pattern}"));
         return Ok(());
     }
 
@@ -512,12 +1785,36 @@ fn cmd_regex_rule(args: &str, app: &mut App, db: &mut Database) -> anyhow::Resul
         };
         let rule = ImportRule::new_regex(pattern.clone(), cat_id);
         db.insert_import_rule(&rule)?;
-        app.refresh_categories(db)?;
-        app.set_status(format!("Added regex rule: /{pattern}/ -> {}", cat.name));
+        let added = format!("Added regex rule: /{pattern}/ -> {}", cat.name);
+        finish_add_rule(app, db, &rule, apply, added)?;
     } else {
-        app.set_status(format!("Category '{category_name}' not found"));
+        app.set_error(format!("Category '{category_name}' not found"));
+    }
+
+    Ok(())
+}
+
+/// Refresh after a rule is added, applying it to existing uncategorized
+/// transactions first when asked.
+fn finish_add_rule(
+    app: &mut App,
+    db: &mut Database,
+    rule: &ImportRule,
+    apply: bool,
+    added: String,
+) -> anyhow::Result<()> {
+    if !apply {
+        app.refresh_categories(db)?;
+        app.set_status(added);
+        return Ok(());
     }
 
+    let count = db.apply_rule_to_existing(rule)?;
+    app.refresh_all(db)?;
+    app.set_status(format!(
+        "{added} (applied to {count} existing transaction{})",
+        if count == 1 { "" } else { "s" }
+    ));
     Ok(())
 }
 
@@ -555,7 +1852,7 @@ fn cmd_recat(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()>
     }
 
     if args.is_empty() {
-        app.set_status("Usage: :recat <category_name>");
+        app.open_recat_picker();
         return Ok(());
     }
 
@@ -577,7 +1874,300 @@ fn cmd_recat(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()>
             }
         }
     } else {
-        app.set_status(format!("Category '{args}' not found"));
+        app.set_error(format!("Category '{args}' not found"));
+    }
+
+    Ok(())
+}
+
+fn cmd_transfer(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if !matches!(args.to_lowercase().as_str(), "" | "on" | "off") {
+        return move_between_accounts(args, app, db);
+    }
+    if app.screen != Screen::Transactions || app.transactions.is_empty() {
+        app.set_status("Navigate to Transactions and select one first");
+        return Ok(());
+    }
+
+    if let Some(txn) = app.transactions.get(app.transaction_index) {
+        let is_transfer = match args.to_lowercase().as_str() {
+            "on" => true,
+            "off" => false,
+            _ => !txn.is_transfer,
+        };
+        if let Some(id) = txn.id {
+            db.set_transaction_transfer(id, is_transfer)?;
+            app.refresh_transactions(db)?;
+            app.refresh_dashboard(db)?;
+            app.set_status(if is_transfer {
+                "Marked as transfer — left out of income and spending"
+            } else {
+                "No longer a transfer"
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// `:transfer <from> <to> <amount> [date]`: record money moving between two
+/// accounts as a linked pair of transfers, out of `from` and into `to`.
+/// Account names may contain spaces; the words are split wherever both
+/// halves name an account.
+fn move_between_accounts(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let usage =
+        "Usage: :transfer [on|off], or :transfer <from account> <to account> <amount> [date]";
+    let mut words: Vec<&str> = args.split_whitespace().collect();
+    if words.len() < 3 {
+        app.set_error(usage);
+        return Ok(());
+    }
+
+    // A date can't parse as an amount, so a trailing non-amount is the date
+    let is_amount = |s: &str| Decimal::from_str(s.trim_start_matches('$')).is_ok();
+    let mut date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    if words.len() >= 4 && !is_amount(words[words.len() - 1]) {
+        let raw = words.pop().unwrap_or_default();
+        match chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+            Ok(d) => date = d.format("%Y-%m-%d").to_string(),
+            Err(_) => {
+                app.set_error(format!("Invalid date: {raw} (expected YYYY-MM-DD)"));
+                return Ok(());
+            }
+        }
+    }
+    let raw_amount = words.pop().unwrap_or_default();
+    let amount = match Decimal::from_str(raw_amount.trim_start_matches('$')) {
+        Ok(a) if a > Decimal::ZERO => a,
+        _ => {
+            app.set_error(format!("Invalid amount: {raw_amount} (must be positive)"));
+            return Ok(());
+        }
+    };
+
+    let accounts = db.get_accounts(true)?;
+    let pair = (1..words.len()).find_map(|i| {
+        let from = find_account_by_name(&accounts, &words[..i].join(" "))?;
+        let to = find_account_by_name(&accounts, &words[i..].join(" "))?;
+        Some((from, to))
+    });
+    let (from, to) = match pair {
+        Some(pair) => pair,
+        None => {
+            let names: Vec<&str> = accounts.iter().map(|a| a.name.as_str()).collect();
+            app.set_error(format!(
+                "Couldn't find two accounts in '{}'. Available: {}",
+                words.join(" "),
+                names.join(", ")
+            ));
+            return Ok(());
+        }
+    };
+    if from.id == to.id {
+        app.set_error("Pick two different accounts");
+        return Ok(());
+    }
+    if !from.currency.eq_ignore_ascii_case(&to.currency) {
+        app.set_error(format!(
+            "{} is in {} and {} in {}; transfers need one currency",
+            from.name, from.currency, to.name, to.currency
+        ));
+        return Ok(());
+    }
+    let (from_id, to_id) = match (from.id, to.id) {
+        (Some(f), Some(t)) => (f, t),
+        _ => return Ok(()),
+    };
+
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let leg = |account_id: i64, description: String, amount: Decimal, side: &str| Transaction {
+        id: None,
+        account_id,
+        date: date.clone(),
+        description: description.clone(),
+        original_description: description,
+        amount,
+        category_id: None,
+        notes: String::new(),
+        is_transfer: true,
+        import_hash: format!("manual-transfer-{created_at}-{side}"),
+        created_at: created_at.clone(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
+    };
+    db.insert_transfer_pair(
+        &leg(from_id, format!("Transfer to {}", to.name), -amount, "out"),
+        &leg(to_id, format!("Transfer from {}", from.name), amount, "in"),
+    )?;
+    app.refresh_transactions(db)?;
+    app.refresh_dashboard(db)?;
+    app.refresh_accounts_tab(db)?;
+    app.set_status(format!(
+        "Moved {} from {} to {} on {date}",
+        format_amount(amount, &from.currency),
+        from.name,
+        to.name
+    ));
+    Ok(())
+}
+
+/// The account called `name`, ignoring case.
+fn find_account_by_name<'a>(accounts: &'a [Account], name: &str) -> Option<&'a Account> {
+    let name = name.to_lowercase();
+    accounts.iter().find(|a| a.name.to_lowercase() == name)
+}
+
+fn cmd_setamount(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Transactions || app.transactions.is_empty() {
+        app.set_status("Navigate to Transactions and select one first");
+        return Ok(());
+    }
+
+    if args.is_empty() {
+        app.set_error("Usage: :setamount <amount> (e.g. :setamount -42.10)");
+        return Ok(());
+    }
+
+    let amount = match Decimal::from_str(args) {
+        Ok(a) => a,
+        Err(_) => {
+            app.set_error(format!("Invalid amount: {args}"));
+            return Ok(());
+        }
+    };
+
+    if let Some(txn) = app.transactions.get(app.transaction_index) {
+        if let Some(id) = txn.id {
+            db.update_transaction_amount(id, amount)?;
+            app.refresh_transactions(db)?;
+            app.refresh_dashboard(db)?;
+            app.set_status(format!("Set amount to {amount}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_setdate(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Transactions || app.transactions.is_empty() {
+        app.set_status("Navigate to Transactions and select one first");
+        return Ok(());
+    }
+
+    if args.is_empty() {
+        app.set_error("Usage: :setdate <YYYY-MM-DD> (e.g. :setdate 2024-01-15)");
+        return Ok(());
+    }
+
+    let date = match chrono::NaiveDate::parse_from_str(args, "%Y-%m-%d") {
+        Ok(d) => d.format("%Y-%m-%d").to_string(),
+        Err(_) => {
+            app.set_error(format!("Invalid date: {args} (use YYYY-MM-DD)"));
+            return Ok(());
+        }
+    };
+
+    if let Some(txn) = app.transactions.get(app.transaction_index) {
+        if let Some(id) = txn.id {
+            db.update_transaction_date(id, &date)?;
+            app.refresh_transactions(db)?;
+            app.refresh_dashboard(db)?;
+            app.set_status(format!("Set date to {date}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_why(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    if app.screen != Screen::Transactions || app.transactions.is_empty() {
+        app.set_status("Navigate to Transactions and select one first");
+        return Ok(());
+    }
+
+    if let Some(txn) = app.transactions.get(app.transaction_index) {
+        let rules = db.get_import_rules()?;
+        let (categorizer, bad_patterns) = crate::categorize::Categorizer::new(&rules);
+        let mut status = match categorizer.categorize(&txn.original_description) {
+            Some((cat_id, rule)) => {
+                let cat_name = Category::find_by_id(&app.categories, cat_id)
+                    .map(|c| c.name.as_str())
+                    .unwrap_or("?");
+                format!("Matched rule '{}' → {cat_name}", rule.pattern)
+            }
+            None => format!("No rule matches '{}'", txn.original_description),
+        };
+        // A broken rule might be the one that was meant to match
+        if bad_patterns.is_empty() {
+            app.set_status(status);
+        } else {
+            status.push_str(&format!(
+                " | Warning: invalid regex rule(s) never match: {}",
+                bad_patterns.join(", ")
+            ));
+            app.set_warning(status);
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_tag(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    edit_selected_tags(args, app, db, true)
+}
+
+fn cmd_untag(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    edit_selected_tags(args, app, db, false)
+}
+
+fn edit_selected_tags(
+    args: &str,
+    app: &mut App,
+    db: &mut Database,
+    add: bool,
+) -> anyhow::Result<()> {
+    let cmd = if add { "tag" } else { "untag" };
+    if app.screen != Screen::Transactions || app.transactions.is_empty() {
+        app.set_status("Navigate to Transactions and select one first");
+        return Ok(());
+    }
+
+    let tag = match Transaction::normalize_tag(args) {
+        Some(t) => t,
+        None => {
+            app.set_error(format!(
+                "Usage: :{cmd} <name> (one word, e.g. :{cmd} tax-deductible)"
+            ));
+            return Ok(());
+        }
+    };
+
+    if let Some(txn) = app.transactions.get(app.transaction_index) {
+        if let Some(id) = txn.id {
+            let mut tags = txn.tags.clone();
+            let has_tag = tags.contains(&tag);
+            if add && !has_tag {
+                tags.push(tag.clone());
+            } else if !add && has_tag {
+                tags.retain(|t| *t != tag);
+            } else {
+                app.set_status(if add {
+                    format!("Already tagged #{tag}")
+                } else {
+                    format!("Not tagged #{tag}")
+                });
+                return Ok(());
+            }
+            db.set_transaction_tags(id, &tags)?;
+            app.refresh_transactions(db)?;
+            app.set_status(if add {
+                format!("Tagged #{tag}")
+            } else {
+                format!("Removed #{tag}")
+            });
+        }
     }
 
     Ok(())
@@ -591,13 +2181,13 @@ fn cmd_accounts(_args: &str, app: &mut App, db: &mut Database) -> anyhow::Result
 
 fn cmd_add_txn(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
     if args.is_empty() {
-        app.set_status("Usage: :add-txn <date> <description> <amount>. Example: :add-txn 2024-01-15 Coffee -4.50");
+        app.set_error("Usage: :add-txn <date> <description> <amount>. Example: :add-txn 2024-01-15 Coffee -4.50");
         return Ok(());
     }
 
     let parts: Vec<&str> = args.splitn(3, ' ').collect();
     if parts.len() < 3 {
-        app.set_status("Usage: :add-txn <date> <description> <amount>");
+        app.set_error("Usage: :add-txn <date> <description> <amount>");
         return Ok(());
     }
 
@@ -606,7 +2196,7 @@ fn cmd_add_txn(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<(
     let rest = parts[1..].join(" ");
     let rest_parts: Vec<&str> = rest.rsplitn(2, ' ').collect();
     if rest_parts.len() < 2 {
-        app.set_status("Usage: :add-txn <date> <description> <amount>");
+        app.set_error("Usage: :add-txn <date> <description> <amount>");
         return Ok(());
     }
 
@@ -616,7 +2206,7 @@ fn cmd_add_txn(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<(
     let amount = match Decimal::from_str(amount_str) {
         Ok(a) => a,
         Err(_) => {
-            app.set_status(format!("Invalid amount: {amount_str}"));
+            app.set_error(format!("Invalid amount: {amount_str}"));
             return Ok(());
         }
     };
@@ -644,6 +2234,10 @@ fn cmd_add_txn(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<(
         is_transfer: false,
         import_hash: format!("manual-{}-{}-{}", date, description, amount),
         created_at: chrono::Utc::now().to_rfc3339(),
+        tags: Vec::new(),
+        quantity: None,
+        symbol: None,
+        flagged: false,
     };
 
     db.insert_transaction(&txn)?;
@@ -655,6 +2249,87 @@ fn cmd_add_txn(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<(
     Ok(())
 }
 
+/// Record a purchase of `qty` units of `symbol` at `price` each in the
+/// filtered or active investment account. It's logged as a transfer: the
+/// cash moves into a position rather than being spent.
+fn cmd_buy(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let usage = "Usage: :buy <symbol> <quantity> <price> (e.g. :buy VTI 10 215.40)";
+    let parts: Vec<&str> = args.split_whitespace().collect();
+    if parts.len() != 3 {
+        app.set_error(usage);
+        return Ok(());
+    }
+
+    let symbol = parts[0].to_uppercase();
+    let parse_positive = |s: &str| {
+        Decimal::from_str(s.trim_start_matches('$'))
+            .ok()
+            .filter(|d| *d > Decimal::ZERO)
+    };
+    let (quantity, price) = match (parse_positive(parts[1]), parse_positive(parts[2])) {
+        (Some(q), Some(p)) => (q, p),
+        (None, _) => {
+            app.set_error(format!("Invalid quantity: {} (must be positive)", parts[1]));
+            return Ok(());
+        }
+        (_, None) => {
+            app.set_error(format!("Invalid price: {} (must be positive)", parts[2]));
+            return Ok(());
+        }
+    };
+
+    let account = match app.transaction_filter_account {
+        Some(id) => app.find_account(id),
+        None => app.accounts.get(app.account_index),
+    };
+    let account = match account {
+        Some(a) if a.account_type == AccountType::Investment => a.clone(),
+        _ => {
+            app.set_error(
+                "Pick an investment account first (:filter-account <name> or n/p on the Dashboard)",
+            );
+            return Ok(());
+        }
+    };
+    let account_id = match account.id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let cost = (quantity * price).round_dp(2);
+    let price_str = format_amount(price, &account.currency);
+    let description = format!("Buy {quantity} {symbol} @ {price_str}");
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let txn = Transaction {
+        id: None,
+        account_id,
+        date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        description: description.clone(),
+        original_description: description,
+        amount: -cost,
+        category_id: None,
+        notes: String::new(),
+        is_transfer: true,
+        // Two identical buys on one day are both real
+        import_hash: format!("manual-buy-{created_at}"),
+        created_at,
+        tags: Vec::new(),
+        quantity: Some(quantity),
+        symbol: Some(symbol.clone()),
+        flagged: false,
+    };
+    db.insert_transaction(&txn)?;
+    app.refresh_transactions(db)?;
+    app.refresh_dashboard(db)?;
+    app.refresh_accounts_tab(db)?;
+    app.set_status(format!(
+        "Bought {quantity} {symbol} at {price_str} ({}) in {}",
+        format_amount(cost, &account.currency),
+        account.name
+    ));
+    Ok(())
+}
+
 fn cmd_delete_txn(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::Result<()> {
     if app.screen != Screen::Transactions || app.transactions.is_empty() {
         app.set_status("Navigate to Transactions and select one first");
@@ -677,6 +2352,22 @@ fn cmd_delete_txn(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::Res
 }
 
 fn cmd_export(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
+    let (columns, args) = match split_columns_flag(args) {
+        Some((None, _)) => {
+            app.set_error("Usage: :export [--columns date,amount,...] [path.csv|path.qif]");
+            return Ok(());
+        }
+        Some((Some(list), rest)) => match ExportColumn::parse_list(list) {
+            Ok(columns) => (Some(columns), rest),
+            Err(e) => {
+                app.set_error(e.to_string());
+                return Ok(());
+            }
+        },
+        None => (None, args.to_string()),
+    };
+    let args = args.as_str();
+
     let path = if args.is_empty() {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
         let suffix = app.current_month.as_deref().unwrap_or("all");
@@ -685,15 +2376,46 @@ fn cmd_export(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()
         crate::run::shellexpand(args)
     };
 
-    let count = db.export_to_csv(&path, app.current_month.as_deref())?;
+    let account = app.transaction_filter_account;
+    let month = app.current_month.as_deref();
+    let count = if path.to_lowercase().ends_with(".qif") {
+        if columns.is_some() {
+            app.set_error("--columns only applies to CSV exports");
+            return Ok(());
+        }
+        db.export_to_qif(&path, month, account)?
+    } else {
+        let columns = columns.as_deref().unwrap_or(&ExportColumn::DEFAULT);
+        db.export_to_csv(&path, month, account, columns)?
+    };
+    let from = account
+        .and_then(|id| app.find_account(id))
+        .map(|a| format!(" from {}", a.name))
+        .unwrap_or_default();
     if count == 0 {
-        app.set_status("No transactions to export");
+        app.set_status(format!("No transactions to export{from}"));
     } else {
-        app.set_status(format!("Exported {count} transactions to {path}"));
+        app.set_status(format!("Exported {count} transactions{from} to {path}"));
     }
     Ok(())
 }
 
+/// Pull `--columns <list>` out of the `:export` arguments. Returns `None`
+/// when the flag isn't there, otherwise the list (if one followed) and the
+/// remaining arguments.
+fn split_columns_flag(args: &str) -> Option<(Option<&str>, String)> {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let flag = words.iter().position(|w| *w == "--columns")?;
+    let list = words.get(flag + 1).copied();
+    let rest: Vec<&str> = words
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != flag && *i != flag + 1)
+        .map(|(_, w)| *w)
+        .collect();
+    Some((list, rest.join(" ")))
+}
+
 fn cmd_filter_account(args: &str, app: &mut App, db: &mut Database) -> anyhow::Result<()> {
     if args.is_empty() {
         // Clear filter
@@ -704,12 +2426,8 @@ fn cmd_filter_account(args: &str, app: &mut App, db: &mut Database) -> anyhow::R
         return Ok(());
     }
 
-    let accounts = db.get_accounts()?;
-    let found = accounts
-        .iter()
-        .find(|a| a.name.to_lowercase() == args.to_lowercase());
-
-    if let Some(acct) = found {
+    let accounts = db.get_accounts(true)?;
+    if let Some(acct) = find_account_by_name(&accounts, args) {
         app.transaction_filter_account = acct.id;
         app.screen = Screen::Transactions;
         app.transaction_index = 0;
@@ -718,7 +2436,7 @@ fn cmd_filter_account(args: &str, app: &mut App, db: &mut Database) -> anyhow::R
         app.set_status(format!("Filtering by account: {}", acct.name));
     } else {
         let names: Vec<&str> = accounts.iter().map(|a| a.name.as_str()).collect();
-        app.set_status(format!(
+        app.set_error(format!(
             "Account not found. Available: {}",
             names.join(", ")
         ));
@@ -755,11 +2473,39 @@ fn cmd_delete_selected(_args: &str, app: &mut App, _db: &mut Database) -> anyhow
     Ok(())
 }
 
-fn advance_month(app: &mut App, db: &mut Database, delta: i32) -> anyhow::Result<()> {
-    let base = app.current_month.as_ref().map_or_else(
-        || chrono::Local::now().format("%Y-%m").to_string(),
-        |m| m.clone(),
+fn cmd_wipe(_args: &str, app: &mut App, _db: &mut Database) -> anyhow::Result<()> {
+    app.command_input.clear();
+    app.confirm_message = format!(
+        "Delete ALL transactions, accounts, budgets, and rules? Type '{WIPE_CONFIRM_PHRASE}':"
     );
+    app.pending_action = Some(PendingAction::WipeAllData);
+    app.input_mode = InputMode::Confirm;
+    Ok(())
+}
+
+/// Switch the viewed month (`None` for all time) and refresh the
+/// month-dependent screens.
+pub(crate) fn set_month(
+    app: &mut App,
+    db: &mut Database,
+    month: Option<String>,
+) -> anyhow::Result<()> {
+    app.current_month = month;
+    app.refresh_dashboard(db)?;
+    app.refresh_budgets(db)?;
+    app.refresh_accounts_tab(db)?;
+    // A month-scoped list follows the viewed month
+    if app.list_month().is_some() {
+        app.refresh_transactions(db)?;
+    }
+    Ok(())
+}
+
+fn advance_month(app: &mut App, db: &mut Database, delta: i32) -> anyhow::Result<()> {
+    let base = app
+        .current_month
+        .clone()
+        .unwrap_or_else(|| app.this_month());
     if let Ok(date) = chrono::NaiveDate::parse_from_str(&format!("{base}-01"), "%Y-%m-%d") {
         let new_date = if delta > 0 {
             date.checked_add_months(chrono::Months::new(1))
@@ -770,11 +2516,8 @@ fn advance_month(app: &mut App, db: &mut Database, delta: i32) -> anyhow::Result
         if let Some(d) = new_date {
             let m = d.format("%Y-%m").to_string();
             app.set_status(format!("Month: {m}"));
-            app.current_month = Some(m);
             app.clear_selections();
-            app.refresh_dashboard(db)?;
-            app.refresh_budgets(db)?;
-            app.refresh_accounts_tab(db)?;
+            set_month(app, db, Some(m))?;
         }
     }
 
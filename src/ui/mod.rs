@@ -5,6 +5,9 @@ pub(crate) mod screens;
 pub(crate) mod theme;
 pub(crate) mod util;
 
+#[cfg(test)]
+#[path = "theme_tests.rs"]
+mod theme_tests;
 #[cfg(test)]
 #[path = "util_tests.rs"]
 mod util_tests;
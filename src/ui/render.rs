@@ -6,9 +6,12 @@ use ratatui::{
     Frame,
 };
 
-use super::app::{App, ImportStep, InputMode, Screen};
+use super::app::{App, ImportStep, InputMode, Screen, StatusLevel};
 use super::commands;
 use super::theme;
+use super::util::{format_amount, format_amount_aligned, sparkline, wrap_text};
+use crate::models::BASE_CURRENCY;
+use rust_decimal::Decimal;
 
 pub(crate) fn render(f: &mut Frame, app: &App) {
     let chunks = Layout::default()
@@ -28,6 +31,12 @@ pub(crate) fn render(f: &mut Frame, app: &App) {
 
     if app.show_nav {
         render_nav_overlay(f, f.area(), app);
+    } else if app.show_month_picker {
+        render_month_picker(f, f.area(), app);
+    } else if app.show_net_worth_history {
+        render_net_worth_history(f, f.area(), app);
+    } else if app.show_transaction_detail {
+        render_transaction_detail(f, f.area(), app);
     } else if app.show_help {
         render_help_overlay(f, f.area());
     }
@@ -44,13 +53,13 @@ fn render_hint_bar(f: &mut Frame, area: Rect, app: &App) {
         Span::styled(
             screen_name,
             Style::default()
-                .fg(theme::ACCENT)
+                .fg(theme::accent())
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(" ".repeat(pad), Style::default()),
-        Span::styled(hint, Style::default().fg(theme::TEXT_DIM)),
+        Span::styled(hint, Style::default().fg(theme::text_dim())),
     ]))
-    .style(Style::default().bg(theme::HEADER_BG));
+    .style(Style::default().bg(theme::header_bg()));
 
     f.render_widget(bar, area);
 }
@@ -70,15 +79,15 @@ fn render_nav_overlay(f: &mut Frame, area: Rect, app: &App) {
 
         let style = if i == app.nav_index {
             Style::default()
-                .fg(theme::HEADER_BG)
-                .bg(theme::ACCENT)
+                .fg(theme::header_bg())
+                .bg(theme::accent())
                 .add_modifier(Modifier::BOLD)
         } else if *screen == app.screen {
             Style::default()
-                .fg(theme::ACCENT)
+                .fg(theme::accent())
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(theme::TEXT)
+            Style::default().fg(theme::text())
         };
 
         lines.push(Line::from(Span::styled(entry, style)));
@@ -87,7 +96,7 @@ fn render_nav_overlay(f: &mut Frame, area: Rect, app: &App) {
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "  1-6 jump │ Enter go │ Esc close  ",
-        Style::default().fg(theme::TEXT_DIM),
+        Style::default().fg(theme::text_dim()),
     )));
 
     let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
@@ -102,16 +111,225 @@ fn render_nav_overlay(f: &mut Frame, area: Rect, app: &App) {
             .title(Span::styled(
                 " Navigate ",
                 Style::default()
-                    .fg(theme::ACCENT)
+                    .fg(theme::accent())
                     .add_modifier(Modifier::BOLD),
             ))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::ACCENT))
-            .style(Style::default().bg(theme::HEADER_BG)),
+            .border_style(Style::default().fg(theme::accent()))
+            .style(Style::default().bg(theme::header_bg())),
     );
     f.render_widget(nav, popup_area);
 }
 
+fn render_month_picker(f: &mut Frame, area: Rect, app: &App) {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let viewed = app.current_month.as_deref();
+
+    let cell_style = |selected: bool, current: bool| {
+        if selected {
+            Style::default()
+                .fg(theme::header_bg())
+                .bg(theme::accent())
+                .add_modifier(Modifier::BOLD)
+        } else if current {
+            Style::default()
+                .fg(theme::accent())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::text())
+        }
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  [  {}  ]", app.month_picker_year),
+            Style::default()
+                .fg(theme::yellow())
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                "  All time  ",
+                cell_style(app.month_picker_index == 0, viewed.is_none()),
+            ),
+        ]),
+        Line::from(""),
+    ];
+
+    for (r, names) in MONTHS.chunks(3).enumerate() {
+        let mut spans = vec![Span::raw("  ")];
+        for (c, name) in names.iter().enumerate() {
+            let idx = r * 3 + c + 1;
+            let month = format!("{}-{idx:02}", app.month_picker_year);
+            spans.push(Span::styled(
+                format!("  {name}  "),
+                cell_style(
+                    app.month_picker_index == idx,
+                    viewed == Some(month.as_str()),
+                ),
+            ));
+            spans.push(Span::raw(" "));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  h/j/k/l move │ [/] year │ Enter go │ Esc close  ",
+        Style::default().fg(theme::text_dim()),
+    )));
+
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let popup_width = 52.min(area.width.saturating_sub(4));
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+    let picker = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(
+                " Month ",
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::accent()))
+            .style(Style::default().bg(theme::header_bg())),
+    );
+    f.render_widget(picker, popup_area);
+}
+
+fn render_net_worth_history(f: &mut Frame, area: Rect, app: &App) {
+    let popup_height = area
+        .height
+        .saturating_sub(4)
+        .min(app.net_worth_history.len() as u16 + 8);
+    let popup_width = 44.min(area.width.saturating_sub(4));
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let values: Vec<Decimal> = app.net_worth_history.iter().map(|(_, v)| *v).collect();
+    let spark: String = sparkline(&values).into_iter().collect();
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(spark, Style::default().fg(theme::accent())),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {:<10} {:>14} {:>12}", "Month", "Net Worth", "Change"),
+            theme::header_style(),
+        )),
+    ];
+
+    // Most recent months when there are more than fit
+    let rows = popup_height.saturating_sub(8) as usize;
+    let skip = app.net_worth_history.len().saturating_sub(rows);
+    let mut prev = skip
+        .checked_sub(1)
+        .and_then(|i| app.net_worth_history.get(i))
+        .map(|(_, v)| *v);
+    for (month, value) in app.net_worth_history.iter().skip(skip) {
+        let change = prev.map(|p| *value - p).unwrap_or(Decimal::ZERO);
+        prev = Some(*value);
+        let change_color = if change > Decimal::ZERO {
+            theme::green()
+        } else if change < Decimal::ZERO {
+            theme::red()
+        } else {
+            theme::text_dim()
+        };
+        let sign = if change > Decimal::ZERO { "+" } else { "" };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {month:<10} "), theme::normal_style()),
+            Span::styled(
+                format_amount_aligned(*value, BASE_CURRENCY, 14),
+                theme::normal_style(),
+            ),
+            Span::styled(
+                format!(
+                    " {:>12}",
+                    format!("{sign}{}", format_amount(change, BASE_CURRENCY))
+                ),
+                Style::default().fg(change_color),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Press any key to close",
+        Style::default().fg(theme::text_dim()),
+    )));
+
+    f.render_widget(Clear, popup_area);
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(
+                " Net Worth History ",
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::accent()))
+            .style(Style::default().bg(theme::header_bg())),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+fn render_transaction_detail(f: &mut Frame, area: Rect, app: &App) {
+    let popup_width = 72.min(area.width.saturating_sub(4));
+    let value_width = (popup_width as usize).saturating_sub(18);
+
+    // Values wrap rather than truncate, so long merchant strings stay readable
+    let mut lines = vec![Line::from("")];
+    for (label, value) in app.transaction_details().unwrap_or_default() {
+        for (i, part) in wrap_text(&value, value_width).into_iter().enumerate() {
+            let label = if i == 0 { label } else { "" };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {label:<13} "), theme::header_style()),
+                Span::styled(part, theme::normal_style()),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Press any key to close",
+        Style::default().fg(theme::text_dim()),
+    )));
+
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(
+                " Transaction ",
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::accent()))
+            .style(Style::default().bg(theme::header_bg())),
+    );
+    f.render_widget(popup, popup_area);
+}
+
 fn render_screen(f: &mut Frame, area: Rect, app: &App) {
     match app.screen {
         Screen::Dashboard => super::screens::dashboard::render(f, area, app),
@@ -121,10 +339,7 @@ fn render_screen(f: &mut Frame, area: Rect, app: &App) {
         }
         Screen::Import => super::screens::import::render(f, area, app),
         Screen::Categories => super::screens::categories::render(f, area, app),
-        Screen::Budgets => {
-            let spending = &app.spending_by_category;
-            super::screens::budgets::render(f, area, app, spending);
-        }
+        Screen::Budgets => super::screens::budgets::render(f, area, app),
     }
 }
 
@@ -132,57 +347,100 @@ fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
     let mode_label = format!(" {} ", app.input_mode);
     let mode_style = match app.input_mode {
         InputMode::Normal => Style::default()
-            .fg(theme::HEADER_BG)
-            .bg(theme::ACCENT)
+            .fg(theme::header_bg())
+            .bg(theme::accent())
             .add_modifier(Modifier::BOLD),
         InputMode::Command => Style::default()
-            .fg(theme::HEADER_BG)
-            .bg(theme::GREEN)
+            .fg(theme::header_bg())
+            .bg(theme::green())
             .add_modifier(Modifier::BOLD),
         InputMode::Search => Style::default()
-            .fg(theme::HEADER_BG)
-            .bg(theme::YELLOW)
+            .fg(theme::header_bg())
+            .bg(theme::yellow())
             .add_modifier(Modifier::BOLD),
         InputMode::Editing => Style::default()
-            .fg(theme::HEADER_BG)
-            .bg(theme::GREEN)
+            .fg(theme::header_bg())
+            .bg(theme::green())
             .add_modifier(Modifier::BOLD),
         InputMode::Confirm => Style::default()
-            .fg(theme::HEADER_BG)
-            .bg(theme::RED)
+            .fg(theme::header_bg())
+            .bg(theme::red())
             .add_modifier(Modifier::BOLD),
     };
 
-    let month_label = app.current_month.as_deref().unwrap_or("All time");
-    let info = format!(
+    let month_label = match app.current_month.as_deref() {
+        // Shifted months also show the dates they cover
+        Some(month) if app.budget_start_day > 1 => {
+            match crate::models::budget_month_start(month, app.budget_start_day) {
+                Some(start) => {
+                    let last = start + chrono::Months::new(1) - chrono::Days::new(1);
+                    format!(
+                        "{month} ({}–{})",
+                        start.format("%b %-d"),
+                        last.format("%b %-d")
+                    )
+                }
+                None => month.to_string(),
+            }
+        }
+        Some(month) => month.to_string(),
+        None => "All time".to_string(),
+    };
+    let mut info = format!(
         " {} | {} | {} txns",
         app.screen, month_label, app.transaction_count
     );
+    if app.screen == Screen::Dashboard {
+        match app.dashboard_account() {
+            Some(account) => info.push_str(&format!(" | {}", account.name)),
+            None => info.push_str(" | All accounts"),
+        }
+    }
+    if app.screen == Screen::Transactions && app.transactions_truncated {
+        info.push_str(&format!(" | first {} shown", app.page_size));
+    }
 
     let right = match app.screen {
-        Screen::Dashboard => " H/L month | n/p account | ? help ",
-        Screen::Accounts => " j/k navigate | Enter view txns | ? help ",
+        Screen::Dashboard => {
+            " H/L month | m pick month | n/p account | A all/one account | u uncategorized | ? help "
+        }
+        Screen::Accounts => {
+            " j/k navigate | Enter view txns | e edit | x delete | A archived | ? help "
+        }
         Screen::Transactions => {
             if app.selected_transactions.is_empty() {
-                " Space select | D delete | /search | ? help "
+                " a add | Enter details | c category | f flag | M memorize | u uncategorized | z by day | t transfer | Space select | D delete | /search | ? help "
             } else {
                 " D delete selected | Esc clear | /search | ? help "
             }
         }
         Screen::Import => match app.import_step {
             ImportStep::SelectFile => " j/k navigate | Enter select | Esc back ",
-            ImportStep::MapColumns => " +/- adjust | Enter next | Esc back ",
+            ImportStep::MapColumns => " +/- adjust | ~ flip signs | Enter next | Esc back ",
             ImportStep::SelectAccount => " j/k navigate | Enter select | n new | Esc back ",
-            ImportStep::Preview => " Enter import | Esc back ",
+            ImportStep::Preview if !app.import_duplicates.is_empty() => {
+                " Space select | t transfer | n note | f flip signs | s skip/keep duplicates | Enter import | Esc back "
+            }
+            ImportStep::Preview => {
+                " Space select | t transfer | n note | f flip signs | Enter import | Esc back "
+            }
             ImportStep::Categorize => " j/k pick | Enter assign | s skip | S skip all | n new ",
             ImportStep::Complete => " Enter view txns | :d dashboard ",
         },
-        Screen::Categories => " r toggle rules | :rule add | ? help ",
+        Screen::Categories => " r toggle rules | :rule add | e edit rule | x delete | ? help ",
         Screen::Budgets => " :budget set | :delete-budget | ? help ",
     };
 
+    // A dot while edits are pending, then the time; both one cell per char
+    let dirty = if app.has_pending_edits() {
+        "● "
+    } else {
+        "  "
+    };
+    let clock = format!("{} ", chrono::Local::now().format("%H:%M"));
+
     let available = area.width as usize;
-    let used = mode_label.len() + info.len() + right.len();
+    let used = mode_label.len() + info.len() + right.len() + dirty.chars().count() + clock.len();
     let pad = available.saturating_sub(used);
 
     let bar = Paragraph::new(Line::from(vec![
@@ -190,6 +448,13 @@ fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
         Span::styled(&info, theme::status_bar_style()),
         Span::styled(" ".repeat(pad), theme::status_bar_style()),
         Span::styled(right, theme::status_bar_style()),
+        Span::styled(
+            dirty,
+            theme::status_bar_style()
+                .fg(theme::yellow())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(clock, theme::status_bar_style()),
     ]));
     f.render_widget(bar, area);
 }
@@ -198,20 +463,24 @@ fn render_command_bar(f: &mut Frame, area: Rect, app: &App) {
     let (content, cursor_offset) = match app.input_mode {
         InputMode::Command => (
             Line::from(vec![
-                Span::styled(":", Style::default().fg(theme::ACCENT)),
+                Span::styled(":", Style::default().fg(theme::accent())),
                 Span::styled(&app.command_input, theme::command_bar_style()),
             ]),
             Some(1 + app.command_input.len() as u16),
         ),
         InputMode::Search => {
             let match_info = if !app.search_input.is_empty() {
-                format!("  ({} matches)", app.transactions.len())
+                format!(
+                    "  ({} matches in {})",
+                    app.transactions.len(),
+                    app.search_scope_label()
+                )
             } else {
-                String::new()
+                format!("  (searching {})", app.search_scope_label())
             };
             (
                 Line::from(vec![
-                    Span::styled("/", Style::default().fg(theme::YELLOW)),
+                    Span::styled("/", Style::default().fg(theme::yellow())),
                     Span::styled(&app.search_input, theme::command_bar_style()),
                     Span::styled(match_info, theme::dim_style()),
                 ]),
@@ -220,35 +489,59 @@ fn render_command_bar(f: &mut Frame, area: Rect, app: &App) {
         }
         InputMode::Editing => (
             Line::from(vec![
-                Span::styled("edit> ", Style::default().fg(theme::GREEN)),
+                Span::styled("edit> ", Style::default().fg(theme::green())),
                 Span::styled(&app.command_input, theme::command_bar_style()),
             ]),
             Some(6 + app.command_input.len() as u16),
         ),
-        InputMode::Confirm => (
-            Line::from(vec![
-                Span::styled(&app.confirm_message, Style::default().fg(theme::YELLOW)),
-                Span::styled(" [y/N] ", Style::default().fg(theme::RED)),
-            ]),
-            None,
-        ),
+        InputMode::Confirm => {
+            let needs_phrase = app
+                .pending_action
+                .as_ref()
+                .and_then(|a| a.confirm_phrase())
+                .is_some();
+            if needs_phrase {
+                (
+                    Line::from(vec![
+                        Span::styled(&app.confirm_message, Style::default().fg(theme::red())),
+                        Span::raw(" "),
+                        Span::styled(&app.command_input, theme::command_bar_style()),
+                    ]),
+                    Some(
+                        app.confirm_message.chars().count() as u16
+                            + 1
+                            + app.command_input.chars().count() as u16,
+                    ),
+                )
+            } else {
+                (
+                    Line::from(vec![
+                        Span::styled(&app.confirm_message, Style::default().fg(theme::yellow())),
+                        Span::styled(" [y/N] ", Style::default().fg(theme::red())),
+                    ]),
+                    None,
+                )
+            }
+        }
         InputMode::Normal => (
-            if app.status_message.is_empty() {
+            if app.status.text.is_empty() {
                 Line::from(Span::styled(
                     " Press : for commands, / to search, ? for help",
                     theme::dim_style(),
                 ))
             } else {
-                Line::from(Span::styled(
-                    &app.status_message,
-                    theme::command_bar_style(),
-                ))
+                let style = match app.status.level {
+                    StatusLevel::Info => theme::command_bar_style(),
+                    StatusLevel::Warning => theme::command_bar_style().fg(theme::yellow()),
+                    StatusLevel::Error => theme::command_bar_style().fg(theme::red()),
+                };
+                Line::from(Span::styled(&app.status.text, style))
             },
             None,
         ),
     };
 
-    let bar = Paragraph::new(content).style(Style::default().bg(theme::COMMAND_BG));
+    let bar = Paragraph::new(content).style(Style::default().bg(theme::command_bg()));
     f.render_widget(bar, area);
 
     if let Some(offset) = cursor_offset {
@@ -261,14 +554,14 @@ fn render_help_overlay(f: &mut Frame, area: Rect) {
         Line::from(Span::styled(
             " BudgeTUI Help ",
             Style::default()
-                .fg(theme::ACCENT)
+                .fg(theme::accent())
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(Span::styled(
             " Navigation",
             Style::default()
-                .fg(theme::YELLOW)
+                .fg(theme::yellow())
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(Span::styled(
@@ -287,11 +580,15 @@ fn render_help_overlay(f: &mut Frame, area: Rect) {
             "  :nav             Screen navigator       Ctrl-q/c   Quit",
             theme::normal_style(),
         )),
+        Line::from(Span::styled(
+            "  m                Month picker",
+            theme::normal_style(),
+        )),
         Line::from(""),
         Line::from(Span::styled(
             " Actions",
             Style::default()
-                .fg(theme::YELLOW)
+                .fg(theme::yellow())
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(Span::styled(
@@ -322,7 +619,7 @@ fn render_help_overlay(f: &mut Frame, area: Rect) {
         Line::from(Span::styled(
             " Commands",
             Style::default()
-                .fg(theme::YELLOW)
+                .fg(theme::yellow())
                 .add_modifier(Modifier::BOLD),
         )),
     ];
@@ -349,7 +646,7 @@ fn render_help_overlay(f: &mut Frame, area: Rect) {
     help_text.push(Line::from(""));
     help_text.push(Line::from(Span::styled(
         " Press any key to close ",
-        Style::default().fg(theme::TEXT_DIM),
+        Style::default().fg(theme::text_dim()),
     )));
 
     // Center the popup, clamped to terminal height
@@ -363,8 +660,8 @@ fn render_help_overlay(f: &mut Frame, area: Rect) {
     let help = Paragraph::new(help_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::ACCENT))
-            .style(Style::default().bg(theme::HEADER_BG)),
+            .border_style(Style::default().fg(theme::accent()))
+            .style(Style::default().bg(theme::header_bg())),
     );
     f.render_widget(help, popup_area);
 }
@@ -1,5 +1,5 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
@@ -7,40 +7,71 @@ use ratatui::{
 };
 use rust_decimal::Decimal;
 
+use crate::models::{AccountType, BASE_CURRENCY};
 use crate::ui::app::App;
 use crate::ui::theme;
-use crate::ui::util::format_amount;
+use crate::ui::util::{format_amount, format_amount_aligned};
+
+/// Income and balance figures on an account card share this width so they
+/// line up down the card and from one card to the next.
+const CARD_AMOUNT_WIDTH: usize = 12;
 
 pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
     if app.account_snapshots.is_empty() {
+        let (headline, hint) = if app.archived_accounts.is_empty() {
+            (
+                "No accounts yet.",
+                "Create one with :account <name> [type] or import a CSV.",
+            )
+        } else {
+            (
+                "All accounts are archived.",
+                "Press A to show them, or create one with :account <name> [type].",
+            )
+        };
         let msg = Paragraph::new(vec![
             Line::from(""),
             Line::from(Span::styled(
-                "No accounts yet.",
+                headline,
                 theme::dim_style().add_modifier(Modifier::BOLD),
             )),
             Line::from(""),
-            Line::from(Span::styled(
-                "Create one with :account <name> [type] or import a CSV.",
-                theme::dim_style(),
-            )),
+            Line::from(Span::styled(hint, theme::dim_style())),
         ])
         .centered()
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(theme::OVERLAY)),
+                .border_style(Style::default().fg(theme::overlay())),
         );
         f.render_widget(msg, area);
         return;
     }
 
+    let area = if app.account_editing {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(7)])
+            .split(area);
+        render_edit_form(f, chunks[1], app);
+        chunks[0]
+    } else {
+        area
+    };
+
     // Each account card is 4 lines: 1 blank, 1 income/expenses, 1 balance, 1 blank
     // We render them as ListItems with 4-line height inside a bordered list.
     let card_height = 4_usize;
     let visible = area.height.saturating_sub(2) as usize; // minus list borders
     let cards_per_page = (visible / card_height).max(1);
 
+    // Once any account is outside the base currency, every balance names
+    // its currency so they aren't mistaken for one another
+    let show_currency = app
+        .account_snapshots
+        .iter()
+        .any(|s| s.account.currency != BASE_CURRENCY);
+
     let items: Vec<ListItem> = app
         .account_snapshots
         .iter()
@@ -52,12 +83,23 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
             let selected = i == app.accounts_tab_index;
 
             let border_color = if selected {
-                theme::ACCENT
+                theme::accent()
             } else {
-                theme::OVERLAY
+                theme::overlay()
             };
 
-            let title = format!(" {} ({}) ", snap.account.name, snap.account.account_type);
+            // The institution tells apart two accounts of the same type
+            let mut title = if snap.account.institution.is_empty() {
+                format!(" {} ({}) ", snap.account.name, snap.account.account_type)
+            } else {
+                format!(
+                    " {} ({}, {}) ",
+                    snap.account.name, snap.account.account_type, snap.account.institution
+                )
+            };
+            if snap.account.archived {
+                title.push_str("[archived] ");
+            }
 
             // Line 1: title with border chars
             let title_line = Line::from(vec![
@@ -66,9 +108,9 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
                     title,
                     Style::default()
                         .fg(if selected {
-                            theme::ACCENT
+                            theme::accent()
                         } else {
-                            theme::TEXT_DIM
+                            theme::text_dim()
                         })
                         .add_modifier(Modifier::BOLD),
                 ),
@@ -84,25 +126,57 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
             let neg_val = snap.month_expenses.abs();
 
             let detail_line = Line::from(vec![
-                Span::styled(format!("  {pos_label}: "), theme::dim_style()),
-                Span::styled(format_amount(pos_val), Style::default().fg(theme::GREEN)),
+                Span::styled(
+                    format!("  {:<10}", format!("{pos_label}:")),
+                    theme::dim_style(),
+                ),
+                Span::styled(
+                    format_amount_aligned(pos_val, &snap.account.currency, CARD_AMOUNT_WIDTH),
+                    Style::default().fg(theme::green()),
+                ),
                 Span::styled(format!("    {neg_label}: "), theme::dim_style()),
-                Span::styled(format_amount(neg_val), Style::default().fg(theme::RED)),
+                Span::styled(
+                    format_amount(neg_val, &snap.account.currency),
+                    Style::default().fg(theme::red()),
+                ),
             ]);
 
             // Line 3: balance
             let bal_color = if snap.balance >= Decimal::ZERO {
-                theme::GREEN
+                theme::green()
             } else {
-                theme::RED
+                theme::red()
             };
-            let balance_line = Line::from(vec![
-                Span::styled("  Balance: ", theme::dim_style()),
+            let mut balance_spans = vec![
+                Span::styled(format!("  {:<10}", "Balance:"), theme::dim_style()),
                 Span::styled(
-                    format_amount(snap.balance),
+                    format_amount_aligned(snap.balance, &snap.account.currency, CARD_AMOUNT_WIDTH),
                     Style::default().fg(bal_color).add_modifier(Modifier::BOLD),
                 ),
-            ]);
+            ];
+            if show_currency {
+                balance_spans.push(Span::styled(
+                    format!(" {}", snap.account.currency),
+                    theme::dim_style(),
+                ));
+            }
+            if !snap.account.opening_balance.is_zero() {
+                balance_spans.push(Span::styled(
+                    format!(
+                        "    (opening {})",
+                        format_amount(snap.account.opening_balance, &snap.account.currency)
+                    ),
+                    theme::dim_style(),
+                ));
+            }
+            if let Some(invested) = snap.invested {
+                balance_spans.push(Span::styled("    Invested: ", theme::dim_style()));
+                balance_spans.push(Span::styled(
+                    format_amount(invested, &snap.account.currency),
+                    Style::default().fg(theme::accent()),
+                ));
+            }
+            let balance_line = Line::from(balance_spans);
 
             // Line 4: bottom border (dynamic width)
             let border_width = (area.width as usize).saturating_sub(3);
@@ -118,14 +192,58 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 format!(
-                    " {} Accounts | j/k navigate | Enter view transactions ",
-                    app.account_snapshots.len()
+                    " {} Accounts | j/k navigate | Enter view transactions | e edit | x delete | A {} archived ",
+                    app.account_snapshots.len(),
+                    if app.show_archived { "hide" } else { "show" }
                 ),
                 theme::dim_style(),
             )),
     );
     f.render_widget(list, area);
 }
+
+fn render_edit_form(f: &mut Frame, area: Rect, app: &App) {
+    let type_name = AccountType::all()
+        .get(app.account_edit_type)
+        .map(|t| t.as_str())
+        .unwrap_or("Checking");
+
+    let form = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Name: ", theme::dim_style()),
+            Span::styled(&app.account_edit_name, Style::default().fg(theme::text())),
+            Span::styled("█", Style::default().fg(theme::accent())),
+        ]),
+        Line::from(vec![
+            Span::styled("  Type: ", theme::dim_style()),
+            Span::styled(
+                type_name,
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  (+/- to change)", theme::dim_style()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Enter to save, Esc to cancel",
+            theme::dim_style(),
+        )),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::accent()))
+            .title(Span::styled(
+                " Edit Account ",
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            )),
+    );
+    f.render_widget(form, area);
+}
@@ -1,19 +1,19 @@
 use ratatui::{
     layout::Rect,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 use rust_decimal::prelude::ToPrimitive;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 
-use crate::models::Category;
+use crate::models::{BudgetPeriod, Category, BASE_CURRENCY};
 use crate::ui::app::App;
 use crate::ui::theme;
 use crate::ui::util::{format_amount, truncate};
 
-pub(crate) fn render(f: &mut Frame, area: Rect, app: &App, spending: &[(String, Decimal)]) {
+pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
     if app.budgets.is_empty() {
         render_empty(f, area);
         return;
@@ -30,28 +30,21 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App, spending: &[(String,
                 .map(|c| c.name.as_str())
                 .unwrap_or("Unknown");
 
-            let spent = spending
-                .iter()
-                .find(|(name, _)| name == cat_name)
-                .map(|(_, amt)| amt.abs())
+            let is_yearly = budget.period == BudgetPeriod::Yearly;
+            let spent = budget
+                .id
+                .and_then(|id| app.budget_spent.get(&id).copied())
                 .unwrap_or(Decimal::ZERO);
 
-            let ratio = if budget.limit_amount > Decimal::ZERO {
-                (spent / budget.limit_amount)
-                    .to_f64()
-                    .unwrap_or(0.0)
-                    .min(1.0)
-            } else {
-                0.0
-            };
+            // Available = limit + whatever carried in from earlier months
+            let rollover = budget
+                .id
+                .and_then(|id| app.budget_rollovers.get(&id).copied())
+                .unwrap_or(Decimal::ZERO);
+            let available = budget.limit_amount + rollover;
 
-            let color = if ratio > 0.9 {
-                theme::RED
-            } else if ratio > 0.7 {
-                theme::YELLOW
-            } else {
-                theme::GREEN
-            };
+            let progress = budget_progress(spent, available);
+            let color = progress.status.color();
 
             let style = if i == app.budget_index {
                 theme::selected_style()
@@ -61,32 +54,54 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App, spending: &[(String,
                 theme::normal_style()
             };
 
-            let bar = create_progress_bar(ratio, 20);
-            let display_name = truncate(cat_name, 17);
+            let bar = create_progress_bar(progress.bar_fill(), 20);
+            let display_name = if budget.include_subcategories {
+                format!("{}+", truncate(cat_name, 16))
+            } else {
+                truncate(cat_name, 17)
+            };
 
-            ListItem::new(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(format!("{display_name:<18}"), style),
                 Span::styled(
                     format!(
                         "{}/{} ",
-                        format_amount(spent),
-                        format_amount(budget.limit_amount)
+                        format_amount(spent, BASE_CURRENCY),
+                        format_amount(available, BASE_CURRENCY)
                     ),
                     Style::default().fg(color),
                 ),
                 Span::styled(bar, Style::default().fg(color)),
                 Span::styled(
-                    format!(" {:.0}%", ratio * 100.0),
+                    format!(" {}", progress.label()),
                     Style::default().fg(color).add_modifier(Modifier::BOLD),
                 ),
-            ]))
+            ];
+            if budget.rollover {
+                let sign = if rollover < Decimal::ZERO { "" } else { "+" };
+                spans.push(Span::styled(
+                    format!(
+                        "  {sign}{} rollover",
+                        format_amount(rollover, BASE_CURRENCY)
+                    ),
+                    theme::dim_style(),
+                ));
+            }
+            if is_yearly {
+                spans.push(Span::styled(
+                    format!("  {} YTD", budget.month),
+                    theme::dim_style(),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 format!(
                     " Budgets for {} ",
@@ -98,6 +113,96 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App, spending: &[(String,
     f.render_widget(list, area);
 }
 
+/// Where a budget stands relative to its available amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BudgetStatus {
+    /// Under 80% used
+    Under,
+    /// 80% up to (but not including) 100% used
+    Near,
+    /// Exactly 100% used
+    OnBudget,
+    Over,
+}
+
+impl BudgetStatus {
+    pub(crate) fn color(self) -> Color {
+        match self {
+            Self::Under => theme::green(),
+            Self::Near | Self::OnBudget => theme::yellow(),
+            Self::Over => theme::red(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BudgetProgress {
+    /// Spent / available, uncapped. `None` when nothing is available.
+    pub(crate) ratio: Option<Decimal>,
+    pub(crate) status: BudgetStatus,
+}
+
+impl BudgetProgress {
+    /// Fraction of the bar to fill; over-budget bars stop at full.
+    pub(crate) fn bar_fill(&self) -> f64 {
+        match (self.ratio, self.status) {
+            (Some(ratio), _) => ratio.to_f64().unwrap_or(0.0).clamp(0.0, 1.0),
+            (None, BudgetStatus::Over) => 1.0,
+            (None, _) => 0.0,
+        }
+    }
+
+    /// The real percentage used, e.g. "85%" or "130%". Rounded away from
+    /// 100 so a bar that isn't exactly on budget never reads "100%".
+    pub(crate) fn label(&self) -> String {
+        match (self.ratio, self.status) {
+            (_, BudgetStatus::OnBudget) => "on budget".to_string(),
+            (None, BudgetStatus::Over) => "over".to_string(),
+            (None, _) => "0%".to_string(),
+            (Some(ratio), status) => {
+                let strategy = if status == BudgetStatus::Over {
+                    RoundingStrategy::AwayFromZero
+                } else {
+                    RoundingStrategy::ToZero
+                };
+                let percent = (ratio * Decimal::ONE_HUNDRED).round_dp_with_strategy(0, strategy);
+                format!("{percent}%")
+            }
+        }
+    }
+}
+
+/// Compare spend against what a budget has available: green under 80%,
+/// yellow from 80% through exactly 100%, red beyond.
+pub(crate) fn budget_progress(spent: Decimal, available: Decimal) -> BudgetProgress {
+    if available <= Decimal::ZERO {
+        let status = if spent > Decimal::ZERO {
+            BudgetStatus::Over
+        } else {
+            BudgetStatus::Under
+        };
+        return BudgetProgress {
+            ratio: None,
+            status,
+        };
+    }
+
+    let ratio = spent / available;
+    let status = if ratio > Decimal::ONE {
+        BudgetStatus::Over
+    } else if ratio == Decimal::ONE {
+        BudgetStatus::OnBudget
+    } else if ratio >= Decimal::new(8, 1) {
+        BudgetStatus::Near
+    } else {
+        BudgetStatus::Under
+    };
+    BudgetProgress {
+        ratio: Some(ratio),
+        status,
+    }
+}
+
 fn render_empty(f: &mut Frame, area: Rect) {
     let msg = Paragraph::new(vec![
         Line::from(""),
@@ -115,7 +220,7 @@ fn render_empty(f: &mut Frame, area: Rect) {
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 " Budgets ",
                 theme::dim_style().add_modifier(Modifier::BOLD),
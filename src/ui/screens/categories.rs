@@ -17,19 +17,102 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
     render_category_list(f, chunks[0], app);
-    render_rules_list(f, chunks[1], app);
+
+    let rules_area = if app.rule_editing {
+        let rule_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(9)])
+            .split(chunks[1]);
+        render_rule_edit_form(f, rule_chunks[1], app);
+        rule_chunks[0]
+    } else {
+        chunks[1]
+    };
+    render_rules_list(f, rules_area, app);
+}
+
+fn render_rule_edit_form(f: &mut Frame, area: Rect, app: &App) {
+    let cat_name = app
+        .categories
+        .get(app.rule_edit_category)
+        .map(|c| c.name.as_str())
+        .unwrap_or("?");
+
+    let form = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Pattern:  ", theme::dim_style()),
+            Span::styled(&app.rule_edit_pattern, Style::default().fg(theme::text())),
+            Span::styled("█", Style::default().fg(theme::accent())),
+        ]),
+        Line::from(vec![
+            Span::styled("  Type:     ", theme::dim_style()),
+            Span::styled(
+                if app.rule_edit_regex {
+                    "regex"
+                } else {
+                    "contains"
+                },
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  (Ctrl+r to toggle)", theme::dim_style()),
+        ]),
+        Line::from(vec![
+            Span::styled("  Words:    ", theme::dim_style()),
+            Span::styled(
+                if app.rule_edit_whole_word {
+                    "whole words only"
+                } else {
+                    "anywhere"
+                },
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  (Ctrl+w to toggle)", theme::dim_style()),
+        ]),
+        Line::from(vec![
+            Span::styled("  Category: ", theme::dim_style()),
+            Span::styled(
+                cat_name,
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  (Tab/Shift+Tab to change)", theme::dim_style()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Enter to save, Esc to cancel",
+            theme::dim_style(),
+        )),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::accent()))
+            .title(Span::styled(
+                " Edit Rule ",
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            )),
+    );
+    f.render_widget(form, area);
 }
 
 fn render_category_list(f: &mut Frame, area: Rect, app: &App) {
     let border_color = if !app.category_view_rules {
-        theme::ACCENT
+        theme::accent()
     } else {
-        theme::OVERLAY
+        theme::overlay()
     };
     let title_color = if !app.category_view_rules {
-        theme::ACCENT
+        theme::accent()
     } else {
-        theme::TEXT_DIM
+        theme::text_dim()
     };
 
     if app.categories.is_empty() {
@@ -39,7 +122,7 @@ fn render_category_list(f: &mut Frame, area: Rect, app: &App) {
             Line::from(""),
             Line::from(Span::styled(
                 "Add with :category <name>",
-                Style::default().fg(theme::ACCENT),
+                Style::default().fg(theme::accent()),
             )),
         ])
         .centered()
@@ -74,7 +157,21 @@ fn render_category_list(f: &mut Frame, area: Rect, app: &App) {
                 theme::normal_style()
             };
 
-            ListItem::new(Line::from(Span::styled(&cat.name, style)))
+            let mut spans = Vec::new();
+            if !cat.icon.is_empty() {
+                spans.push(Span::styled(format!("{} ", cat.icon), style));
+            }
+            spans.push(Span::styled(&cat.name, style));
+            if let Some(color) = theme::category_color(&cat.color) {
+                spans.push(Span::styled(" ●", Style::default().fg(color)));
+            }
+            let parent = cat
+                .parent_id
+                .and_then(|pid| Category::find_by_id(&app.categories, pid));
+            if let Some(p) = parent {
+                spans.push(Span::styled(format!("  ({})", p.name), theme::dim_style()));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -94,14 +191,14 @@ fn render_category_list(f: &mut Frame, area: Rect, app: &App) {
 
 fn render_rules_list(f: &mut Frame, area: Rect, app: &App) {
     let rules_border_color = if app.category_view_rules {
-        theme::ACCENT
+        theme::accent()
     } else {
-        theme::OVERLAY
+        theme::overlay()
     };
     let rules_title_color = if app.category_view_rules {
-        theme::ACCENT
+        theme::accent()
     } else {
-        theme::TEXT_DIM
+        theme::text_dim()
     };
 
     if app.import_rules.is_empty() {
@@ -118,7 +215,7 @@ fn render_rules_list(f: &mut Frame, area: Rect, app: &App) {
             )),
             Line::from(Span::styled(
                 "e.g. :rule amazon Shopping",
-                Style::default().fg(theme::ACCENT),
+                Style::default().fg(theme::accent()),
             )),
         ])
         .centered()
@@ -137,7 +234,7 @@ fn render_rules_list(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let header_cells = ["Pattern", "Category", "Type"]
+    let header_cells = ["Pattern", "Category", "Type", "From", "Last used"]
         .iter()
         .map(|h| Cell::from(*h).style(theme::header_style()));
     let header = Row::new(header_cells).height(1);
@@ -163,7 +260,14 @@ fn render_rules_list(f: &mut Frame, area: Rect, app: &App) {
             Row::new(vec![
                 Cell::from(rule.pattern.as_str()),
                 Cell::from(cat_name),
-                Cell::from(if rule.is_regex { "regex" } else { "contains" }),
+                Cell::from(match (rule.is_regex, rule.whole_word) {
+                    (true, true) => "regex, word",
+                    (true, false) => "regex",
+                    (false, true) => "word",
+                    (false, false) => "contains",
+                }),
+                Cell::from(rule.source.as_str()),
+                last_used_cell(rule.last_matched_at.as_deref()),
             ])
             .style(style)
         })
@@ -172,6 +276,8 @@ fn render_rules_list(f: &mut Frame, area: Rect, app: &App) {
     let widths = [
         Constraint::Min(20),
         Constraint::Length(18),
+        Constraint::Length(12),
+        Constraint::Length(9),
         Constraint::Length(10),
     ];
 
@@ -181,7 +287,7 @@ fn render_rules_list(f: &mut Frame, area: Rect, app: &App) {
             .border_style(Style::default().fg(rules_border_color))
             .title(Span::styled(
                 format!(
-                    " Rules ({}) | :rule <pattern> <category> to add ",
+                    " Rules ({}) | :rule <pattern> <category> to add | e edit | x delete ",
                     app.import_rules.len()
                 ),
                 Style::default()
@@ -191,3 +297,17 @@ fn render_rules_list(f: &mut Frame, area: Rect, app: &App) {
     );
     f.render_widget(table, area);
 }
+
+/// The local date a rule last categorized a stored transaction. Rules that
+/// never have are marked in yellow as candidates for cleanup.
+fn last_used_cell(last_matched_at: Option<&str>) -> Cell<'static> {
+    match last_matched_at.map(chrono::DateTime::parse_from_rfc3339) {
+        Some(Ok(t)) => Cell::from(
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string(),
+        ),
+        Some(Err(_)) => Cell::from("?"),
+        None => Cell::from(Span::styled("never", Style::default().fg(theme::yellow()))),
+    }
+}
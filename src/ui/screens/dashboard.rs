@@ -1,3 +1,4 @@
+use chrono::{Months, NaiveDate};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -6,30 +7,43 @@ use ratatui::{
     Frame,
 };
 use rust_decimal::prelude::ToPrimitive;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 
+use crate::models::{budget_month_start, Category};
 use crate::ui::app::App;
 use crate::ui::theme;
-use crate::ui::util::{format_amount, truncate};
+use crate::ui::util::{format_amount, format_amount_aligned, format_decimal, sparkline, truncate};
 
 pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .spacing(1)
-        .constraints([
-            Constraint::Length(5), // Debit accounts row
-            Constraint::Length(5), // Credit accounts row
+    // Scoped to one account, only the row for its kind has anything to show
+    let card_rows: &[fn(&mut Frame, Rect, &App)] = match app.dashboard_account() {
+        Some(account) if account.account_type.is_credit() => &[render_credit_row],
+        Some(_) => &[render_debit_row],
+        None => &[render_debit_row, render_credit_row],
+    };
+    let constraints = card_rows
+        .iter()
+        .map(|_| Constraint::Length(5)) // Debit / credit account rows
+        .chain([
             Constraint::Length(3), // Net worth
+            Constraint::Length(3), // Income vs expenses
             Constraint::Min(8),    // Spending by category
             Constraint::Length(5), // Monthly trend
-        ])
+        ]);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .spacing(1)
+        .constraints(constraints)
         .split(area);
 
-    render_debit_row(f, chunks[0], app);
-    render_credit_row(f, chunks[1], app);
-    render_net_worth(f, chunks[2], app);
-    render_spending_chart(f, chunks[3], app);
-    render_trend_chart(f, chunks[4], app);
+    for (row, chunk) in card_rows.iter().zip(chunks.iter()) {
+        row(f, *chunk, app);
+    }
+    let rest = &chunks[card_rows.len()..];
+    render_net_worth(f, rest[0], app);
+    render_income_expense_bar(f, rest[1], app);
+    render_spending_chart(f, rest[2], app);
+    render_trend_chart(f, rest[3], app);
 }
 
 fn render_debit_row(f: &mut Frame, area: Rect, app: &App) {
@@ -43,33 +57,38 @@ fn render_debit_row(f: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
     let debit_net = app.debit_income + app.debit_expenses;
+    let group = card_group(app, "Debit");
+    let currency = app.dashboard_currency();
 
     render_card(
         f,
         cards[0],
-        "Debit",
+        group,
         "Income",
         app.debit_income,
-        theme::GREEN,
+        currency,
+        theme::green(),
     );
     render_card(
         f,
         cards[1],
-        "Debit",
+        group,
         "Expenses",
         app.debit_expenses.abs(),
-        theme::RED,
+        currency,
+        theme::red(),
     );
     render_card(
         f,
         cards[2],
-        "Debit",
+        group,
         "Net",
         debit_net,
+        currency,
         if debit_net >= Decimal::ZERO {
-            theme::GREEN
+            theme::green()
         } else {
-            theme::RED
+            theme::red()
         },
     );
 }
@@ -84,75 +103,317 @@ fn render_credit_row(f: &mut Frame, area: Rect, app: &App) {
         ])
         .split(area);
 
+    let group = card_group(app, "Credit");
+    let currency = app.dashboard_currency();
+
     render_card(
         f,
         cards[0],
-        "Credit",
+        group,
         "Charges",
         app.credit_charges.abs(),
-        theme::RED,
+        currency,
+        theme::red(),
     );
     render_card(
         f,
         cards[1],
-        "Credit",
+        group,
         "Payments",
         app.credit_payments,
-        theme::GREEN,
+        currency,
+        theme::green(),
     );
     render_card(
         f,
         cards[2],
-        "Credit",
+        group,
         "Balance",
         app.credit_balance,
+        currency,
         if app.credit_balance >= Decimal::ZERO {
-            theme::GREEN
+            theme::green()
         } else {
-            theme::RED
+            theme::red()
         },
     );
 }
 
+/// Card title prefix: the scoped account's name, or the kind of account
+/// the row sums up.
+fn card_group<'a>(app: &'a App, kind: &'a str) -> &'a str {
+    app.dashboard_account()
+        .map(|a| a.name.as_str())
+        .unwrap_or(kind)
+}
+
 fn render_net_worth(f: &mut Frame, area: Rect, app: &App) {
-    let display = format_amount(app.net_worth);
+    let display = format_amount(app.net_worth, app.dashboard_currency());
     let color = if app.net_worth >= Decimal::ZERO {
-        theme::GREEN
+        theme::green()
     } else {
-        theme::RED
+        theme::red()
     };
 
-    let bar = Paragraph::new(Line::from(vec![
-        Span::styled(
-            " Net Worth  ",
-            theme::dim_style().add_modifier(Modifier::BOLD),
-        ),
+    let label = if app.dashboard_account().is_some() {
+        " Balance  "
+    } else {
+        " Net Worth  "
+    };
+    let mut spans = vec![
+        Span::styled(label, theme::dim_style().add_modifier(Modifier::BOLD)),
         Span::styled(
             display,
             Style::default().fg(color).add_modifier(Modifier::BOLD),
         ),
-    ]))
-    .block(
+    ];
+    // The trend behind the sparkline covers every account
+    if app.dashboard_account().is_none() {
+        spans.extend(net_income_sparkline(app));
+    }
+    spans.extend(spending_pace_spans(app));
+    if !app.unconverted_balances.is_empty() {
+        // Foreign balances with no exchange rate are listed, not counted
+        let parts: Vec<String> = app
+            .unconverted_balances
+            .iter()
+            .map(|(currency, amount)| format!("{currency} {}", format_decimal(*amount, 2, true)))
+            .collect();
+        spans.push(Span::styled(
+            format!("   ⚠ No rate, excluded: {}", parts.join(", ")),
+            Style::default().fg(theme::yellow()),
+        ));
+    }
+
+    let bar = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::overlay())),
+    );
+    f.render_widget(bar, area);
+}
+
+/// Cells of a `width`-wide bar given to income and to expenses, in
+/// proportion to their magnitudes. A side with any amount keeps at least one
+/// cell; a month with neither leaves the bar empty.
+pub(crate) fn income_expense_split(
+    income: Decimal,
+    expenses: Decimal,
+    width: usize,
+) -> (usize, usize) {
+    let (income, expenses) = (income.abs(), expenses.abs());
+    let total = income + expenses;
+    if total.is_zero() || width == 0 {
+        return (0, 0);
+    }
+    let share = (income / total * Decimal::from(width))
+        .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+        .to_usize()
+        .unwrap_or(0)
+        .min(width);
+    // Rounding can swallow a small side whole; give it a sliver back
+    let share = if income.is_zero() || width < 2 {
+        share
+    } else if expenses.is_zero() {
+        width
+    } else {
+        share.clamp(1, width - 1)
+    };
+    (share, width - share)
+}
+
+fn render_income_expense_bar(f: &mut Frame, area: Rect, app: &App) {
+    let currency = app.dashboard_currency();
+    let net = app.monthly_income + app.monthly_expenses;
+    let net_color = if net >= Decimal::ZERO {
+        theme::green()
+    } else {
+        theme::red()
+    };
+    let net_text = format!("   Net  {}", format_amount(net, currency));
+    let label = " In vs Out  ";
+
+    let inner = area.width.saturating_sub(2) as usize;
+    let width = inner.saturating_sub(label.chars().count() + net_text.chars().count() + 2);
+    let (income_cells, expense_cells) =
+        income_expense_split(app.monthly_income, app.monthly_expenses, width);
+
+    let mut spans = vec![Span::styled(
+        label,
+        theme::dim_style().add_modifier(Modifier::BOLD),
+    )];
+    if income_cells + expense_cells == 0 {
+        spans.push(Span::styled("░".repeat(width), theme::dim_style()));
+    } else {
+        spans.push(Span::styled(
+            "█".repeat(income_cells),
+            Style::default().fg(theme::green()),
+        ));
+        spans.push(Span::styled(
+            "█".repeat(expense_cells),
+            Style::default().fg(theme::red()),
+        ));
+    }
+    spans.push(Span::styled(
+        net_text,
+        Style::default().fg(net_color).add_modifier(Modifier::BOLD),
+    ));
+
+    let bar = Paragraph::new(Line::from(spans)).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY)),
+            .border_style(Style::default().fg(theme::overlay())),
     );
     f.render_widget(bar, area);
 }
 
+/// Net income per month from `monthly_trend`, oldest first, with the viewed
+/// month (or this month when viewing all time) highlighted. No history
+/// renders as a flat baseline.
+fn net_income_sparkline(app: &App) -> Vec<Span<'static>> {
+    let highlight = app
+        .current_month
+        .clone()
+        .unwrap_or_else(|| app.this_month());
+
+    let nets: Vec<Decimal> = app
+        .monthly_trend
+        .iter()
+        .map(|(_, income, expenses)| income + expenses)
+        .collect();
+
+    let mut spans = vec![Span::styled("   Net / month  ", theme::dim_style())];
+    if nets.is_empty() {
+        spans.push(Span::styled("▁".repeat(12), theme::dim_style()));
+        return spans;
+    }
+
+    for (((month, _, _), net), ch) in app.monthly_trend.iter().zip(&nets).zip(sparkline(&nets)) {
+        let color = if *net >= Decimal::ZERO {
+            theme::green()
+        } else {
+            theme::red()
+        };
+        let style = if *month == highlight {
+            Style::default()
+                .fg(color)
+                .bg(theme::surface())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        };
+        spans.push(Span::styled(ch.to_string(), style));
+    }
+    spans
+}
+
+/// How the viewed month's spending is going, shown beside net worth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SpendingPace {
+    /// The month under way: average per elapsed day (today included) and
+    /// that rate carried to the last day of the month.
+    InProgress { daily: Decimal, projected: Decimal },
+    /// A finished month's total spend.
+    Final(Decimal),
+}
+
+/// Pace for `spent` (a positive total) in `month` (`YYYY-MM`) as of
+/// `today`. Months that haven't started, or malformed months, get none.
+pub(crate) fn spending_pace(
+    spent: Decimal,
+    month: &str,
+    today: NaiveDate,
+    start_day: u32,
+) -> Option<SpendingPace> {
+    let first = budget_month_start(month, start_day)?;
+    let next = first.checked_add_months(Months::new(1))?;
+    if today >= next {
+        return Some(SpendingPace::Final(spent));
+    }
+    if today < first {
+        return None;
+    }
+    // Today counts as elapsed, so day one divides by one, never zero
+    let elapsed = Decimal::from((today - first).num_days() + 1);
+    let days = Decimal::from((next - first).num_days());
+    let daily = spent / elapsed;
+    Some(SpendingPace::InProgress {
+        daily: daily.round_dp(2),
+        projected: (daily * days).round_dp(2),
+    })
+}
+
+fn spending_pace_spans(app: &App) -> Vec<Span<'static>> {
+    let currency = app.dashboard_currency();
+    match app.spending_pace {
+        Some(SpendingPace::InProgress { daily, projected }) => vec![
+            Span::styled("   Avg/day  ", theme::dim_style()),
+            Span::styled(
+                format_amount(daily, currency),
+                Style::default().fg(theme::red()),
+            ),
+            Span::styled("   On pace for  ", theme::dim_style()),
+            Span::styled(
+                format_amount(projected, currency),
+                Style::default()
+                    .fg(theme::red())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ],
+        Some(SpendingPace::Final(total)) => vec![
+            Span::styled("   Month spend  ", theme::dim_style()),
+            Span::styled(
+                format_amount(total, currency),
+                Style::default().fg(theme::red()),
+            ),
+        ],
+        None => Vec::new(),
+    }
+}
+
+/// How much of the month's spending still needs a category, in yellow
+/// until it's all been filed.
+fn uncategorized_title(app: &App) -> Line<'static> {
+    let (total, count) = app.uncategorized_summary;
+    if count == 0 {
+        return Line::from(Span::styled(
+            " ✓ All spending categorized ",
+            Style::default().fg(theme::green()),
+        ));
+    }
+    let noun = if count == 1 {
+        "transaction"
+    } else {
+        "transactions"
+    };
+    Line::from(vec![
+        Span::styled(
+            format!(
+                " Uncategorized: {} across {count} {noun}",
+                format_amount(total, app.dashboard_currency())
+            ),
+            Style::default()
+                .fg(theme::yellow())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" · u to review ", theme::dim_style()),
+    ])
+}
+
 fn render_card(
     f: &mut Frame,
     area: Rect,
     group: &str,
     title: &str,
     amount: Decimal,
+    currency: &str,
     color: ratatui::style::Color,
 ) {
-    let display = format_amount(amount);
+    let display = format_amount(amount, currency);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme::OVERLAY))
+        .border_style(Style::default().fg(theme::overlay()))
         .title(Span::styled(
             format!(" {group}: {title} "),
             theme::dim_style().add_modifier(Modifier::BOLD),
@@ -172,21 +433,31 @@ fn render_card(
 }
 
 fn render_spending_chart(f: &mut Frame, area: Rect, app: &App) {
-    let block = Block::default()
+    let title = match app.transfer_count {
+        0 => " Spending by Category ".to_string(),
+        1 => " Spending by Category · 1 transfer excluded ".to_string(),
+        n => format!(" Spending by Category · {n} transfers excluded "),
+    };
+    let mut block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme::OVERLAY))
+        .border_style(Style::default().fg(theme::overlay()))
         .title(Span::styled(
-            " Spending by Category ",
+            title,
             theme::dim_style().add_modifier(Modifier::BOLD),
         ));
+    let spending = app.dashboard_spending();
+    if !spending.is_empty() {
+        block = block.title(uncategorized_title(app).right_aligned());
+    }
 
-    if app.spending_by_category.is_empty() {
-        let msg = Paragraph::new(Line::from(Span::styled(
-            "No transactions for this month. Import a CSV with :i",
-            theme::dim_style(),
-        )))
-        .centered()
-        .block(block);
+    if spending.is_empty() {
+        let text = match app.dashboard_account() {
+            Some(account) => format!("No spending from {} in this period", account.name),
+            None => "No transactions for this month. Import a CSV with :i".to_string(),
+        };
+        let msg = Paragraph::new(Line::from(Span::styled(text, theme::dim_style())))
+            .centered()
+            .block(block);
         f.render_widget(msg, area);
         return;
     }
@@ -194,72 +465,99 @@ fn render_spending_chart(f: &mut Frame, area: Rect, app: &App) {
     let inner = block.inner(area);
     let width = inner.width as usize;
 
-    let categories: Vec<_> = app
-        .spending_by_category
+    let currency = app.dashboard_currency();
+    let categories: Vec<_> = spending
         .iter()
         .take(12)
-        .map(|(name, amt)| (truncate(name, 14), amt.abs()))
+        .map(|(name, amt)| {
+            let delta = app.spending_deltas.get(name).copied();
+            let color = Category::find_by_name(&app.categories, name)
+                .and_then(|c| theme::category_color(&c.color));
+            let over = app.over_budget.get(name).map(|pct| format!(" ({pct})"));
+            (truncate(name, 14), amt.abs(), delta, color, over)
+        })
         .collect();
 
     let max_val = categories
         .iter()
-        .map(|(_, a)| a.to_f64().unwrap_or(0.0))
+        .map(|(_, a, _, _, _)| a.to_f64().unwrap_or(0.0))
         .fold(0.0_f64, f64::max);
 
     let label_width = 15; // right-aligned label column
     let amount_width = 12; // right-aligned dollar amount
-    let bar_area = width.saturating_sub(label_width + amount_width + 2); // 2 for spacing
+    let delta_width = if app.spending_deltas.is_empty() {
+        0
+    } else {
+        12 // " ▲$1,234.56" vs. the previous month
+    };
+    // " (120%)" after the amount of categories past their budget
+    let over_width = categories
+        .iter()
+        .filter_map(|(_, _, _, _, over)| over.as_ref().map(|o| o.chars().count()))
+        .max()
+        .unwrap_or(0);
+    let bar_area = width.saturating_sub(label_width + amount_width + over_width + delta_width + 2); // 2 for spacing
 
     let count = categories.len();
 
     let inner_rows = inner.height as usize;
     // Use 2 rows per category (bar + blank) when space allows, else 1
-    let rows_per = if count > 0 {
-        let natural = inner_rows / count;
-        if natural >= 2 {
-            natural
-        } else {
-            1
-        }
-    } else {
-        1
+    let rows_per = match inner_rows.checked_div(count) {
+        Some(natural) if natural >= 2 => natural,
+        _ => 1,
     };
 
     let mut lines: Vec<Line> = Vec::new();
 
-    for (i, (name, amt)) in categories.iter().enumerate() {
-        let color = theme::SPENDING_COLORS[i % theme::SPENDING_COLORS.len()];
+    for (i, (name, amt, delta, custom, over)) in categories.iter().enumerate() {
+        let color = custom.unwrap_or(theme::spending_colors()[i % theme::spending_colors().len()]);
         let val = amt.to_f64().unwrap_or(0.0);
         let bar_len = if max_val > 0.0 {
             ((val / max_val) * bar_area as f64).round() as usize
         } else {
             0
         };
-        let amount_str = format_amount(*amt);
 
         // Right-align the label
         let padded_label = format!("{:>width$}", name, width = label_width);
         // Build the bar: filled + empty
         let bar_filled: String = "\u{2588}".repeat(bar_len);
         let bar_empty: String = " ".repeat(bar_area.saturating_sub(bar_len));
-        // Right-align the amount
-        let padded_amount = format!("{:>width$}", amount_str, width = amount_width);
+        let padded_amount = format_amount_aligned(*amt, currency, amount_width);
 
-        let line = Line::from(vec![
-            Span::styled(padded_label, Style::default().fg(theme::TEXT)),
+        // Past its budget: the label and amount turn red
+        let text = if over.is_some() {
+            theme::red()
+        } else {
+            theme::text()
+        };
+        let mut spans = vec![
+            Span::styled(padded_label, Style::default().fg(text)),
             Span::raw(" "),
             Span::styled(bar_filled, Style::default().fg(color)),
             Span::raw(bar_empty),
             Span::raw(" "),
             Span::styled(
                 padded_amount,
-                Style::default()
-                    .fg(theme::TEXT)
-                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(text).add_modifier(Modifier::BOLD),
             ),
-        ]);
+        ];
+        if over_width > 0 {
+            let over = over.as_deref().unwrap_or("");
+            spans.push(Span::styled(
+                format!("{over:<over_width$}"),
+                Style::default().fg(theme::red()),
+            ));
+        }
+        if delta_width > 0 {
+            spans.push(delta_span(
+                delta.unwrap_or(Decimal::ZERO),
+                currency,
+                delta_width,
+            ));
+        }
 
-        lines.push(line);
+        lines.push(Line::from(spans));
 
         // Add blank lines to fill space when fewer categories
         for _ in 1..rows_per {
@@ -271,12 +569,32 @@ fn render_spending_chart(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(chart, area);
 }
 
+/// Month-over-month change in a category's spending. More spending is red.
+fn delta_span(delta: Decimal, currency: &str, width: usize) -> Span<'static> {
+    let (text, color) = if delta > Decimal::ZERO {
+        (format!("▲{}", format_amount(delta, currency)), theme::red())
+    } else if delta < Decimal::ZERO {
+        (
+            format!("▼{}", format_amount(delta.abs(), currency)),
+            theme::green(),
+        )
+    } else {
+        ("=".to_string(), theme::text_dim())
+    };
+    Span::styled(format!("{text:>width$}"), Style::default().fg(color))
+}
+
 fn render_trend_chart(f: &mut Frame, area: Rect, app: &App) {
+    let title = if app.dashboard_account().is_some() {
+        " Monthly Spending Trend · all accounts "
+    } else {
+        " Monthly Spending Trend "
+    };
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme::OVERLAY))
+        .border_style(Style::default().fg(theme::overlay()))
         .title(Span::styled(
-            " Monthly Spending Trend ",
+            title,
             theme::dim_style().add_modifier(Modifier::BOLD),
         ));
 
@@ -312,7 +630,7 @@ fn render_trend_chart(f: &mut Frame, area: Rect, app: &App) {
     // Each slot = bar_width + bar_gap (last bar has no trailing gap)
     // total = n * bar_width + (n-1) * bar_gap
     // Target: bar ~55% of slot, gap ~45%
-    let slot = if n > 0 { width / n } else { 1 };
+    let slot = width.checked_div(n).unwrap_or(1);
     let bar_w = (slot * 5 / 9).clamp(3, 7) as u16;
     let bar_g = (slot as u16).saturating_sub(bar_w).max(1);
 
@@ -336,7 +654,7 @@ fn render_trend_chart(f: &mut Frame, area: Rect, app: &App) {
                 .value(val)
                 .text_value(String::new())
                 .label(Line::from(Span::styled(label, theme::dim_style())))
-                .style(Style::default().fg(theme::ACCENT))
+                .style(Style::default().fg(theme::accent()))
         })
         .collect();
 
@@ -6,9 +6,11 @@ use ratatui::{
     Frame,
 };
 
-use crate::ui::app::{App, ImportStep};
+use crate::import::delimiter_name;
+use crate::models::AccountType;
+use crate::ui::app::{App, ImportStep, SAMPLE_ROWS};
 use crate::ui::theme;
-use crate::ui::util::{format_amount, truncate};
+use crate::ui::util::{format_amount_aligned, truncate, AMOUNT_WIDTH};
 
 pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::default()
@@ -43,16 +45,16 @@ fn render_step_indicator(f: &mut Frame, area: Rect, app: &App) {
         .unwrap_or(0);
 
     let mut spans: Vec<Span> = Vec::new();
-    spans.push(Span::styled(" ", Style::default().bg(theme::HEADER_BG)));
+    spans.push(Span::styled(" ", Style::default().bg(theme::header_bg())));
     for (i, (_, label)) in steps.iter().enumerate() {
         let style = if i == current_idx {
             Style::default()
-                .fg(theme::HEADER_BG)
-                .bg(theme::ACCENT)
+                .fg(theme::header_bg())
+                .bg(theme::accent())
                 .add_modifier(Modifier::BOLD)
         } else if i < current_idx {
             Style::default()
-                .fg(theme::GREEN)
+                .fg(theme::green())
                 .add_modifier(Modifier::BOLD)
         } else {
             theme::dim_style()
@@ -60,7 +62,7 @@ fn render_step_indicator(f: &mut Frame, area: Rect, app: &App) {
         spans.push(Span::styled(format!(" {label} "), style));
         if i < steps.len() - 1 {
             let connector_style = if i < current_idx {
-                Style::default().fg(theme::GREEN)
+                Style::default().fg(theme::green())
             } else {
                 theme::dim_style()
             };
@@ -68,7 +70,7 @@ fn render_step_indicator(f: &mut Frame, area: Rect, app: &App) {
         }
     }
 
-    let bar = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme::HEADER_BG));
+    let bar = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme::header_bg()));
     f.render_widget(bar, area);
 }
 
@@ -84,31 +86,31 @@ fn render_file_browser(f: &mut Frame, area: Rect, app: &App) {
         Span::styled(" Path: ", theme::dim_style()),
         Span::styled(
             app.file_browser_path.display().to_string(),
-            Style::default().fg(theme::ACCENT),
+            Style::default().fg(theme::accent()),
         ),
     ];
     if !app.file_browser_filter.is_empty() || input_focused {
         spans.push(Span::styled("  Filter: ", theme::dim_style()));
         spans.push(Span::styled(
             &app.file_browser_filter,
-            Style::default().fg(theme::TEXT),
+            Style::default().fg(theme::text()),
         ));
         if input_focused {
-            spans.push(Span::styled("█", Style::default().fg(theme::ACCENT)));
+            spans.push(Span::styled("█", Style::default().fg(theme::accent())));
         }
     }
 
     let input_border = if input_focused {
-        theme::ACCENT
+        theme::accent()
     } else {
-        theme::OVERLAY
+        theme::overlay()
     };
     let path_display = Paragraph::new(Line::from(spans)).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(input_border))
             .title(Span::styled(
-                " Select CSV File ",
+                " Select CSV or QIF File ",
                 theme::dim_style().add_modifier(Modifier::BOLD),
             )),
     );
@@ -124,8 +126,11 @@ fn render_file_browser(f: &mut Frame, area: Rect, app: &App) {
         .take(file_list_rows)
         .map(|(display_idx, &real_idx)| {
             let path = &app.file_browser_entries[real_idx];
+            let is_recent = app.is_recent_entry(real_idx);
             let name = if Some(path.as_path()) == app.file_browser_path.parent() {
                 "📁 ..".to_string()
+            } else if is_recent {
+                format!("🕘 {}", path.display())
             } else if path.is_dir() {
                 format!(
                     "📁 {}",
@@ -140,6 +145,8 @@ fn render_file_browser(f: &mut Frame, area: Rect, app: &App) {
 
             let style = if display_idx == app.file_browser_index {
                 theme::selected_style()
+            } else if is_recent {
+                Style::default().fg(theme::accent())
             } else {
                 theme::normal_style()
             };
@@ -149,9 +156,9 @@ fn render_file_browser(f: &mut Frame, area: Rect, app: &App) {
         .collect();
 
     let list_border = if input_focused {
-        theme::OVERLAY
+        theme::overlay()
     } else {
-        theme::ACCENT
+        theme::accent()
     };
     let hidden_hint = if app.file_browser_show_hidden {
         " . hide dotfiles"
@@ -184,7 +191,7 @@ fn render_column_mapper(f: &mut Frame, area: Rect, app: &App) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),  // Detected bank
-            Constraint::Length(11), // Column mapping fields (7 fields + borders)
+            Constraint::Length(13), // Column mapping fields (9 fields + borders)
             Constraint::Min(5),     // Sample data
         ])
         .split(area);
@@ -197,12 +204,12 @@ fn render_column_mapper(f: &mut Frame, area: Rect, app: &App) {
     };
     let status = Paragraph::new(Line::from(Span::styled(
         bank_msg,
-        Style::default().fg(theme::ACCENT),
+        Style::default().fg(theme::accent()),
     )))
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 " Column Mapping ",
                 theme::dim_style().add_modifier(Modifier::BOLD),
@@ -238,7 +245,14 @@ fn render_column_mapper(f: &mut Frame, area: Rect, app: &App) {
                 .map(|c| c.to_string())
                 .unwrap_or_else(|| "—".into()),
         ),
-        ("Date Format", app.import_profile.date_format.clone()),
+        (
+            "Date Format",
+            match app.sample_date_parse() {
+                Some(Ok(date)) => format!("{}   ✓ {date}", app.import_profile.date_format),
+                Some(Err(_)) => format!("{}   ✗ unparseable", app.import_profile.date_format),
+                None => app.import_profile.date_format.clone(),
+            },
+        ),
         (
             "Has Header",
             if app.import_profile.has_header {
@@ -248,6 +262,18 @@ fn render_column_mapper(f: &mut Frame, area: Rect, app: &App) {
             }
             .into(),
         ),
+        (
+            "Delimiter",
+            delimiter_name(app.import_profile.delimiter).into(),
+        ),
+        (
+            "Signs",
+            if app.import_flip_signs {
+                format!("{}, flipped", app.import_profile.sign_convention.label())
+            } else {
+                app.import_profile.sign_convention.label().into()
+            },
+        ),
     ];
 
     let field_items: Vec<ListItem> = fields
@@ -269,7 +295,7 @@ fn render_column_mapper(f: &mut Frame, area: Rect, app: &App) {
     let field_list = List::new(field_items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 " j/k navigate, +/- adjust, Enter to preview, Tab for account ",
                 theme::dim_style(),
@@ -277,8 +303,8 @@ fn render_column_mapper(f: &mut Frame, area: Rect, app: &App) {
     );
     f.render_widget(field_list, chunks[1]);
 
-    // Sample data preview
-    let header_cells: Vec<Cell> = app
+    // Sample data preview, ending with each row's amount as it will import
+    let mut header_cells: Vec<Cell> = app
         .import_headers
         .iter()
         .enumerate()
@@ -287,27 +313,51 @@ fn render_column_mapper(f: &mut Frame, area: Rect, app: &App) {
             Cell::from(label).style(theme::header_style())
         })
         .collect();
+    header_cells
+        .push(Cell::from(format!("{:>AMOUNT_WIDTH$}", "→ Amount")).style(theme::header_style()));
     let header = Row::new(header_cells).height(1);
 
     let sample_rows: Vec<Row> = app
         .import_rows
         .iter()
-        .take(5)
-        .map(|row| {
-            let cells: Vec<Cell> = row.iter().map(|c| Cell::from(c.as_str())).collect();
+        .zip(app.sample_amounts())
+        .map(|(row, amount)| {
+            let mut cells: Vec<Cell> = row.iter().map(|c| Cell::from(c.as_str())).collect();
+            let amount = match amount {
+                Some(a) if a > rust_decimal::Decimal::ZERO => Span::styled(
+                    format_amount_aligned(a, "", AMOUNT_WIDTH),
+                    theme::income_style(),
+                ),
+                Some(a) if a < rust_decimal::Decimal::ZERO => Span::styled(
+                    format_amount_aligned(a, "", AMOUNT_WIDTH),
+                    theme::expense_style(),
+                ),
+                Some(a) => Span::styled(
+                    format_amount_aligned(a, "", AMOUNT_WIDTH),
+                    theme::normal_style(),
+                ),
+                None => Span::styled(format!("{:>AMOUNT_WIDTH$}", "?"), theme::dim_style()),
+            };
+            cells.push(Cell::from(amount));
             Row::new(cells).style(theme::normal_style())
         })
         .collect();
 
     let col_count = app.import_headers.len().max(1);
-    let widths: Vec<Constraint> = (0..col_count).map(|_| Constraint::Min(12)).collect();
+    let mut widths: Vec<Constraint> = (0..col_count).map(|_| Constraint::Min(12)).collect();
+    widths.push(Constraint::Length(AMOUNT_WIDTH as u16));
 
+    let flipped = if app.import_flip_signs {
+        ", signs flipped"
+    } else {
+        ""
+    };
     let table = Table::new(sample_rows, widths).header(header).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
-                " Sample Data (first 5 rows) ",
+                format!(" Sample Data (first {SAMPLE_ROWS} rows{flipped}) | ~ flip signs "),
                 theme::dim_style(),
             )),
     );
@@ -332,14 +382,17 @@ fn render_select_account(f: &mut Frame, area: Rect, app: &App) {
         "Suggested type: Checking"
     };
     let info = Paragraph::new(Line::from(vec![
-        Span::styled(format!("  {bank_msg}"), Style::default().fg(theme::ACCENT)),
+        Span::styled(
+            format!("  {bank_msg}"),
+            Style::default().fg(theme::accent()),
+        ),
         Span::styled("  |  ", theme::dim_style()),
         Span::styled(type_hint, theme::dim_style()),
     ]))
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 " Select Account ",
                 theme::dim_style().add_modifier(Modifier::BOLD),
@@ -360,16 +413,16 @@ fn render_select_account(f: &mut Frame, area: Rect, app: &App) {
                 Span::styled("  Name: ", theme::dim_style()),
                 Span::styled(
                     &app.import_new_account_name,
-                    Style::default().fg(theme::TEXT),
+                    Style::default().fg(theme::text()),
                 ),
-                Span::styled("█", Style::default().fg(theme::ACCENT)),
+                Span::styled("█", Style::default().fg(theme::accent())),
             ]),
             Line::from(vec![
                 Span::styled("  Type: ", theme::dim_style()),
                 Span::styled(
                     type_name,
                     Style::default()
-                        .fg(theme::ACCENT)
+                        .fg(theme::accent())
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled("  (+/- to change)", theme::dim_style()),
@@ -383,11 +436,11 @@ fn render_select_account(f: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(theme::ACCENT))
+                .border_style(Style::default().fg(theme::accent()))
                 .title(Span::styled(
                     " New Account ",
                     Style::default()
-                        .fg(theme::ACCENT)
+                        .fg(theme::accent())
                         .add_modifier(Modifier::BOLD),
                 )),
         );
@@ -419,7 +472,7 @@ fn render_select_account(f: &mut Frame, area: Rect, app: &App) {
         let list = List::new(items).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(theme::OVERLAY))
+                .border_style(Style::default().fg(theme::overlay()))
                 .title(Span::styled(hint, theme::dim_style())),
         );
         f.render_widget(list, chunks[1]);
@@ -427,59 +480,223 @@ fn render_select_account(f: &mut Frame, area: Rect, app: &App) {
 }
 
 fn render_preview(f: &mut Frame, area: Rect, app: &App) {
-    let header_cells = ["Date", "Description", "Amount"]
-        .iter()
-        .map(|h| Cell::from(*h).style(theme::header_style()));
+    let area = if app.import_skipped.is_empty() {
+        area
+    } else {
+        let shown = app.import_skipped.len().min(5) as u16;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(shown + 2)])
+            .split(area);
+        render_skipped_rows(f, chunks[1], app);
+        chunks[0]
+    };
+    let area = if app.import_signs_suspect {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(5)])
+            .split(area);
+        let account_is_card = app
+            .accounts
+            .iter()
+            .find(|a| a.id == app.import_account_id)
+            .is_some_and(|a| a.account_type == AccountType::CreditCard);
+        let warning = if account_is_card {
+            " ⚠ Most of these are payments or refunds, not charges. The signs are probably flipped — press f to flip them."
+        } else {
+            " ⚠ Most of these are money in, not spending. The signs are probably flipped — press f to flip them."
+        };
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                warning,
+                Style::default()
+                    .fg(theme::yellow())
+                    .add_modifier(Modifier::BOLD),
+            )),
+            chunks[0],
+        );
+        chunks[1]
+    } else {
+        area
+    };
+    let area = match app.import_mapping_summary() {
+        Some(mapping) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(5)])
+                .split(area);
+            f.render_widget(
+                Paragraph::new(Span::styled(format!(" {mapping}"), theme::dim_style())),
+                chunks[0],
+            );
+            chunks[1]
+        }
+        None => area,
+    };
+
+    let amount_header = format!("{:>AMOUNT_WIDTH$}", "Amount");
+    let header_cells = ["  Date", "Description", &amount_header, ""]
+        .into_iter()
+        .map(|h| Cell::from(h).style(theme::header_style()));
     let header = Row::new(header_cells).height(1);
 
     let rows: Vec<Row> = app
         .import_preview
         .iter()
-        .take(50)
-        .map(|txn| {
-            let amount_style = if txn.amount > rust_decimal::Decimal::ZERO {
-                theme::income_style()
-            } else if txn.amount < rust_decimal::Decimal::ZERO {
-                theme::expense_style()
+        .enumerate()
+        .skip(app.import_preview_scroll)
+        .take(area.height.saturating_sub(3) as usize)
+        .map(|(i, txn)| {
+            let date = if app.import_preview_selected.contains(&i) {
+                format!("\u{2022} {}", txn.date)
             } else {
-                theme::normal_style()
+                format!("  {}", txn.date)
             };
-            Row::new(vec![
-                Cell::from(txn.date.as_str()),
-                Cell::from(truncate(&txn.description, 50)),
-                Cell::from(Span::styled(format_amount(txn.amount), amount_style)),
-            ])
+            let mut flags = Vec::new();
+            if txn.is_transfer {
+                flags.push("⇄");
+            }
+            if !txn.notes.is_empty() {
+                flags.push("✎");
+            }
+            let is_dup = app.import_duplicates.contains(&i);
+            if is_dup {
+                flags.push("≈ dup");
+            }
+            let flags = Cell::from(Span::styled(
+                flags.join(" "),
+                Style::default().fg(theme::yellow()),
+            ));
+
+            let row = if is_dup {
+                // Probable duplicate: dimmed, and struck through when skipped
+                let style = if app.import_skip_duplicates {
+                    theme::dim_style().add_modifier(Modifier::CROSSED_OUT)
+                } else {
+                    theme::dim_style()
+                };
+                Row::new(vec![
+                    Cell::from(date),
+                    Cell::from(truncate(&txn.description, 50)),
+                    Cell::from(format_amount_aligned(
+                        txn.amount,
+                        app.account_currency(txn.account_id),
+                        AMOUNT_WIDTH,
+                    )),
+                    flags,
+                ])
+                .style(style)
+            } else {
+                let amount_style = if txn.amount > rust_decimal::Decimal::ZERO {
+                    theme::income_style()
+                } else if txn.amount < rust_decimal::Decimal::ZERO {
+                    theme::expense_style()
+                } else {
+                    theme::normal_style()
+                };
+                Row::new(vec![
+                    Cell::from(date),
+                    Cell::from(truncate(&txn.description, 50)),
+                    Cell::from(Span::styled(
+                        format_amount_aligned(
+                            txn.amount,
+                            app.account_currency(txn.account_id),
+                            AMOUNT_WIDTH,
+                        ),
+                        amount_style,
+                    )),
+                    flags,
+                ])
+            };
+            if i == app.import_preview_index {
+                let mut style = theme::selected_style();
+                if is_dup && app.import_skip_duplicates {
+                    style = style.add_modifier(Modifier::CROSSED_OUT);
+                }
+                row.style(style)
+            } else {
+                row
+            }
         })
         .collect();
 
     let widths = [
-        Constraint::Length(12),
-        Constraint::Min(20),
         Constraint::Length(14),
+        Constraint::Min(20),
+        Constraint::Length(AMOUNT_WIDTH as u16),
+        Constraint::Length(10),
     ];
 
     let table = Table::new(rows, widths).header(header).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 {
                     let total = app.import_preview.len();
-                    let shown = total.min(50);
-                    if shown < total {
-                        format!(" Preview: showing {shown} of {total} transactions | Enter to commit, Esc to go back ")
-                    } else {
-                        format!(" Preview: {total} transactions | Enter to commit, Esc to go back ")
-                    }
+                    let selected = match app.import_preview_selected.len() {
+                        0 => String::new(),
+                        n => format!(" [{n} selected]"),
+                    };
+                    let dupes = match app.import_duplicates.len() {
+                        0 => String::new(),
+                        n if app.import_skip_duplicates => {
+                            format!(" | {n} probable duplicates skipped (s to keep)")
+                        }
+                        n => format!(" | {n} probable duplicates kept (s to skip)"),
+                    };
+                    let skipped = match app.import_skipped.len() {
+                        0 => String::new(),
+                        n => format!(" | {n} rows skipped (unparseable date)"),
+                    };
+                    let row = app.import_preview_index + 1;
+                    format!(" Preview: {total} transactions (row {row}){selected}{dupes}{skipped} | Enter to commit, Esc to go back ")
                 },
                 Style::default()
-                    .fg(theme::ACCENT)
+                    .fg(theme::accent())
                     .add_modifier(Modifier::BOLD),
             )),
     );
     f.render_widget(table, area);
 }
 
+/// Rows dropped from the preview because their date didn't parse, so it's
+/// clear what won't be imported.
+fn render_skipped_rows(f: &mut Frame, area: Rect, app: &App) {
+    let total = app.import_skipped.len();
+    let mut lines: Vec<Line> = app
+        .import_skipped
+        .iter()
+        .take(5)
+        .map(|row| {
+            Line::from(vec![
+                Span::styled(format!("  row {:<5}", row.row), theme::dim_style()),
+                Span::styled(
+                    format!("{:<14}", truncate(&row.date, 12)),
+                    Style::default().fg(theme::red()),
+                ),
+                Span::styled(truncate(&row.description, 50), theme::normal_style()),
+            ])
+        })
+        .collect();
+    if total > 5 {
+        if let Some(last) = lines.last_mut() {
+            *last = Line::from(Span::styled(
+                format!("  … and {} more", total - 4),
+                theme::dim_style(),
+            ));
+        }
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::overlay()))
+        .title(Span::styled(
+            format!(" Skipped: {total} rows with unparseable dates (check the date format) "),
+            Style::default().fg(theme::yellow()),
+        ));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 fn render_categorize(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -505,13 +722,13 @@ fn render_categorize(f: &mut Frame, area: Rect, app: &App) {
             Span::styled(
                 truncate(&desc, 60),
                 Style::default()
-                    .fg(theme::TEXT)
+                    .fg(theme::text())
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
             Span::styled("  Transactions: ", theme::dim_style()),
-            Span::styled(format!("{count}"), Style::default().fg(theme::ACCENT)),
+            Span::styled(format!("{count}"), Style::default().fg(theme::accent())),
             Span::styled(
                 format!("  ({current} of {total} unique descriptions)"),
                 theme::dim_style(),
@@ -521,18 +738,28 @@ fn render_categorize(f: &mut Frame, area: Rect, app: &App) {
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::ACCENT))
+            .border_style(Style::default().fg(theme::accent()))
             .title(Span::styled(
                 " What category is this? ",
                 Style::default()
-                    .fg(theme::ACCENT)
+                    .fg(theme::accent())
                     .add_modifier(Modifier::BOLD),
             )),
     );
     f.render_widget(desc_block, chunks[0]);
 
-    // ── Category picker list ─────────────────────────────────
-    let cat_visible = chunks[1].height.saturating_sub(2) as usize; // minus list borders
+    render_category_picker(
+        f,
+        chunks[1],
+        app,
+        "Enter assign | s skip | S skip all | n new",
+    );
+}
+
+/// Scrollable category list with an inline "new category" row, shared by
+/// the categorize step and the transactions screen's picker.
+pub(crate) fn render_category_picker(f: &mut Frame, area: Rect, app: &App, hints: &str) {
+    let cat_visible = area.height.saturating_sub(2) as usize; // minus list borders
     let mut items: Vec<ListItem> = app
         .categories
         .iter()
@@ -555,73 +782,122 @@ fn render_categorize(f: &mut Frame, area: Rect, app: &App) {
             Span::styled(
                 "  + New category: ",
                 Style::default()
-                    .fg(theme::GREEN)
+                    .fg(theme::green())
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(&app.import_cat_new_name, Style::default().fg(theme::TEXT)),
-            Span::styled("█", Style::default().fg(theme::ACCENT)),
+            Span::styled(&app.import_cat_new_name, Style::default().fg(theme::text())),
+            Span::styled("█", Style::default().fg(theme::accent())),
         ])));
     }
 
     let title = if app.import_cat_creating {
         " New Category (Enter create | Esc cancel) ".to_string()
     } else {
-        format!(
-            " Categories ({}) | Enter assign | s skip | S skip all | n new ",
-            app.categories.len()
-        )
+        format!(" Categories ({}) | {hints} ", app.categories.len())
     };
 
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(title, theme::dim_style())),
     );
-    f.render_widget(list, chunks[1]);
+    f.render_widget(list, area);
 }
 
 fn render_complete(f: &mut Frame, area: Rect, app: &App) {
-    let msg = Paragraph::new(vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "✓ Import complete!",
             Style::default()
-                .fg(theme::GREEN)
+                .fg(theme::green())
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from(Span::styled(&app.status_message, theme::normal_style())),
+        Line::from(Span::styled(&app.import_summary, theme::normal_style())),
         Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "Enter ",
-                Style::default()
-                    .fg(theme::ACCENT)
-                    .add_modifier(Modifier::BOLD),
+    ];
+    if let Some(report) = &app.import_report {
+        let mut rows = vec![
+            (
+                "Rows parsed",
+                report.parsed.to_string(),
+                theme::normal_style(),
             ),
-            Span::styled("to finish  ", theme::dim_style()),
-            Span::styled(
-                "i ",
-                Style::default()
-                    .fg(theme::ACCENT)
-                    .add_modifier(Modifier::BOLD),
+            (
+                "Imported",
+                report.inserted.to_string(),
+                theme::income_style(),
             ),
-            Span::styled("import another file  ", theme::dim_style()),
-            Span::styled(
-                "1 ",
-                Style::default()
-                    .fg(theme::ACCENT)
-                    .add_modifier(Modifier::BOLD),
+            (
+                "Duplicates skipped",
+                report.duplicates.to_string(),
+                theme::dim_style(),
             ),
-            Span::styled("go to Dashboard", theme::dim_style()),
-        ]),
-    ])
-    .centered()
-    .block(
+            (
+                "Auto-categorized",
+                report.auto_categorized.to_string(),
+                theme::normal_style(),
+            ),
+            (
+                "Still uncategorized",
+                report.uncategorized.to_string(),
+                if report.uncategorized > 0 {
+                    Style::default().fg(theme::yellow())
+                } else {
+                    theme::normal_style()
+                },
+            ),
+        ];
+        if report.bad_dates > 0 {
+            rows.insert(
+                1,
+                (
+                    "Bad dates, left out",
+                    report.bad_dates.to_string(),
+                    Style::default().fg(theme::yellow()),
+                ),
+            );
+        }
+        if let Some((first, last)) = &report.date_range {
+            rows.push(("Dates", format!("{first} – {last}"), theme::normal_style()));
+        }
+        for (label, value, style) in rows {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{label:<20}"), theme::dim_style()),
+                Span::styled(format!("{value:>23}"), style),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Enter ",
+            Style::default()
+                .fg(theme::accent())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("to finish  ", theme::dim_style()),
+        Span::styled(
+            "i ",
+            Style::default()
+                .fg(theme::accent())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("import another file  ", theme::dim_style()),
+        Span::styled(
+            "1 ",
+            Style::default()
+                .fg(theme::accent())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("go to Dashboard", theme::dim_style()),
+    ]));
+    let msg = Paragraph::new(lines).centered().block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::GREEN)),
+            .border_style(Style::default().fg(theme::green())),
     );
     f.render_widget(msg, area);
 }
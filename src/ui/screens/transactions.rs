@@ -1,23 +1,79 @@
 use ratatui::{
-    layout::{Constraint, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
     Frame,
 };
 
-use crate::models::Category;
-use crate::ui::app::App;
+use rust_decimal::Decimal;
+
+use crate::models::{Category, Transaction};
+use crate::ui::app::{App, RECAT_PICKER_HEIGHT, TXN_FORM_FIELDS};
+use crate::ui::screens::import::render_category_picker;
 use crate::ui::theme;
-use crate::ui::util::{format_amount, truncate};
+use crate::ui::util::{
+    format_amount, format_amount_aligned, fuzzy_match, substring_positions, truncate, AMOUNT_WIDTH,
+};
 
 pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
+    let area = if app.txn_adding {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(10)])
+            .split(area);
+        render_add_form(f, chunks[1], app);
+        chunks[0]
+    } else if app.txn_recat {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(RECAT_PICKER_HEIGHT)])
+            .split(area);
+        render_category_picker(f, chunks[1], app, "Enter assign | n new | Esc cancel");
+        chunks[0]
+    } else {
+        area
+    };
+
     if app.transactions.is_empty() {
-        let msg = if !app.search_input.is_empty() {
+        let msg = if app.transaction_filter_uncategorized && app.search_input.is_empty() {
+            vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!(
+                        "✓ Nothing left to categorize in {}",
+                        app.search_scope_label()
+                    ),
+                    Style::default().fg(theme::green()),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Press Esc to clear the filter",
+                    theme::dim_style(),
+                )),
+            ]
+        } else if app.transaction_filter_flagged && app.search_input.is_empty() {
+            vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "No flagged transactions — press f on a row to flag it",
+                    theme::dim_style(),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Press Esc to clear the filter",
+                    theme::dim_style(),
+                )),
+            ]
+        } else if !app.search_input.is_empty() {
             vec![
                 Line::from(""),
                 Line::from(Span::styled(
-                    format!("No transactions matching '{}'", app.search_input),
+                    format!(
+                        "No transactions matching '{}' in {}",
+                        app.search_input,
+                        app.search_scope_label()
+                    ),
                     theme::dim_style(),
                 )),
                 Line::from(""),
@@ -42,7 +98,7 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
         };
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 " Transactions (0) ",
                 theme::dim_style().add_modifier(Modifier::BOLD),
@@ -51,93 +107,85 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let header_cells = ["Date", "Description", "Category", "Amount"]
-        .iter()
-        .map(|h| Cell::from(*h).style(theme::header_style()));
+    let show_balance = app.transaction_filter_account.is_some();
+    let mut headers = vec![
+        "Date".to_string(),
+        "Description".to_string(),
+        "Category".to_string(),
+        format!("{:>AMOUNT_WIDTH$}", "Amount"),
+    ];
+    if show_balance {
+        headers.push(format!("{:>AMOUNT_WIDTH$}", "Balance"));
+    }
+    let header_cells = headers
+        .into_iter()
+        .map(|h| Cell::from(h).style(theme::header_style()));
     let header = Row::new(header_cells).height(1);
 
     let has_selections = !app.selected_transactions.is_empty();
 
-    let rows: Vec<Row> = app
-        .transactions
+    let lines = transaction_lines(&app.transactions, app.transaction_group_by_day);
+    let height = area.height.saturating_sub(3) as usize;
+    let first = first_visible_line(
+        &lines,
+        app.transaction_scroll,
+        app.transaction_index,
+        height,
+    );
+    let rows: Vec<Row> = lines[first..]
         .iter()
-        .enumerate()
-        .skip(app.transaction_scroll)
-        .take(area.height.saturating_sub(3) as usize)
-        .map(|(i, txn)| {
-            let is_selected = txn
-                .id
-                .is_some_and(|id| app.selected_transactions.contains(&id));
-            let is_cursor = i == app.transaction_index;
-
-            let cat_name = txn
-                .category_id
-                .and_then(|cid| Category::find_by_id(&app.categories, cid))
-                .map(|c| c.name.as_str())
-                .unwrap_or("—");
-
-            let amount_style = if txn.is_income() {
-                theme::income_style()
-            } else {
-                theme::expense_style()
-            };
-
-            let amount_str = if txn.is_income() {
-                format!("+{}", format_amount(txn.amount))
-            } else {
-                format_amount(txn.amount)
-            };
-
-            let date_cell = if is_selected {
-                format!("\u{2022} {}", txn.date)
-            } else {
-                format!("  {}", txn.date)
-            };
-
-            let style = if is_cursor && is_selected {
-                Style::default().fg(theme::HEADER_BG).bg(theme::YELLOW)
-            } else if is_cursor {
-                theme::selected_style()
-            } else if is_selected {
-                Style::default().fg(theme::YELLOW)
-            } else if i % 2 == 1 {
-                theme::alt_row_style()
-            } else {
-                theme::normal_style()
-            };
-
-            Row::new(vec![
-                Cell::from(date_cell),
-                Cell::from(truncate(&txn.description, 40)),
-                Cell::from(cat_name),
-                Cell::from(Span::styled(amount_str, amount_style)),
-            ])
-            .style(style)
+        .take(height)
+        .map(|line| match *line {
+            ListLine::Day(start, len) => day_header_row(app, &app.transactions[start..start + len]),
+            ListLine::Txn(i) => transaction_row(app, i, show_balance),
         })
         .collect();
 
-    let widths = [
+    let mut widths = vec![
         Constraint::Length(14),
         Constraint::Min(20),
         Constraint::Length(18),
-        Constraint::Length(14),
+        Constraint::Length(AMOUNT_WIDTH as u16),
     ];
+    if show_balance {
+        widths.push(Constraint::Length(AMOUNT_WIDTH as u16));
+    }
 
     let table = Table::new(rows, widths).header(header).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::OVERLAY))
+            .border_style(Style::default().fg(theme::overlay()))
             .title(Span::styled(
                 format!(
-                    " Transactions ({}) {}{} ",
+                    " Transactions ({}) {}{}{}{} ",
                     app.transactions.len(),
+                    if app.transaction_filter_uncategorized {
+                        format!("[uncategorized in {}] ", app.search_scope_label())
+                    } else {
+                        String::new()
+                    },
+                    if app.transaction_filter_flagged {
+                        "[flagged] "
+                    } else {
+                        ""
+                    },
                     if has_selections {
                         format!("[{} selected] ", app.selected_transactions.len())
                     } else {
                         String::new()
                     },
-                    if !app.search_input.is_empty() {
-                        format!("search: '{}'", app.search_input)
+                    if app.fuzzy_search.is_some() {
+                        format!(
+                            "search: '{}' (fuzzy, {})",
+                            app.search_input,
+                            app.search_scope_label()
+                        )
+                    } else if !app.search_input.is_empty() {
+                        format!(
+                            "search: '{}' ({})",
+                            app.search_input,
+                            app.search_scope_label()
+                        )
                     } else {
                         String::new()
                     }
@@ -148,3 +196,283 @@ pub(crate) fn render(f: &mut Frame, area: Rect, app: &App) {
 
     f.render_widget(table, area);
 }
+
+/// One line of the transactions table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ListLine {
+    /// Header for `transactions[start..start + len]`, which share a date.
+    Day(usize, usize),
+    /// `transactions[i]`.
+    Txn(usize),
+}
+
+/// Table lines for the date-sorted `transactions`: one per transaction, with
+/// a `Day` header before each date's run when `group_by_day` is set.
+pub(crate) fn transaction_lines(transactions: &[Transaction], group_by_day: bool) -> Vec<ListLine> {
+    let mut lines = Vec::with_capacity(transactions.len());
+    let mut start = 0;
+    while start < transactions.len() {
+        let date = &transactions[start].date;
+        let len = transactions[start..]
+            .iter()
+            .take_while(|t| t.date == *date)
+            .count();
+        if group_by_day {
+            lines.push(ListLine::Day(start, len));
+        }
+        lines.extend((start..start + len).map(ListLine::Txn));
+        start += len;
+    }
+    lines
+}
+
+/// First line to draw. The list scrolls by transaction, so start at the
+/// `scroll` transaction (or its day header when it opens a day), then move
+/// down as far as headers push the `cursor` transaction out of `height`.
+pub(crate) fn first_visible_line(
+    lines: &[ListLine],
+    scroll: usize,
+    cursor: usize,
+    height: usize,
+) -> usize {
+    let position = |target: usize| {
+        lines
+            .iter()
+            .position(|line| *line == ListLine::Txn(target))
+            .unwrap_or(0)
+    };
+    let mut first = position(scroll);
+    if first > 0 && matches!(lines[first - 1], ListLine::Day(..)) {
+        first -= 1;
+    }
+    let cursor_line = position(cursor);
+    if height > 0 && cursor_line >= first + height {
+        first = cursor_line + 1 - height;
+    }
+    first.min(cursor_line)
+}
+
+/// `Mon Jan 15 — 4 txns, -$63.20`, split across the date and description
+/// columns. The total is left out when the day mixes currencies.
+fn day_header_row<'a>(app: &App, day: &[Transaction]) -> Row<'a> {
+    let date = day.first().map(|t| t.date.as_str()).unwrap_or_default();
+    let label = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.format("%a %b %-d").to_string())
+        .unwrap_or_else(|_| date.to_string());
+    let count = format!(
+        "— {} txn{}",
+        day.len(),
+        if day.len() == 1 { "" } else { "s" }
+    );
+    let currency = day
+        .first()
+        .map(|t| app.account_currency(t.account_id))
+        .unwrap_or_default();
+    let summary = if day
+        .iter()
+        .all(|t| app.account_currency(t.account_id) == currency)
+    {
+        let total: Decimal = day.iter().map(|t| t.amount).sum();
+        let sign = if total > Decimal::ZERO { "+" } else { "" };
+        format!("{count}, {sign}{}", format_amount(total, currency))
+    } else {
+        count
+    };
+    let style = theme::dim_style().add_modifier(Modifier::BOLD);
+    Row::new(vec![
+        Cell::from(Span::styled(label, style)),
+        Cell::from(Span::styled(summary, style)),
+    ])
+}
+
+fn transaction_row<'a>(app: &'a App, i: usize, show_balance: bool) -> Row<'a> {
+    let txn = &app.transactions[i];
+    let is_selected = txn
+        .id
+        .is_some_and(|id| app.selected_transactions.contains(&id));
+    let is_cursor = i == app.transaction_index;
+
+    let cat_name = txn
+        .category_id
+        .and_then(|cid| Category::find_by_id(&app.categories, cid))
+        .map(|c| c.name.as_str())
+        .unwrap_or("—");
+    let cat_name = if txn.is_transfer {
+        format!("⇄ {cat_name}")
+    } else {
+        cat_name.to_string()
+    };
+
+    let amount_style = if txn.is_income() {
+        theme::income_style()
+    } else {
+        theme::expense_style()
+    };
+
+    let currency = app.account_currency(txn.account_id);
+    let amount_str = if txn.is_income() {
+        format!(
+            "{:>AMOUNT_WIDTH$}",
+            format!("+{}", format_amount(txn.amount, currency))
+        )
+    } else {
+        format_amount_aligned(txn.amount, currency, AMOUNT_WIDTH)
+    };
+
+    let date_cell = format!(
+        "{}{} {}",
+        if is_selected { '\u{2022}' } else { ' ' },
+        if txn.flagged { '\u{2691}' } else { ' ' },
+        txn.date
+    );
+
+    let style = if is_cursor && is_selected {
+        Style::default().fg(theme::header_bg()).bg(theme::yellow())
+    } else if is_cursor {
+        theme::selected_style()
+    } else if is_selected {
+        Style::default().fg(theme::yellow())
+    } else if i % 2 == 1 {
+        theme::alt_row_style()
+    } else {
+        theme::normal_style()
+    };
+
+    let positions = match (&app.fuzzy_search, &app.substring_search) {
+        (Some(text), _) => fuzzy_match(text, &txn.description)
+            .map(|m| m.positions)
+            .unwrap_or_default(),
+        (None, Some(text)) => substring_positions(&txn.description, text),
+        (None, None) => Vec::new(),
+    };
+    let mut desc_spans = highlight_matches(&txn.description, &positions, 40);
+    for tag in &txn.tags {
+        desc_spans.push(Span::raw(" "));
+        desc_spans.push(Span::styled(
+            format!("#{tag}"),
+            Style::default().fg(theme::header_bg()).bg(theme::accent()),
+        ));
+    }
+
+    let mut cells = vec![
+        Cell::from(date_cell),
+        Cell::from(Line::from(desc_spans)),
+        Cell::from(cat_name),
+        Cell::from(Span::styled(amount_str, amount_style)),
+    ];
+    if show_balance {
+        let balance = txn
+            .id
+            .and_then(|id| app.running_balances.get(&id))
+            .map(|b| format_amount_aligned(*b, currency, AMOUNT_WIDTH))
+            .unwrap_or_default();
+        cells.push(Cell::from(balance));
+    }
+    Row::new(cells).style(style)
+}
+
+/// Form for entering a transaction by hand, opened with `a`.
+fn render_add_form(f: &mut Frame, area: Rect, app: &App) {
+    let category = app
+        .txn_add_category
+        .checked_sub(1)
+        .and_then(|i| app.categories.get(i))
+        .map(|c| c.name.as_str())
+        .unwrap_or("(none)");
+    let account = app
+        .accounts
+        .get(app.txn_add_account)
+        .map(|a| a.name.as_str())
+        .unwrap_or("(no accounts)");
+    let values = [
+        app.txn_add_date.as_str(),
+        app.txn_add_description.as_str(),
+        app.txn_add_amount.as_str(),
+        category,
+        account,
+    ];
+
+    let mut lines = vec![Line::from("")];
+    for (i, (label, value)) in TXN_FORM_FIELDS.iter().zip(values).enumerate() {
+        let active = i == app.txn_add_field;
+        let label_style = if active {
+            Style::default()
+                .fg(theme::accent())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            theme::dim_style()
+        };
+        let mut spans = vec![Span::styled(format!("  {label:<14}"), label_style)];
+        if i >= 3 {
+            // Choice fields cycle with the arrow keys
+            spans.push(Span::styled(
+                value.to_string(),
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            ));
+            if active {
+                spans.push(Span::styled("  (←/→ to change)", theme::dim_style()));
+            }
+        } else {
+            spans.push(Span::styled(
+                value.to_string(),
+                Style::default().fg(theme::text()),
+            ));
+            if active {
+                spans.push(Span::styled("█", Style::default().fg(theme::accent())));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Tab/Shift+Tab to move, Enter to save, Esc to cancel. Spending is negative (-4.50)",
+        theme::dim_style(),
+    )));
+
+    let form = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::accent()))
+            .title(Span::styled(
+                " New Transaction ",
+                Style::default()
+                    .fg(theme::accent())
+                    .add_modifier(Modifier::BOLD),
+            )),
+    );
+    f.render_widget(form, area);
+}
+
+/// Truncated description with the characters matched by a search (char
+/// indices in `positions`) underlined.
+fn highlight_matches(description: &str, positions: &[usize], max: usize) -> Vec<Span<'static>> {
+    let highlight = Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    // Group runs of matched / unmatched characters into spans
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in truncate(description, max).chars().enumerate() {
+        let matched = positions.contains(&i) && c != '…';
+        if matched != run_matched && !run.is_empty() {
+            let text = std::mem::take(&mut run);
+            spans.push(if run_matched {
+                Span::styled(text, highlight)
+            } else {
+                Span::raw(text)
+            });
+        }
+        run_matched = matched;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(if run_matched {
+            Span::styled(run, highlight)
+        } else {
+            Span::raw(run)
+        });
+    }
+    spans
+}
@@ -1,68 +1,264 @@
 use ratatui::style::{Color, Modifier, Style};
 
-pub(crate) const HEADER_BG: Color = Color::Rgb(30, 30, 46);
-pub(crate) const HEADER_FG: Color = Color::Rgb(205, 214, 244);
-pub(crate) const ACCENT: Color = Color::Rgb(137, 180, 250);
-pub(crate) const GREEN: Color = Color::Rgb(166, 227, 161);
-pub(crate) const RED: Color = Color::Rgb(243, 139, 168);
-pub(crate) const YELLOW: Color = Color::Rgb(249, 226, 175);
-pub(crate) const SURFACE: Color = Color::Rgb(49, 50, 68);
-pub(crate) const TEXT: Color = Color::Rgb(205, 214, 244);
-pub(crate) const TEXT_DIM: Color = Color::Rgb(127, 132, 156);
-pub(crate) const OVERLAY: Color = Color::Rgb(69, 71, 90);
-pub(crate) const COMMAND_BG: Color = Color::Rgb(24, 24, 37);
-
-/// 12 shades of blue by rank: lightest (lowest spender) to deepest (highest).
-pub(crate) const SPENDING_COLORS: [Color; 12] = [
-    Color::Rgb(198, 219, 252), // 0  — ice
-    Color::Rgb(180, 208, 250), // 1
-    Color::Rgb(162, 197, 248), // 2
-    Color::Rgb(147, 186, 246), // 3
-    Color::Rgb(132, 175, 244), // 4
-    Color::Rgb(117, 164, 240), // 5
-    Color::Rgb(102, 153, 234), // 6
-    Color::Rgb(87, 140, 226),  // 7
-    Color::Rgb(72, 127, 218),  // 8
-    Color::Rgb(58, 114, 208),  // 9
-    Color::Rgb(45, 101, 198),  // 10
-    Color::Rgb(33, 88, 188),   // 11 — deep
-];
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Colors used across every screen. Loaded once at startup from
+/// `theme.toml` in the config directory, falling back to the built-in
+/// palette for anything missing or malformed.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Theme {
+    pub(crate) header_bg: Color,
+    pub(crate) header_fg: Color,
+    pub(crate) accent: Color,
+    pub(crate) green: Color,
+    pub(crate) red: Color,
+    pub(crate) yellow: Color,
+    pub(crate) surface: Color,
+    pub(crate) text: Color,
+    pub(crate) text_dim: Color,
+    pub(crate) overlay: Color,
+    pub(crate) command_bg: Color,
+    /// 12 shades by rank: lightest (lowest spender) to deepest (highest).
+    pub(crate) spending: [Color; 12],
+    /// Whether per-category colors set with `:set-color` are honored.
+    pub(crate) category_colors: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            header_bg: Color::Rgb(30, 30, 46),
+            header_fg: Color::Rgb(205, 214, 244),
+            accent: Color::Rgb(137, 180, 250),
+            green: Color::Rgb(166, 227, 161),
+            red: Color::Rgb(243, 139, 168),
+            yellow: Color::Rgb(249, 226, 175),
+            surface: Color::Rgb(49, 50, 68),
+            text: Color::Rgb(205, 214, 244),
+            text_dim: Color::Rgb(127, 132, 156),
+            overlay: Color::Rgb(69, 71, 90),
+            command_bg: Color::Rgb(24, 24, 37),
+            spending: [
+                Color::Rgb(198, 219, 252), // 0  — ice
+                Color::Rgb(180, 208, 250), // 1
+                Color::Rgb(162, 197, 248), // 2
+                Color::Rgb(147, 186, 246), // 3
+                Color::Rgb(132, 175, 244), // 4
+                Color::Rgb(117, 164, 240), // 5
+                Color::Rgb(102, 153, 234), // 6
+                Color::Rgb(87, 140, 226),  // 7
+                Color::Rgb(72, 127, 218),  // 8
+                Color::Rgb(58, 114, 208),  // 9
+                Color::Rgb(45, 101, 198),  // 10
+                Color::Rgb(33, 88, 188),   // 11 — deep
+            ],
+            category_colors: true,
+        }
+    }
+}
+
+impl Theme {
+    /// Black and white only, for `--no-color`, `NO_COLOR` and terminals
+    /// that can't render the palette.
+    pub(crate) fn monochrome() -> Self {
+        let fg = Color::White;
+        let bg = Color::Black;
+        Self {
+            header_bg: bg,
+            header_fg: fg,
+            accent: fg,
+            green: fg,
+            red: fg,
+            yellow: fg,
+            surface: bg,
+            text: fg,
+            text_dim: fg,
+            overlay: fg,
+            command_bg: bg,
+            spending: [fg; 12],
+            category_colors: false,
+        }
+    }
+
+    /// Parse `key = "#rrggbb"` lines on top of the default palette.
+    /// Comments (whole-line or trailing), section headers, unknown keys and
+    /// bad colors are ignored.
+    pub(crate) fn parse(contents: &str) -> Self {
+        let mut theme = Self::default();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let value = strip_comment(value)
+                    .trim()
+                    .trim_matches('"')
+                    .trim_matches('\'');
+                if let Ok(color) = Color::from_str(value) {
+                    theme.set(key.trim(), color);
+                }
+            }
+        }
+        theme
+    }
+
+    /// Load the theme file at `path`, or the defaults if it doesn't exist.
+    pub(crate) fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Parse a category's stored color (hex or named) for this theme.
+    pub(crate) fn category_color(&self, spec: &str) -> Option<Color> {
+        if spec.is_empty() || !self.category_colors {
+            return None;
+        }
+        Color::from_str(spec).ok()
+    }
+
+    fn set(&mut self, key: &str, color: Color) {
+        let slot = match key {
+            "header_bg" => &mut self.header_bg,
+            "header_fg" => &mut self.header_fg,
+            "accent" => &mut self.accent,
+            "green" => &mut self.green,
+            "red" => &mut self.red,
+            "yellow" => &mut self.yellow,
+            "surface" => &mut self.surface,
+            "text" => &mut self.text,
+            "text_dim" => &mut self.text_dim,
+            "overlay" => &mut self.overlay,
+            "command_bg" => &mut self.command_bg,
+            _ => return,
+        };
+        *slot = color;
+    }
+}
+
+/// A value without its trailing ` # comment`. A `#` inside quotes, or
+/// one the value starts with, is part of the color.
+fn strip_comment(value: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in value.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '#') if prev.is_whitespace() && !value[..i].trim().is_empty() => {
+                return &value[..i];
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    value
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Install the theme for this run. Only the first call has any effect.
+pub(crate) fn init(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+fn current() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+pub(crate) fn header_bg() -> Color {
+    current().header_bg
+}
+
+pub(crate) fn header_fg() -> Color {
+    current().header_fg
+}
+
+pub(crate) fn accent() -> Color {
+    current().accent
+}
+
+pub(crate) fn green() -> Color {
+    current().green
+}
+
+pub(crate) fn red() -> Color {
+    current().red
+}
+
+pub(crate) fn yellow() -> Color {
+    current().yellow
+}
+
+pub(crate) fn surface() -> Color {
+    current().surface
+}
+
+pub(crate) fn text() -> Color {
+    current().text
+}
+
+pub(crate) fn text_dim() -> Color {
+    current().text_dim
+}
+
+pub(crate) fn overlay() -> Color {
+    current().overlay
+}
+
+pub(crate) fn command_bg() -> Color {
+    current().command_bg
+}
+
+pub(crate) fn spending_colors() -> &'static [Color; 12] {
+    &current().spending
+}
+
+/// A category's stored color, unless it's unset, unparseable, or the
+/// theme is monochrome.
+pub(crate) fn category_color(spec: &str) -> Option<Color> {
+    current().category_color(spec)
+}
 
 pub(crate) fn header_style() -> Style {
     Style::default()
-        .fg(HEADER_FG)
-        .bg(HEADER_BG)
+        .fg(header_fg())
+        .bg(header_bg())
         .add_modifier(Modifier::BOLD)
 }
 
 pub(crate) fn selected_style() -> Style {
-    Style::default().fg(HEADER_BG).bg(ACCENT)
+    Style::default().fg(header_bg()).bg(accent())
 }
 
 pub(crate) fn normal_style() -> Style {
-    Style::default().fg(TEXT)
+    Style::default().fg(text())
 }
 
 pub(crate) fn dim_style() -> Style {
-    Style::default().fg(TEXT_DIM)
+    Style::default().fg(text_dim())
 }
 
 pub(crate) fn income_style() -> Style {
-    Style::default().fg(GREEN)
+    Style::default().fg(green())
 }
 
 pub(crate) fn expense_style() -> Style {
-    Style::default().fg(RED)
+    Style::default().fg(red())
 }
 
 pub(crate) fn alt_row_style() -> Style {
-    Style::default().fg(TEXT).bg(SURFACE)
+    Style::default().fg(text()).bg(surface())
 }
 
 pub(crate) fn command_bar_style() -> Style {
-    Style::default().fg(TEXT).bg(COMMAND_BG)
+    Style::default().fg(text()).bg(command_bg())
 }
 
 pub(crate) fn status_bar_style() -> Style {
-    Style::default().fg(TEXT_DIM).bg(SURFACE)
+    Style::default().fg(text_dim()).bg(surface())
 }
@@ -0,0 +1,77 @@
+#![allow(clippy::unwrap_used)]
+
+use ratatui::style::Color;
+
+use super::theme::Theme;
+
+// ── Theme::parse ──────────────────────────────────────────────
+
+#[test]
+fn test_parse_empty_is_default() {
+    assert_eq!(Theme::parse(""), Theme::default());
+}
+
+#[test]
+fn test_parse_overrides_hex_keys() {
+    let theme = Theme::parse(
+        "# my colors\n[colors]\naccent = \"#ff8800\"\nred = '#112233'\ntext_dim=\"gray\"\n",
+    );
+    assert_eq!(theme.accent, Color::Rgb(0xff, 0x88, 0x00));
+    assert_eq!(theme.red, Color::Rgb(0x11, 0x22, 0x33));
+    assert_eq!(theme.text_dim, Color::Gray);
+    assert_eq!(theme.green, Theme::default().green);
+}
+
+#[test]
+fn test_parse_ignores_trailing_comments() {
+    let theme = Theme::parse(
+        "accent = \"#89b4fa\"  # blue\nred = '#112233' # dark\ngreen = #a6e3a1 # bare\n",
+    );
+    assert_eq!(theme.accent, Color::Rgb(0x89, 0xb4, 0xfa));
+    assert_eq!(theme.red, Color::Rgb(0x11, 0x22, 0x33));
+    assert_eq!(theme.green, Color::Rgb(0xa6, 0xe3, 0xa1));
+}
+
+#[test]
+fn test_parse_malformed_keys_fall_back() {
+    let theme = Theme::parse("accent = \"#zzzzzz\"\ngreen\nbogus = \"#ffffff\"\n");
+    assert_eq!(theme, Theme::default());
+}
+
+#[test]
+fn test_load_missing_file_is_default() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        Theme::load(&dir.path().join("theme.toml")),
+        Theme::default()
+    );
+}
+
+#[test]
+fn test_monochrome_has_no_palette_colors() {
+    let theme = Theme::monochrome();
+    assert!(theme
+        .spending
+        .iter()
+        .chain([&theme.accent, &theme.red, &theme.green, &theme.header_bg])
+        .all(|c| matches!(c, Color::White | Color::Black)));
+}
+
+// ── Theme::category_color ─────────────────────────────────────
+
+#[test]
+fn test_category_color_hex_and_named() {
+    let theme = Theme::default();
+    assert_eq!(
+        theme.category_color("#ff8800"),
+        Some(Color::Rgb(0xff, 0x88, 0x00))
+    );
+    assert_eq!(theme.category_color("lightblue"), Some(Color::LightBlue));
+    assert_eq!(theme.category_color(""), None);
+    assert_eq!(theme.category_color("not a color"), None);
+}
+
+#[test]
+fn test_category_color_ignored_when_monochrome() {
+    assert_eq!(Theme::monochrome().category_color("#ff8800"), None);
+}
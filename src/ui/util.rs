@@ -1,27 +1,97 @@
-use rust_decimal::Decimal;
-
-/// Format a decimal amount with thousand separators and 2 decimal places.
-/// e.g. `1234567.89` → `"1,234,567.89"`
-pub(crate) fn format_amount(val: Decimal) -> String {
-    let abs = val.abs();
-    let formatted = format!("{abs:.2}");
-    let mut parts = formatted.split('.');
-    let int_part = parts.next().unwrap_or("0");
-    let dec_part = parts.next().unwrap_or("00");
-
-    let with_commas: String = int_part
-        .as_bytes()
-        .rchunks(3)
-        .rev()
-        .map(|chunk| std::str::from_utf8(chunk).unwrap_or(""))
-        .collect::<Vec<_>>()
-        .join(",");
-
-    if val < Decimal::ZERO {
-        format!("-${with_commas}.{dec_part}")
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::str::FromStr;
+
+use crate::models::Transaction;
+
+/// Symbol and decimal places for a currency code. Unknown codes are shown as
+/// the code itself, e.g. `CHF 12.00`.
+fn currency_format(currency: &str) -> (String, u32) {
+    match currency.to_uppercase().as_str() {
+        "USD" | "" => ("$".into(), 2),
+        "EUR" => ("€".into(), 2),
+        "GBP" => ("£".into(), 2),
+        "JPY" => ("¥".into(), 0),
+        other => (format!("{other} "), 2),
+    }
+}
+
+/// Format an amount in the given currency with thousand separators.
+/// e.g. `1234567.89, "USD"` → `"$1,234,567.89"`, `-1500, "JPY"` → `"-¥1,500"`
+pub(crate) fn format_amount(val: Decimal, currency: &str) -> String {
+    let (symbol, places) = currency_format(currency);
+    let digits = format_decimal(val, places, true);
+    match digits.strip_prefix('-') {
+        Some(abs) => format!("-{symbol}{abs}"),
+        None => format!("{symbol}{digits}"),
+    }
+}
+
+/// `d` rounded to `decimals` places, halves away from zero, with en-US
+/// `,` thousands grouping when `thousands` is set: `-1234.5, 2, true` →
+/// `"-1,234.50"`. Anything that rounds to zero prints without a sign.
+pub(crate) fn format_decimal(d: Decimal, decimals: u32, thousands: bool) -> String {
+    let rounded = d.round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero);
+    let formatted = format!("{:.places$}", rounded.abs(), places = decimals as usize);
+    let (int_part, frac_part) = match formatted.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (formatted.as_str(), None),
+    };
+
+    let mut out = String::new();
+    if rounded < Decimal::ZERO {
+        out.push('-');
+    }
+    if thousands {
+        let groups: Vec<&str> = int_part
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|chunk| std::str::from_utf8(chunk).unwrap_or(""))
+            .collect();
+        out.push_str(&groups.join(","));
     } else {
-        format!("${with_commas}.{dec_part}")
+        out.push_str(int_part);
     }
+    if let Some(frac_part) = frac_part {
+        out.push('.');
+        out.push_str(frac_part);
+    }
+    out
+}
+
+/// Width of an amount column, enough for `-$1,234,567.89`.
+pub(crate) const AMOUNT_WIDTH: usize = 14;
+
+/// [`format_amount`] right-aligned in `width` columns, so amounts stacked in
+/// a column line up on the decimal point and the sign always sits just
+/// before the symbol. Amounts wider than `width` are returned unpadded.
+pub(crate) fn format_amount_aligned(val: Decimal, currency: &str, width: usize) -> String {
+    format!("{:>width$}", format_amount(val, currency))
+}
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Map values onto block characters scaled between their min and max.
+/// A flat series renders every value at the lowest level.
+pub(crate) fn sparkline(values: &[Decimal]) -> Vec<char> {
+    let (min, max) = match (values.iter().min(), values.iter().max()) {
+        (Some(min), Some(max)) => (min, max),
+        _ => return Vec::new(),
+    };
+    let range = *max - *min;
+    let top = Decimal::from(SPARK_LEVELS.len() - 1);
+
+    values
+        .iter()
+        .map(|v| {
+            if range.is_zero() {
+                return SPARK_LEVELS[0];
+            }
+            let idx = ((*v - *min) / range * top).round().to_usize().unwrap_or(0);
+            SPARK_LEVELS[idx.min(SPARK_LEVELS.len() - 1)]
+        })
+        .collect()
 }
 
 /// Truncate a string to `max` visible characters, appending "…" if truncated.
@@ -39,6 +109,37 @@ pub(crate) fn truncate(s: &str, max: usize) -> String {
     format!("{truncated}…")
 }
 
+/// Word-wrap a string into lines of at most `width` characters. Words longer
+/// than a line are split. Always returns at least one (possibly empty) line.
+pub(crate) fn wrap_text(s: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_len = 0;
+    for word in s.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        if line_len > 0 && line_len + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+            line_len = 0;
+        }
+        while word.len() > width {
+            let rest = word.split_off(width);
+            lines.push(word.into_iter().collect());
+            word = rest;
+        }
+        if line_len > 0 {
+            line.push(' ');
+            line_len += 1;
+        }
+        line_len += word.len();
+        line.extend(word);
+    }
+    if line_len > 0 || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
 /// Move a list cursor down by one, adjusting scroll to keep cursor visible.
 pub(crate) fn scroll_down(index: &mut usize, scroll: &mut usize, len: usize, page: usize) {
     if *index + 1 < len {
@@ -70,3 +171,273 @@ pub(crate) fn scroll_to_bottom(index: &mut usize, scroll: &mut usize, len: usize
         *scroll = index.saturating_sub(page.saturating_sub(1));
     }
 }
+
+/// Move a list cursor to `target`, scrolling only as far as needed to show it.
+pub(crate) fn scroll_to(index: &mut usize, scroll: &mut usize, target: usize, page: usize) {
+    *index = target;
+    if target < *scroll {
+        *scroll = target;
+    } else if target >= *scroll + page {
+        *scroll = target.saturating_sub(page.saturating_sub(1));
+    }
+}
+
+/// Position of the first name starting with `c`, ignoring ASCII case. Backs
+/// the type-a-letter jump in category and rule lists.
+pub(crate) fn first_starting_with<'a>(
+    mut names: impl Iterator<Item = &'a str>,
+    c: char,
+) -> Option<usize> {
+    let lower = c.to_ascii_lowercase();
+    names.position(|name| name.starts_with(|ch: char| ch.to_ascii_lowercase() == lower))
+}
+
+/// A `/` search string split into free text and structured predicates.
+///
+/// Supported predicates (whitespace-separated, ANDed together):
+/// `amount>50`, `amount>=50`, `amount<-100`, `amount<=-100`, `amount=4.50`,
+/// `date:2024-01..2024-03`, `date:2024-01..`, `date:..2024-03`, `date:2024-01-15`,
+/// and `#tag` to require a tag.
+/// Anything else is kept as substring text. A leading `~` forces fuzzy
+/// ranking of that text instead of a substring match.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct SearchQuery {
+    pub(crate) text: Option<String>,
+    pub(crate) min_amount: Option<Decimal>,
+    pub(crate) max_amount: Option<Decimal>,
+    pub(crate) date_from: Option<String>,
+    pub(crate) date_to: Option<String>,
+    pub(crate) tags: Vec<String>,
+    /// Rank by `fuzzy_match` instead of requiring a substring match.
+    pub(crate) fuzzy: bool,
+    /// `amount>` excludes the bound itself.
+    pub(crate) min_exclusive: bool,
+    /// `amount<` excludes the bound itself.
+    pub(crate) max_exclusive: bool,
+}
+
+impl SearchQuery {
+    pub(crate) fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut words: Vec<&str> = Vec::new();
+
+        let input = match input.trim_start().strip_prefix('~') {
+            Some(rest) => {
+                query.fuzzy = true;
+                rest
+            }
+            None => input,
+        };
+
+        for token in input.split_whitespace() {
+            let lower = token.to_ascii_lowercase();
+            let handled = if let Some(rest) = lower.strip_prefix("amount") {
+                query.apply_amount(rest)
+            } else if let Some(rest) = lower.strip_prefix("date:") {
+                query.apply_date(rest)
+            } else if let Some(tag) = lower.strip_prefix('#').and_then(Transaction::normalize_tag) {
+                query.tags.push(tag);
+                true
+            } else {
+                false
+            };
+            if !handled {
+                words.push(token);
+            }
+        }
+
+        if !words.is_empty() {
+            query.text = Some(words.join(" "));
+        }
+        query
+    }
+
+    fn apply_amount(&mut self, rest: &str) -> bool {
+        let (op, value) = if let Some(v) = rest.strip_prefix(">=") {
+            (">=", v)
+        } else if let Some(v) = rest.strip_prefix("<=") {
+            ("<=", v)
+        } else if let Some(v) = rest.strip_prefix('>') {
+            (">", v)
+        } else if let Some(v) = rest.strip_prefix('<') {
+            ("<", v)
+        } else if let Some(v) = rest.strip_prefix('=') {
+            ("=", v)
+        } else {
+            return false;
+        };
+        let value = match Decimal::from_str(value) {
+            Ok(v) => v,
+            Err(_) => return false,
+        };
+        match op {
+            ">" | ">=" => {
+                self.min_amount = Some(value);
+                self.min_exclusive = op == ">";
+            }
+            "<" | "<=" => {
+                self.max_amount = Some(value);
+                self.max_exclusive = op == "<";
+            }
+            _ => {
+                self.min_amount = Some(value);
+                self.max_amount = Some(value);
+                self.min_exclusive = false;
+                self.max_exclusive = false;
+            }
+        }
+        true
+    }
+
+    fn apply_date(&mut self, rest: &str) -> bool {
+        let (from, to) = match rest.split_once("..") {
+            Some((from, to)) => (from, to),
+            None => (rest, rest),
+        };
+        if (from.is_empty() && to.is_empty())
+            || (!from.is_empty() && !is_date_prefix(from))
+            || (!to.is_empty() && !is_date_prefix(to))
+        {
+            return false;
+        }
+        if !from.is_empty() {
+            self.date_from = Some(from.to_string());
+        }
+        if !to.is_empty() {
+            self.date_to = Some(to.to_string());
+        }
+        true
+    }
+}
+
+/// Accepts `YYYY`, `YYYY-MM`, or `YYYY-MM-DD`.
+fn is_date_prefix(s: &str) -> bool {
+    match s.len() {
+        4 => s.chars().all(|c| c.is_ascii_digit()),
+        7 => chrono::NaiveDate::parse_from_str(&format!("{s}-01"), "%Y-%m-%d").is_ok(),
+        10 => chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok(),
+        _ => false,
+    }
+}
+
+/// Char indices of every case-insensitive occurrence of `needle` in `text`,
+/// for highlighting a substring search. Overlapping occurrences are skipped.
+pub(crate) fn substring_positions(text: &str, needle: &str) -> Vec<usize> {
+    let lower = |s: &str| -> Vec<char> {
+        s.chars()
+            .map(|c| c.to_lowercase().next().unwrap_or(c))
+            .collect()
+    };
+    let t = lower(text);
+    let n = lower(needle);
+    let mut positions = Vec::new();
+    if n.is_empty() {
+        return positions;
+    }
+    let mut i = 0;
+    while i + n.len() <= t.len() {
+        if t[i..i + n.len()] == n[..] {
+            positions.extend(i..i + n.len());
+            i += n.len();
+        } else {
+            i += 1;
+        }
+    }
+    positions
+}
+
+/// A fuzzy search hit: higher `score` is better, `positions` are the char
+/// indices of `text` that matched (empty for typo-tolerant matches).
+#[derive(Debug, PartialEq)]
+pub(crate) struct FuzzyMatch {
+    pub(crate) score: i64,
+    pub(crate) positions: Vec<usize>,
+}
+
+/// Case-insensitive fuzzy match of `query` against `text`.
+///
+/// Characters of the query appearing in order (a subsequence) score best,
+/// with bonuses for runs of consecutive characters and word starts. Failing
+/// that, a close substring within a small edit distance still matches so
+/// typos like "starbukcs" find "Starbucks", ranked below any subsequence hit.
+pub(crate) fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let lower = |s: &str| -> Vec<char> {
+        s.chars()
+            .map(|c| c.to_lowercase().next().unwrap_or(c))
+            .collect()
+    };
+    let q = lower(query.trim());
+    let t = lower(text);
+    if q.is_empty() {
+        return None;
+    }
+
+    // Try a greedy subsequence from every occurrence of the first character
+    // and keep the best-scoring one.
+    let mut best: Option<FuzzyMatch> = None;
+    for start in (0..t.len()).filter(|&i| t[i] == q[0]) {
+        let mut positions = vec![start];
+        let mut ti = start + 1;
+        for &qc in &q[1..] {
+            match t[ti..].iter().position(|&tc| tc == qc) {
+                Some(offset) => {
+                    positions.push(ti + offset);
+                    ti += offset + 1;
+                }
+                None => break,
+            }
+        }
+        if positions.len() < q.len() {
+            break; // later starts can't match either
+        }
+        let score = subsequence_score(&t, &positions);
+        if best.as_ref().is_none_or(|b| score > b.score) {
+            best = Some(FuzzyMatch { score, positions });
+        }
+    }
+    if best.is_some() {
+        return best;
+    }
+
+    // Typo tolerance: about one edit per four characters
+    if q.len() < 3 {
+        return None;
+    }
+    let allowed = (q.len() / 4).max(1);
+    let distance = substring_edit_distance(&q, &t);
+    (distance <= allowed).then(|| FuzzyMatch {
+        score: 500 - 100 * distance as i64,
+        positions: Vec::new(),
+    })
+}
+
+fn subsequence_score(text: &[char], positions: &[usize]) -> i64 {
+    let mut score = 1000;
+    for (i, &pos) in positions.iter().enumerate() {
+        if i > 0 && pos == positions[i - 1] + 1 {
+            score += 15;
+        }
+        if pos == 0 || !text[pos - 1].is_alphanumeric() {
+            score += 10;
+        }
+    }
+    let span = positions.last().unwrap_or(&0) - positions.first().unwrap_or(&0) + 1;
+    score -= (span - positions.len()) as i64;
+    score -= positions.first().copied().unwrap_or(0).min(20) as i64;
+    score
+}
+
+/// Fewest edits turning `query` into some substring of `text`.
+fn substring_edit_distance(query: &[char], text: &[char]) -> usize {
+    // Row for the empty query prefix is all zeros: a match may start anywhere
+    let mut prev = vec![0; text.len() + 1];
+    for (i, &qc) in query.iter().enumerate() {
+        let mut row = vec![i + 1; text.len() + 1];
+        for (j, &tc) in text.iter().enumerate() {
+            let substitute = prev[j] + usize::from(qc != tc);
+            row[j + 1] = substitute.min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+    prev.into_iter().min().unwrap_or(query.len())
+}
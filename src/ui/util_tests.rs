@@ -1,8 +1,13 @@
 #![allow(clippy::unwrap_used)]
 
+use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
+use super::screens::budgets::{budget_progress, BudgetStatus};
+use super::screens::dashboard::{income_expense_split, spending_pace, SpendingPace};
+use super::screens::transactions::{first_visible_line, transaction_lines, ListLine};
 use super::util::*;
+use crate::models::Transaction;
 
 // ── truncate ──────────────────────────────────────────────────
 
@@ -70,49 +75,508 @@ fn test_truncate_max_one_with_long_string() {
     assert_eq!(truncate("abc", 1), "…");
 }
 
+// ── wrap_text ─────────────────────────────────────────────────
+
+#[test]
+fn test_wrap_text_breaks_at_words_and_splits_long_ones() {
+    assert_eq!(
+        wrap_text("SQ *BLUE BOTTLE COFFEE OAKLAND", 12),
+        vec!["SQ *BLUE", "BOTTLE", "COFFEE", "OAKLAND"]
+    );
+    assert_eq!(
+        wrap_text("REF#0123456789ABCDEF done", 8),
+        vec!["REF#0123", "456789AB", "CDEF", "done"]
+    );
+    assert_eq!(wrap_text("", 10), vec![""]);
+}
+
 // ── format_amount ──────────────────────────────────────────
 
 #[test]
 fn test_format_amount_basic() {
-    assert_eq!(format_amount(dec!(1234.56)), "$1,234.56");
+    assert_eq!(format_amount(dec!(1234.56), "USD"), "$1,234.56");
 }
 
 #[test]
 fn test_format_amount_no_commas() {
-    assert_eq!(format_amount(dec!(999.99)), "$999.99");
+    assert_eq!(format_amount(dec!(999.99), "USD"), "$999.99");
 }
 
 #[test]
 fn test_format_amount_zero() {
-    assert_eq!(format_amount(dec!(0)), "$0.00");
+    assert_eq!(format_amount(dec!(0), "USD"), "$0.00");
 }
 
 #[test]
 fn test_format_amount_negative() {
-    assert_eq!(format_amount(dec!(-42.50)), "-$42.50");
+    assert_eq!(format_amount(dec!(-42.50), "USD"), "-$42.50");
 }
 
 #[test]
 fn test_format_amount_large() {
-    assert_eq!(format_amount(dec!(1234567.89)), "$1,234,567.89");
+    assert_eq!(format_amount(dec!(1234567.89), "USD"), "$1,234,567.89");
 }
 
 #[test]
 fn test_format_amount_millions() {
-    assert_eq!(format_amount(dec!(10000000.00)), "$10,000,000.00");
+    assert_eq!(format_amount(dec!(10000000.00), "USD"), "$10,000,000.00");
 }
 
 #[test]
 fn test_format_amount_rounds_to_two_decimals() {
-    assert_eq!(format_amount(dec!(1.5)), "$1.50");
+    assert_eq!(format_amount(dec!(1.5), "USD"), "$1.50");
 }
 
 #[test]
 fn test_format_amount_negative_large() {
-    assert_eq!(format_amount(dec!(-99999.01)), "-$99,999.01");
+    assert_eq!(format_amount(dec!(-99999.01), "USD"), "-$99,999.01");
+}
+
+#[test]
+fn test_format_amount_currency_symbols() {
+    assert_eq!(format_amount(dec!(1234.5), "EUR"), "€1,234.50");
+    assert_eq!(format_amount(dec!(-12.99), "GBP"), "-£12.99");
+    assert_eq!(format_amount(dec!(0.5), "usd"), "$0.50");
+}
+
+#[test]
+fn test_format_amount_jpy_no_decimals() {
+    assert_eq!(format_amount(dec!(1500), "JPY"), "¥1,500");
+    assert_eq!(format_amount(dec!(-250000), "JPY"), "-¥250,000");
+}
+
+#[test]
+fn test_format_amount_unknown_currency_uses_code() {
+    assert_eq!(format_amount(dec!(42), "CHF"), "CHF 42.00");
+    assert_eq!(format_amount(dec!(-1234.56), "CAD"), "-CAD 1,234.56");
 }
 
 #[test]
 fn test_format_amount_single_digit() {
-    assert_eq!(format_amount(dec!(5)), "$5.00");
+    assert_eq!(format_amount(dec!(5), "USD"), "$5.00");
+}
+
+#[test]
+fn test_format_amount_aligned_lines_up_decimals() {
+    let column: Vec<String> = [dec!(-1234.56), dec!(5), dec!(-0.5)]
+        .into_iter()
+        .map(|v| format_amount_aligned(v, "USD", AMOUNT_WIDTH))
+        .collect();
+    assert_eq!(
+        column,
+        vec!["    -$1,234.56", "         $5.00", "        -$0.50"]
+    );
+    assert!(column.iter().all(|s| s.chars().count() == AMOUNT_WIDTH));
+
+    // Multi-byte symbols pad by character, and overflow isn't truncated
+    assert_eq!(format_amount_aligned(dec!(-3), "EUR", 8), "  -€3.00");
+    assert_eq!(
+        format_amount_aligned(dec!(1234567), "USD", 6),
+        "$1,234,567.00"
+    );
+}
+
+// ── format_decimal ─────────────────────────────────────────
+
+#[test]
+fn test_format_decimal_groups_thousands() {
+    assert_eq!(format_decimal(dec!(1234567.891), 2, true), "1,234,567.89");
+    assert_eq!(format_decimal(dec!(1234567.891), 2, false), "1234567.89");
+    assert_eq!(format_decimal(dec!(999.5), 0, true), "1,000");
+    assert_eq!(format_decimal(dec!(100), 2, true), "100.00");
+}
+
+#[test]
+fn test_format_decimal_negatives_and_zero() {
+    assert_eq!(format_decimal(dec!(-1234.5), 2, true), "-1,234.50");
+    assert_eq!(format_decimal(dec!(-0.5), 2, true), "-0.50");
+    assert_eq!(format_decimal(dec!(0), 2, true), "0.00");
+    // Rounds to zero: no stray minus sign
+    assert_eq!(format_decimal(dec!(-0.001), 2, true), "0.00");
+}
+
+#[test]
+fn test_format_decimal_rounds_halves_away_from_zero() {
+    assert_eq!(format_decimal(dec!(2.345), 2, false), "2.35");
+    assert_eq!(format_decimal(dec!(-2.345), 2, false), "-2.35");
+    assert_eq!(format_decimal(dec!(2.5), 0, false), "3");
+    assert_eq!(format_decimal(dec!(0.125), 1, false), "0.1");
+}
+
+// ── sparkline ─────────────────────────────────────────────────
+
+#[test]
+fn test_sparkline_scales_min_to_max() {
+    let chars = sparkline(&[dec!(-100), dec!(0), dec!(200), dec!(600)]);
+    assert_eq!(chars, vec!['▁', '▂', '▄', '█']);
+}
+
+#[test]
+fn test_sparkline_flat_and_empty() {
+    assert!(sparkline(&[]).is_empty());
+    assert_eq!(sparkline(&[dec!(5), dec!(5)]), vec!['▁', '▁']);
+    assert_eq!(sparkline(&[dec!(42)]), vec!['▁']);
+}
+
+// ── letter jump ───────────────────────────────────────────────
+
+#[test]
+fn test_first_starting_with_ignores_case() {
+    let names = ["Coffee Shops", "groceries", "Gas"];
+    assert_eq!(first_starting_with(names.iter().copied(), 'g'), Some(1));
+    assert_eq!(first_starting_with(names.iter().copied(), 'C'), Some(0));
+    assert_eq!(first_starting_with(names.iter().copied(), 'z'), None);
+}
+
+#[test]
+fn test_scroll_to_keeps_target_visible() {
+    let (mut index, mut scroll) = (0, 0);
+    scroll_to(&mut index, &mut scroll, 25, 10);
+    assert_eq!((index, scroll), (25, 16));
+    // Already visible: scroll stays put
+    scroll_to(&mut index, &mut scroll, 20, 10);
+    assert_eq!((index, scroll), (20, 16));
+    scroll_to(&mut index, &mut scroll, 3, 10);
+    assert_eq!((index, scroll), (3, 3));
+}
+
+// ── SearchQuery ───────────────────────────────────────────────
+
+#[test]
+fn test_search_query_fuzzy_prefix() {
+    let q = SearchQuery::parse("~strbks amount<0");
+    assert!(q.fuzzy);
+    assert_eq!(q.text.as_deref(), Some("strbks"));
+    assert_eq!(q.max_amount, Some(dec!(0)));
+    assert!(!SearchQuery::parse("coffee").fuzzy);
+}
+
+#[test]
+fn test_search_query_plain_text() {
+    let q = SearchQuery::parse("coffee shop");
+    assert_eq!(q.text.as_deref(), Some("coffee shop"));
+    assert!(q.min_amount.is_none());
+    assert!(q.date_from.is_none());
+}
+
+#[test]
+fn test_search_query_empty() {
+    assert_eq!(SearchQuery::parse("  "), SearchQuery::default());
+}
+
+#[test]
+fn test_search_query_amount_predicates() {
+    let q = SearchQuery::parse("amount>50");
+    assert_eq!(q.min_amount, Some(dec!(50)));
+    assert!(q.min_exclusive);
+    assert!(q.text.is_none());
+
+    let q = SearchQuery::parse("amount<=-100");
+    assert_eq!(q.max_amount, Some(dec!(-100)));
+    assert!(!q.max_exclusive);
+
+    let q = SearchQuery::parse("amount=4.50");
+    assert_eq!(q.min_amount, Some(dec!(4.50)));
+    assert_eq!(q.max_amount, Some(dec!(4.50)));
+}
+
+#[test]
+fn test_search_query_date_range() {
+    let q = SearchQuery::parse("date:2024-01..2024-03");
+    assert_eq!(q.date_from.as_deref(), Some("2024-01"));
+    assert_eq!(q.date_to.as_deref(), Some("2024-03"));
+
+    let q = SearchQuery::parse("date:2024-01..");
+    assert_eq!(q.date_from.as_deref(), Some("2024-01"));
+    assert!(q.date_to.is_none());
+
+    let q = SearchQuery::parse("date:2024-02-15");
+    assert_eq!(q.date_from.as_deref(), Some("2024-02-15"));
+    assert_eq!(q.date_to.as_deref(), Some("2024-02-15"));
+}
+
+#[test]
+fn test_search_query_combined() {
+    let q = SearchQuery::parse("coffee amount<-5 date:2024");
+    assert_eq!(q.text.as_deref(), Some("coffee"));
+    assert_eq!(q.max_amount, Some(dec!(-5)));
+    assert_eq!(q.date_from.as_deref(), Some("2024"));
+}
+
+#[test]
+fn test_search_query_tags() {
+    let q = SearchQuery::parse("#Work coffee #reimbursable");
+    assert_eq!(q.tags, vec!["work", "reimbursable"]);
+    assert_eq!(q.text.as_deref(), Some("coffee"));
+
+    // A bare '#' is ordinary text
+    let q = SearchQuery::parse("#");
+    assert!(q.tags.is_empty());
+    assert_eq!(q.text.as_deref(), Some("#"));
+}
+
+#[test]
+fn test_search_query_invalid_predicates_are_text() {
+    let q = SearchQuery::parse("amount>abc date:2024-13");
+    assert_eq!(q.text.as_deref(), Some("amount>abc date:2024-13"));
+    assert!(q.min_amount.is_none());
+    assert!(q.date_from.is_none());
+}
+
+// ── substring_positions ───────────────────────────────────────
+
+#[test]
+fn test_substring_positions_every_match_ignoring_case() {
+    assert_eq!(
+        substring_positions("Coffee & COFFEE", "coffee"),
+        vec![0, 1, 2, 3, 4, 5, 9, 10, 11, 12, 13, 14]
+    );
+    assert_eq!(substring_positions("Café", "É"), vec![3]);
+    assert_eq!(substring_positions("aaaa", "aa"), vec![0, 1, 2, 3]);
+    assert!(substring_positions("Gas", "coffee").is_empty());
+    assert!(substring_positions("Gas", "").is_empty());
+}
+
+// ── fuzzy_match ───────────────────────────────────────────────
+
+#[test]
+fn test_fuzzy_match_subsequence_positions() {
+    let m = fuzzy_match("sbck", "Starbucks").unwrap();
+    assert_eq!(m.positions, vec![0, 4, 6, 7]);
+}
+
+#[test]
+fn test_fuzzy_match_case_insensitive() {
+    assert!(fuzzy_match("WHOLE", "whole foods").is_some());
+    assert!(fuzzy_match("whole", "WHOLE FOODS").is_some());
+}
+
+#[test]
+fn test_fuzzy_match_typo_tolerated() {
+    let m = fuzzy_match("starbukcs", "Starbucks Coffee").unwrap();
+    assert!(m.positions.is_empty());
+    assert!(fuzzy_match("starbukcs", "Shell Gas").is_none());
+}
+
+#[test]
+fn test_fuzzy_match_no_match() {
+    assert!(fuzzy_match("xyz", "Starbucks").is_none());
+    assert!(fuzzy_match("", "Starbucks").is_none());
+}
+
+#[test]
+fn test_fuzzy_match_ranks_contiguous_first() {
+    let contiguous = fuzzy_match("gas", "Shell Gas Station").unwrap().score;
+    let scattered = fuzzy_match("gas", "Grocery Asian Store").unwrap().score;
+    let typo = fuzzy_match("gsa", "Shell Gas").map(|m| m.score);
+    assert!(contiguous > scattered);
+    assert!(typo.is_none_or(|t| t < scattered));
+}
+
+// ── budget_progress ───────────────────────────────────────────
+
+#[test]
+fn test_budget_progress_thresholds() {
+    assert_eq!(
+        budget_progress(dec!(79.99), dec!(100)).status,
+        BudgetStatus::Under
+    );
+    assert_eq!(
+        budget_progress(dec!(80), dec!(100)).status,
+        BudgetStatus::Near
+    );
+    assert_eq!(
+        budget_progress(dec!(99.99), dec!(100)).status,
+        BudgetStatus::Near
+    );
+    assert_eq!(
+        budget_progress(dec!(100), dec!(100)).status,
+        BudgetStatus::OnBudget
+    );
+    assert_eq!(
+        budget_progress(dec!(100.01), dec!(100)).status,
+        BudgetStatus::Over
+    );
+}
+
+#[test]
+fn test_budget_progress_on_budget_label() {
+    assert_eq!(budget_progress(dec!(250), dec!(250)).label(), "on budget");
+}
+
+#[test]
+fn test_budget_progress_over_caps_bar_not_text() {
+    let progress = budget_progress(dec!(130), dec!(100));
+    assert_eq!(progress.bar_fill(), 1.0);
+    assert_eq!(progress.label(), "130%");
+}
+
+#[test]
+fn test_budget_progress_never_rounds_to_100() {
+    assert_eq!(budget_progress(dec!(99.9), dec!(100)).label(), "99%");
+    assert_eq!(budget_progress(dec!(100.1), dec!(100)).label(), "101%");
+}
+
+#[test]
+fn test_budget_progress_nothing_available() {
+    let progress = budget_progress(dec!(10), Decimal::ZERO);
+    assert_eq!(progress.status, BudgetStatus::Over);
+    assert_eq!(progress.bar_fill(), 1.0);
+    assert_eq!(
+        budget_progress(Decimal::ZERO, Decimal::ZERO).status,
+        BudgetStatus::Under
+    );
+}
+
+// ── spending_pace ─────────────────────────────────────────────
+
+fn day(s: &str) -> chrono::NaiveDate {
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn test_spending_pace_mid_month_projects_linearly() {
+    // $300 over the first 10 days of a 30-day month
+    assert_eq!(
+        spending_pace(dec!(300), "2024-04", day("2024-04-10"), 1),
+        Some(SpendingPace::InProgress {
+            daily: dec!(30),
+            projected: dec!(900),
+        })
+    );
+}
+
+#[test]
+fn test_spending_pace_day_one() {
+    assert_eq!(
+        spending_pace(dec!(12.50), "2024-02", day("2024-02-01"), 1),
+        Some(SpendingPace::InProgress {
+            daily: dec!(12.50),
+            projected: dec!(362.50),
+        })
+    );
+    assert_eq!(
+        spending_pace(Decimal::ZERO, "2024-02", day("2024-02-01"), 1),
+        Some(SpendingPace::InProgress {
+            daily: Decimal::ZERO,
+            projected: Decimal::ZERO,
+        })
+    );
+}
+
+#[test]
+fn test_spending_pace_past_and_future_months() {
+    assert_eq!(
+        spending_pace(dec!(1234), "2024-01", day("2024-02-01"), 1),
+        Some(SpendingPace::Final(dec!(1234)))
+    );
+    assert_eq!(
+        spending_pace(dec!(5), "2024-03", day("2024-02-15"), 1),
+        None
+    );
+    assert_eq!(spending_pace(dec!(5), "bogus", day("2024-02-15"), 1), None);
+}
+
+#[test]
+fn test_spending_pace_with_shifted_month() {
+    // January starting on the 25th runs Jan 25 – Feb 24 (31 days); Feb 3 is day 10
+    assert_eq!(
+        spending_pace(dec!(100), "2024-01", day("2024-02-03"), 25),
+        Some(SpendingPace::InProgress {
+            daily: dec!(10),
+            projected: dec!(310),
+        })
+    );
+    assert_eq!(
+        spending_pace(dec!(100), "2024-01", day("2024-02-25"), 25),
+        Some(SpendingPace::Final(dec!(100)))
+    );
+}
+
+// ── income_expense_split ──────────────────────────────────────
+
+#[test]
+fn test_income_expense_split_proportional() {
+    assert_eq!(income_expense_split(dec!(3000), dec!(-1000), 40), (30, 10));
+    assert_eq!(income_expense_split(dec!(50), dec!(-50), 9), (5, 4));
+    // A tiny side still gets a cell
+    assert_eq!(income_expense_split(dec!(10000), dec!(-1), 20), (19, 1));
+    assert_eq!(income_expense_split(dec!(1), dec!(-10000), 20), (1, 19));
+}
+
+#[test]
+fn test_income_expense_split_zero_sides() {
+    assert_eq!(
+        income_expense_split(Decimal::ZERO, Decimal::ZERO, 20),
+        (0, 0)
+    );
+    assert_eq!(income_expense_split(dec!(100), Decimal::ZERO, 20), (20, 0));
+    assert_eq!(income_expense_split(Decimal::ZERO, dec!(-100), 20), (0, 20));
+    assert_eq!(income_expense_split(dec!(100), dec!(-100), 0), (0, 0));
+}
+
+// ── day grouping ──────────────────────────────────────────────
+
+fn dated(dates: &[&str]) -> Vec<Transaction> {
+    dates
+        .iter()
+        .map(|date| Transaction {
+            id: None,
+            account_id: 1,
+            date: date.to_string(),
+            description: String::new(),
+            original_description: String::new(),
+            amount: dec!(-1),
+            category_id: None,
+            notes: String::new(),
+            is_transfer: false,
+            import_hash: String::new(),
+            created_at: String::new(),
+            tags: Vec::new(),
+            quantity: None,
+            symbol: None,
+            flagged: false,
+        })
+        .collect()
+}
+
+#[test]
+fn test_transaction_lines_group_by_day() {
+    use ListLine::{Day, Txn};
+    let txns = dated(&["2024-01-15", "2024-01-15", "2024-01-14", "2024-01-12"]);
+    assert_eq!(
+        transaction_lines(&txns, true),
+        vec![
+            Day(0, 2),
+            Txn(0),
+            Txn(1),
+            Day(2, 1),
+            Txn(2),
+            Day(3, 1),
+            Txn(3)
+        ]
+    );
+    assert_eq!(
+        transaction_lines(&txns, false),
+        vec![Txn(0), Txn(1), Txn(2), Txn(3)]
+    );
+    assert!(transaction_lines(&[], true).is_empty());
+}
+
+#[test]
+fn test_first_visible_line_keeps_cursor_on_screen() {
+    let txns = dated(&["2024-01-15", "2024-01-15", "2024-01-14", "2024-01-12"]);
+    let grouped = transaction_lines(&txns, true);
+    // Scrolled to the top: the first day's header leads
+    assert_eq!(first_visible_line(&grouped, 0, 0, 4), 0);
+    // Headers push transaction 3 (line 6) below a 4-line window
+    assert_eq!(first_visible_line(&grouped, 0, 3, 4), 3);
+    // A scroll position that opens a day shows that day's header
+    assert_eq!(first_visible_line(&grouped, 2, 2, 4), 3);
+    // Mid-day scroll positions start at the transaction itself
+    assert_eq!(first_visible_line(&grouped, 1, 1, 4), 2);
+
+    // Without headers the window follows the scroll position exactly
+    let flat = transaction_lines(&txns, false);
+    assert_eq!(first_visible_line(&flat, 1, 3, 3), 1);
 }